@@ -0,0 +1,614 @@
+# Settings window
+clear-button = Clear (C)
+language = Language
+
+add-abacus = + Add Abacus
+remove-abacus = Remove Selected
+arrangement = Arrangement
+arrangement-row = Row
+arrangement-grid = Grid
+camera-focus = Camera Focus
+camera-focus-shared = All Abaci
+camera-focus-selected = Selected Abacus
+link-with = Linked With
+link-none = None
+carries-into = Carries Into
+abacus-label = Abacus { $number } (base { $base })
+
+panel-structure = Structure
+columns = Columns
+top-beads = Top Beads (per section)
+bottom-beads = Bottom Beads (per section)
+top-bead-base-value = Top Bead Base Value
+numeric-base = Abacus Numeric Base
+bead-value-multiplier = Bead Value Multiplier
+max-value = Max value: { $value }
+column-overrides = Column Overrides (column 0 is the ones column):
+override-column = Col
+override-top = Top
+override-bottom = Bottom
+remove = Remove
+add-column-override = Add Column Override
+
+section-animation = Animation
+bead-easing = Bead Easing
+easing-linear = Linear
+easing-ease-out = Ease Out
+easing-spring = Spring
+easing-snappy = Snappy
+move-duration = Move Duration (s)
+bead-stagger = Per-Bead Stagger (s)
+reduced-motion = Reduced Motion (no bead animation)
+
+section-physics = Physics
+simulated-beads = Simulated Beads (mass, friction, bounce)
+
+section-display = Display Options
+show-total-value = Show Total Value
+show-column-values = Show Column Values
+show-value-in-words = Show Value in Words
+digit-grouping = Digit Grouping
+grouping-off = Off
+grouping-locale = Language Default
+grouping-comma = 1,234,567
+grouping-space = 1 234 567
+digit-group-separator = ,
+group-column-colors = Tint Every Three Columns
+ui-scale = UI Scale
+
+panel-appearance = Appearance
+bead-color = Bead Color
+bead-hover-color = Bead Hover (non-mobile)
+frame-color = Frame Color
+background-color = Background Color
+value-text-size = Value Text Size
+presets = Presets:
+preset-classic = Classic
+preset-high-contrast = High Contrast
+preset-deuteranopia-safe = Deuteranopia-Safe
+preset-dark-background = Dark Background
+preset-applied = Appearance preset '{ $name }' applied.
+sound-theme = Sound Theme
+sound-theme-wood = Wood
+sound-theme-glass = Glass
+sound-theme-metal = Metal
+
+section-sound = Sound & Haptics
+mute = Mute
+bead-click-volume = Bead Click Volume
+vibrate-on-tap = Vibrate on Tap (mobile web)
+
+panel-controls = Controls
+reset-rotation = Reset Rotation
+set-abacus-value = Set Abacus Value:
+enter-value = Enter value
+set = Set
+modify-abacus-value = Modify Abacus Value:
+enter-amount = Enter amount
+add = Add
+subtract = Subtract
+log-setting-value = Setting abacus total value to: { $value }
+log-modifying-value = Setting abacus total value to: { $value } (from { $current } { $operator } { $amount })
+log-invalid-set = Invalid input for Set: Please enter a non-negative integer.
+log-invalid-modify = Invalid input for Modify: Please enter a non-negative integer.
+log-abacus-missing = Could not find Abacus component to modify value.
+
+section-playback = Playback
+step-delay = Step Delay (s)
+play = Play
+pause = Pause
+step = Step
+cancel = Cancel
+pending-moves = Pending column moves: { $count }
+
+section-save-load = Save/Load Configurations
+profile = Profile
+profile-guest = Guest
+profile-new-name = New student name
+profile-create = Add Profile
+config-name = Config Name:
+save-config = Save Current Configuration
+saved-config = Saved Configuration
+load-config = Load Selected Configuration
+delete-config = Delete Selected Configuration
+log-config-saved = Configuration saved.
+log-config-name-missing = Please enter a name to save the configuration.
+log-config-loaded = Configuration '{ $name }' loaded.
+log-config-not-found = Selected configuration '{ $name }' not found to load.
+log-first-config-loaded = Loaded first available configuration '{ $name }'.
+log-no-config = No configuration selected or available to load.
+log-config-deleted = Configuration '{ $name }' deleted.
+log-config-delete-missing = Configuration '{ $name }' not found to delete.
+log-no-config-to-delete = No configuration selected to delete.
+log-rebuilding = Rebuilding abacus structure
+log-cleared = Abacus cleared
+
+# Welcome window
+welcome-window = Welcome to Abacus Simulator
+welcome-heading = Welcome to Abacus Simulator!
+welcome-intro = This interactive simulator lets you explore different types of abaci from around the world.
+welcome-controls = Controls
+welcome-click = • Click on beads to move them up/down
+welcome-select = • With several abaci, click one to select it for the settings and controls
+welcome-scroll = • Scroll the mouse wheel over a column to count it up/down
+welcome-rotate = • Right-click and drag to rotate the 3D view
+welcome-touch = • On a touch screen, drag a bead along its rod to push it, move two fingers to orbit, pinch to zoom and double-tap empty space to reset the view
+welcome-reset-rotation = • Use the Reset Rotation button to return to default view
+welcome-set-value = • Use the Set Value field to set a specific number
+welcome-clear = • Press C or Delete (or the Clear button) to reset the abacus to zero
+welcome-shake = • Shake it clear: drag rapidly back and forth on empty space, or shake your phone
+welcome-add-subtract = • Use Add/Subtract to perform calculations
+welcome-bases = • Numbers display in the selected numeric base (e.g., base 16 shows 10 as 'A')
+welcome-save-load = • Save and load different abacus configurations
+welcome-types = Abacus Types (Available in Save/Load Configurations)
+welcome-suanpan = • Suanpan (Chinese): 2 top beads worth 5 each, 5 bottom beads
+welcome-soroban = • Soroban (Japanese): 1 top bead worth 5, 4 bottom beads
+welcome-binary = • Binary: Represents binary numbers (base 2)
+welcome-custom = • Custom: Create your own abacus configuration!
+welcome-customization = Customization
+welcome-you-can-customize = You can customize:
+welcome-custom-columns = • Number of columns
+welcome-custom-beads = • Number of beads (top and bottom sections)
+welcome-custom-top-value = • Value of top beads
+welcome-custom-base = • Numeric base (decimal, binary, hexadecimal, etc.)
+welcome-custom-colors = • Colors of beads and frame
+close = Close
+
+# Lessons window
+lessons-window = Lessons
+lesson-step-count = Step { $step } of { $total }
+lesson-previous = Previous
+lesson-show-me = Show Me
+lesson-show-me-hint = Perform the next step automatically
+lesson-restart = Restart
+lesson-exit = Exit Lesson
+lessons-completed = Completed lessons ({ $count })
+lesson-files = Lessons from Files
+lesson-error-file-base = This lesson is written for base { $base }; switch the abacus to that base first.
+editor-window = Lesson Editor
+editor-title = Lesson title
+editor-intro = Record a lesson by moving the beads yourself: after each move, describe it and keep it as a step.
+editor-start = Start Recording on Selected Abacus
+editor-current-value = Abacus shows { $value }
+editor-step-text = What this step shows:
+editor-step-hints = Hints (one per line, optional):
+editor-keep-step = Keep as Step
+editor-export = Save to assets/lessons
+editor-copy = Copy as RON
+editor-stop = Stop Recording
+editor-exported = Saved to { $path }; it appears under Lessons from Files on the next start.
+editor-copied = Lesson copied; save it as a .lesson.ron file in assets/lessons.
+editor-export-failed = Couldn't export the lesson: { $error }
+editor-abacus-gone = The abacus being recorded was removed; recording stopped.
+game-window = Target Game
+game-intro = Set the target number on the abacus before the time runs out.
+game-difficulty = Difficulty
+game-easy = Easy (2 digits)
+game-medium = Medium (4 digits)
+game-hard = Hard (5 digits in the abacus base)
+game-start = Start Game
+game-stop = Stop
+game-skip = Skip
+game-target = Target: { $target }
+game-target-base = Written in base { $base }
+game-time-left = Time left: { $seconds }s
+game-streak = Streak: { $streak } (best { $best })
+game-score = Score: { $score }
+game-solved = Solved in { $seconds }s, +{ $points } points!
+game-time-up = Time's up! The target was { $target }.
+game-best = Best streak { $best }, last score { $score }
+stats-window = Statistics
+stats-this-session = This Session
+stats-bead-moves = Bead moves: { $count }
+stats-solved = Problems solved: { $solved } of { $attempts }
+stats-accuracy = Accuracy: { $percent }%
+stats-average-time = Average solve time: { $seconds }s
+stats-no-history = Progress across sessions appears here after your next session.
+stats-progress = Progress by Session
+stats-plot-solved = Solved
+stats-plot-accuracy = Accuracy %
+stats-plot-time = Average solve time (s)
+lesson-start = Start Lesson
+lesson-multiplication = Suanpan Multiplication
+lesson-division = Short Division
+lesson-square-root = Square Root (Advanced)
+lesson-square-root-hint = Best followed on the Soroban (Japanese 1/4) configuration.
+multiplicand = Multiplicand
+multiplier = Multiplier
+dividend = Dividend
+divisor = Divisor
+radicand = Radicand
+log-lesson-started = Starting lesson '{ $title }'
+log-game-started = Target game started
+log-profile-loaded = Loaded profile '{ $name }'
+
+lesson-error-columns = This problem needs { $required } columns; the abacus has { $available }.
+lesson-error-too-large = This problem is too large for the current numeric base.
+lesson-error-divide-by-zero = Cannot divide by zero.
+lesson-error-base = Please enter numbers in base { $base }.
+lesson-remainder = Remainder: { $value }
+lesson-root-so-far = Root so far: { $value }
+lesson-root = Root: { $value }
+
+multiplication-title = Multiplication: { $multiplicand } × { $multiplier }
+multiplication-set-multiplicand = Set the multiplicand { $multiplicand } on the highlighted columns.
+multiplication-set-multiplier = Leave one column empty, then set the multiplier { $multiplier }.
+multiplication-partial = Multiply { $a } × { $b } = { $partial }. Add it to the product so its last digit lands { $place } { $place ->
+        [one] column
+       *[other] columns
+    } from the right.
+multiplication-done = Done: { $multiplicand } × { $multiplier } = { $product }, read from the right-hand columns.
+
+division-title = Division: { $dividend } ÷ { $divisor }
+division-set-divisor = Set the divisor { $divisor } on the highlighted columns.
+division-set-dividend = Set the dividend { $dividend } on the highlighted columns.
+division-trial = { $divisor } goes into { $prefix } { $quotient } { $times ->
+        [one] time
+       *[other] times
+    }. Record the trial quotient { $quotient } in the quotient columns.
+division-subtract = Subtract { $quotient } × { $divisor } = { $subtrahend } from { $prefix }, leaving { $difference }.
+division-done = Done: { $dividend } ÷ { $divisor } = { $quotient } remainder { $remainder }. The quotient is in the middle, the remainder on the right.
+
+square-root-title = Square Root of { $radicand }
+square-root-set-radicand = Set the radicand { $radicand } on the right-hand columns and group its digits in pairs from the right.
+square-root-digit = Bring down the next pair. The largest digit d with ({ $doubled_root } + d) × d ≤ { $prefix } is { $digit }. Set it as the next root digit.
+square-root-subtract = Subtract ({ $doubled_root } + { $digit }) × { $digit } = { $subtrahend } from { $prefix }, leaving { $difference }.
+square-root-done = Done: the square root of { $radicand } is { $root } with remainder { $remainder }. The root is on the left, the remainder on the right.
+
+# Screen reader announcements
+announce-total = Abacus total { $total }
+announce-column = column { $column } is { $value }
+overflow-toast = { $requested } is more than this abacus can hold, so it stopped at its maximum of { $max }.
+
+macros-window = Macros
+macros-intro = Record bead moves, Set, Add and Subtract on the selected abacus, then replay them on any abacus.
+macros-record = ⏺ Record on Selected Abacus
+macros-recording = Recording: { $count } operations so far
+macros-name = Name
+macros-save = Stop and Save
+macros-discard = Discard
+macros-speed = Playback speed
+macros-playing = Playing { $name }: { $step } / { $total }
+macros-stop = Stop
+macros-play = ▶ Play
+macros-entry = { $name } ({ $count } operations)
+macros-empty = No macros recorded yet.
+macros-default-name = Macro { $number }
+macros-abacus-gone = The abacus was removed; the macro stopped.
+
+snapshots-window = Snapshots
+snapshots-name = Snapshot name
+snapshots-save = Save Snapshot
+snapshots-default-name = Snapshot { $number }
+snapshots-empty = Save the selected abacus's beads to come back to them later.
+snapshots-restore = Restore
+snapshots-wrong-columns = Needs an abacus with { $count } columns
+snapshots-entry = { $name } ({ $value })
+
+history-window = History
+history-empty = Move some beads; every value the selected abacus shows is kept here to scrub back through.
+history-first = First value
+history-back = Previous value
+history-forward = Next value
+history-latest = Latest value
+history-position = Value { $step } of { $count }: { $value }
+
+oplog-window = Operation Log
+oplog-empty = Every bead move, Set, Add and Subtract on any abacus is listed here.
+oplog-entry = [{ $time }] Abacus { $abacus }: { $text }
+oplog-beads = bead moved on column { $column } → { $total }
+oplog-set = set → { $total }
+oplog-add = +{ $amount } → { $total }
+oplog-subtract = −{ $amount } → { $total }
+oplog-format-csv = CSV
+oplog-format-json = JSON
+oplog-export = Export
+oplog-exported = Exported to { $path }
+oplog-export-failed = Couldn't export the log: { $error }
+oplog-copy = Copy
+oplog-copied = Log copied to the clipboard.
+oplog-clear = Clear
+
+copy-share-link = Copy Share Link
+log-share-link-copied = Share link copied to the clipboard
+
+console-window = Console
+console-hint = Type a command, e.g. set 1234 (Tab completes, ↑↓ history)
+console-help = Commands: { $commands }. set, add and sub take a number; columns, base, top and bottom change the selected abacus's structure; preset loads a saved configuration by name; clear returns the abacus to zero; scripts lists the scripts, run starts one and stop ends it.
+console-unknown = Unknown command '{ $command }'; type help for the list.
+console-needs-number = { $command } needs a whole number.
+console-out-of-range = { $command } takes { $min } to { $max }.
+console-needs-preset = preset needs the name of a saved configuration.
+console-no-preset = No saved configuration matches '{ $name }'.
+console-several-presets = Several configurations match: { $names }
+console-no-abacus = There's no abacus selected.
+
+console-needs-script = run needs the name of a script in assets/scripts.
+console-scripting-disabled = Scripts need the scripting feature: cargo run --features scripting
+console-no-scripts = No scripts found in assets/scripts.
+console-scripts = Scripts: { $names }
+console-script-missing = No script called '{ $name }'; type scripts for the list.
+console-script-started = Running { $name } ({ $count } steps)
+console-script-failed = { $name } failed: { $error }
+console-script-stopped = Stopped { $name }
+console-script-finished = { $name } finished
+
+import-config = Import Configuration…
+export-config = Export Configuration…
+log-config-imported = Configuration '{ $name }' imported; load it from the list below.
+log-config-exported = Configuration exported to { $path }
+log-config-file-failed = Couldn't use the configuration file: { $error }
+
+dropped-config-window = Configuration Added
+dropped-config-question = '{ $name }' is now in your saved configurations. Apply it to the selected abacus?
+dropped-config-apply = Apply Now
+dropped-config-keep = Not Now
+log-dropped-file-ignored = Only .ron and .json configuration files can be dropped here, not { $path }
+
+save-scene = Save Scene…
+load-scene = Load Scene…
+log-scene-saved = Scene saved to { $path }
+log-scene-loaded = Scene loaded with { $count } abaci
+log-scene-file-failed = Couldn't use the scene file: { $error }
+
+screenshot = Screenshot (F12)
+screenshot-hides-ui = Hide panels
+log-screenshot-saving = Saving screenshot to { $path }
+log-screenshot-failed = Couldn't save the screenshot: { $error }
+
+turntable-window = Turntable Export
+turntable-intro = Captures the selected abacus while the camera circles it or a macro plays, for slides and worksheets. Files go to ~/.abacus-simulator/exports.
+turntable-motion = Motion
+turntable-orbit = Circle the abacus
+turntable-frames = Frames
+turntable-format = Format
+turntable-format-gif = Animated GIF
+turntable-format-png = PNG sequence
+turntable-record = ⏺ Record
+turntable-capturing = Capturing: { $count } frames
+turntable-encoding = Encoding…
+turntable-saved = Saved to { $path }
+turntable-failed = Couldn't save the export: { $error }
+
+export-svg = Export SVG
+copy-svg = Copy SVG
+log-svg-exported = Diagram exported to { $path }
+log-svg-failed = Couldn't export the diagram: { $error }
+log-svg-copied = SVG diagram copied to the clipboard
+
+export-gltf = Export glTF
+log-gltf-exported = 3D model exported to { $path }
+log-gltf-failed = Couldn't export the 3D model: { $error }
+
+flat-mode = Flat 2D mode (faster on low-end devices)
+graphics-quality = Graphics quality
+quality-low = Low
+quality-medium = Medium
+quality-high = High
+stress-test-window = Stress Test
+stress-test-description = Adds an abacus with { $columns } columns and every bead, times the value engine on it, then measures frame time while all its beads move.
+stress-test-run = Run Stress Test
+stress-test-running = Measuring…
+stress-test-size = { $columns } columns, { $beads } beads
+stress-test-split = Split a total into columns: { $micros } µs
+stress-test-read = Read the total from the beads: { $micros } µs
+stress-test-set = Set the beads to a total: { $millis } ms
+stress-test-frames = Frame time: { $average } ms average, { $worst } ms worst ({ $fps } fps)
+log-stress-test = Stress test on { $columns } columns: { $average } ms per frame on average, { $worst } ms at worst
+log-inconsistent-long = Abacus { $abacus } column { $column }: the beads show { $beads } on a deck whose long says { $long }
+log-inconsistent-column = Abacus { $abacus } column { $column }: the beads show { $beads } but the column reads “{ $text }”
+log-inconsistent-total = Abacus { $abacus }: the beads show { $beads } but the total is { $total } and reads “{ $text }”
+hud-total = Show total at top of screen
+hud-text-size = Top total size
+text-font = Font
+text-font-default = Default
+log-font-loaded = Using the font { $name }
+log-font-failed = Could not load the font { $name }: { $error }
+presentation-mode = Presentation mode (F11, Esc to leave)
+log-kiosk-started = Kiosk mode: the attract animation starts after { $seconds } seconds without a touch
+attract-mode = Demo mode
+attract-style = Demo values
+attract-random = Random
+attract-count-up = Count up
+attract-interval = Seconds between values
+stopwatch-window = Stopwatch
+stopwatch-mode = Clock
+stopwatch-mode-stopwatch = Stopwatch
+stopwatch-mode-countdown = Countdown
+stopwatch-game-limit = Game time limit ({ $seconds }s)
+stopwatch-time = { $seconds }s
+stopwatch-start = Start
+stopwatch-stop = Stop
+stopwatch-lap = Lap
+stopwatch-reset = Reset
+stopwatch-lap-entry = Lap { $number }: { $seconds }s (+{ $split }s)
+log-countdown-finished = Countdown finished
+count-to-window = Count To
+count-to-target = Target
+count-to-step = Step
+count-to-rate = Steps per second
+count-to-start = Count
+count-to-stop = Stop
+count-to-progress = At { $value }, counting to { $target }
+tally-window = Tally Counter
+tally-enabled = Count triggers
+tally-key = Trigger key
+tally-press-key = Press a key…
+tally-step = Step
+tally-count = Counted { $count } times
+tally-reset = Reset
+log-remote-listening = Remote control listening on ws://{ $address }
+log-remote-failed = Couldn't start remote control on { $address }: { $error }
+broadcast-window = Teacher Broadcast
+broadcast-host = Broadcast the selected abacus
+broadcast-host-failed = Couldn't start broadcasting: { $error }
+broadcast-join-code = Join code: { $code }
+broadcast-loopback = No network was found, so only this computer can join.
+broadcast-code-hint = Join code
+broadcast-join = Join
+broadcast-invalid-code = That isn't a join code
+broadcast-connecting = Connecting…
+broadcast-watching = Watching the teacher's abacus
+broadcast-lost = Connection lost: { $error }
+broadcast-leave = Leave
+log-broadcast-joined = Joined the teacher's broadcast
+log-broadcast-lost = Lost the teacher's broadcast: { $error }
+log-broadcast-host-failed = Couldn't start the teacher broadcast: { $error }
+log-osc-listening = Listening for OSC on UDP port { $port }
+log-osc-failed = Couldn't listen for OSC on UDP port { $port }: { $error }
+log-osc-bad-target = Can't send OSC to '{ $target }'; expected host:port
+log-osc-bad-packet = Ignoring a malformed OSC packet: { $error }
+log-osc-unknown = Ignoring OSC message { $address }
+log-osc-bad-argument = OSC message { $address } needs a whole number
+midi-window = MIDI Input
+midi-no-ports = No MIDI inputs
+midi-refresh = Refresh
+midi-connect = Connect
+midi-disconnect = Disconnect
+midi-connected = Listening to { $port }
+midi-first-note = Ones-column note
+midi-total-control = Total control change
+midi-last-message = Last message: { $message }
+log-midi-connected = Listening to MIDI input { $port }
+log-midi-failed = Couldn't open MIDI input { $port }: { $error }
+keybindings-window = Keybindings
+keybindings-add = Add a key or mouse button
+keybindings-remove = Remove this binding
+keybindings-press-key = Press a key…
+keybindings-cancel = Cancel
+keybindings-reset = Reset to defaults
+action-clear = Clear
+action-undo = Undo
+action-redo = Redo
+action-rotate-drag = Drag to rotate
+action-rotate-left = Rotate left
+action-rotate-right = Rotate right
+action-tilt-up = Tilt up
+action-tilt-down = Tilt down
+action-reset-rotation = Reset rotation
+action-camera-focus = Switch camera focus
+action-presentation = Presentation mode
+action-exit-presentation = Leave presentation mode
+action-screenshot = Screenshot
+action-fullscreen = Full screen
+action-digit = Type { $digit }
+action-enter-digits = Set typed value
+action-erase-digit = Erase typed digit
+digit-entry = Set to: { $digits }
+action-column-left = Next column left
+action-column-right = Next column right
+action-column-up = Column up one bead
+action-column-down = Column down one bead
+action-deselect-column = Deselect column
+column-entry-hint = Base { $base }
+column-place-value = ×{ $value } ({ $base }^{ $power })
+money-mode = Money mode (last two columns are cents)
+money-symbol = Currency symbol
+money-window = Money
+money-mode-off = Turn on Money mode in Display Options to read the total as an amount.
+value-model = Value model
+value-model-place = Place value
+value-model-counting = Counting (beads moved left)
+horizontal-rods = Horizontal rods (rekenrek)
+two-tone-beads = Two-tone beads in blocks of five
+override-top-value = Top value
+override-base = Base
+grooves = Grooves instead of rods
+column-unit-value = ×{ $value }
+extra-decks = Extra decks (above the top deck)
+deck-beads = Beads
+add-deck = Add Deck
+ones-on-left = Ones column on the left
+add-column-left = + Column Left
+add-column-right = + Column Right
+remove-column = Remove Column
+bead-spacing = Bead spacing
+column-spacing = Column spacing
+bead-radius = Bead radius
+model-scale = Model scale
+bead-shape = Bead shape
+bead-shape-disk = Disk
+bead-shape-bicone = Bicone
+bead-shape-sphere = Sphere
+bead-shape-torus = Torus
+models-window = Models
+models-hint = glTF (.glb) files under assets/ to draw the beads and rods with. Leave a path empty for the built-in piece.
+bead-model = Bead model
+rod-model = Rod model
+load-models = Load
+built-in-models = Built-in models
+surfaces = Textures and finish
+bead-surface = Beads
+frame-surface = Frame
+surface-texture = Texture
+surface-normal-map = Normal map
+surface-metallic = Metallic
+surface-roughness = Roughness
+wood-grain = Wood grain
+wood-grain-tint = Grain color
+wood-grain-rings = Rings
+wood-grain-variation = Variation
+environment-window = Lighting
+environment-classroom = Classroom
+environment-studio = Studio
+environment-dark-stage = Dark stage
+ambient-color = Ambient light color
+ambient-brightness = Ambient brightness
+key-light-color = Key light color
+key-light-brightness = Key light brightness
+fill-light-color = Fill light color
+fill-light-brightness = Fill light brightness
+sky = Sky
+sky-color = Background color
+sky-gradient = Gradient
+sky-environment-map = Environment map
+sky-top = Sky color
+sky-horizon = Horizon color
+environment-map-hint = Prefiltered KTX2 cubemaps under assets/ light the beads when both are set.
+diffuse-map = Diffuse map
+specular-map = Specular map
+environment-intensity = Environment brightness
+table = Table under the abaci
+table-color = Table color
+table-texture = Table texture
+contact-shadows = Soft contact shadows
+frame-title = Frame title
+frame-title-hint = School or student name
+frame-title-color = Title color
+frame-title-size = Title size
+column-colors = Column bead colors
+column-colors-first = From column
+column-colors-width = Width
+column-colors-every = Every
+add-column-color = Add column color
+column-colors-thousands = Alternate thousands
+themes-window = Themes
+themes-hint = No themes found. Add .theme.ron files to assets/themes.
+panel-practice = Practice
+panel-dock = Dock:
+dock-left = Left
+dock-floating = Window
+dock-right = Right
+section-scene = Abaci in the Scene
+reset-window-layout = Reset Window Layout
+fullscreen = Full Screen (F10)
+log-replay-started = Replaying { $count } operations from { $path }
+log-replay-read-failed = Couldn't read replay { $path }: { $error }
+log-replay-saved = Saved replay of { $count } operations to { $path }
+log-replay-save-failed = Couldn't save replay { $path }: { $error }
+log-replay-missing-abacus = Replay skipped an operation on missing abacus { $abacus }
+log-replay-finished = Replay finished; abaci show { $totals }
+log-replay-mismatch = Replay finished on { $totals }, but the recording ended on { $expected }
+log-fullscreen-failed = Couldn't go full screen: { $error }
+log-saved-data-ignored = Ignoring saved '{ $key }': { $error }
+log-save-failed = Couldn't save '{ $key }': { $error }
+log-share-parameter-ignored = Ignoring share link parameter '{ $key }={ $value }'
+log-config-clamped = Configuration '{ $name }' had { $fields } out of range, so they were brought within the settings panel's limits
+log-arg-not-a-number = { $flag } needs a whole number, not '{ $text }'
+log-arg-unknown = Ignoring unknown argument '{ $arg }'
+log-arg-headless-without-replay = --headless only applies with --replay
+log-startup-config = Starting with configuration { $path }
+log-startup-config-failed = Couldn't load configuration { $path }: { $error }
+log-js-callback-failed = on_abacus_changed callback failed: { $error }
+log-js-post-failed = Couldn't post the abacus value to the embedding page: { $error }
+log-custom-model-failed = Couldn't load a custom abacus model, keeping the built-in one: { $error }
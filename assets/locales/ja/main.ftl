@@ -0,0 +1,608 @@
+# 設定ウィンドウ
+clear-button = ご破算 (C)
+language = 言語
+
+add-abacus = + そろばんを追加
+remove-abacus = 選択中を削除
+arrangement = 配置
+arrangement-row = 横一列
+arrangement-grid = グリッド
+camera-focus = カメラの注視点
+camera-focus-shared = すべてのそろばん
+camera-focus-selected = 選択中のそろばん
+link-with = 連動するそろばん
+link-none = なし
+carries-into = 繰り上がり先
+abacus-label = そろばん { $number }（{ $base } 進法）
+
+panel-structure = 構造
+columns = 桁数
+top-beads = 上の珠（1桁あたり）
+bottom-beads = 下の珠（1桁あたり）
+top-bead-base-value = 上の珠の値
+numeric-base = 基数
+bead-value-multiplier = 珠の倍率
+max-value = 最大値：{ $value }
+column-overrides = 桁ごとの設定（0 桁目が一の位）：
+override-column = 桁
+override-top = 上
+override-bottom = 下
+remove = 削除
+add-column-override = 桁ごとの設定を追加
+
+section-animation = アニメーション
+bead-easing = 珠のイージング
+easing-linear = リニア
+easing-ease-out = イーズアウト
+easing-spring = バネ
+easing-snappy = キビキビ
+move-duration = 移動時間（秒）
+bead-stagger = 珠ごとの遅れ（秒）
+reduced-motion = 動きを減らす（珠のアニメーションなし）
+
+section-physics = 物理
+simulated-beads = 珠をシミュレート（質量・摩擦・跳ね返り）
+
+section-display = 表示オプション
+show-total-value = 合計値を表示
+show-column-values = 各桁の値を表示
+show-value-in-words = 数の読み方を表示
+digit-grouping = 桁区切り
+grouping-off = なし
+grouping-locale = 言語の既定
+grouping-comma = 1,234,567
+grouping-space = 1 234 567
+digit-group-separator = ,
+group-column-colors = 3 桁ごとに色分け
+ui-scale = UI の拡大率
+
+panel-appearance = 外観
+bead-color = 珠の色
+bead-hover-color = ホバー時の色（モバイル以外）
+frame-color = 枠の色
+background-color = 背景色
+value-text-size = 数値の文字サイズ
+presets = プリセット：
+preset-classic = クラシック
+preset-high-contrast = ハイコントラスト
+preset-deuteranopia-safe = 2型色覚に配慮
+preset-dark-background = ダーク背景
+preset-applied = 外観プリセット「{ $name }」を適用しました。
+sound-theme = サウンドテーマ
+sound-theme-wood = 木
+sound-theme-glass = ガラス
+sound-theme-metal = 金属
+
+section-sound = サウンドと振動
+mute = ミュート
+bead-click-volume = 珠の音量
+vibrate-on-tap = タップ時に振動（モバイル Web）
+
+panel-controls = 操作
+reset-rotation = 回転をリセット
+set-abacus-value = そろばんの値を設定：
+enter-value = 値を入力
+set = 設定
+modify-abacus-value = そろばんの値を変更：
+enter-amount = 数を入力
+add = 足す
+subtract = 引く
+log-setting-value = そろばんの合計値を { $value } に設定します
+log-modifying-value = そろばんの合計値を { $value } に設定します（{ $current } { $operator } { $amount }）
+log-invalid-set = 設定の入力が無効です：0 以上の整数を入力してください。
+log-invalid-modify = 変更の入力が無効です：0 以上の整数を入力してください。
+log-abacus-missing = 値を変更するそろばんが見つかりません。
+
+section-playback = 再生
+step-delay = ステップ間隔（秒）
+play = 再生
+pause = 一時停止
+step = 1 ステップ
+cancel = キャンセル
+pending-moves = 待機中の桁の移動：{ $count }
+
+section-save-load = 構成の保存/読み込み
+profile = プロフィール
+profile-guest = ゲスト
+profile-new-name = 新しい生徒の名前
+profile-create = プロフィールを追加
+config-name = 構成名：
+save-config = 現在の構成を保存
+saved-config = 保存済みの構成
+load-config = 選択した構成を読み込む
+delete-config = 選択した構成を削除
+log-config-saved = 構成を保存しました。
+log-config-name-missing = 構成を保存するには名前を入力してください。
+log-config-loaded = 構成「{ $name }」を読み込みました。
+log-config-not-found = 読み込む構成「{ $name }」が見つかりません。
+log-first-config-loaded = 最初の構成「{ $name }」を読み込みました。
+log-no-config = 読み込める構成が選択されていないか、ありません。
+log-config-deleted = 構成「{ $name }」を削除しました。
+log-config-delete-missing = 削除する構成「{ $name }」が見つかりません。
+log-no-config-to-delete = 削除する構成が選択されていません。
+log-rebuilding = そろばんの構造を再構築しています
+log-cleared = ご破算にしました
+
+# ようこそウィンドウ
+welcome-window = そろばんシミュレーターへようこそ
+welcome-heading = そろばんシミュレーターへようこそ！
+welcome-intro = このシミュレーターでは、世界各地のさまざまなそろばんを試すことができます。
+welcome-controls = 操作方法
+welcome-click = • 珠をクリックすると上下に動きます
+welcome-select = • そろばんが複数あるときは、クリックして設定と操作の対象を選びます
+welcome-scroll = • 桁の上でマウスホイールを回すと 1 ずつ増減します
+welcome-rotate = • 右ドラッグで 3D ビューを回転します
+welcome-touch = • タッチ画面では、珠を軸に沿ってドラッグして動かし、2 本指で視点を回し、ピンチで拡大縮小、何もない所をダブルタップで視点を戻します
+welcome-reset-rotation = • 「回転をリセット」ボタンで元の向きに戻します
+welcome-set-value = • 「値を設定」欄で好きな数を置けます
+welcome-clear = • C か Delete（または「ご破算」ボタン）でゼロに戻します
+welcome-shake = • 振ってご破算：何もない所を素早く左右にドラッグするか、スマートフォンを振ります
+welcome-add-subtract = • 「足す/引く」で計算します
+welcome-bases = • 数は選んだ基数で表示されます（例：16 進数では 10 は「A」）
+welcome-save-load = • さまざまな構成を保存・読み込みできます
+welcome-types = そろばんの種類（構成の保存/読み込みから選べます）
+welcome-suanpan = • 算盤（中国）：上の珠 2 個はそれぞれ 5、下の珠 5 個
+welcome-soroban = • そろばん（日本）：上の珠 1 個が 5、下の珠 4 個
+welcome-binary = • 2 進数：2 進数を表します（基数 2）
+welcome-custom = • カスタム：自分だけのそろばんを作れます！
+welcome-customization = カスタマイズ
+welcome-you-can-customize = 次の項目を変更できます：
+welcome-custom-columns = • 桁数
+welcome-custom-beads = • 珠の数（上段と下段）
+welcome-custom-top-value = • 上の珠の値
+welcome-custom-base = • 基数（10 進数、2 進数、16 進数など）
+welcome-custom-colors = • 珠と枠の色
+close = 閉じる
+
+# レッスンウィンドウ
+lessons-window = レッスン
+lesson-step-count = ステップ { $step } / { $total }
+lesson-previous = 前へ
+lesson-show-me = お手本
+lesson-show-me-hint = 次のステップを自動で実行します
+lesson-restart = 最初から
+lesson-exit = レッスンを終了
+lessons-completed = 完了したレッスン（{ $count }）
+lesson-files = ファイルのレッスン
+lesson-error-file-base = このレッスンは { $base } 進法用です。先にそろばんをその進法に切り替えてください。
+editor-window = レッスンエディター
+editor-title = レッスンのタイトル
+editor-intro = 自分で珠を動かしてレッスンを録画します。動かすたびに説明を書き、ステップとして残してください。
+editor-start = 選択中のそろばんで録画開始
+editor-current-value = そろばんの値は { $value }
+editor-step-text = このステップで示すこと：
+editor-step-hints = ヒント（1 行に 1 つ、任意）：
+editor-keep-step = ステップとして残す
+editor-export = assets/lessons に保存
+editor-copy = RON としてコピー
+editor-stop = 録画を停止
+editor-exported = { $path } に保存しました。次回の起動時に「ファイルのレッスン」に表示されます。
+editor-copied = レッスンをコピーしました。assets/lessons に .lesson.ron ファイルとして保存してください。
+editor-export-failed = レッスンを書き出せませんでした：{ $error }
+editor-abacus-gone = 録画中のそろばんが削除されたため、録画を停止しました。
+game-window = ターゲットゲーム
+game-intro = 時間切れになる前に、目標の数をそろばんに置きましょう。
+game-difficulty = 難易度
+game-easy = やさしい（2 桁）
+game-medium = ふつう（4 桁）
+game-hard = むずかしい（そろばんの進法で 5 桁）
+game-start = ゲーム開始
+game-stop = やめる
+game-skip = スキップ
+game-target = 目標：{ $target }
+game-target-base = { $base } 進法で表記
+game-time-left = 残り時間：{ $seconds } 秒
+game-streak = 連続正解：{ $streak }（最高 { $best }）
+game-score = スコア：{ $score }
+game-solved = { $seconds } 秒で正解、+{ $points } 点！
+game-time-up = 時間切れ！目標は { $target } でした。
+game-best = 最高連続正解 { $best }、前回のスコア { $score }
+stats-window = 統計
+stats-this-session = 今回の練習
+stats-bead-moves = 珠を動かした回数：{ $count }
+stats-solved = 解いた問題：{ $solved } / { $attempts }
+stats-accuracy = 正答率：{ $percent }%
+stats-average-time = 平均解答時間：{ $seconds } 秒
+stats-no-history = 次回の練習のあと、ここに練習ごとの上達が表示されます。
+stats-progress = 練習ごとの上達
+stats-plot-solved = 正解
+stats-plot-accuracy = 正答率 %
+stats-plot-time = 平均解答時間（秒）
+lesson-start = レッスン開始
+lesson-multiplication = 算盤のかけ算
+lesson-division = 割り算
+lesson-square-root = 開平（上級）
+lesson-square-root-hint = そろばん（日本 1/4）の構成で行うのがおすすめです。
+multiplicand = 被乗数
+multiplier = 乗数
+dividend = 被除数
+divisor = 除数
+radicand = 被開平数
+log-lesson-started = レッスン「{ $title }」を開始します
+log-game-started = ターゲットゲームを開始しました
+log-profile-loaded = プロフィール「{ $name }」を読み込みました
+
+lesson-error-columns = この問題には { $required } 桁必要ですが、そろばんは { $available } 桁です。
+lesson-error-too-large = この問題は現在の基数では大きすぎます。
+lesson-error-divide-by-zero = 0 で割ることはできません。
+lesson-error-base = { $base } 進数で入力してください。
+lesson-remainder = 余り：{ $value }
+lesson-root-so-far = ここまでの根：{ $value }
+lesson-root = 根：{ $value }
+
+multiplication-title = かけ算：{ $multiplicand } × { $multiplier }
+multiplication-set-multiplicand = 被乗数 { $multiplicand } を強調表示された桁に置きます。
+multiplication-set-multiplier = 1 桁空けて、乗数 { $multiplier } を置きます。
+multiplication-partial = { $a } × { $b } = { $partial }。一の位が右から { $place } 桁目に来るように積に足します。
+multiplication-done = 完成：{ $multiplicand } × { $multiplier } = { $product }。右側の桁から読みます。
+
+division-title = 割り算：{ $dividend } ÷ { $divisor }
+division-set-divisor = 除数 { $divisor } を強調表示された桁に置きます。
+division-set-dividend = 被除数 { $dividend } を強調表示された桁に置きます。
+division-trial = { $prefix } の中に { $divisor } は { $quotient } 回入ります。仮商 { $quotient } を商の桁に置きます。
+division-subtract = { $prefix } から { $quotient } × { $divisor } = { $subtrahend } を引くと { $difference } が残ります。
+division-done = 完成：{ $dividend } ÷ { $divisor } = { $quotient } 余り { $remainder }。商は中央、余りは右側にあります。
+
+square-root-title = { $radicand } の平方根
+square-root-set-radicand = 被開平数 { $radicand } を右側の桁に置き、右から 2 桁ずつ区切ります。
+square-root-digit = 次の 2 桁をおろします。({ $doubled_root } + d) × d ≤ { $prefix } となる最大の数字 d は { $digit } です。これを根の次の桁に置きます。
+square-root-subtract = { $prefix } から ({ $doubled_root } + { $digit }) × { $digit } = { $subtrahend } を引くと { $difference } が残ります。
+square-root-done = 完成：{ $radicand } の平方根は { $root }、余り { $remainder } です。根は左側、余りは右側にあります。
+
+# 読み上げ
+announce-total = そろばんの合計 { $total }
+announce-column = { $column } 桁目は { $value }
+overflow-toast = { $requested } はこのそろばんで表せる範囲を超えているため、最大値 { $max } で止めました。
+
+macros-window = マクロ
+macros-intro = 選択中のそろばんで珠の移動、設定、足し算、引き算を記録し、どのそろばんでも再生できます。
+macros-record = ⏺ 選択中のそろばんで記録
+macros-recording = 記録中：これまでに { $count } 件の操作
+macros-name = 名前
+macros-save = 停止して保存
+macros-discard = 破棄
+macros-speed = 再生速度
+macros-playing = { $name } を再生中：{ $step } / { $total }
+macros-stop = 停止
+macros-play = ▶ 再生
+macros-entry = { $name }（{ $count } 件の操作）
+macros-empty = まだマクロがありません。
+macros-default-name = マクロ { $number }
+macros-abacus-gone = そろばんが削除されたため、マクロを停止しました。
+
+snapshots-window = スナップショット
+snapshots-name = スナップショット名
+snapshots-save = スナップショットを保存
+snapshots-default-name = スナップショット { $number }
+snapshots-empty = 選択中のそろばんの珠の位置を保存して、あとで戻せます。
+snapshots-restore = 復元
+snapshots-wrong-columns = { $count } 桁のそろばんが必要です
+snapshots-entry = { $name }（{ $value }）
+
+history-window = 履歴
+history-empty = 珠を動かしてみましょう。選択中のそろばんが示した値はすべてここに残り、さかのぼって確認できます。
+history-first = 最初の値
+history-back = 前の値
+history-forward = 次の値
+history-latest = 最新の値
+history-position = { $count } 件中 { $step } 件目の値：{ $value }
+
+oplog-window = 操作ログ
+oplog-empty = どのそろばんでも、珠の移動・設定・足し算・引き算がすべてここに記録されます。
+oplog-entry = [{ $time }] そろばん { $abacus }：{ $text }
+oplog-beads = { $column } 桁目の珠を移動 → { $total }
+oplog-set = 設定 → { $total }
+oplog-add = +{ $amount } → { $total }
+oplog-subtract = −{ $amount } → { $total }
+oplog-format-csv = CSV
+oplog-format-json = JSON
+oplog-export = 書き出し
+oplog-exported = { $path } に書き出しました
+oplog-export-failed = ログを書き出せませんでした：{ $error }
+oplog-copy = コピー
+oplog-copied = ログをクリップボードにコピーしました。
+oplog-clear = 消去
+
+copy-share-link = 共有リンクをコピー
+log-share-link-copied = 共有リンクをクリップボードにコピーしました
+
+console-window = コンソール
+console-hint = コマンドを入力（例：set 1234、Tab で補完、↑↓ で履歴）
+console-help = コマンド：{ $commands }。set・add・sub には数を付けます。columns・base・top・bottom は選択中のそろばんの構造を変え、preset は保存済みの設定を名前で読み込み、clear はそろばんをゼロに戻します。scripts でスクリプト一覧を表示し、run で実行、stop で停止します。
+console-unknown = 不明なコマンド「{ $command }」です。help で一覧を表示します。
+console-needs-number = { $command } には整数が必要です。
+console-out-of-range = { $command } は { $min } から { $max } までです。
+console-needs-preset = preset には保存済みの設定名が必要です。
+console-no-preset = 「{ $name }」に一致する保存済みの設定がありません。
+console-several-presets = 複数の設定が一致します：{ $names }
+console-no-abacus = そろばんが選択されていません。
+
+console-needs-script = run には assets/scripts にあるスクリプトの名前が必要です。
+console-scripting-disabled = スクリプトには scripting 機能が必要です：cargo run --features scripting
+console-no-scripts = assets/scripts にスクリプトが見つかりません。
+console-scripts = スクリプト：{ $names }
+console-script-missing = 「{ $name }」というスクリプトはありません。scripts で一覧を表示します。
+console-script-started = { $name } を実行中（{ $count } ステップ）
+console-script-failed = { $name } が失敗しました：{ $error }
+console-script-stopped = { $name } を停止しました
+console-script-finished = { $name } が完了しました
+
+import-config = 設定をインポート…
+export-config = 設定をエクスポート…
+log-config-imported = 設定「{ $name }」をインポートしました。下のリストから読み込めます。
+log-config-exported = 設定を { $path } にエクスポートしました
+log-config-file-failed = 設定ファイルを使えませんでした：{ $error }
+
+dropped-config-window = 設定を追加しました
+dropped-config-question = 「{ $name }」を保存済みの設定に追加しました。選択中のそろばんに適用しますか？
+dropped-config-apply = 今すぐ適用
+dropped-config-keep = あとで
+log-dropped-file-ignored = ここにドロップできるのは .ron と .json の設定ファイルだけです：{ $path }
+
+save-scene = シーンを保存…
+load-scene = シーンを読み込む…
+log-scene-saved = シーンを { $path } に保存しました
+log-scene-loaded = そろばん { $count } 台のシーンを読み込みました
+log-scene-file-failed = シーンファイルを使えませんでした：{ $error }
+
+screenshot = スクリーンショット (F12)
+screenshot-hides-ui = パネルを隠す
+log-screenshot-saving = スクリーンショットを { $path } に保存しています
+log-screenshot-failed = スクリーンショットを保存できませんでした：{ $error }
+
+turntable-window = ターンテーブル書き出し
+turntable-intro = カメラが選択中のそろばんを回る間、またはマクロの再生中にキャプチャし、スライドやプリントに使えます。ファイルは ~/.abacus-simulator/exports に保存されます。
+turntable-motion = 動き
+turntable-orbit = そろばんを一周
+turntable-frames = フレーム数
+turntable-format = 形式
+turntable-format-gif = アニメーション GIF
+turntable-format-png = PNG 連番
+turntable-record = ⏺ 録画
+turntable-capturing = キャプチャ中：{ $count } フレーム
+turntable-encoding = エンコード中…
+turntable-saved = { $path } に保存しました
+turntable-failed = 書き出しを保存できませんでした：{ $error }
+
+export-svg = SVG を書き出す
+copy-svg = SVG をコピー
+log-svg-exported = 図を { $path } に書き出しました
+log-svg-failed = 図を書き出せませんでした：{ $error }
+log-svg-copied = SVG の図をクリップボードにコピーしました
+
+export-gltf = glTF を書き出す
+log-gltf-exported = 3D モデルを { $path } に書き出しました
+log-gltf-failed = 3D モデルを書き出せませんでした：{ $error }
+
+flat-mode = フラット 2D モード（低性能な端末向け）
+graphics-quality = 画質
+quality-low = 低
+quality-medium = 中
+quality-high = 高
+stress-test-window = ストレステスト
+stress-test-description = { $columns } 列で珠がすべて揃ったそろばんを追加し、数値エンジンの時間を計ってから、すべての珠が動く間のフレーム時間を測ります。
+stress-test-run = ストレステストを実行
+stress-test-running = 測定中…
+stress-test-size = { $columns } 列、珠 { $beads } 個
+stress-test-split = 合計を各列に分ける：{ $micros } µs
+stress-test-read = 珠から合計を読む：{ $micros } µs
+stress-test-set = 珠を合計に合わせる：{ $millis } ms
+stress-test-frames = フレーム時間：平均 { $average } ms、最悪 { $worst } ms（{ $fps } fps）
+log-stress-test = { $columns } 列のストレステスト：1 フレーム平均 { $average } ms、最悪 { $worst } ms
+log-inconsistent-long = そろばん { $abacus } の { $column } 列：珠は { $beads } を示していますが、桁の値は { $long } です
+log-inconsistent-column = そろばん { $abacus } の { $column } 列：珠は { $beads } を示していますが、列の表示は「{ $text }」です
+log-inconsistent-total = そろばん { $abacus }：珠は { $beads } を示していますが、合計は { $total }、表示は「{ $text }」です
+hud-total = 合計を画面上部に表示
+hud-text-size = 上部の合計のサイズ
+text-font = フォント
+text-font-default = 既定
+log-font-loaded = フォント { $name } を使用しています
+log-font-failed = フォント { $name } を読み込めませんでした：{ $error }
+presentation-mode = プレゼンテーションモード（F11、Esc で終了）
+log-kiosk-started = キオスクモード：{ $seconds } 秒間操作がないとアトラクトアニメーションを開始します
+attract-mode = デモモード
+attract-style = デモの値
+attract-random = ランダム
+attract-count-up = カウントアップ
+attract-interval = 値の間隔（秒）
+stopwatch-window = ストップウォッチ
+stopwatch-mode = 計時方法
+stopwatch-mode-stopwatch = ストップウォッチ
+stopwatch-mode-countdown = カウントダウン
+stopwatch-game-limit = ゲームの制限時間（{ $seconds } 秒）
+stopwatch-time = { $seconds } 秒
+stopwatch-start = スタート
+stopwatch-stop = ストップ
+stopwatch-lap = ラップ
+stopwatch-reset = リセット
+stopwatch-lap-entry = ラップ { $number }：{ $seconds } 秒（+{ $split } 秒）
+log-countdown-finished = カウントダウンが終了しました
+count-to-window = カウント
+count-to-target = 目標
+count-to-step = ステップ
+count-to-rate = 毎秒のステップ数
+count-to-start = 数える
+count-to-stop = 停止
+count-to-progress = 現在 { $value }、{ $target } まで数えています
+tally-window = 数取り器
+tally-enabled = トリガーを数える
+tally-key = トリガーキー
+tally-press-key = キーを押してください…
+tally-step = ステップ
+tally-count = { $count } 回数えました
+tally-reset = リセット
+log-remote-listening = リモート操作を ws://{ $address } で待ち受けています
+log-remote-failed = { $address } でリモート操作を開始できませんでした：{ $error }
+broadcast-window = 先生の配信
+broadcast-host = 選択中のそろばんを配信
+broadcast-host-failed = 配信を始められませんでした：{ $error }
+broadcast-join-code = 参加コード：{ $code }
+broadcast-loopback = ネットワークが見つからないため、このコンピューターからしか参加できません。
+broadcast-code-hint = 参加コード
+broadcast-join = 参加
+broadcast-invalid-code = 参加コードではありません
+broadcast-connecting = 接続中…
+broadcast-watching = 先生のそろばんを見ています
+broadcast-lost = 接続が切れました：{ $error }
+broadcast-leave = 退出
+log-broadcast-joined = 先生の配信に参加しました
+log-broadcast-lost = 先生の配信との接続が切れました：{ $error }
+log-broadcast-host-failed = 先生の配信を始められませんでした：{ $error }
+log-osc-listening = UDP ポート { $port } で OSC を待ち受けています
+log-osc-failed = UDP ポート { $port } で OSC を待ち受けられませんでした：{ $error }
+log-osc-bad-target = 「{ $target }」に OSC を送れません。ホスト:ポート の形式にしてください
+log-osc-bad-packet = 不正な OSC パケットを無視します：{ $error }
+log-osc-unknown = OSC メッセージ { $address } を無視します
+log-osc-bad-argument = OSC メッセージ { $address } には整数が必要です
+midi-window = MIDI 入力
+midi-no-ports = MIDI 入力がありません
+midi-refresh = 更新
+midi-connect = 接続
+midi-disconnect = 切断
+midi-connected = { $port } を受信中
+midi-first-note = 一の位の音
+midi-total-control = 合計を設定するコントロールチェンジ
+midi-last-message = 最後のメッセージ：{ $message }
+log-midi-connected = MIDI 入力 { $port } を受信しています
+log-midi-failed = MIDI 入力 { $port } を開けませんでした：{ $error }
+keybindings-window = キー割り当て
+keybindings-add = キーまたはマウスボタンを追加
+keybindings-remove = この割り当てを削除
+keybindings-press-key = キーを押してください…
+keybindings-cancel = キャンセル
+keybindings-reset = 初期設定に戻す
+action-clear = クリア
+action-undo = 元に戻す
+action-redo = やり直し
+action-rotate-drag = ドラッグで回転
+action-rotate-left = 左に回転
+action-rotate-right = 右に回転
+action-tilt-up = 上に傾ける
+action-tilt-down = 下に傾ける
+action-reset-rotation = 回転をリセット
+action-camera-focus = カメラの焦点を切り替え
+action-presentation = プレゼンテーションモード
+action-exit-presentation = プレゼンテーションモードを終了
+action-screenshot = スクリーンショット
+action-fullscreen = 全画面表示
+action-digit = { $digit } を入力
+action-enter-digits = 入力した値に設定
+action-erase-digit = 入力した数字を消す
+digit-entry = 設定値：{ $digits }
+action-column-left = 左の桁を選ぶ
+action-column-right = 右の桁を選ぶ
+action-column-up = 桁を一珠上げる
+action-column-down = 桁を一珠下げる
+action-deselect-column = 桁の選択を解除
+column-entry-hint = { $base } 進法
+column-place-value = 位取り ×{ $value }（{ $base }^{ $power }）
+money-mode = お金モード（下二桁はセント）
+money-symbol = 通貨記号
+money-window = お金
+money-mode-off = 表示オプションでお金モードをオンにすると、合計が金額として表示されます。
+value-model = 値の数え方
+value-model-place = 位取り
+value-model-counting = 数える（左に寄せた玉）
+horizontal-rods = 横向きの軸（レケンレック）
+two-tone-beads = 五つごとに玉の色を変える
+override-top-value = 上の玉の値
+override-base = 基数
+grooves = 軸の代わりに溝
+column-unit-value = ×{ $value }
+extra-decks = 追加の段（上段の上）
+deck-beads = 玉の数
+add-deck = 段を追加
+ones-on-left = 一の位を左端に
+add-column-left = + 左に列を追加
+add-column-right = + 右に列を追加
+remove-column = 列を削除
+bead-spacing = 珠の間隔
+column-spacing = 桁の間隔
+bead-radius = 珠の半径
+model-scale = モデルの拡大率
+bead-shape = 珠の形
+bead-shape-disk = 円盤
+bead-shape-bicone = そろばん玉
+bead-shape-sphere = 球
+bead-shape-torus = 輪
+models-window = モデル
+models-hint = 珠と軸の描画に使う assets/ 内の glTF (.glb) ファイル。空欄にすると標準の部品を使います。
+bead-model = 珠のモデル
+rod-model = 軸のモデル
+load-models = 読み込む
+built-in-models = 標準のモデル
+surfaces = テクスチャと質感
+bead-surface = 珠
+frame-surface = 枠
+surface-texture = テクスチャ
+surface-normal-map = 法線マップ
+surface-metallic = 金属感
+surface-roughness = 粗さ
+wood-grain = 木目
+wood-grain-tint = 木目の色
+wood-grain-rings = 年輪
+wood-grain-variation = ばらつき
+environment-window = 照明
+environment-classroom = 教室
+environment-studio = スタジオ
+environment-dark-stage = 暗い舞台
+ambient-color = 環境光の色
+ambient-brightness = 環境光の明るさ
+key-light-color = キーライトの色
+key-light-brightness = キーライトの明るさ
+fill-light-color = フィルライトの色
+fill-light-brightness = フィルライトの明るさ
+sky = 空
+sky-color = 背景色
+sky-gradient = グラデーション
+sky-environment-map = 環境マップ
+sky-top = 空の色
+sky-horizon = 地平線の色
+environment-map-hint = assets/ 内のプリフィルタ済み KTX2 キューブマップを両方指定すると、珠を照らします。
+diffuse-map = 拡散マップ
+specular-map = 鏡面マップ
+environment-intensity = 環境の明るさ
+table = そろばんの下にテーブル
+table-color = テーブルの色
+table-texture = テーブルのテクスチャ
+contact-shadows = やわらかい接地影
+frame-title = 枠のタイトル
+frame-title-hint = 学校名や生徒の名前
+frame-title-color = タイトルの色
+frame-title-size = タイトルの大きさ
+column-colors = 桁ごとの玉の色
+column-colors-first = 開始桁
+column-colors-width = 幅
+column-colors-every = 間隔
+add-column-color = 桁の色を追加
+column-colors-thousands = 千の位ごとに交互
+themes-window = テーマ
+themes-hint = テーマが見つかりません。assets/themes に .theme.ron ファイルを追加してください。
+panel-practice = 練習
+panel-dock = 配置:
+dock-left = 左
+dock-floating = ウィンドウ
+dock-right = 右
+section-scene = シーンのそろばん
+reset-window-layout = ウィンドウの配置をリセット
+fullscreen = 全画面表示（F10）
+log-replay-started = { $path } から { $count } 件の操作を再生しています
+log-replay-read-failed = リプレイ { $path } を読み込めませんでした：{ $error }
+log-replay-saved = { $count } 件の操作のリプレイを { $path } に保存しました
+log-replay-save-failed = リプレイ { $path } を保存できませんでした：{ $error }
+log-replay-missing-abacus = そろばん { $abacus } がないため、リプレイの操作を 1 件飛ばしました
+log-replay-finished = リプレイが終わりました。そろばんの値：{ $totals }
+log-replay-mismatch = リプレイは { $totals } で終わりましたが、記録は { $expected } で終わっています
+log-fullscreen-failed = 全画面表示にできませんでした：{ $error }
+log-saved-data-ignored = 保存された「{ $key }」を無視します：{ $error }
+log-save-failed = 「{ $key }」を保存できませんでした：{ $error }
+log-share-parameter-ignored = 共有リンクのパラメーター「{ $key }={ $value }」を無視します
+log-config-clamped = 設定「{ $name }」の { $fields } が範囲外だったため、設定パネルの範囲に収めました
+log-arg-not-a-number = { $flag } には整数が必要です（「{ $text }」ではなく）
+log-arg-unknown = 不明な引数「{ $arg }」を無視します
+log-arg-headless-without-replay = --headless は --replay と一緒にのみ使えます
+log-startup-config = 設定 { $path } で起動します
+log-startup-config-failed = 設定 { $path } を読み込めませんでした：{ $error }
+log-js-callback-failed = on_abacus_changed コールバックが失敗しました：{ $error }
+log-js-post-failed = 埋め込み元のページにそろばんの値を送れませんでした：{ $error }
+log-custom-model-failed = カスタムのそろばんモデルを読み込めませんでした。組み込みのモデルのままにします：{ $error }
@@ -0,0 +1,608 @@
+# 设置窗口
+clear-button = 清盘 (C)
+language = 语言
+
+add-abacus = + 添加算盘
+remove-abacus = 移除所选
+arrangement = 排列
+arrangement-row = 一行
+arrangement-grid = 网格
+camera-focus = 镜头焦点
+camera-focus-shared = 全部算盘
+camera-focus-selected = 所选算盘
+link-with = 联动算盘
+link-none = 无
+carries-into = 进位到
+abacus-label = 算盘 { $number }（{ $base } 进制）
+
+panel-structure = 结构
+columns = 档数
+top-beads = 上珠数（每档）
+bottom-beads = 下珠数（每档）
+top-bead-base-value = 上珠基数
+numeric-base = 进制
+bead-value-multiplier = 算珠倍数
+max-value = 最大值：{ $value }
+column-overrides = 单档设置（第 0 档为个位）：
+override-column = 档
+override-top = 上
+override-bottom = 下
+remove = 删除
+add-column-override = 添加单档设置
+
+section-animation = 动画
+bead-easing = 算珠缓动
+easing-linear = 线性
+easing-ease-out = 缓出
+easing-spring = 弹簧
+easing-snappy = 利落
+move-duration = 移动时长（秒）
+bead-stagger = 算珠间隔（秒）
+reduced-motion = 减少动态效果（算珠不播放动画）
+
+section-physics = 物理
+simulated-beads = 模拟算珠（质量、摩擦、回弹）
+
+section-display = 显示选项
+show-total-value = 显示总值
+show-column-values = 显示各档数值
+show-value-in-words = 显示数值读法
+digit-grouping = 数位分组
+grouping-off = 关闭
+grouping-locale = 跟随语言
+grouping-comma = 1,234,567
+grouping-space = 1 234 567
+digit-group-separator = ,
+group-column-colors = 每三档变换颜色
+ui-scale = 界面缩放
+
+panel-appearance = 外观
+bead-color = 算珠颜色
+bead-hover-color = 悬停颜色（非移动端）
+frame-color = 框架颜色
+background-color = 背景颜色
+value-text-size = 数值字号
+presets = 预设：
+preset-classic = 经典
+preset-high-contrast = 高对比度
+preset-deuteranopia-safe = 绿色弱友好
+preset-dark-background = 深色背景
+preset-applied = 已应用外观预设“{ $name }”。
+sound-theme = 声音主题
+sound-theme-wood = 木质
+sound-theme-glass = 玻璃
+sound-theme-metal = 金属
+
+section-sound = 声音与振动
+mute = 静音
+bead-click-volume = 算珠音量
+vibrate-on-tap = 点击时振动（移动网页）
+
+panel-controls = 控制
+reset-rotation = 重置旋转
+set-abacus-value = 设定算盘数值：
+enter-value = 输入数值
+set = 设定
+modify-abacus-value = 修改算盘数值：
+enter-amount = 输入数量
+add = 加
+subtract = 减
+log-setting-value = 将算盘总值设为：{ $value }
+log-modifying-value = 将算盘总值设为：{ $value }（由 { $current } { $operator } { $amount }）
+log-invalid-set = 设定输入无效：请输入非负整数。
+log-invalid-modify = 修改输入无效：请输入非负整数。
+log-abacus-missing = 找不到要修改数值的算盘。
+
+section-playback = 回放
+step-delay = 步进间隔（秒）
+play = 播放
+pause = 暂停
+step = 单步
+cancel = 取消
+pending-moves = 待执行的档位移动：{ $count }
+
+section-save-load = 保存/载入配置
+profile = 用户
+profile-guest = 访客
+profile-new-name = 新学生姓名
+profile-create = 添加用户
+config-name = 配置名称：
+save-config = 保存当前配置
+saved-config = 已保存的配置
+load-config = 载入所选配置
+delete-config = 删除所选配置
+log-config-saved = 配置已保存。
+log-config-name-missing = 请输入名称以保存配置。
+log-config-loaded = 已载入配置“{ $name }”。
+log-config-not-found = 未找到要载入的配置“{ $name }”。
+log-first-config-loaded = 已载入第一个可用配置“{ $name }”。
+log-no-config = 没有选中或可载入的配置。
+log-config-deleted = 已删除配置“{ $name }”。
+log-config-delete-missing = 未找到要删除的配置“{ $name }”。
+log-no-config-to-delete = 没有选中要删除的配置。
+log-rebuilding = 正在重建算盘结构
+log-cleared = 算盘已清盘
+
+# 欢迎窗口
+welcome-window = 欢迎使用算盘模拟器
+welcome-heading = 欢迎使用算盘模拟器！
+welcome-intro = 这个交互式模拟器让你探索世界各地不同类型的算盘。
+welcome-controls = 操作
+welcome-click = • 点击算珠可将其上下拨动
+welcome-select = • 有多个算盘时，点击其中一个即可选中它，用于设置和控制
+welcome-scroll = • 在某一档上滚动鼠标滚轮可逐一加减
+welcome-rotate = • 按住右键拖动可旋转三维视图
+welcome-touch = • 在触摸屏上，沿档拖动算珠即可拨动，双指移动可环绕视角，双指捏合可缩放，双击空白处可复原视角
+welcome-reset-rotation = • 使用“重置旋转”按钮恢复默认视角
+welcome-set-value = • 使用“设定数值”输入框设定指定数字
+welcome-clear = • 按 C 或 Delete（或“清盘”按钮）将算盘归零
+welcome-shake = • 摇一摇清盘：在空白处快速来回拖动，或摇动手机
+welcome-add-subtract = • 使用“加/减”进行计算
+welcome-bases = • 数字以所选进制显示（例如十六进制中 10 显示为“A”）
+welcome-save-load = • 保存和载入不同的算盘配置
+welcome-types = 算盘类型（可在保存/载入配置中选择）
+welcome-suanpan = • 中国算盘：上珠 2 颗各当 5，下珠 5 颗
+welcome-soroban = • 日本算盘：上珠 1 颗当 5，下珠 4 颗
+welcome-binary = • 二进制：表示二进制数（基数 2）
+welcome-custom = • 自定义：创建你自己的算盘配置！
+welcome-customization = 自定义
+welcome-you-can-customize = 你可以自定义：
+welcome-custom-columns = • 档数
+welcome-custom-beads = • 算珠数（上下两部分）
+welcome-custom-top-value = • 上珠的数值
+welcome-custom-base = • 进制（十进制、二进制、十六进制等）
+welcome-custom-colors = • 算珠和框架的颜色
+close = 关闭
+
+# 课程窗口
+lessons-window = 课程
+lesson-step-count = 第 { $step } 步，共 { $total } 步
+lesson-previous = 上一步
+lesson-show-me = 演示
+lesson-show-me-hint = 自动执行下一步
+lesson-restart = 重新开始
+lesson-exit = 退出课程
+lessons-completed = 已完成的课程（{ $count }）
+lesson-files = 文件中的课程
+lesson-error-file-base = 这节课是为 { $base } 进制编写的，请先把算盘切换到该进制。
+editor-window = 课程编辑器
+editor-title = 课程标题
+editor-intro = 亲自拨动算珠来录制课程：每拨一步，写下说明并保存为一个步骤。
+editor-start = 在所选算盘上开始录制
+editor-current-value = 算盘显示 { $value }
+editor-step-text = 这一步展示了什么：
+editor-step-hints = 提示（每行一条，可选）：
+editor-keep-step = 保存为步骤
+editor-export = 保存到 assets/lessons
+editor-copy = 复制为 RON
+editor-stop = 停止录制
+editor-exported = 已保存到 { $path }；下次启动时会出现在“文件中的课程”里。
+editor-copied = 课程已复制；请把它保存为 assets/lessons 中的 .lesson.ron 文件。
+editor-export-failed = 无法导出课程：{ $error }
+editor-abacus-gone = 正在录制的算盘已被移除，录制已停止。
+game-window = 目标游戏
+game-intro = 在时间用完前，把目标数拨到算盘上。
+game-difficulty = 难度
+game-easy = 简单（2 位）
+game-medium = 中等（4 位）
+game-hard = 困难（算盘进制下 5 位）
+game-start = 开始游戏
+game-stop = 停止
+game-skip = 跳过
+game-target = 目标：{ $target }
+game-target-base = 以 { $base } 进制书写
+game-time-left = 剩余时间：{ $seconds } 秒
+game-streak = 连胜：{ $streak }（最佳 { $best }）
+game-score = 得分：{ $score }
+game-solved = 用时 { $seconds } 秒，+{ $points } 分！
+game-time-up = 时间到！目标是 { $target }。
+game-best = 最佳连胜 { $best }，上次得分 { $score }
+stats-window = 统计
+stats-this-session = 本次练习
+stats-bead-moves = 拨珠次数：{ $count }
+stats-solved = 已解题目：{ $solved } / { $attempts }
+stats-accuracy = 正确率：{ $percent }%
+stats-average-time = 平均解题时间：{ $seconds } 秒
+stats-no-history = 下次练习后，这里会显示各次练习的进步情况。
+stats-progress = 各次练习的进步
+stats-plot-solved = 已解
+stats-plot-accuracy = 正确率 %
+stats-plot-time = 平均解题时间（秒）
+lesson-start = 开始课程
+lesson-multiplication = 珠算乘法
+lesson-division = 短除法
+lesson-square-root = 开平方（进阶）
+lesson-square-root-hint = 建议使用日本算盘（1/4）配置学习。
+multiplicand = 被乘数
+multiplier = 乘数
+dividend = 被除数
+divisor = 除数
+radicand = 被开方数
+log-lesson-started = 开始课程“{ $title }”
+log-game-started = 目标游戏已开始
+log-profile-loaded = 已加载用户“{ $name }”
+
+lesson-error-columns = 此题需要 { $required } 档，而算盘只有 { $available } 档。
+lesson-error-too-large = 此题对于当前进制来说太大了。
+lesson-error-divide-by-zero = 不能除以零。
+lesson-error-base = 请输入 { $base } 进制的数字。
+lesson-remainder = 余数：{ $value }
+lesson-root-so-far = 当前的根：{ $value }
+lesson-root = 根：{ $value }
+
+multiplication-title = 乘法：{ $multiplicand } × { $multiplier }
+multiplication-set-multiplicand = 在高亮的档上拨入被乘数 { $multiplicand }。
+multiplication-set-multiplier = 空出一档，再拨入乘数 { $multiplier }。
+multiplication-partial = 计算 { $a } × { $b } = { $partial }。把它加到积上，使其末位落在从右数第 { $place } 档。
+multiplication-done = 完成：{ $multiplicand } × { $multiplier } = { $product }，从右边各档读出。
+
+division-title = 除法：{ $dividend } ÷ { $divisor }
+division-set-divisor = 在高亮的档上拨入除数 { $divisor }。
+division-set-dividend = 在高亮的档上拨入被除数 { $dividend }。
+division-trial = { $prefix } 里有 { $quotient } 个 { $divisor }。在商的档位上记下试商 { $quotient }。
+division-subtract = 从 { $prefix } 中减去 { $quotient } × { $divisor } = { $subtrahend }，余下 { $difference }。
+division-done = 完成：{ $dividend } ÷ { $divisor } = { $quotient } 余 { $remainder }。商在中间，余数在右边。
+
+square-root-title = { $radicand } 的平方根
+square-root-set-radicand = 在右边的档上拨入被开方数 { $radicand }，并从右往左每两位分为一组。
+square-root-digit = 移下一组。满足 ({ $doubled_root } + d) × d ≤ { $prefix } 的最大数字 d 是 { $digit }。把它作为根的下一位。
+square-root-subtract = 从 { $prefix } 中减去 ({ $doubled_root } + { $digit }) × { $digit } = { $subtrahend }，余下 { $difference }。
+square-root-done = 完成：{ $radicand } 的平方根是 { $root }，余数 { $remainder }。根在左边，余数在右边。
+
+# 读屏播报
+announce-total = 算盘总值 { $total }
+announce-column = 第 { $column } 档为 { $value }
+overflow-toast = { $requested } 超出了这个算盘能表示的范围，已停在最大值 { $max }。
+
+macros-window = 宏
+macros-intro = 在所选算盘上录制拨珠、设置、加法和减法操作，然后在任意算盘上回放。
+macros-record = ⏺ 在所选算盘上录制
+macros-recording = 正在录制：已记录 { $count } 个操作
+macros-name = 名称
+macros-save = 停止并保存
+macros-discard = 放弃
+macros-speed = 回放速度
+macros-playing = 正在播放 { $name }：{ $step } / { $total }
+macros-stop = 停止
+macros-play = ▶ 播放
+macros-entry = { $name }（{ $count } 个操作）
+macros-empty = 还没有录制任何宏。
+macros-default-name = 宏 { $number }
+macros-abacus-gone = 算盘已被移除，宏已停止。
+
+snapshots-window = 快照
+snapshots-name = 快照名称
+snapshots-save = 保存快照
+snapshots-default-name = 快照 { $number }
+snapshots-empty = 保存所选算盘的算珠位置，稍后可以随时恢复。
+snapshots-restore = 恢复
+snapshots-wrong-columns = 需要 { $count } 档的算盘
+snapshots-entry = { $name }（{ $value }）
+
+history-window = 历史
+history-empty = 拨动一些算珠；所选算盘显示过的每个数值都会保存在这里，可以拖动回看。
+history-first = 第一个数值
+history-back = 上一个数值
+history-forward = 下一个数值
+history-latest = 最新数值
+history-position = 第 { $step } / { $count } 个数值：{ $value }
+
+oplog-window = 操作记录
+oplog-empty = 任何算盘上的每次拨珠、设置、加法和减法都会列在这里。
+oplog-entry = [{ $time }] 算盘 { $abacus }：{ $text }
+oplog-beads = 拨动了第 { $column } 档 → { $total }
+oplog-set = 设置 → { $total }
+oplog-add = +{ $amount } → { $total }
+oplog-subtract = −{ $amount } → { $total }
+oplog-format-csv = CSV
+oplog-format-json = JSON
+oplog-export = 导出
+oplog-exported = 已导出到 { $path }
+oplog-export-failed = 无法导出记录：{ $error }
+oplog-copy = 复制
+oplog-copied = 记录已复制到剪贴板。
+oplog-clear = 清空
+
+copy-share-link = 复制分享链接
+log-share-link-copied = 分享链接已复制到剪贴板
+
+console-window = 控制台
+console-hint = 输入命令，例如 set 1234（Tab 补全，↑↓ 历史）
+console-help = 命令：{ $commands }。set、add 和 sub 后接数字；columns、base、top 和 bottom 修改所选算盘的结构；preset 按名称载入已保存的配置；clear 将算盘清零；scripts 列出脚本，run 运行脚本，stop 停止脚本。
+console-unknown = 未知命令“{ $command }”；输入 help 查看命令列表。
+console-needs-number = { $command } 需要一个整数。
+console-out-of-range = { $command } 的取值范围是 { $min } 到 { $max }。
+console-needs-preset = preset 需要已保存配置的名称。
+console-no-preset = 没有与“{ $name }”匹配的已保存配置。
+console-several-presets = 有多个配置匹配：{ $names }
+console-no-abacus = 没有选中的算盘。
+
+console-needs-script = run 需要 assets/scripts 中脚本的名称。
+console-scripting-disabled = 脚本需要 scripting 功能：cargo run --features scripting
+console-no-scripts = 在 assets/scripts 中没有找到脚本。
+console-scripts = 脚本：{ $names }
+console-script-missing = 没有名为“{ $name }”的脚本；输入 scripts 查看列表。
+console-script-started = 正在运行 { $name }（{ $count } 步）
+console-script-failed = { $name } 运行失败：{ $error }
+console-script-stopped = 已停止 { $name }
+console-script-finished = { $name } 已完成
+
+import-config = 导入配置…
+export-config = 导出配置…
+log-config-imported = 已导入配置“{ $name }”，可在下方列表中加载。
+log-config-exported = 配置已导出到 { $path }
+log-config-file-failed = 无法使用配置文件：{ $error }
+
+dropped-config-window = 已添加配置
+dropped-config-question = “{ $name }”已加入已保存的配置。要将其应用到所选算盘吗？
+dropped-config-apply = 立即应用
+dropped-config-keep = 暂不应用
+log-dropped-file-ignored = 只能拖入 .ron 和 .json 配置文件，无法使用 { $path }
+
+save-scene = 保存场景…
+load-scene = 加载场景…
+log-scene-saved = 场景已保存到 { $path }
+log-scene-loaded = 已加载场景，共 { $count } 个算盘
+log-scene-file-failed = 无法使用场景文件：{ $error }
+
+screenshot = 截图 (F12)
+screenshot-hides-ui = 隐藏面板
+log-screenshot-saving = 正在将截图保存到 { $path }
+log-screenshot-failed = 无法保存截图：{ $error }
+
+turntable-window = 转盘导出
+turntable-intro = 在相机环绕所选算盘或播放宏时进行捕获，可用于幻灯片和练习纸。文件保存在 ~/.abacus-simulator/exports。
+turntable-motion = 运动方式
+turntable-orbit = 环绕算盘
+turntable-frames = 帧数
+turntable-format = 格式
+turntable-format-gif = 动态 GIF
+turntable-format-png = PNG 序列
+turntable-record = ⏺ 录制
+turntable-capturing = 正在捕获：{ $count } 帧
+turntable-encoding = 正在编码…
+turntable-saved = 已保存到 { $path }
+turntable-failed = 无法保存导出：{ $error }
+
+export-svg = 导出 SVG
+copy-svg = 复制 SVG
+log-svg-exported = 示意图已导出到 { $path }
+log-svg-failed = 无法导出示意图：{ $error }
+log-svg-copied = SVG 示意图已复制到剪贴板
+
+export-gltf = 导出 glTF
+log-gltf-exported = 3D 模型已导出到 { $path }
+log-gltf-failed = 无法导出 3D 模型：{ $error }
+
+flat-mode = 平面 2D 模式（低端设备上更流畅）
+graphics-quality = 画质
+quality-low = 低
+quality-medium = 中
+quality-high = 高
+stress-test-window = 压力测试
+stress-test-description = 添加一个有 { $columns } 列且珠子全满的算盘，测量数值引擎的耗时，然后在所有珠子移动时测量帧时间。
+stress-test-run = 运行压力测试
+stress-test-running = 正在测量…
+stress-test-size = { $columns } 列，{ $beads } 颗珠子
+stress-test-split = 将总数拆分到各列：{ $micros } µs
+stress-test-read = 从珠子读取总数：{ $micros } µs
+stress-test-set = 将珠子设置为总数：{ $millis } ms
+stress-test-frames = 帧时间：平均 { $average } ms，最差 { $worst } ms（{ $fps } fps）
+log-stress-test = { $columns } 列的压力测试：平均每帧 { $average } ms，最差 { $worst } ms
+log-inconsistent-long = 算盘 { $abacus } 第 { $column } 列：珠子显示 { $beads }，但该档记录为 { $long }
+log-inconsistent-column = 算盘 { $abacus } 第 { $column } 列：珠子显示 { $beads }，但该列显示“{ $text }”
+log-inconsistent-total = 算盘 { $abacus }：珠子显示 { $beads }，但总数为 { $total }，显示为“{ $text }”
+hud-total = 在屏幕顶部显示总数
+hud-text-size = 顶部总数大小
+text-font = 字体
+text-font-default = 默认
+log-font-loaded = 正在使用字体 { $name }
+log-font-failed = 无法加载字体 { $name }：{ $error }
+presentation-mode = 演示模式（F11，按 Esc 退出）
+log-kiosk-started = 展台模式：无人触摸 { $seconds } 秒后开始播放演示动画
+attract-mode = 演示模式
+attract-style = 演示数值
+attract-random = 随机
+attract-count-up = 逐一递增
+attract-interval = 数值间隔（秒）
+stopwatch-window = 秒表
+stopwatch-mode = 计时方式
+stopwatch-mode-stopwatch = 秒表
+stopwatch-mode-countdown = 倒计时
+stopwatch-game-limit = 游戏时限（{ $seconds } 秒）
+stopwatch-time = { $seconds } 秒
+stopwatch-start = 开始
+stopwatch-stop = 停止
+stopwatch-lap = 计圈
+stopwatch-reset = 重置
+stopwatch-lap-entry = 第 { $number } 圈：{ $seconds } 秒（+{ $split } 秒）
+log-countdown-finished = 倒计时结束
+count-to-window = 数到
+count-to-target = 目标
+count-to-step = 步长
+count-to-rate = 每秒步数
+count-to-start = 开始数
+count-to-stop = 停止
+count-to-progress = 当前 { $value }，数到 { $target }
+tally-window = 计数器
+tally-enabled = 统计触发
+tally-key = 触发键
+tally-press-key = 请按一个键…
+tally-step = 步长
+tally-count = 已计数 { $count } 次
+tally-reset = 重置
+log-remote-listening = 远程控制正在监听 ws://{ $address }
+log-remote-failed = 无法在 { $address } 启动远程控制：{ $error }
+broadcast-window = 教师广播
+broadcast-host = 广播选中的算盘
+broadcast-host-failed = 无法开始广播：{ $error }
+broadcast-join-code = 加入码：{ $code }
+broadcast-loopback = 未找到网络，只有这台电脑可以加入。
+broadcast-code-hint = 加入码
+broadcast-join = 加入
+broadcast-invalid-code = 这不是有效的加入码
+broadcast-connecting = 正在连接…
+broadcast-watching = 正在观看老师的算盘
+broadcast-lost = 连接已断开：{ $error }
+broadcast-leave = 离开
+log-broadcast-joined = 已加入老师的广播
+log-broadcast-lost = 与老师的广播断开：{ $error }
+log-broadcast-host-failed = 无法开始老师广播：{ $error }
+log-osc-listening = 正在 UDP 端口 { $port } 上监听 OSC
+log-osc-failed = 无法在 UDP 端口 { $port } 上监听 OSC：{ $error }
+log-osc-bad-target = 无法向“{ $target }”发送 OSC；应为 主机:端口
+log-osc-bad-packet = 忽略格式错误的 OSC 数据包：{ $error }
+log-osc-unknown = 忽略 OSC 消息 { $address }
+log-osc-bad-argument = OSC 消息 { $address } 需要一个整数
+midi-window = MIDI 输入
+midi-no-ports = 没有 MIDI 输入
+midi-refresh = 刷新
+midi-connect = 连接
+midi-disconnect = 断开
+midi-connected = 正在接收 { $port }
+midi-first-note = 个位列的音符
+midi-total-control = 设置总数的控制器
+midi-last-message = 最近的消息：{ $message }
+log-midi-connected = 正在接收 MIDI 输入 { $port }
+log-midi-failed = 无法打开 MIDI 输入 { $port }：{ $error }
+keybindings-window = 快捷键
+keybindings-add = 添加按键或鼠标按钮
+keybindings-remove = 删除此绑定
+keybindings-press-key = 请按一个键…
+keybindings-cancel = 取消
+keybindings-reset = 恢复默认
+action-clear = 清零
+action-undo = 撤销
+action-redo = 重做
+action-rotate-drag = 拖动旋转
+action-rotate-left = 向左旋转
+action-rotate-right = 向右旋转
+action-tilt-up = 向上倾斜
+action-tilt-down = 向下倾斜
+action-reset-rotation = 重置旋转
+action-camera-focus = 切换镜头焦点
+action-presentation = 演示模式
+action-exit-presentation = 退出演示模式
+action-screenshot = 截图
+action-fullscreen = 全屏
+action-digit = 输入 { $digit }
+action-enter-digits = 设置输入的数值
+action-erase-digit = 删除输入的数字
+digit-entry = 设置为：{ $digits }
+action-column-left = 选择左边一列
+action-column-right = 选择右边一列
+action-column-up = 本列加一珠
+action-column-down = 本列减一珠
+action-deselect-column = 取消选择列
+column-entry-hint = { $base } 进制
+column-place-value = 位值 ×{ $value }（{ $base }^{ $power }）
+money-mode = 货币模式（最后两列为分）
+money-symbol = 货币符号
+money-window = 货币
+money-mode-off = 在显示选项中开启货币模式，即可将总数显示为金额。
+value-model = 计值方式
+value-model-place = 位值
+value-model-counting = 计数（向左拨动的珠子）
+horizontal-rods = 横向珠杆（计数架）
+two-tone-beads = 每五颗珠子换一种颜色
+override-top-value = 上珠值
+override-base = 进制
+grooves = 用槽代替珠杆
+column-unit-value = ×{ $value }
+extra-decks = 额外的珠档（在上档之上）
+deck-beads = 珠数
+add-deck = 添加珠档
+ones-on-left = 个位在左侧
+add-column-left = + 左侧加一列
+add-column-right = + 右侧加一列
+remove-column = 移除一列
+bead-spacing = 珠距
+column-spacing = 档距
+bead-radius = 算珠半径
+model-scale = 模型缩放
+bead-shape = 算珠形状
+bead-shape-disk = 圆饼
+bead-shape-bicone = 双锥
+bead-shape-sphere = 球形
+bead-shape-torus = 圆环
+models-window = 模型
+models-hint = 用于绘制算珠和档的 assets/ 下的 glTF (.glb) 文件。留空则使用内置部件。
+bead-model = 算珠模型
+rod-model = 档模型
+load-models = 加载
+built-in-models = 内置模型
+surfaces = 纹理与质感
+bead-surface = 算珠
+frame-surface = 框架
+surface-texture = 纹理
+surface-normal-map = 法线贴图
+surface-metallic = 金属度
+surface-roughness = 粗糙度
+wood-grain = 木纹
+wood-grain-tint = 木纹颜色
+wood-grain-rings = 年轮
+wood-grain-variation = 变化
+environment-window = 照明
+environment-classroom = 教室
+environment-studio = 摄影棚
+environment-dark-stage = 暗色舞台
+ambient-color = 环境光颜色
+ambient-brightness = 环境光亮度
+key-light-color = 主光颜色
+key-light-brightness = 主光亮度
+fill-light-color = 补光颜色
+fill-light-brightness = 补光亮度
+sky = 天空
+sky-color = 背景色
+sky-gradient = 渐变
+sky-environment-map = 环境贴图
+sky-top = 天空颜色
+sky-horizon = 地平线颜色
+environment-map-hint = 同时设置 assets/ 下预过滤的 KTX2 立方体贴图后，会用它们照亮算珠。
+diffuse-map = 漫反射贴图
+specular-map = 镜面贴图
+environment-intensity = 环境亮度
+table = 算盘下方的桌面
+table-color = 桌面颜色
+table-texture = 桌面纹理
+contact-shadows = 柔和接触阴影
+frame-title = 框架标题
+frame-title-hint = 学校或学生姓名
+frame-title-color = 标题颜色
+frame-title-size = 标题大小
+column-colors = 按列的算珠颜色
+column-colors-first = 起始列
+column-colors-width = 宽度
+column-colors-every = 间隔
+add-column-color = 添加列颜色
+column-colors-thousands = 千位分组交替
+themes-window = 主题
+themes-hint = 未找到主题。请将 .theme.ron 文件添加到 assets/themes。
+panel-practice = 练习
+panel-dock = 停靠：
+dock-left = 左侧
+dock-floating = 窗口
+dock-right = 右侧
+section-scene = 场景中的算盘
+reset-window-layout = 重置窗口布局
+fullscreen = 全屏（F10）
+log-replay-started = 正在从 { $path } 回放 { $count } 个操作
+log-replay-read-failed = 无法读取回放 { $path }：{ $error }
+log-replay-saved = 已将 { $count } 个操作的回放保存到 { $path }
+log-replay-save-failed = 无法保存回放 { $path }：{ $error }
+log-replay-missing-abacus = 找不到算盘 { $abacus }，回放跳过了一个操作
+log-replay-finished = 回放结束；算盘显示 { $totals }
+log-replay-mismatch = 回放结束于 { $totals }，但录制结束于 { $expected }
+log-fullscreen-failed = 无法进入全屏：{ $error }
+log-saved-data-ignored = 忽略已保存的“{ $key }”：{ $error }
+log-save-failed = 无法保存“{ $key }”：{ $error }
+log-share-parameter-ignored = 忽略分享链接参数“{ $key }={ $value }”
+log-config-clamped = 配置“{ $name }”的 { $fields } 超出范围，已调整到设置面板的范围内
+log-arg-not-a-number = { $flag } 需要一个整数，而不是“{ $text }”
+log-arg-unknown = 忽略未知参数“{ $arg }”
+log-arg-headless-without-replay = --headless 只能与 --replay 一起使用
+log-startup-config = 使用配置 { $path } 启动
+log-startup-config-failed = 无法加载配置 { $path }：{ $error }
+log-js-callback-failed = on_abacus_changed 回调失败：{ $error }
+log-js-post-failed = 无法将算盘的值发送给嵌入页面：{ $error }
+log-custom-model-failed = 无法加载自定义算盘模型，继续使用内置模型：{ $error }
@@ -0,0 +1,65 @@
+// Wood grain for the beads and frame (see src/wood_grain.rs). The grain is worked out in
+// each piece's own space, so it moves with the piece, and seeded from its mesh tag.
+
+#import bevy_pbr::{
+    mesh_functions,
+    pbr_fragment::pbr_input_from_standard_material,
+    pbr_functions::alpha_discard,
+    forward_io::{VertexOutput, FragmentOutput},
+    pbr_functions::{apply_pbr_lighting, main_pass_post_lighting_processing},
+}
+
+struct WoodGrain {
+    tint: vec4<f32>,
+    ring_scale: f32,
+    variation: f32,
+    strength: f32,
+}
+
+@group(2) @binding(100) var<uniform> wood_grain: WoodGrain;
+
+fn hash(p: vec3<f32>) -> f32 {
+    return fract(sin(dot(p, vec3(12.9898, 78.233, 37.719))) * 43758.5453);
+}
+
+// Smooth value noise, for the rings' wobble
+fn noise(p: vec3<f32>) -> f32 {
+    let i = floor(p);
+    let f = fract(p);
+    let u = f * f * (3.0 - 2.0 * f);
+    return mix(
+        mix(mix(hash(i), hash(i + vec3(1.0, 0.0, 0.0)), u.x),
+            mix(hash(i + vec3(0.0, 1.0, 0.0)), hash(i + vec3(1.0, 1.0, 0.0)), u.x), u.y),
+        mix(mix(hash(i + vec3(0.0, 0.0, 1.0)), hash(i + vec3(1.0, 0.0, 1.0)), u.x),
+            mix(hash(i + vec3(0.0, 1.0, 1.0)), hash(i + vec3(1.0, 1.0, 1.0)), u.x), u.y),
+        u.z);
+}
+
+@fragment
+fn fragment(in: VertexOutput, @builtin(front_facing) is_front: bool) -> FragmentOutput {
+    var pbr_input = pbr_input_from_standard_material(in, is_front);
+
+    if wood_grain.strength > 0.0 {
+        let local_from_world = mesh_functions::get_local_from_world(in.instance_index);
+        let local = (local_from_world * vec4(in.world_position.xyz, 1.0)).xyz;
+        // Each piece is cut from a different part of the log
+        let seed = f32(mesh_functions::get_tag(in.instance_index));
+        let offset = vec3(hash(vec3(seed, 0.0, 0.0)), hash(vec3(0.0, seed, 0.0)), hash(vec3(0.0, 0.0, seed))) - 0.5;
+        let p = local + offset * wood_grain.variation * 2.0;
+        // Rings around the rod, wobbled and stretched along it like a turned bead
+        let wobble = noise(p * vec3(4.0, 4.0, 1.0)) * wood_grain.variation;
+        let ring = fract(length(p.xy) * wood_grain.ring_scale + wobble * 3.0);
+        let late_wood = smoothstep(0.0, 0.25, ring) * (1.0 - smoothstep(0.55, 1.0, ring));
+        let streaks = noise(p * vec3(40.0, 40.0, 2.0)) * 0.15 * wood_grain.variation;
+        let grain = mix(wood_grain.tint.rgb, vec3(1.0), clamp(late_wood + streaks, 0.0, 1.0));
+        let color = pbr_input.material.base_color.rgb * mix(vec3(1.0), grain, wood_grain.strength);
+        pbr_input.material.base_color = vec4(color, pbr_input.material.base_color.a);
+    }
+
+    pbr_input.material.base_color = alpha_discard(pbr_input.material, pbr_input.material.base_color);
+
+    var out: FragmentOutput;
+    out.color = apply_pbr_lighting(pbr_input);
+    out.color = main_pass_post_lighting_processing(pbr_input, out.color);
+    return out;
+}
@@ -0,0 +1,67 @@
+//! Times the value engine on wide and mixed-radix abaci: splitting a total into the longs
+//! that show it, as setting a total does, and reading the total back from the longs.
+//! Run with `cargo bench`.
+
+use abacus_simulator::value_engine::{ColumnLayout, DeckLayout, ValueEngine, ValueModel};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::hint::black_box;
+
+/// A column of base `radix`: one top bead worth half the base, and enough bottom beads
+/// to count up to it, as on a soroban.
+fn column(radix: u64) -> ColumnLayout {
+    ColumnLayout {
+        decks: vec![
+            DeckLayout { bead_count: 1, bead_value: radix / 2 },
+            DeckLayout { bead_count: (radix / 2 - 1) as usize, bead_value: 1 },
+        ],
+        bead_value_multiplier: 1,
+        radix,
+    }
+}
+
+/// Decimal soroban columns, up to the widest abacus the panel allows.
+fn decimal(column_count: usize) -> Vec<ColumnLayout> {
+    (0..column_count).map(|_| column(10)).collect()
+}
+
+/// Columns cycling through bases 10, 12 and 20, like old money and measures.
+fn mixed_radix(column_count: usize) -> Vec<ColumnLayout> {
+    (0..column_count).map(|i| column([10, 12, 20][i % 3])).collect()
+}
+
+fn configurations() -> Vec<(String, Vec<ColumnLayout>, ValueModel)> {
+    vec![
+        ("decimal-13".to_string(), decimal(13), ValueModel::PlaceValue),
+        ("decimal-200".to_string(), decimal(200), ValueModel::PlaceValue),
+        ("mixed-radix-15".to_string(), mixed_radix(15), ValueModel::PlaceValue),
+        ("mixed-radix-200".to_string(), mixed_radix(200), ValueModel::PlaceValue),
+        ("counting-200".to_string(), decimal(200), ValueModel::Counting),
+    ]
+}
+
+fn set_total_value(c: &mut Criterion) {
+    let mut group = c.benchmark_group("set_total_value");
+    for (name, columns, value_model) in configurations() {
+        let engine = ValueEngine { columns: &columns, value_model };
+        let total = engine.max_total_value() / 3;
+        group.bench_with_input(BenchmarkId::from_parameter(name), &total, |b, &total| {
+            b.iter(|| engine.long_values_for_total(black_box(total)));
+        });
+    }
+    group.finish();
+}
+
+fn get_total_value(c: &mut Criterion) {
+    let mut group = c.benchmark_group("get_total_value");
+    for (name, columns, value_model) in configurations() {
+        let engine = ValueEngine { columns: &columns, value_model };
+        let long_values = engine.long_values_for_total(engine.max_total_value() / 3);
+        group.bench_with_input(BenchmarkId::from_parameter(name), &long_values, |b, long_values| {
+            b.iter(|| engine.total_value(black_box(long_values)));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, set_total_value, get_total_value);
+criterion_main!(benches);
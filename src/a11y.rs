@@ -0,0 +1,63 @@
+use bevy::prelude::*;
+use bevy::a11y::AccessibilityNode;
+use bevy::a11y::accesskit::{Live, Node, Role};
+
+use crate::abacus::*;
+use crate::format_number_in_base;
+use crate::localization::*;
+use crate::scene_layout::SelectedAbacus;
+
+/// Polite live region that screen readers announce whenever the abacus changes.
+#[derive(Component)]
+pub struct AbacusAnnouncer;
+
+/// Column values from the previous announcement, to name the column that changed.
+#[derive(Resource, Default)]
+pub struct AnnouncedColumns {
+    values: Vec<u64>,
+}
+
+pub fn spawn_announcer(mut commands: Commands, localization: Res<Localization>) {
+    let mut node = Node::new(Role::Status);
+    node.set_live(Live::Polite);
+    node.set_label(tr!(localization, "announce-total", total = "0"));
+    commands.spawn((AbacusAnnouncer, AccessibilityNode(node), Name::new("Abacus Announcer")));
+}
+
+/// Runs after the totals are recomputed on `AbacusChanged`.
+pub fn announce_abacus_changes(
+    abacus_query: Query<&Abacus>,
+    abacus_long_query: Query<&AbacusLong>,
+    mut announced: ResMut<AnnouncedColumns>,
+    mut announcer_query: Query<&mut AccessibilityNode, With<AbacusAnnouncer>>,
+    localization: Res<Localization>,
+    selected: Res<SelectedAbacus>,
+) {
+    // Only the abacus being worked on is read out
+    let Some(abacus) = selected.0.and_then(|entity| abacus_query.get(entity).ok()) else {
+        return;
+    };
+    let base = abacus.text_base();
+
+    let values: Vec<u64> = (0..abacus.longs.len())
+        .map(|i| abacus.get_column_value(i, &abacus_long_query))
+        .collect();
+
+    // Columns are announced counting from the ones column, which is column 1
+    let changed_columns: Vec<String> = values.iter().enumerate()
+        .filter(|&(i, value)| announced.values.get(i) != Some(value))
+        .map(|(i, &value)| tr!(localization, "announce-column", column = i + 1, value = format_number_in_base(value, base)))
+        .collect();
+
+    let mut announcement = tr!(localization, "announce-total", total = format_number_in_base(abacus.total_value, base));
+    // A rebuilt abacus changes every column; only the total is worth reading out then
+    if !changed_columns.is_empty() && announced.values.len() == values.len() {
+        announcement.push_str(". ");
+        announcement.push_str(&changed_columns.join(", "));
+    }
+    announced.values = values;
+
+    for mut node in &mut announcer_query {
+        node.set_label(announcement.clone());
+    }
+}
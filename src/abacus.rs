@@ -1,20 +1,53 @@
 use bevy::prelude::*;
 use std::f32::consts::PI;
 use bevy::color::palettes::tailwind;
+use bevy::picking::events::Scroll;
+use bevy::math::curve::{Curve, EaseFunction, EasingCurve};
+use bevy::asset::RenderAssetUsages;
+use bevy::render::mesh::{Indices, PrimitiveTopology};
+
+// The value math is in the library, where the benchmarks can reach it
+pub use abacus_simulator::value_engine::{ColumnLayout, DeckLayout, ValueEngine, ValueModel, MAX_COLUMNS};
+
+use crate::column_colors::{resting_bead_material, ColumnColored};
+use crate::operations::{AbacusOperation, ColumnOpQueue, OperationPerformed};
+use crate::scene_layout::Billboard;
+use crate::touch_gestures::TouchDragged;
+use crate::wood_grain::AbacusMaterial;
 
 #[derive(Event)]
 pub struct AbacusChanged;
 
 pub const BEAD_HEIGHT: f32 = 0.4;
+/// Radius of the bead mesh; beads of other sizes are scaled from it.
+pub const BEAD_RADIUS: f32 = 0.5;
 pub const BEAD_SPACING: f32 = 0.5;
 pub const LONG_SPACING: f32 = 0.8;
 pub const COLUMN_SPACING: f32 = 1.1;
+/// Abaci wider than this draw their beads with the coarser shared mesh. Every bead of
+/// such an abacus still shares that one mesh and its materials, so they are drawn in
+/// instanced batches, just with fewer vertices each.
+pub const LOD_COLUMNS: usize = 40;
+/// Most beads a top deck can have, as on the Nepōhualtzintzin.
+pub const MAX_TOP_BEADS: usize = 3;
+/// Most decks that can stack above the usual two.
+pub const MAX_EXTRA_DECKS: usize = 8;
 pub const ROW_SPACING: f32 = 0.4;
 //pub const BEAD_COUNT: usize = 5;
 pub const FRAME_THICKNESS: f32 = 0.1;
+/// A groove is a rod flattened into a wide, shallow slot, set in behind the beads.
+const GROOVE_SCALE: Vec2 = Vec2::new(2.5, 0.3);
+const GROOVE_DEPTH: f32 = 0.55;
+/// Size of the value-in-words line relative to the total.
+pub const VALUE_IN_WORDS_SCALE: f32 = 0.4;
 
 pub const BEAD_NORMAL_COLOR: Srgba = tailwind::RED_600;
 pub const BEAD_HOVER_COLOR: Srgba = tailwind::RED_200;
+pub const BEAD_HIGHLIGHT_COLOR: Srgba = tailwind::AMBER_400;
+/// The second color of two-tone rods, as on a rekenrek.
+pub const BEAD_ALT_COLOR: Srgba = tailwind::STONE_100;
+/// Beads per color block on two-tone rods.
+pub const TWO_TONE_GROUP: u64 = 5;
 
 pub const FRAME_COLOR: Srgba = tailwind::ZINC_700;
 
@@ -26,15 +59,170 @@ pub struct BelongsTo(pub Entity);
 #[relationship_target(relationship = BelongsTo)]
 pub struct BeadsOf(Vec<Entity>);
 
+/// A bead drawn in the second color of a two-tone rod.
 #[derive(Component)]
+pub struct AltBead;
+
+/// Profile of every bead, turned about the rod. All fit the same radius and height.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect, serde::Serialize, serde::Deserialize)]
+pub enum BeadShape {
+    /// A flat-sided puck.
+    #[default]
+    Disk,
+    /// Two cones meeting at a sharp rim, as on a suanpan or soroban.
+    Bicone,
+    /// Rounded all over, squashed to the bead's height.
+    Sphere,
+    /// A ring around the rod.
+    Torus,
+}
+
+impl BeadShape {
+    pub const ALL: [BeadShape; 4] = [BeadShape::Disk, BeadShape::Bicone, BeadShape::Sphere, BeadShape::Torus];
+
+    /// Localization id of the shape's display name.
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            BeadShape::Disk => "bead-shape-disk",
+            BeadShape::Bicone => "bead-shape-bicone",
+            BeadShape::Sphere => "bead-shape-sphere",
+            BeadShape::Torus => "bead-shape-torus",
+        }
+    }
+
+    /// The shape's outline from the bottom of the bead to the top, as (radius, height)
+    /// points with the outward normal at each. A corner repeats its point with the
+    /// normal of the side beyond it.
+    fn profile(&self, resolution: u32) -> Vec<(Vec2, Vec2)> {
+        let r = BEAD_RADIUS;
+        let h = BEAD_HEIGHT / 2.0;
+        match self {
+            BeadShape::Disk => vec![
+                (Vec2::new(0.0, -h), Vec2::NEG_Y),
+                (Vec2::new(r, -h), Vec2::NEG_Y),
+                (Vec2::new(r, -h), Vec2::X),
+                (Vec2::new(r, h), Vec2::X),
+                (Vec2::new(r, h), Vec2::Y),
+                (Vec2::new(0.0, h), Vec2::Y),
+            ],
+            BeadShape::Bicone => {
+                // Flattened where the bead meets its neighbours on the rod
+                let flat = 0.15;
+                let lower = Vec2::new(h, flat - r).normalize();
+                let upper = Vec2::new(h, r - flat).normalize();
+                vec![
+                    (Vec2::new(0.0, -h), Vec2::NEG_Y),
+                    (Vec2::new(flat, -h), Vec2::NEG_Y),
+                    (Vec2::new(flat, -h), lower),
+                    (Vec2::new(r, 0.0), lower),
+                    (Vec2::new(r, 0.0), upper),
+                    (Vec2::new(flat, h), upper),
+                    (Vec2::new(flat, h), Vec2::Y),
+                    (Vec2::new(0.0, h), Vec2::Y),
+                ]
+            }
+            BeadShape::Sphere => {
+                let steps = (resolution / 2).max(4);
+                (0..=steps).map(|i| {
+                    let angle = (i as f32 / steps as f32 - 0.5) * PI;
+                    let (sin, cos) = angle.sin_cos();
+                    (Vec2::new(r * cos, h * sin), Vec2::new(cos / r, sin / h).normalize())
+                }).collect()
+            }
+            BeadShape::Torus => {
+                // The tube is as thick as the bead is high, leaving room for the rod
+                let steps = resolution.max(8);
+                let center = r - h;
+                (0..=steps).map(|i| {
+                    let angle = (i as f32 / steps as f32 * 2.0 - 1.0) * PI;
+                    let (sin, cos) = angle.sin_cos();
+                    (Vec2::new(center + h * cos, h * sin), Vec2::new(cos, sin))
+                }).collect()
+            }
+        }
+    }
+}
+
+/// Distances and sizes abaci are drawn with, for compact or exaggerated layouts. Changing
+/// them moves the parts of every abacus rather than rebuilding it.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct Proportions {
+    /// Distance between neighbouring beads on a rod.
+    pub bead_spacing: f32,
+    /// Distance between neighbouring rods.
+    pub column_spacing: f32,
+    pub bead_radius: f32,
+    /// Scale of the whole abacus, texts included.
+    pub model_scale: f32,
+}
+
+impl Default for Proportions {
+    fn default() -> Self {
+        Self {
+            bead_spacing: BEAD_SPACING,
+            column_spacing: COLUMN_SPACING,
+            bead_radius: BEAD_RADIUS,
+            model_scale: 1.0,
+        }
+    }
+}
+
+impl Proportions {
+    /// Scale of a bead's transform, which widens the shared mesh without thickening it.
+    pub fn bead_scale(&self) -> Vec3 {
+        let scale = self.bead_radius / BEAD_RADIUS;
+        Vec3::new(scale, scale, 1.0)
+    }
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
 #[require(Transform)]
 pub struct AbacusBead {
     pub value: u64,
     pub target: Vec3,
+    pub start: Vec3,  // Where the current move began
+    pub elapsed: f32, // Seconds into the current move; negative while waiting out its stagger
+}
+
+/// Easing applied to bead moves by `animate_beads`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BeadEasing {
+    Linear,
+    #[default]
+    EaseOut,
+    Spring,
+    Snappy,
+}
+
+impl BeadEasing {
+    pub const ALL: [BeadEasing; 4] = [BeadEasing::Linear, BeadEasing::EaseOut, BeadEasing::Spring, BeadEasing::Snappy];
+
+    /// Localization id of the easing's display name.
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            BeadEasing::Linear => "easing-linear",
+            BeadEasing::EaseOut => "easing-ease-out",
+            BeadEasing::Spring => "easing-spring",
+            BeadEasing::Snappy => "easing-snappy",
+        }
+    }
+
+    /// Eased progress for a linear progress `t` in 0..=1.
+    pub fn sample(&self, t: f32) -> f32 {
+        let ease_function = match self {
+            BeadEasing::Linear => EaseFunction::Linear,
+            BeadEasing::EaseOut => EaseFunction::CubicOut,
+            BeadEasing::Spring => EaseFunction::BackOut,
+            BeadEasing::Snappy => EaseFunction::QuinticOut,
+        };
+        EasingCurve::new(0.0, 1.0, ease_function).sample_clamped(t)
+    }
 }
 
 #[cfg(not(target_arch = "wasm32"))]
-fn is_mobile_device() -> bool {
+pub fn is_mobile_device() -> bool {
     false // Default to desktop for non-wasm builds
 }
 
@@ -44,27 +232,69 @@ use wasm_bindgen::prelude::*;
 #[cfg(target_arch = "wasm32")]
 #[wasm_bindgen]
 extern "C" {
-    fn is_mobile_device() -> bool;
+    pub fn is_mobile_device() -> bool;
+}
+
+/// A bead of `shape` around its local Z axis, with `resolution` segments around the rim.
+/// Every bead shares one, held in the settings.
+pub fn bead_mesh(shape: BeadShape, resolution: u32) -> Mesh {
+    lathe_mesh(&shape.profile(resolution), resolution)
+}
+
+/// Turns a profile of (radius, height) points and their outward normals about the Z axis.
+pub fn lathe_mesh(profile: &[(Vec2, Vec2)], resolution: u32) -> Mesh {
+    let resolution = resolution.max(3);
+    let ring = resolution + 1; // The seam is doubled so the texture wraps cleanly
+    let mut positions = Vec::new();
+    let mut normals = Vec::new();
+    let mut uvs = Vec::new();
+    for (i, &(point, normal)) in profile.iter().enumerate() {
+        for j in 0..ring {
+            let (sin, cos) = (j as f32 / resolution as f32 * 2.0 * PI).sin_cos();
+            positions.push([point.x * cos, point.x * sin, point.y]);
+            normals.push([normal.x * cos, normal.x * sin, normal.y]);
+            uvs.push([j as f32 / resolution as f32, i as f32 / (profile.len() - 1).max(1) as f32]);
+        }
+    }
+    let mut indices = Vec::new();
+    for i in 0..profile.len().saturating_sub(1) as u32 {
+        for j in 0..resolution {
+            let (a, b) = (i * ring + j, (i + 1) * ring + j);
+            indices.extend([a, a + 1, b, b, a + 1, b + 1]);
+        }
+    }
+    Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::default())
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+        .with_inserted_indices(Indices::U32(indices))
+}
+
+/// A rod one unit long, stretched to each column's height by its transform.
+pub fn rod_mesh() -> Mesh {
+    Extrusion::new(Circle::new(FRAME_THICKNESS), 1.0).into()
 }
 
 pub fn spawn_abacus_bead (
     commands: &mut Commands,
-    meshes: &mut ResMut<Assets<Mesh>>,
     value: u64,
-    bead_material_handle: &Handle<StandardMaterial>,
-    bead_hover_material_handle: &Handle<StandardMaterial>,
+    bead_mesh_handle: &Handle<Mesh>,
+    bead_material_handle: &Handle<AbacusMaterial>,
+    bead_scale: Vec3,
 ) -> Entity {
     let norm_material = bead_material_handle.clone();
-    let hover_material = bead_hover_material_handle.clone();
 
     let mut entity_builder = commands.spawn(
         (AbacusBead {
             value: value,
             target: Vec3::new(0.0, 0.0, 0.0),
+            start: Vec3::new(0.0, 0.0, 0.0),
+            elapsed: 0.0,
         },
             Transform::from_xyz(0.0, 0.0, 0.0)
-                .with_rotation(Quat::from_rotation_x(PI / 2.0)),
-            Mesh3d(meshes.add(Extrusion::new(Circle::default(), BEAD_HEIGHT))),
+                .with_rotation(Quat::from_rotation_x(PI / 2.0))
+                .with_scale(bead_scale),
+            Mesh3d(bead_mesh_handle.clone()),
             MeshMaterial3d(norm_material),
             Visibility::Inherited,
             InheritedVisibility::default(),
@@ -72,44 +302,142 @@ pub fn spawn_abacus_bead (
     );
     
     entity_builder.observe(update_long_value::<Pointer<Click>>());
-    
-    if !is_mobile_device() {
-        entity_builder
-            .observe(update_material_on::<Pointer<Over>>(hover_material))
-            .observe(update_material_on::<Pointer<Out>>(bead_material_handle.clone()));
-    }
+    // Fingers can push the bead along its rod as well as tap it
+    entity_builder
+        .observe(crate::touch_gestures::drag_bead)
+        .observe(crate::touch_gestures::end_bead_drag);
     
     entity_builder.id()
 }
 
-fn update_material_on<E>(
-    new_material: Handle<StandardMaterial>,
-) -> impl Fn(Trigger<E>, Query<&mut MeshMaterial3d<StandardMaterial>>) {
-    move |trigger, mut query| {
-        if let Ok(mut material) = query.get_mut(trigger.target()) {
-            material.0 = new_material.clone();
+fn update_long_value<E>() -> impl Fn(Trigger<E>, Query<(&AbacusBead, &BelongsTo)>, Query<&mut AbacusLong>, Query<&ChildOf>, Query<&Abacus>, Query<(), With<TouchDragged>>, Commands) {
+    move |trigger, beads, mut longs, parents, abaci, dragged, mut commands| {
+        // A bead already pushed by dragging it isn't pushed back when the finger lifts
+        if dragged.contains(trigger.target()) {
+            return;
         }
+        push_bead(trigger.target(), &beads, &mut longs, &parents, &abaci, &mut commands);
     }
 }
 
-fn update_long_value<E>() -> impl Fn(Trigger<E>, Query<(&AbacusBead, &BelongsTo)>, Query<&mut AbacusLong>, Commands) {
-    move |trigger, beads, mut longs, mut commands| {
-        if let Ok((bead, BelongsTo(long))) = beads.get(trigger.target()) {
-            if let Ok(mut abacus_long) = longs.get_mut(*long) {
-                if abacus_long.value + 1 != bead.value {
-                    abacus_long.value = bead.value - 1;
-                } else {
-                    abacus_long.value = bead.value;
-                }
+/// Pushes a bead across the gap, as a click on it does.
+pub fn push_bead(
+    bead_entity: Entity,
+    beads: &Query<(&AbacusBead, &BelongsTo)>,
+    longs: &mut Query<&mut AbacusLong>,
+    parents: &Query<&ChildOf>,
+    abaci: &Query<&Abacus>,
+    commands: &mut Commands,
+) {
+    if let Ok((bead, BelongsTo(long))) = beads.get(bead_entity) {
+        if let Ok(mut abacus_long) = longs.get_mut(*long) {
+            // Beads 1..=value rest in the lower group. Like a real rod, pushing bead k
+            // also pushes every bead between it and the gap along with it.
+            if bead.value <= abacus_long.value {
+                // Lower group bead: it and everything above it in the group move up
+                abacus_long.value = bead.value - 1;
+            } else {
+                // Upper group bead: it and everything below it in the group move down
+                abacus_long.value = bead.value;
+            }
+            let long_value = abacus_long.value;
+
+            commands.send_event(AbacusChanged);
+            info!("Abacus Long Value Now {}", long_value);
+
+            // Report the click as an operation so it can be logged and recorded
+            let Ok(ChildOf(abacus_entity)) = parents.get(*long) else {
+                return;
+            };
+            let Ok(abacus) = abaci.get(*abacus_entity) else {
+                return;
+            };
+            if let Some((column, deck)) = abacus.deck_of(*long) {
+                commands.send_event(OperationPerformed {
+                    abacus: *abacus_entity,
+                    operation: AbacusOperation::MoveBeads { column, deck, long_value },
+                    total: abacus.beads_total_value(&longs.as_readonly()),
+                });
+            }
+        }
+    }
+}
+
+fn scroll_column_value(
+    mut trigger: Trigger<Pointer<Scroll>>,
+    parents: Query<&ChildOf>,
+    abaci: Query<&Abacus>,
+    longs: Query<&AbacusLong>,
+    mut op_queue: ResMut<ColumnOpQueue>,
+) {
+    trigger.propagate(false);
+    let long_entity = trigger.target();
+    let scroll_y = trigger.event().y;
+    if scroll_y == 0.0 {
+        return;
+    }
 
-                commands.send_event(AbacusChanged);
-                info!("Abacus Long Value Now {}", abacus_long.value);
+    let Ok(ChildOf(abacus_entity)) = parents.get(long_entity) else {
+        return;
+    };
+    if let Ok(abacus) = abaci.get(*abacus_entity) {
+        if let Some((column_index, _)) = abacus.deck_of(long_entity) {
+            // Step from where any queued ripple will end up, so fast scrolling accumulates
+            let current_value = op_queue.pending_total_value(*abacus_entity, abacus, &longs);
+            if let Some(new_value) = abacus.stepped_total_value(column_index, scroll_y > 0.0, current_value) {
+                op_queue.queue_total_value(*abacus_entity, abacus, new_value, &longs);
             }
         }
     }
 }
 
-#[derive(Component)]
+/// Columns whose beads are drawn with the highlight material (e.g. by a lesson step).
+#[derive(Resource, Default)]
+pub struct ColumnHighlight {
+    /// The abacus the columns belong to; the others are left alone.
+    pub abacus: Option<Entity>,
+    pub columns: Vec<usize>,
+}
+
+pub fn apply_column_highlights(
+    highlight: Res<ColumnHighlight>,
+    settings: Res<crate::AbacusSettings>,
+    abacus_query: Query<(Entity, &Abacus), Added<Abacus>>,
+    all_abacus_query: Query<(Entity, &Abacus)>,
+    beads_of_query: Query<&BeadsOf>,
+    mut material_query: Query<(&mut MeshMaterial3d<AbacusMaterial>, Has<AltBead>, Option<&ColumnColored>), With<AbacusBead>>,
+) {
+    // Re-apply when the highlight changes or when an abacus was just rebuilt
+    let abaci: Vec<(Entity, &Abacus)> = if highlight.is_changed() {
+        all_abacus_query.iter().collect()
+    } else {
+        abacus_query.iter().collect()
+    };
+
+    for (abacus_entity, abacus) in abaci {
+        let highlighted = highlight.abacus == Some(abacus_entity);
+        for (i, longs) in abacus.longs.iter().enumerate() {
+            let lit = highlighted && highlight.columns.contains(&i);
+            for &long in longs {
+                let Ok(beads) = beads_of_query.get(long) else {
+                    continue;
+                };
+                for &bead in &beads[..] {
+                    if let Ok((mut bead_material, alt, colored)) = material_query.get_mut(bead) {
+                        bead_material.0 = if lit {
+                            settings.bead_highlight_material.clone()
+                        } else {
+                            resting_bead_material(&settings, alt, colored).clone()
+                        };
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
 #[require(Transform)]
 pub struct AbacusLong {
     pub value: u64,
@@ -117,13 +445,13 @@ pub struct AbacusLong {
 
 pub fn spawn_abacus_long(
     commands: &mut Commands,
-    meshes: &mut ResMut<Assets<Mesh>>,
     bead_count: usize,
-    bead_material_handle: &Handle<StandardMaterial>,
-    bead_hover_material_handle: &Handle<StandardMaterial>,
-    frame_material_handle: &Handle<StandardMaterial>,
+    settings: &crate::AbacusSettings,
     value: u64,
 ) -> Entity {
+    let bead_material_handle = &settings.bead_material;
+    let frame_material_handle = &settings.frame_material;
+
     // Spawn the AbacusLong component entity first. It will always exist logically.
     let abacus_long_entity = commands.spawn((
         AbacusLong {
@@ -134,25 +462,37 @@ pub fn spawn_abacus_long(
         Transform::from_xyz(0.0, 0.0, 0.0), // Positioned by parent Abacus
     )).id();
 
+    // Scroll events from the rod and beads bubble up to the long
+    commands.entity(abacus_long_entity).observe(scroll_column_value);
+    // So do hovers, which light up the whole column
+    if !is_mobile_device() {
+        commands.entity(abacus_long_entity)
+            .observe(crate::column_hover::hover_column)
+            .observe(crate::column_hover::unhover_column);
+    }
+
     if bead_count > 0 {
         // Only spawn the visual rod and beads if bead_count > 0
-        let abacus_long_height = bead_count as f32 * BEAD_SPACING + LONG_SPACING + FRAME_THICKNESS * 2.0;
-        let abacus_long_width = FRAME_THICKNESS;
-
         let rod_mesh_entity = commands.spawn((
-            Mesh3d(meshes.add(Extrusion::new(Circle::new(abacus_long_width), abacus_long_height))),
+            Mesh3d(settings.rod_mesh.clone()),
             MeshMaterial3d(frame_material_handle.clone()),
-            Transform::from_xyz(0.0, abacus_long_height / 2.0 - BEAD_SPACING / 2.0 - FRAME_THICKNESS, 0.0)
-                .with_rotation(Quat::from_rotation_x(PI / 2.0)),
-            Pickable::IGNORE,
+            rod_transform(bead_count, &settings.proportions, settings.grooved),
+            // The rod is a hover target so the wheel works anywhere along the column
             Visibility::Inherited,
             InheritedVisibility::default(),
         )).id();
         commands.entity(abacus_long_entity).add_child(rod_mesh_entity);
 
         let mut beads = Vec::new(); // This vec is local and not stored in AbacusLong, which is fine.
+        let bead_mesh_handle = if settings.column_count > LOD_COLUMNS { &settings.bead_lod_mesh } else { &settings.bead_mesh };
         for i in 0..bead_count {
-            let new_bead = spawn_abacus_bead(commands, meshes, i as u64 + 1, bead_material_handle, bead_hover_material_handle);
+            // Blocks of five alternate colors, the block nearest the bar first
+            let alt = settings.two_tone_beads && ((bead_count - 1 - i) as u64 / TWO_TONE_GROUP) % 2 == 1;
+            let material = if alt { &settings.bead_alt_material } else { bead_material_handle };
+            let new_bead = spawn_abacus_bead(commands, i as u64 + 1, bead_mesh_handle, material, settings.proportions.bead_scale());
+            if alt {
+                commands.entity(new_bead).insert(AltBead);
+            }
             commands.entity(new_bead).insert((
                 BelongsTo(abacus_long_entity),
                 // Beads are children of the AbacusLong entity so they move with it if the AbacusLong's transform is changed relative to Abacus.
@@ -169,62 +509,225 @@ pub fn spawn_abacus_long(
     abacus_long_entity // Return the logical AbacusLong entity ID
 }
 
+/// Where a long's rod sits, stretched to run past its beads; a groove sits in behind them.
+pub fn rod_transform(bead_count: usize, proportions: &Proportions, grooved: bool) -> Transform {
+    let abacus_long_height = bead_count as f32 * proportions.bead_spacing + LONG_SPACING + FRAME_THICKNESS * 2.0;
+    let rod_y = abacus_long_height / 2.0 - proportions.bead_spacing / 2.0 - FRAME_THICKNESS;
+    let transform = if grooved {
+        let depth = GROOVE_DEPTH * proportions.bead_radius / BEAD_RADIUS;
+        Transform::from_xyz(0.0, rod_y, depth).with_scale(GROOVE_SCALE.extend(abacus_long_height))
+    } else {
+        Transform::from_xyz(0.0, rod_y, 0.0).with_scale(Vec3::new(1.0, 1.0, abacus_long_height))
+    };
+    transform.with_rotation(Quat::from_rotation_x(PI / 2.0))
+}
+
+/// Per-column deviation from the uniform bead layout (e.g. a last column with 10 beads).
+#[derive(Clone, Debug, PartialEq, Reflect, serde::Serialize, serde::Deserialize)]
+pub struct ColumnOverride {
+    pub column: usize,
+    pub top_bead_count: usize,
+    pub bottom_bead_count: usize,
+    pub bead_value_multiplier: u64,
+    /// What each top bead counts for; the abacus's if unset.
+    #[serde(default)]
+    pub top_bead_base_value: Option<u64>,
+    /// The column's own base, for mixed-radix abaci like the Roman one; the abacus's if unset.
+    #[serde(default)]
+    pub radix: Option<u64>,
+}
+
+/// Marks an abacus that mirrors someone else's and can't be moved by hand.
 #[derive(Component)]
+pub struct ReadOnlyAbacus;
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
 #[require(Transform)]
 pub struct Abacus {
-    pub top_longs: Vec<Entity>,
-    pub bottom_longs: Vec<Entity>,
+    /// Each column's longs, one per deck, top deck first.
+    pub longs: Vec<Vec<Entity>>,
     pub column_texts: Vec<Entity>,
     pub total_text: Entity,
-    pub top_bead_count: usize,
-    pub bottom_bead_count: usize,
-    pub top_bead_base_value: u64,
+    /// The total written out in words, shown just below the total.
+    pub words_text: Entity,
+    pub columns: Vec<ColumnLayout>,
     pub abacus_base: u64,
+    pub value_model: ValueModel,
+    /// Rods run across, one above the other, with the counted beads pushed to the left.
+    pub horizontal: bool,
+    /// The ones column is at the viewer's left rather than the right.
+    pub ones_on_left: bool,
+    pub proportions: Proportions,
     pub total_value: u64,
+    /// Position in the scene's row or grid of abaci; kept when the abacus is rebuilt.
+    pub slot: usize,
 }
 
 impl Abacus {
+    /// Footprint of the frame, used to lay several abaci out side by side.
+    pub fn size(&self) -> Vec2 {
+        let size = Vec2::new(self.columns.len() as f32 * self.proportions.column_spacing, self.frame_height());
+        let size = if self.horizontal { size.yx() } else { size };
+        size * self.proportions.model_scale
+    }
+
+    pub fn rod_rotation(&self) -> Quat {
+        rod_rotation(self.horizontal)
+    }
+
+    pub fn column_x(&self, column: usize) -> f32 {
+        column_x(column, self.columns.len(), self.ones_on_left, self.proportions.column_spacing)
+    }
+
+    /// Height of the upright frame, laid out for the tallest column.
+    pub fn frame_height(&self) -> f32 {
+        deck_positions(&self.columns, self.proportions.bead_spacing).1
+    }
+
+    /// Where `deck` of `column` sits in the frame.
+    pub fn long_translation(&self, column: usize, deck: usize) -> Vec3 {
+        let (deck_ys, height) = deck_positions(&self.columns, self.proportions.bead_spacing);
+        let layout = &self.columns[column];
+        // Shorter bottom decks are lifted so their active beads still meet the beam
+        let offset = if layout.is_bottom(deck) {
+            let max_bottom_bead_count = tallest_decks(&self.columns).last().copied().unwrap_or(0);
+            (max_bottom_bead_count - layout.deck(deck).bead_count) as f32 * self.proportions.bead_spacing
+        } else {
+            0.0
+        };
+        self.rod_rotation() * Vec3::new(self.column_x(column), deck_ys[deck] + offset - height/2.0, 0.0)
+    }
+
+    /// Where `column`'s value text sits: across the bottom, or down the right-hand ends of
+    /// horizontal rods.
+    pub fn column_text_translation(&self, column: usize) -> Vec3 {
+        let height = self.frame_height();
+        self.rod_rotation() * Vec3::new(self.column_x(column), -0.7 - height/2.0, 0.0)
+    }
+
+    /// Height of the words line above the frame; the total is lifted above it.
+    pub fn words_y(&self) -> f32 {
+        let height = if self.horizontal { self.columns.len() as f32 * self.proportions.column_spacing } else { self.frame_height() };
+        height/2.0 + 0.1
+    }
+
+    /// Column step that moves one column to the viewer's left.
+    pub fn left_step(&self) -> isize {
+        if self.ones_on_left { -1 } else { 1 }
+    }
+
+    /// The column and deck a long belongs to.
+    pub fn deck_of(&self, long: Entity) -> Option<(usize, usize)> {
+        self.longs.iter().enumerate()
+            .find_map(|(column, longs)| longs.iter().position(|&e| e == long).map(|deck| (column, deck)))
+    }
+
+    /// Whether any column has a base of its own.
+    pub fn is_mixed_radix(&self) -> bool {
+        self.columns.iter().any(|layout| layout.radix != self.abacus_base)
+    }
+
+    /// Base the column value texts are written in; beads on a counting frame are just counted.
+    pub fn text_base(&self) -> u64 {
+        match self.value_model {
+            ValueModel::PlaceValue => self.abacus_base,
+            ValueModel::Counting => 10,
+        }
+    }
+
+    /// The abacus's value math, apart from its beads.
+    pub fn engine(&self) -> ValueEngine<'_> {
+        ValueEngine { columns: &self.columns, value_model: self.value_model }
+    }
+
+    /// What one unit in `column` is worth in the total.
+    pub fn place_value(&self, column: usize) -> u64 {
+        self.engine().place_value(column)
+    }
+
     pub fn get_column_value(
         &self,
         column_index: usize,
         abacus_long_query: &Query<&AbacusLong>,
     ) -> u64 {
-        if column_index >= self.top_longs.len() {
+        let Some(longs) = self.longs.get(column_index) else {
             return 0; // Index out of bounds
-        }
-        let top_long_entity = self.top_longs[column_index];
-        let bottom_long_entity = self.bottom_longs[column_index];
+        };
 
         // Use get() which returns Option, handle potential errors gracefully
-        let top_long_val = match abacus_long_query.get(top_long_entity) {
-            Ok(long) => long.value,
-            Err(_) => return 0, // Or handle error appropriately
-        };
-        let bottom_long_val = match abacus_long_query.get(bottom_long_entity) {
-            Ok(long) => long.value,
-            Err(_) => return 0, // Or handle error appropriately
-        };
-        
-        // Value from bottom beads + (is top active * top bead base value)
-        // Check top_bead_count > 0 before using top_bead_base_value
-        let top_contribution =  (top_long_val) * self.top_bead_base_value;
-        
-        (self.bottom_bead_count as u64 - bottom_long_val) + top_contribution
+        let mut long_values = Vec::with_capacity(longs.len());
+        for &long in longs {
+            match abacus_long_query.get(long) {
+                Ok(long) => long_values.push(long.value),
+                Err(_) => return 0, // Or handle error appropriately
+            }
+        }
+
+        self.columns[column_index].value(&long_values)
     }
 
     pub fn get_total_value(
         &mut self,
         abacus_long_query: &Query<&AbacusLong>,
     ) -> u64 {
+        let current_total_value = self.beads_total_value(abacus_long_query);
+        self.total_value = current_total_value; // Update internal state
+        current_total_value
+    }
+
+    /// The total the beads show right now, without updating `total_value`.
+    pub fn beads_total_value(&self, abacus_long_query: &Query<&AbacusLong>) -> u64 {
         let mut current_total_value = 0;
  
-        for i in 0..self.top_longs.len() {
-            current_total_value += self.get_column_value(i, abacus_long_query) * self.abacus_base.pow(i as u32);
+        // Saturates on abaci too wide for a u64, like max_total_value
+        for i in 0..self.longs.len() {
+            let place_value = self.place_value(i);
+            current_total_value = self.get_column_value(i, abacus_long_query).saturating_mul(place_value).saturating_add(current_total_value);
         }
-        self.total_value = current_total_value; // Update internal state
         current_total_value
     }
 
+    /// Returns every bead to its zero position, sending a single `AbacusChanged`.
+    pub fn clear(
+        &self,
+        abacus_long_query: &mut Query<&mut AbacusLong>,
+        commands: &mut Commands,
+    ) {
+        for (layout, longs) in self.columns.iter().zip(&self.longs) {
+            for (deck, &long) in longs.iter().enumerate() {
+                if let Ok(mut abacus_long) = abacus_long_query.get_mut(long) {
+                    // Every bead away from its bar
+                    abacus_long.value = layout.long_value(deck, 0);
+                }
+            }
+        }
+        commands.send_event(AbacusChanged);
+    }
+
+    /// The total after adding or removing one bead-step worth of value on a column,
+    /// carrying into (or borrowing from) neighbouring columns as needed.
+    /// Returns `None` if the step would run off either end of the abacus.
+    pub fn stepped_total_value(
+        &self,
+        column_index: usize,
+        increment: bool,
+        current_value: u64,
+    ) -> Option<u64> {
+        if column_index >= self.longs.len() {
+            warn!("stepped_total_value: Index {} out of bounds", column_index);
+            return None;
+        }
+
+        let step = self.columns[column_index].bead_value_multiplier
+            .saturating_mul(self.place_value(column_index));
+        if increment {
+            current_value.checked_add(step).filter(|&v| v <= self.max_total_value())
+        } else {
+            current_value.checked_sub(step)
+        }
+    }
+
     /// Sets the beads of a specific column to represent the target value.
     /// Clamps the value to the maximum representable by the column configuration.
     pub fn set_column_value(
@@ -234,61 +737,38 @@ impl Abacus {
         abacus_long_query: &mut Query<&mut AbacusLong>,
         commands: &mut Commands, 
     ) {
-        if column_index >= self.top_longs.len() {
+        if column_index >= self.longs.len() {
             warn!("set_column_value: Index {} out of bounds", column_index);
             return;
         }
 
-        let max_bottom_value = self.bottom_bead_count as u64;
-        // Max top contribution (all top beads activated)
-        let max_top_contribution = if self.top_bead_count > 0 {
-            self.top_bead_count as u64 * self.top_bead_base_value
-        } else {
-            0
-        };
-        // Max column value is sum of max bottom value and max top contribution
-        let max_column_value = max_bottom_value + max_top_contribution;
-
-        // Clamp the target value
-        let clamped_value = target_value.min(max_column_value);
-
-        let top_long_entity = self.top_longs[column_index];
-        let bottom_long_entity = self.bottom_longs[column_index];
-
-        // Determine how many top beads to activate (0 to top_bead_count)
-        let mut top_beads_to_activate = 0;
-        let mut value_from_bottom = clamped_value;
-
-        // Try to activate top beads if available and needed
-        if self.top_bead_count > 0 && clamped_value >= self.top_bead_base_value {
-            // Calculate how many top beads to activate (integer division)
-            top_beads_to_activate = (clamped_value / self.top_bead_base_value).min(self.top_bead_count as u64);
-            // Remaining value to be represented by bottom beads
-            value_from_bottom = clamped_value - (top_beads_to_activate * self.top_bead_base_value);
-        }
-
-        // Ensure value_from_bottom doesn't exceed what bottom beads can show
-        value_from_bottom = value_from_bottom.min(max_bottom_value);
-
-        // Update top AbacusLong - set value directly to number of beads to activate
-        if let Ok(mut top_long) = abacus_long_query.get_mut(top_long_entity) {
-            top_long.value = top_beads_to_activate;
-        } else {
-            error!("Failed to get mutable AbacusLong for top entity at index {}", column_index);
-        }
-
-        // Update bottom AbacusLong
-        if let Ok(mut bottom_long) = abacus_long_query.get_mut(bottom_long_entity) {
-            // bottom_long.value stores count of beads *away* from the bar (inactive)
-            bottom_long.value = max_bottom_value - value_from_bottom;
-        } else {
-            error!("Failed to get mutable AbacusLong for bottom entity at index {}", column_index);
+        let long_values = self.columns[column_index].long_values(target_value);
+        for (deck, long_value) in long_values.into_iter().enumerate() {
+            if let Ok(mut abacus_long) = abacus_long_query.get_mut(self.longs[column_index][deck]) {
+                abacus_long.value = long_value;
+            } else {
+                error!("Failed to get mutable AbacusLong for deck {} at index {}", deck, column_index);
+            }
         }
         
         // Signal that the abacus state changed
         commands.send_event(AbacusChanged);
     }
 
+    /// Largest total the abacus can hold with its current column layouts.
+    pub fn max_total_value(&self) -> u64 {
+        self.engine().max_total_value()
+    }
+
+    /// Splits `target` into what the abacus shows after rolling over like an odometer,
+    /// and how many times it rolled over, which a chained abacus carries on.
+    pub fn split_overflow(&self, target: u64) -> (u64, u64) {
+        match self.max_total_value().checked_add(1) {
+            Some(modulus) => (target % modulus, target / modulus),
+            None => (target, 0),
+        }
+    }
+
     /// Sets the abacus beads to represent the target total value.
     pub fn set_total_value(
         &mut self,
@@ -296,142 +776,184 @@ impl Abacus {
         abacus_long_query: &mut Query<&mut AbacusLong>,
         commands: &mut Commands,
     ) {
-        let num_columns = self.top_longs.len();
-        
-        // Calculate the maximum possible value the abacus can hold with current settings
-        let max_bottom_val = self.bottom_bead_count as u64;
-        let max_top_val = if self.top_bead_count > 0 { 
-            self.top_bead_count as u64 * self.top_bead_base_value
-        } else { 
-            0 
-        };
-        let max_column_val = max_bottom_val + max_top_val;
-        
-        let mut max_abacus_val = 0;
-        for i in 0..num_columns {
-            max_abacus_val += max_column_val * self.abacus_base.pow(i as u32);
-        }
-        
         // Clamp the target value to what the abacus can represent
-        target_total_value = target_total_value.min(max_abacus_val);
-        
-        let mut remaining_value = target_total_value;
-
-        // Iterate from most significant column down to least significant
-        for i in (0..num_columns).rev() {
-            let base_power = self.abacus_base.pow(i as u32);
-            if base_power == 0 && remaining_value > 0 && i > 0 { // Avoid division by zero for large bases/powers
-                warn!("Abacus base calculation overflow for column {}, skipping", i);
-                continue;
-            }
-            if base_power == 0 && i == 0 { // Handle the last column if base is huge
-                let column_value = remaining_value;
-                self.set_column_value(i, column_value, abacus_long_query, commands);
-                remaining_value = 0;
-            } else {
-                let column_value = remaining_value / base_power;
-                self.set_column_value(i, column_value, abacus_long_query, commands);
-                remaining_value %= base_power;
-            }
+        target_total_value = target_total_value.min(self.max_total_value());
+
+        for (i, column_value) in self.column_values_for_total(target_total_value).into_iter().enumerate() {
+            self.set_column_value(i, column_value, abacus_long_query, commands);
         }
         
         // Update the internal total_value state (might be slightly redundant if get_total_value is called later, but good practice)
         self.total_value = target_total_value;
         // Final event send handled by set_column_value calls
     }
+
+    /// Splits a total into the value each column should show (ones column first).
+    /// The total is clamped to what the abacus can represent.
+    pub fn column_values_for_total(&self, target_total_value: u64) -> Vec<u64> {
+        self.engine().column_values_for_total(target_total_value)
+    }
 }
         
 
+/// Where a column sits across the upright layout. The camera looks along +Z, so world +X
+/// is the viewer's left, and column 0, the ones column, ends up on the right unless
+/// `ones_on_left`.
+pub fn column_x(column: usize, column_count: usize, ones_on_left: bool, column_spacing: f32) -> f32 {
+    let x = (column as f32 - (column_count as f32 - 1.0) / 2.0) * column_spacing;
+    if ones_on_left { -x } else { x }
+}
+
+/// Turns the upright layout so the rods run across, with the beads counted at the left.
+pub fn rod_rotation(horizontal: bool) -> Quat {
+    if horizontal { Quat::from_rotation_z(-PI / 2.0) } else { Quat::IDENTITY }
+}
+
+/// Most beads any column has in each deck, top deck first.
+fn tallest_decks(columns: &[ColumnLayout]) -> Vec<usize> {
+    let deck_count = columns.iter().map(|c| c.decks.len()).max().unwrap_or(0);
+    (0..deck_count).map(|deck| columns.iter().map(|c| c.deck(deck).bead_count).max().unwrap_or(0)).collect()
+}
+
+/// Height of each deck's long above the bottom of the frame, top deck first, and the
+/// frame's height. Decks are laid out for the tallest column so the bars stay straight
+/// across overrides.
+fn deck_positions(columns: &[ColumnLayout], bead_spacing: f32) -> (Vec<f32>, f32) {
+    let tallest = tallest_decks(columns);
+    let mut positions = vec![0.0; tallest.len()];
+    let mut y = 0.0;
+    for deck in (0..tallest.len()).rev() {
+        positions[deck] = y;
+        y += tallest[deck] as f32 * bead_spacing + LONG_SPACING + ROW_SPACING;
+    }
+    // No bar above the top deck
+    (positions, (y - ROW_SPACING).max(0.0))
+}
+
 pub fn spawn_abacus(
     commands: &mut Commands,
-    meshes: &mut ResMut<Assets<Mesh>>,
     settings: &crate::AbacusSettings,
-) {
-    let mut top_longs_temp = Vec::new();
-    let mut bottom_longs_temp = Vec::new();
-    let mut column_texts = Vec::new();
-    
+    slot: usize,
+) -> Entity {
     let text_font = TextFont {
-        font_size: 64.0,
+        font: settings.text_font.clone(),
+        font_size: settings.text_size * settings.ui_scale,
         ..default()
     };
+    let text_color = TextColor(settings.text_color);
     let scale = Vec3::new(-0.01, 0.01, 0.01);
 
+    let structure = crate::AbacusStructure::from_settings(settings);
     let column_count = settings.column_count;
-    let top_bead_count = settings.top_bead_count;
-    let bottom_bead_count = settings.bottom_bead_count;
-    let top_bead_base_value = settings.top_bead_base_value;
-    let abacus_base = settings.abacus_base;
-    let bead_material_handle = &settings.bead_material;
-    let bead_hover_material_handle = &settings.bead_hover_material;
-    let frame_material_handle = &settings.frame_material;
-
-    let top_long_y = (bottom_bead_count as f32) * BEAD_SPACING + LONG_SPACING + ROW_SPACING;
-    let top_abacus_y = top_long_y + (top_bead_count as f32) * BEAD_SPACING + LONG_SPACING;
+    let columns: Vec<ColumnLayout> = (0..column_count).map(|i| structure.column_layout(i)).collect();
+
+    // Laid out first so the parts can be placed by it; the entities are filled in below
+    let mut abacus = Abacus {
+        longs: Vec::new(),
+        column_texts: Vec::new(),
+        total_text: Entity::PLACEHOLDER,
+        words_text: Entity::PLACEHOLDER,
+        columns,
+        abacus_base: settings.abacus_base,
+        value_model: settings.value_model,
+        horizontal: settings.horizontal_rods,
+        ones_on_left: settings.ones_on_left,
+        proportions: settings.proportions,
+        total_value: 0,
+        slot,
+    };
 
     for i in 0..column_count {
-        let top_long = spawn_abacus_long(commands, meshes, top_bead_count, bead_material_handle, bead_hover_material_handle, frame_material_handle, 0);
-        let bottom_long = spawn_abacus_long(commands, meshes, bottom_bead_count, bead_material_handle, bead_hover_material_handle, frame_material_handle, bottom_bead_count as u64);
-
-        let x = (i as f32 - ((column_count as f32 - 1.0) / 2.0)) * COLUMN_SPACING;
-        
-        commands.entity(top_long).insert(Transform {
-            translation: Vec3::new(x, top_long_y - top_abacus_y/2.0, 0.0),
-            ..default()
-        });
-
-        commands.entity(bottom_long).insert(Transform {
-            translation: Vec3::new(x, - top_abacus_y/2.0, 0.0),
-            ..default()
-        });
-
-        top_longs_temp.push(top_long);
-        bottom_longs_temp.push(bottom_long);
-
-        let y = -0.7; 
-        let text_entity = commands.spawn((
-            Text2d::new("0"),
-            text_font.clone(),
-            Transform::from_xyz(x, y- top_abacus_y/2.0, 0.0).with_scale(scale.clone()),
-            Visibility::Inherited,
-            InheritedVisibility::default(),
-        )).id();
-        column_texts.push(text_entity);
+        let longs = spawn_column_longs(commands, &abacus, i, settings, &[]);
+        abacus.longs.push(longs);
+        let text_entity = spawn_column_text(commands, &abacus, i, settings);
+        abacus.column_texts.push(text_entity);
     }
 
-    let total_text_entity = commands.spawn((
+    // The words line takes the total's usual place; the total is lifted above it
+    let words_y = abacus.words_y() + settings.title_lift();
+    abacus.total_text = commands.spawn((
+        Billboard,
         Text2d::new("0"),
         text_font.clone(),
-        Transform::from_xyz(0.0, top_abacus_y/2.0 + 0.1, 0.0).with_scale(scale.clone()),
+        text_color,
+        Transform::from_xyz(0.0, words_y + settings.total_text_lift(), 0.0).with_scale(scale.clone()),
         Visibility::Inherited,
         InheritedVisibility::default(),
     )).id();
 
-    let abacus_id = commands.spawn((
-        Abacus {
-            top_longs: top_longs_temp.clone(),
-            bottom_longs: bottom_longs_temp.clone(),
-            column_texts: column_texts.clone(),
-            total_text: total_text_entity,
-            top_bead_count,
-            bottom_bead_count,
-            top_bead_base_value,
-            abacus_base,
-            total_value: 0,
+    abacus.words_text = commands.spawn((
+        Billboard,
+        Text2d::new(""),
+        TextFont {
+            font_size: settings.text_size * settings.ui_scale * VALUE_IN_WORDS_SCALE,
+            ..default()
         },
+        text_color,
+        Transform::from_xyz(0.0, words_y, 0.0).with_scale(scale.clone()),
+        if settings.show_value_in_words { Visibility::Inherited } else { Visibility::Hidden },
         InheritedVisibility::default(),
     )).id();
 
-    for &top_long_entity in &top_longs_temp {
-        commands.entity(abacus_id).add_child(top_long_entity);
-    }
-    for &bottom_long_entity in &bottom_longs_temp {
-        commands.entity(abacus_id).add_child(bottom_long_entity);
-    }
-    for &text_entity in &column_texts {
-        commands.entity(abacus_id).add_child(text_entity);
-    }
-    commands.entity(abacus_id).add_child(total_text_entity);
+    let children: Vec<Entity> = abacus.longs.iter().flatten()
+        .chain(&abacus.column_texts)
+        .chain([&abacus.total_text, &abacus.words_text])
+        .copied()
+        .collect();
+    let abacus_id = commands.spawn((
+        abacus,
+        structure,
+        Transform::from_scale(Vec3::splat(settings.proportions.model_scale)),
+        InheritedVisibility::default(),
+    )).id();
+    commands.entity(abacus_id).add_children(&children);
 
     commands.send_event(AbacusChanged);
+    abacus_id
+}
+
+/// Spawns the longs of `column`, one per deck, placed where `abacus` lays them out and
+/// showing `long_values` (every bead away from its bar where missing). The caller parents
+/// them to the abacus.
+pub fn spawn_column_longs(
+    commands: &mut Commands,
+    abacus: &Abacus,
+    column: usize,
+    settings: &crate::AbacusSettings,
+    long_values: &[u64],
+) -> Vec<Entity> {
+    let layout = &abacus.columns[column];
+    let rotation = abacus.rod_rotation();
+    layout.decks.iter().enumerate().map(|(deck, deck_layout)| {
+        let value = long_values.get(deck).copied().unwrap_or_else(|| layout.long_value(deck, 0));
+        let long = spawn_abacus_long(commands, deck_layout.bead_count, settings, value);
+        commands.entity(long).insert(Transform {
+            translation: abacus.long_translation(column, deck),
+            rotation,
+            ..default()
+        });
+        long
+    }).collect()
+}
+
+/// Spawns the value text under `column`. The caller parents it to the abacus.
+pub fn spawn_column_text(
+    commands: &mut Commands,
+    abacus: &Abacus,
+    column: usize,
+    settings: &crate::AbacusSettings,
+) -> Entity {
+    commands.spawn((
+        Billboard,
+        Text2d::new("0"),
+        TextFont {
+            font: settings.text_font.clone(),
+            font_size: settings.text_size * settings.ui_scale,
+            ..default()
+        },
+        TextColor(settings.column_text_color(column)),
+        Transform::from_translation(abacus.column_text_translation(column)).with_scale(Vec3::new(-0.01, 0.01, 0.01)),
+        Visibility::Inherited,
+        InheritedVisibility::default(),
+    )).id()
 }
\ No newline at end of file
@@ -0,0 +1,96 @@
+//! The active column of the selected abacus, picked with the gamepad's d-pad or the arrow
+//! keys, and the translucent cursor drawn around it so it can be seen from the sofa.
+
+use bevy::prelude::*;
+
+use crate::abacus::*;
+use crate::scene_layout::SelectedAbacus;
+
+const CURSOR_COLOR: Color = Color::srgba(1.0, 0.75, 0.2, 0.25);
+/// How much wider and deeper than a column the cursor is.
+const CURSOR_MARGIN: f32 = 0.1;
+
+/// The column being worked on, on the selected abacus; `None` until one is picked.
+#[derive(Resource, Default)]
+pub struct ActiveColumn {
+    pub column: Option<usize>,
+}
+
+impl ActiveColumn {
+    /// Moves `offset` columns towards the higher places, starting from the ones column.
+    pub fn step(&mut self, offset: isize, column_count: usize) {
+        let column = match self.column {
+            Some(column) => column.saturating_add_signed(offset),
+            None => 0,
+        };
+        self.column = Some(column.min(column_count.saturating_sub(1)));
+    }
+}
+
+#[derive(Component)]
+struct ColumnCursor;
+
+pub struct ActiveColumnPlugin;
+
+impl Plugin for ActiveColumnPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ActiveColumn>()
+            .add_systems(Startup, spawn_column_cursor)
+            .add_systems(Update, update_column_cursor);
+    }
+}
+
+fn spawn_column_cursor(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.spawn((
+        ColumnCursor,
+        Mesh3d(meshes.add(Cuboid::new(1.0, 1.0, 1.0))),
+        MeshMaterial3d(materials.add(StandardMaterial {
+            base_color: CURSOR_COLOR,
+            alpha_mode: AlphaMode::Blend,
+            unlit: true,
+            ..default()
+        })),
+        Transform::default(),
+        Visibility::Hidden,
+    ));
+}
+
+/// Keeps the active column on the selected abacus, and the cursor around it.
+fn update_column_cursor(
+    mut active: ResMut<ActiveColumn>,
+    selected: Res<SelectedAbacus>,
+    abacus_query: Query<(&Abacus, &Transform), Without<ColumnCursor>>,
+    mut cursor_query: Query<(&mut Transform, &mut Visibility), With<ColumnCursor>>,
+) {
+    let Ok((mut cursor_transform, mut visibility)) = cursor_query.single_mut() else {
+        return;
+    };
+    let Some((abacus, abacus_transform)) = selected.0.and_then(|entity| abacus_query.get(entity).ok()) else {
+        *visibility = Visibility::Hidden;
+        return;
+    };
+    // The abacus may have been rebuilt with fewer columns
+    if active.column.is_some_and(|column| column >= abacus.columns.len()) {
+        active.column = abacus.columns.len().checked_sub(1);
+    }
+    let Some(column) = active.column else {
+        *visibility = Visibility::Hidden;
+        return;
+    };
+
+    *cursor_transform = column_box(abacus, abacus_transform, column, CURSOR_MARGIN);
+    *visibility = Visibility::Inherited;
+}
+
+/// Where a unit cube goes to wrap `column`'s rod and beads, `margin` wider and deeper.
+pub fn column_box(abacus: &Abacus, abacus_transform: &Transform, column: usize, margin: f32) -> Transform {
+    let x = abacus.column_x(column);
+    let proportions = &abacus.proportions;
+    let size = Vec3::new(proportions.column_spacing - margin, abacus.frame_height() + margin, proportions.bead_radius * 2.0 + margin);
+    let upright = Transform::from_xyz(x, 0.0, 0.0).with_scale(size);
+    abacus_transform.mul_transform(Transform::from_rotation(abacus.rod_rotation()).mul_transform(upright))
+}
@@ -0,0 +1,59 @@
+use bevy::prelude::*;
+
+/// A complete look for the abacus: bead, hover and frame colors plus the
+/// background and value text. Several presets target accessibility needs.
+pub struct AppearancePreset {
+    /// Localization id of the preset's display name.
+    pub message_id: &'static str,
+    pub bead_color: Color,
+    pub bead_hover_color: Color,
+    pub frame_color: Color,
+    pub background_color: Color,
+    pub text_color: Color,
+    pub text_size: f32,
+}
+
+pub const DEFAULT_TEXT_SIZE: f32 = 80.0;
+/// Range of the UI scale slider: small phones up to 4K monitors.
+pub const MIN_UI_SCALE: f32 = 0.5;
+pub const MAX_UI_SCALE: f32 = 3.0;
+
+pub const APPEARANCE_PRESETS: [AppearancePreset; 4] = [
+    AppearancePreset {
+        message_id: "preset-classic",
+        bead_color: Color::srgb(0.86, 0.15, 0.15),
+        bead_hover_color: Color::srgb(0.99, 0.79, 0.79),
+        frame_color: Color::srgb(0.25, 0.25, 0.27),
+        background_color: Color::srgb(0.17, 0.17, 0.17),
+        text_color: Color::WHITE,
+        text_size: DEFAULT_TEXT_SIZE,
+    },
+    AppearancePreset {
+        message_id: "preset-high-contrast",
+        bead_color: Color::BLACK,
+        bead_hover_color: Color::srgb(1.0, 0.85, 0.0),
+        frame_color: Color::srgb(0.1, 0.1, 0.1),
+        background_color: Color::WHITE,
+        text_color: Color::BLACK,
+        text_size: 112.0,
+    },
+    AppearancePreset {
+        // Okabe-Ito blue and orange stay distinct for red-green color blindness
+        message_id: "preset-deuteranopia-safe",
+        bead_color: Color::srgb(0.0, 0.45, 0.70),
+        bead_hover_color: Color::srgb(0.90, 0.62, 0.0),
+        frame_color: Color::srgb(0.35, 0.35, 0.35),
+        background_color: Color::srgb(0.95, 0.95, 0.92),
+        text_color: Color::BLACK,
+        text_size: 96.0,
+    },
+    AppearancePreset {
+        message_id: "preset-dark-background",
+        bead_color: Color::srgb(0.96, 0.62, 0.04),
+        bead_hover_color: Color::srgb(0.99, 0.88, 0.55),
+        frame_color: Color::srgb(0.75, 0.75, 0.78),
+        background_color: Color::srgb(0.02, 0.02, 0.03),
+        text_color: Color::WHITE,
+        text_size: 96.0,
+    },
+];
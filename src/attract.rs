@@ -0,0 +1,119 @@
+//! The attract animation: every few seconds each abacus counts its way to a new random
+//! value, or steps up by one, so an unattended screen shows the beads moving. Kiosk mode
+//! plays it when idle, and **Demo mode** under Animation plays it on demand.
+
+use bevy::prelude::*;
+
+use crate::abacus::*;
+use crate::operations::{AbacusOperation, OperationRequested};
+use crate::AbacusSettings;
+
+/// Seconds between new values to start with, long enough for the beads to finish rippling.
+pub const DEFAULT_ATTRACT_INTERVAL: f32 = 4.0;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AttractStyle {
+    /// A new random value each time.
+    #[default]
+    Random,
+    /// One more each time, back to zero past the largest value.
+    CountUp,
+}
+
+impl AttractStyle {
+    pub const ALL: [AttractStyle; 2] = [AttractStyle::Random, AttractStyle::CountUp];
+
+    /// Localization id of the style's display name.
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            AttractStyle::Random => "attract-random",
+            AttractStyle::CountUp => "attract-count-up",
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct Attract {
+    pub playing: bool,
+    timer: Timer,
+    rng: u64,
+}
+
+impl Default for Attract {
+    fn default() -> Self {
+        Self {
+            playing: false,
+            timer: Timer::from_seconds(DEFAULT_ATTRACT_INTERVAL, TimerMode::Repeating),
+            rng: 1,
+        }
+    }
+}
+
+impl Attract {
+    /// Starts from a fresh value right away, seeded with `seed`.
+    pub fn start(&mut self, seed: u64) {
+        self.playing = true;
+        self.rng = seed | 1;
+        // The first value comes on the next frame
+        let duration = self.timer.duration();
+        self.timer.set_elapsed(duration);
+    }
+
+    /// xorshift64*, as the game uses for its targets.
+    fn next_random(&mut self) -> u64 {
+        self.rng ^= self.rng >> 12;
+        self.rng ^= self.rng << 25;
+        self.rng ^= self.rng >> 27;
+        self.rng.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+}
+
+pub struct AttractPlugin;
+
+impl Plugin for AttractPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Attract>()
+            .add_systems(Update, (follow_demo_mode.run_if(resource_changed::<AbacusSettings>), play_attract).chain());
+    }
+}
+
+/// Starts and stops the animation with the Demo mode checkbox.
+fn follow_demo_mode(settings: Res<AbacusSettings>, mut was_on: Local<bool>, mut attract: ResMut<Attract>, time: Res<Time>) {
+    if settings.attract_mode == *was_on {
+        return;
+    }
+    *was_on = settings.attract_mode;
+    if settings.attract_mode {
+        attract.start(time.elapsed().as_nanos() as u64);
+    } else {
+        attract.playing = false;
+    }
+}
+
+fn play_attract(
+    mut attract: ResMut<Attract>,
+    settings: Res<AbacusSettings>,
+    time: Res<Time>,
+    abacus_query: Query<(Entity, &Abacus)>,
+    mut operations: EventWriter<OperationRequested>,
+) {
+    if !attract.playing {
+        return;
+    }
+    let interval = std::time::Duration::from_secs_f32(settings.attract_interval);
+    if attract.timer.duration() != interval {
+        attract.timer.set_duration(interval);
+    }
+    if !attract.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+    for (entity, abacus) in &abacus_query {
+        let largest = abacus.max_total_value();
+        let operation = match settings.attract_style {
+            AttractStyle::Random => AbacusOperation::Set(attract.next_random() % largest.saturating_add(1)),
+            AttractStyle::CountUp if abacus.total_value >= largest => AbacusOperation::Set(0),
+            AttractStyle::CountUp => AbacusOperation::Add(1),
+        };
+        operations.write(OperationRequested { abacus: entity, operation });
+    }
+}
@@ -0,0 +1,157 @@
+use bevy::prelude::*;
+use bevy::audio::Volume;
+
+use crate::AbacusSettings;
+
+/// Most stop clicks played in one frame, so moving a whole rod doesn't turn into a roar.
+const MAX_CLICKS_PER_FRAME: usize = 4;
+/// Slides overlap more than clicks, so fewer of them are layered at once.
+const MAX_SLIDES_PER_FRAME: usize = 2;
+/// Playback speed (and so pitch) varies by up to this fraction per click.
+const PITCH_VARIATION: f32 = 0.12;
+
+/// Sent by `animate_beads` when a bead comes to rest on its target, or by the physics
+/// simulation when a bead settles or knocks into another.
+#[derive(Event)]
+pub struct BeadArrived(pub Entity);
+
+/// Sent by `move_all_abacus_beads` when a bead is given a new target.
+#[derive(Event)]
+pub struct BeadDeparted(pub Entity);
+
+/// Sent when every column of the abacus has been returned to zero.
+#[derive(Event)]
+pub struct AbacusCleared;
+
+/// Material the beads sound like; each theme ships its own set of samples.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum SoundTheme {
+    #[default]
+    Wood,
+    Glass,
+    Metal,
+}
+
+impl SoundTheme {
+    pub const ALL: [SoundTheme; 3] = [SoundTheme::Wood, SoundTheme::Glass, SoundTheme::Metal];
+
+    /// Localization id of the theme's display name.
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            SoundTheme::Wood => "sound-theme-wood",
+            SoundTheme::Glass => "sound-theme-glass",
+            SoundTheme::Metal => "sound-theme-metal",
+        }
+    }
+
+    /// Folder of the theme's samples under `assets/sounds/`.
+    fn dir(&self) -> &'static str {
+        match self {
+            SoundTheme::Wood => "wood",
+            SoundTheme::Glass => "glass",
+            SoundTheme::Metal => "metal",
+        }
+    }
+}
+
+struct ThemeSamples {
+    bead_stop: Handle<AudioSource>,
+    bead_slide: Handle<AudioSource>,
+    clear_all: Handle<AudioSource>,
+}
+
+impl ThemeSamples {
+    /// Loads the samples of a theme from `assets/sounds/<dir>/`, so a theme's sounds can be
+    /// swapped without rebuilding.
+    fn load(asset_server: &AssetServer, theme: SoundTheme) -> Self {
+        let path = |sample: &str| format!("sounds/{}/{}.wav", theme.dir(), sample);
+        Self {
+            bead_stop: asset_server.load(path("stop")),
+            bead_slide: asset_server.load(path("slide")),
+            clear_all: asset_server.load(path("clear")),
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct SoundThemes {
+    wood: ThemeSamples,
+    glass: ThemeSamples,
+    metal: ThemeSamples,
+}
+
+impl SoundThemes {
+    fn samples(&self, theme: SoundTheme) -> &ThemeSamples {
+        match theme {
+            SoundTheme::Wood => &self.wood,
+            SoundTheme::Glass => &self.glass,
+            SoundTheme::Metal => &self.metal,
+        }
+    }
+}
+
+impl FromWorld for SoundThemes {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.resource::<AssetServer>();
+
+        Self {
+            wood: ThemeSamples::load(asset_server, SoundTheme::Wood),
+            glass: ThemeSamples::load(asset_server, SoundTheme::Glass),
+            metal: ThemeSamples::load(asset_server, SoundTheme::Metal),
+        }
+    }
+}
+
+/// Cheap per-bead pitch jitter; exact randomness doesn't matter for a click.
+fn pitch_jitter(bead: Entity, time: &Time) -> f32 {
+    let seed = bead.index() as f32 * 12.9898 + time.elapsed_secs() * 78.233;
+    let jitter = (seed.sin() * 43758.547).fract() * 2.0 - 1.0;
+    1.0 + jitter * PITCH_VARIATION
+}
+
+pub fn play_bead_sounds(
+    mut commands: Commands,
+    mut arrivals: EventReader<BeadArrived>,
+    mut departures: EventReader<BeadDeparted>,
+    mut clears: EventReader<AbacusCleared>,
+    themes: Res<SoundThemes>,
+    settings: Res<AbacusSettings>,
+    time: Res<Time>,
+) {
+    if settings.sound_muted || settings.sound_volume <= 0.0 {
+        arrivals.clear();
+        departures.clear();
+        clears.clear();
+        return;
+    }
+
+    let samples = themes.samples(settings.sound_theme);
+    let volume = Volume::Linear(settings.sound_volume);
+
+    for BeadArrived(bead) in arrivals.read().take(MAX_CLICKS_PER_FRAME) {
+        commands.spawn((
+            AudioPlayer(samples.bead_stop.clone()),
+            PlaybackSettings::DESPAWN
+                .with_volume(volume)
+                .with_speed(pitch_jitter(*bead, &time)),
+        ));
+    }
+    for BeadDeparted(bead) in departures.read().take(MAX_SLIDES_PER_FRAME) {
+        commands.spawn((
+            AudioPlayer(samples.bead_slide.clone()),
+            PlaybackSettings::DESPAWN
+                .with_volume(volume)
+                .with_speed(pitch_jitter(*bead, &time)),
+        ));
+    }
+    if clears.read().next().is_some() {
+        commands.spawn((
+            AudioPlayer(samples.clear_all.clone()),
+            PlaybackSettings::DESPAWN.with_volume(volume),
+        ));
+    }
+
+    arrivals.clear();
+    departures.clear();
+    clears.clear();
+}
@@ -0,0 +1,72 @@
+//! The settings on phones and narrow windows. Floating windows are hard to drag and read
+//! there, so the panels become a bar of large tabs along the bottom of the screen, and the
+//! chosen one opens as a sheet over the lower half, leaving the abaci in view above it.
+//! Buttons, sliders and gaps grow to fit a fingertip.
+
+use bevy_egui::egui;
+
+use crate::abacus::is_mobile_device;
+use crate::localization::*;
+use crate::window_layout::SettingsPanel;
+
+/// Narrower than this, in points, and a desktop window uses the sheet too.
+const COMPACT_WIDTH: f32 = 600.0;
+/// About a fingertip.
+const TAB_HEIGHT: f32 = 48.0;
+/// Share of the screen's height an open sheet may cover before it scrolls.
+const SHEET_SHARE: f32 = 0.5;
+
+/// Whether the settings show as a sheet rather than as windows.
+pub fn is_compact(ctx: &egui::Context) -> bool {
+    is_mobile_device() || ctx.screen_rect().width() < COMPACT_WIDTH
+}
+
+/// Sizes for fingers when `compact`, or egui's own.
+pub fn apply_touch_spacing(ctx: &egui::Context, compact: bool) {
+    let mut spacing = egui::style::Spacing::default();
+    if compact {
+        spacing.interact_size.y = 40.0;
+        spacing.button_padding = egui::vec2(12.0, 8.0);
+        spacing.item_spacing = egui::vec2(10.0, 10.0);
+        spacing.slider_width = 180.0;
+        spacing.icon_width = 24.0;
+        spacing.icon_width_inner = 14.0;
+    }
+    if ctx.style().spacing != spacing {
+        ctx.style_mut(|style| style.spacing = spacing);
+    }
+}
+
+#[derive(Clone, Copy)]
+struct SheetState {
+    open: bool,
+    tab: SettingsPanel,
+}
+
+/// The tab bar, and the sheet above it with `contents` filling in the open tab's panel.
+pub fn show_bottom_sheet(ctx: &egui::Context, localization: &Localization, mut contents: impl FnMut(SettingsPanel, &mut egui::Ui)) {
+    let id = egui::Id::new("settings_sheet");
+    let mut state = ctx.data_mut(|data| *data.get_temp_mut_or(id, SheetState { open: false, tab: SettingsPanel::Practice }));
+    egui::TopBottomPanel::bottom(id)
+        .resizable(false)
+        .show(ctx, |ui| {
+            if state.open {
+                egui::ScrollArea::vertical()
+                    .max_height(ctx.screen_rect().height() * SHEET_SHARE)
+                    .show(ui, |ui| contents(state.tab, ui));
+                ui.separator();
+            }
+            ui.columns(SettingsPanel::ALL.len(), |columns| {
+                for (ui, panel) in columns.iter_mut().zip(SettingsPanel::ALL) {
+                    let showing = state.open && state.tab == panel;
+                    let tab = egui::Button::new(tr!(localization, panel.message_id())).selected(showing);
+                    if ui.add_sized([ui.available_width(), TAB_HEIGHT], tab).clicked() {
+                        // Tapping the open tab again puts the sheet away
+                        state.open = !showing;
+                        state.tab = panel;
+                    }
+                }
+            });
+        });
+    ctx.data_mut(|data| data.insert_temp(id, state));
+}
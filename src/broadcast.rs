@@ -0,0 +1,447 @@
+//! Teacher broadcast, with the `remote` feature. The teacher ticks **Broadcast** to send
+//! the selected abacus's structure and beads to every student who joins; students type
+//! the join code shown there, and the teacher's abacus appears beside their own as a copy
+//! they can watch but not move. Their own abacus stays theirs to work along on.
+//!
+//! The broadcast has its own endpoint, apart from the remote-control server: students can
+//! only listen to it, and only with the token in the join code.
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::io::ErrorKind;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, SocketAddrV4, TcpListener, TcpStream, UdpSocket};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tungstenite::handshake::server::{ErrorResponse, Request, Response};
+use tungstenite::http::StatusCode;
+use tungstenite::Message;
+
+use crate::abacus::*;
+use crate::localization::*;
+use crate::scene_layout::SelectedAbacus;
+use crate::snapshots::deck_values;
+use crate::{format_number_in_base, AbacusSettings, AbacusStructure};
+
+/// Seconds between repeats of an unchanged broadcast, so students who join late catch up.
+const RESEND_SECONDS: f32 = 1.0;
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+/// How long a connection waits for a message before checking whether to leave.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+/// The join code's token is 40 random bits, eight base-36 characters.
+const TOKEN_BITS: u32 = 40;
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum BroadcastMessage {
+    /// The teacher's abacus: its structure and the long values of each column's decks,
+    /// top deck first, ones column first.
+    Broadcast { structure: AbacusStructure, columns: Vec<Vec<u64>> },
+}
+
+enum ViewerEvent {
+    Connected,
+    State { structure: AbacusStructure, columns: Vec<Vec<u64>> },
+    Lost(String),
+}
+
+enum ViewerStatus {
+    Connecting,
+    Watching,
+    Lost(String),
+}
+
+/// A student's connection to a teacher's broadcast.
+struct Viewer {
+    inbox: Mutex<Receiver<ViewerEvent>>,
+    stop: Arc<AtomicBool>,
+    status: ViewerStatus,
+    /// The read-only copy of the teacher's abacus, once spawned, and its structure.
+    abacus: Option<(Entity, AbacusStructure)>,
+    /// The latest broadcast not shown yet.
+    pending: Option<(AbacusStructure, Vec<Vec<u64>>)>,
+}
+
+/// Messages outgoing to each student watching.
+type Clients = Arc<Mutex<Vec<Sender<String>>>>;
+
+/// The teacher's endpoint, listening while **Broadcast** is ticked.
+struct BroadcastHost {
+    address: SocketAddrV4,
+    token: u64,
+    clients: Clients,
+    stop: Arc<AtomicBool>,
+}
+
+impl BroadcastHost {
+    /// Sends `message` to every student watching, dropping those that have left.
+    fn send(&self, message: &str) {
+        if let Ok(mut clients) = self.clients.lock() {
+            clients.retain(|client| client.send(message.to_string()).is_ok());
+        }
+    }
+}
+
+impl Drop for BroadcastHost {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+#[derive(Resource, Default)]
+struct Broadcast {
+    hosting: bool,
+    host: Option<BroadcastHost>,
+    host_error: Option<String>,
+    join_code: String,
+    invalid_code: bool,
+    viewer: Option<Viewer>,
+}
+
+pub struct BroadcastPlugin;
+
+impl Plugin for BroadcastPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Broadcast>()
+            .add_systems(Update, (broadcast_ui_system, broadcast_selected_abacus, follow_broadcast).chain());
+    }
+}
+
+/// The address other machines on the network reach this one by.
+fn lan_address() -> Option<Ipv4Addr> {
+    // Connecting a UDP socket sends nothing; it only picks the interface that would be used
+    let socket = UdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect("192.0.2.1:9").ok()?;
+    match socket.local_addr().ok()?.ip() {
+        IpAddr::V4(ip) => Some(ip),
+        IpAddr::V6(_) => None,
+    }
+}
+
+/// A fresh token for the join code. Each `RandomState` is keyed from the system's
+/// randomness, so hashing nothing with one gives random bits without another dependency.
+fn new_token() -> u64 {
+    RandomState::new().build_hasher().finish() & ((1 << TOKEN_BITS) - 1)
+}
+
+/// The host's IPv4 address and port as ten base-36 characters, then its token as eight,
+/// like `2Q8VH-0B7QX-1K9Z-04MF`.
+fn join_code(address: SocketAddrV4, token: u64) -> String {
+    let number = (u64::from(u32::from(*address.ip())) << 16) | u64::from(address.port());
+    let code = format!("{:0>10}{:0>8}", format_number_in_base(number, 36), format_number_in_base(token, 36));
+    format!("{}-{}-{}-{}", &code[..5], &code[5..10], &code[10..14], &code[14..])
+}
+
+fn parse_join_code(code: &str) -> Option<(SocketAddrV4, u64)> {
+    let digits: String = code.chars().filter(char::is_ascii_alphanumeric).collect();
+    if digits.len() != 18 {
+        return None;
+    }
+    let number = u64::from_str_radix(&digits[..10], 36).ok().filter(|&number| number < 1 << 48)?;
+    let token = u64::from_str_radix(&digits[10..], 36).ok().filter(|&token| token < 1 << TOKEN_BITS)?;
+    Some((SocketAddrV4::new(Ipv4Addr::from((number >> 16) as u32), number as u16), token))
+}
+
+/// Starts listening for students, on the network if there is one.
+fn start_host() -> std::io::Result<BroadcastHost> {
+    let listener = TcpListener::bind((lan_address().unwrap_or(Ipv4Addr::LOCALHOST), 0))?;
+    let SocketAddr::V4(address) = listener.local_addr()? else {
+        return Err(ErrorKind::AddrNotAvailable.into());
+    };
+    // Accepting without blocking lets the thread notice when broadcasting stops
+    listener.set_nonblocking(true)?;
+    let token = new_token();
+    let clients = Clients::default();
+    let stop = Arc::new(AtomicBool::new(false));
+    let (thread_clients, thread_stop) = (clients.clone(), stop.clone());
+    std::thread::spawn(move || {
+        while !thread_stop.load(Ordering::Relaxed) {
+            match listener.accept() {
+                Ok((stream, _)) => {
+                    let (clients, stop) = (thread_clients.clone(), thread_stop.clone());
+                    std::thread::spawn(move || serve_viewer(stream, token, clients, stop));
+                }
+                Err(error) if error.kind() == ErrorKind::WouldBlock => std::thread::sleep(POLL_INTERVAL),
+                Err(_) => return,
+            }
+        }
+    });
+    Ok(BroadcastHost { address, token, clients, stop })
+}
+
+/// Sends one student the broadcasts until they leave or broadcasting stops. Students
+/// connect to `/<token>`; anyone else is turned away, and whatever students send is ignored.
+fn serve_viewer(stream: TcpStream, token: u64, clients: Clients, stop: Arc<AtomicBool>) {
+    // Some platforms hand out accepted streams nonblocking, like their listener
+    if stream.set_nonblocking(false).is_err() || stream.set_read_timeout(Some(CONNECT_TIMEOUT)).is_err() {
+        return;
+    }
+    let check_token = |request: &Request, response: Response| {
+        if request.uri().path() == format!("/{:x}", token) {
+            Ok(response)
+        } else {
+            let mut refusal = ErrorResponse::new(None);
+            *refusal.status_mut() = StatusCode::FORBIDDEN;
+            Err(refusal)
+        }
+    };
+    let Ok(mut socket) = tungstenite::accept_hdr(stream, check_token) else {
+        return;
+    };
+    if socket.get_ref().set_read_timeout(Some(POLL_INTERVAL)).is_err() {
+        return;
+    }
+    let (outgoing, outbox) = channel();
+    if let Ok(mut clients) = clients.lock() {
+        clients.push(outgoing);
+    }
+    while !stop.load(Ordering::Relaxed) {
+        for text in outbox.try_iter() {
+            if socket.send(Message::text(text)).is_err() {
+                return;
+            }
+        }
+        match socket.read() {
+            Ok(Message::Close(_)) => return,
+            Ok(_) => {}
+            Err(tungstenite::Error::Io(error)) if matches!(error.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+            Err(_) => return,
+        }
+    }
+    let _ = socket.close(None);
+}
+
+/// Passes the teacher's broadcasts on to the app until told to stop or cut off.
+fn watch_broadcast(address: SocketAddrV4, token: u64, events: Sender<ViewerEvent>, stop: Arc<AtomicBool>) {
+    let stream = match TcpStream::connect_timeout(&address.into(), CONNECT_TIMEOUT) {
+        Ok(stream) => stream,
+        Err(error) => {
+            let _ = events.send(ViewerEvent::Lost(error.to_string()));
+            return;
+        }
+    };
+    // The handshake gets the connect timeout; after that reads only wait a moment
+    if stream.set_read_timeout(Some(CONNECT_TIMEOUT)).is_err() {
+        return;
+    }
+    let mut socket = match tungstenite::client(format!("ws://{}/{:x}", address, token), stream) {
+        Ok((socket, _)) => socket,
+        Err(error) => {
+            let _ = events.send(ViewerEvent::Lost(error.to_string()));
+            return;
+        }
+    };
+    if socket.get_ref().set_read_timeout(Some(POLL_INTERVAL)).is_err() || events.send(ViewerEvent::Connected).is_err() {
+        return;
+    }
+    while !stop.load(Ordering::Relaxed) {
+        match socket.read() {
+            Ok(Message::Text(text)) => {
+                if let Ok(BroadcastMessage::Broadcast { mut structure, columns }) = serde_json::from_str(text.as_str()) {
+                    // Whoever sent it, it can't be allowed to hang the app or divide by zero
+                    structure.sanitize();
+                    if events.send(ViewerEvent::State { structure, columns }).is_err() {
+                        return;
+                    }
+                }
+            }
+            Ok(_) => {}
+            Err(tungstenite::Error::Io(error)) if matches!(error.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+            Err(error) => {
+                let _ = events.send(ViewerEvent::Lost(error.to_string()));
+                return;
+            }
+        }
+    }
+    let _ = socket.close(None);
+}
+
+fn broadcast_ui_system(
+    mut contexts: EguiContexts,
+    mut broadcast: ResMut<Broadcast>,
+    mut commands: Commands,
+    localization: Res<Localization>,
+) {
+    let localization = &*localization;
+    let broadcast = &mut *broadcast;
+    egui::Window::new(tr!(localization, "broadcast-window"))
+        .id(egui::Id::new("broadcast"))
+        .default_pos([10.0, 1240.0])
+        .default_open(false)
+        .show(contexts.ctx_mut(), |ui| {
+            if ui.checkbox(&mut broadcast.hosting, tr!(localization, "broadcast-host")).changed() {
+                broadcast.host = None;
+                broadcast.host_error = None;
+                if broadcast.hosting {
+                    match start_host() {
+                        Ok(host) => broadcast.host = Some(host),
+                        Err(error) => {
+                            warn!("{}", tr!(localization, "log-broadcast-host-failed", error = error.to_string()));
+                            broadcast.host_error = Some(error.to_string());
+                            broadcast.hosting = false;
+                        }
+                    }
+                }
+            }
+            if let Some(host) = &broadcast.host {
+                ui.heading(tr!(localization, "broadcast-join-code", code = join_code(host.address, host.token)));
+                if host.address.ip().is_loopback() {
+                    ui.label(tr!(localization, "broadcast-loopback"));
+                }
+            }
+            if let Some(error) = &broadcast.host_error {
+                ui.colored_label(egui::Color32::LIGHT_RED, tr!(localization, "broadcast-host-failed", error = error.clone()));
+            }
+
+            ui.separator();
+            match &broadcast.viewer {
+                Some(viewer) => {
+                    match &viewer.status {
+                        ViewerStatus::Connecting => ui.label(tr!(localization, "broadcast-connecting")),
+                        ViewerStatus::Watching => ui.label(tr!(localization, "broadcast-watching")),
+                        ViewerStatus::Lost(error) => ui.colored_label(egui::Color32::LIGHT_RED, tr!(localization, "broadcast-lost", error = error.clone())),
+                    };
+                    if ui.button(tr!(localization, "broadcast-leave")).clicked() {
+                        viewer.stop.store(true, Ordering::Relaxed);
+                        if let Some((entity, _)) = viewer.abacus {
+                            commands.entity(entity).despawn();
+                        }
+                        broadcast.viewer = None;
+                    }
+                }
+                None => {
+                    ui.horizontal(|ui| {
+                        let response = ui.add_sized([180.0, ui.available_height()],
+                            egui::TextEdit::singleline(&mut broadcast.join_code).hint_text(tr!(localization, "broadcast-code-hint")));
+                        let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                        if ui.button(tr!(localization, "broadcast-join")).clicked() || submitted {
+                            match parse_join_code(&broadcast.join_code) {
+                                Some((address, token)) => {
+                                    let (events, inbox) = channel();
+                                    let stop = Arc::new(AtomicBool::new(false));
+                                    let thread_stop = stop.clone();
+                                    std::thread::spawn(move || watch_broadcast(address, token, events, thread_stop));
+                                    broadcast.viewer = Some(Viewer {
+                                        inbox: Mutex::new(inbox),
+                                        stop,
+                                        status: ViewerStatus::Connecting,
+                                        abacus: None,
+                                        pending: None,
+                                    });
+                                    broadcast.invalid_code = false;
+                                }
+                                None => broadcast.invalid_code = true,
+                            }
+                        }
+                    });
+                    if broadcast.invalid_code {
+                        ui.colored_label(egui::Color32::LIGHT_RED, tr!(localization, "broadcast-invalid-code"));
+                    }
+                }
+            }
+        });
+}
+
+/// Sends the selected abacus to every student whenever it changes, and again every second.
+fn broadcast_selected_abacus(
+    broadcast: Res<Broadcast>,
+    selected: Res<SelectedAbacus>,
+    abacus_query: Query<(&Abacus, &AbacusStructure)>,
+    long_query: Query<&AbacusLong>,
+    time: Res<Time>,
+    mut last_sent: Local<String>,
+    mut since_sent: Local<f32>,
+) {
+    let Some(host) = &broadcast.host else {
+        return;
+    };
+    let Some((abacus, structure)) = selected.0.and_then(|entity| abacus_query.get(entity).ok()) else {
+        return;
+    };
+    let message = BroadcastMessage::Broadcast { structure: structure.clone(), columns: deck_values(abacus, &long_query) };
+    let Ok(message) = serde_json::to_string(&message) else {
+        return;
+    };
+    *since_sent += time.delta_secs();
+    if message != *last_sent || *since_sent >= RESEND_SECONDS {
+        host.send(&message);
+        *last_sent = message;
+        *since_sent = 0.0;
+    }
+}
+
+/// Keeps the read-only copy of the teacher's abacus in step with the broadcasts,
+/// rebuilding it when the teacher's structure changes.
+fn follow_broadcast(
+    mut broadcast: ResMut<Broadcast>,
+    mut commands: Commands,
+    settings: Res<AbacusSettings>,
+    abacus_query: Query<&Abacus>,
+    mut long_query: Query<&mut AbacusLong>,
+    localization: Res<Localization>,
+) {
+    let Some(viewer) = &mut broadcast.viewer else {
+        return;
+    };
+    if let Ok(inbox) = viewer.inbox.lock() {
+        for event in inbox.try_iter() {
+            match event {
+                ViewerEvent::Connected => {
+                    viewer.status = ViewerStatus::Watching;
+                    info!("{}", tr!(localization, "log-broadcast-joined"));
+                }
+                ViewerEvent::State { structure, columns } => viewer.pending = Some((structure, columns)),
+                ViewerEvent::Lost(error) => {
+                    warn!("{}", tr!(localization, "log-broadcast-lost", error = error.clone()));
+                    viewer.status = ViewerStatus::Lost(error);
+                }
+            }
+        }
+    }
+    let Some((structure, columns)) = &viewer.pending else {
+        return;
+    };
+
+    if viewer.abacus.as_ref().is_none_or(|(_, shown)| shown != structure) {
+        if let Some((entity, _)) = viewer.abacus.take() {
+            commands.entity(entity).despawn();
+        }
+        // The copy goes in its own slot, built from a copy of the settings so the panel's
+        // structure is left alone and nothing restyles as if the settings had changed
+        let mut teacher_settings = settings.clone();
+        structure.apply_to(&mut teacher_settings);
+        let slot = abacus_query.iter().map(|abacus| abacus.slot + 1).max().unwrap_or(0);
+        let entity = spawn_abacus(&mut commands, &teacher_settings, slot);
+        commands.entity(entity).insert(ReadOnlyAbacus);
+        viewer.abacus = Some((entity, structure.clone()));
+        // Its beads can be moved once it has been spawned, next frame
+        return;
+    }
+    let Some(entity) = viewer.abacus.as_ref().map(|(entity, _)| *entity) else {
+        return;
+    };
+    if let Ok(abacus) = abacus_query.get(entity) {
+        // The copy takes no operation requests, so its beads are set here, each deck
+        // held to the beads it has
+        let mut changed = false;
+        for ((layout, longs), target) in abacus.columns.iter().zip(&abacus.longs).zip(columns) {
+            for (deck, (&long, &long_value)) in longs.iter().zip(target).enumerate() {
+                let long_value = long_value.min(layout.deck(deck).bead_count as u64);
+                if let Ok(mut long) = long_query.get_mut(long) {
+                    if long.value != long_value {
+                        long.value = long_value;
+                        changed = true;
+                    }
+                }
+            }
+        }
+        if changed {
+            commands.send_event(AbacusChanged);
+        }
+        viewer.pending = None;
+    }
+}
@@ -0,0 +1,170 @@
+//! Bead colors for particular columns, such as a white units column or every other group of
+//! three tinted so thousands and millions stand apart. Each rule has a shared material of its
+//! own, copied from the plain beads' so the grain and finish match; configurations save the
+//! rules.
+
+use bevy::prelude::*;
+use bevy_egui::egui;
+use serde::{Deserialize, Serialize};
+
+use crate::abacus::*;
+use crate::localization::*;
+use crate::wood_grain::{abacus_material, AbacusMaterial};
+use crate::AbacusSettings;
+
+/// Colors a block of columns, once or repeating along the abacus. Columns count from the
+/// units; where rules overlap the last one wins.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ColumnColor {
+    pub first_column: usize,
+    /// Columns in the block.
+    pub width: usize,
+    /// Columns from the start of one block to the next; zero colors a single block.
+    pub every: usize,
+    pub color: Color,
+}
+
+impl Default for ColumnColor {
+    /// The units column, in white.
+    fn default() -> Self {
+        Self {
+            first_column: 0,
+            width: 1,
+            every: 0,
+            color: Color::srgb(0.95, 0.93, 0.88),
+        }
+    }
+}
+
+impl ColumnColor {
+    /// Every other group of three from the thousands, for reading long numbers.
+    fn alternate_thousands() -> Self {
+        Self {
+            first_column: 3,
+            width: 3,
+            every: 6,
+            color: Color::srgb(0.2, 0.35, 0.6),
+        }
+    }
+
+    pub fn covers(&self, column: usize) -> bool {
+        let Some(offset) = column.checked_sub(self.first_column) else {
+            return false;
+        };
+        let offset = if self.every > 0 { offset % self.every } else { offset };
+        offset < self.width
+    }
+}
+
+/// A bead in a column colored by the rule at this index in the settings.
+#[derive(Component)]
+pub struct ColumnColored(pub usize);
+
+/// The material a bead is drawn with when it isn't hovered or highlighted.
+pub fn resting_bead_material<'a>(settings: &'a AbacusSettings, alt: bool, colored: Option<&ColumnColored>) -> &'a Handle<AbacusMaterial> {
+    match colored.and_then(|colored| settings.column_bead_materials.get(colored.0)) {
+        Some(material) => material,
+        None if alt => &settings.bead_alt_material,
+        None => &settings.bead_material,
+    }
+}
+
+pub struct ColumnColorsPlugin;
+
+impl Plugin for ColumnColorsPlugin {
+    fn build(&self, app: &mut App) {
+        // Before Update, so new beads have their color before a fade-in copies their material
+        app.add_systems(PreUpdate, apply_column_colors);
+    }
+}
+
+/// Keeps one material per rule and puts each colored column's beads on it, when the rules
+/// change or beads are added by a rebuild or a spliced column. Hovered and highlighted beads
+/// are left alone and come back to the right color when that ends.
+fn apply_column_colors(
+    mut commands: Commands,
+    mut settings: ResMut<AbacusSettings>,
+    mut applied: Local<Option<Vec<ColumnColor>>>,
+    mut materials: ResMut<Assets<AbacusMaterial>>,
+    new_beads: Query<(), Added<AbacusBead>>,
+    abacus_query: Query<&Abacus>,
+    beads_of_query: Query<&BeadsOf>,
+    mut bead_query: Query<(&mut MeshMaterial3d<AbacusMaterial>, Has<AltBead>), With<AbacusBead>>,
+) {
+    let rules_changed = applied.as_ref() != Some(&settings.column_colors);
+    if !rules_changed && new_beads.is_empty() {
+        return;
+    }
+    if rules_changed {
+        *applied = Some(settings.column_colors.clone());
+        let settings = &mut *settings;
+        settings.column_bead_materials.truncate(settings.column_colors.len());
+        while settings.column_bead_materials.len() < settings.column_colors.len() {
+            let material = materials.get(&settings.bead_material).cloned().unwrap_or_else(|| abacus_material(settings.ui_bead_color));
+            settings.column_bead_materials.push(materials.add(material));
+        }
+        for (rule, handle) in settings.column_colors.iter().zip(&settings.column_bead_materials) {
+            if let Some(material) = materials.get_mut(handle) {
+                material.base.base_color = rule.color;
+            }
+        }
+    }
+
+    let settings = &*settings;
+    for abacus in &abacus_query {
+        for (column, longs) in abacus.longs.iter().enumerate() {
+            let rule = settings.column_colors.iter().rposition(|rule| rule.covers(column));
+            for &long in longs {
+                let Ok(beads) = beads_of_query.get(long) else {
+                    continue;
+                };
+                for &bead in &beads[..] {
+                    let Ok((mut material, alt)) = bead_query.get_mut(bead) else {
+                        continue;
+                    };
+                    match rule {
+                        Some(rule) => commands.entity(bead).insert(ColumnColored(rule)),
+                        None => commands.entity(bead).remove::<ColumnColored>(),
+                    };
+                    if material.0 != settings.bead_hover_material && material.0 != settings.bead_highlight_material {
+                        let colored = rule.map(ColumnColored);
+                        material.0 = resting_bead_material(settings, alt, colored.as_ref()).clone();
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The list of column color rules, shown under Appearance.
+pub fn column_color_controls(ui: &mut egui::Ui, column_colors: &mut Vec<ColumnColor>, column_count: usize, localization: &Localization) {
+    ui.label(tr!(localization, "column-colors"));
+    let max_column_index = column_count.saturating_sub(1);
+    let mut rule_to_remove = None;
+    for (idx, rule) in column_colors.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            let mut color = rule.color.to_srgba().to_f32_array_no_alpha();
+            if ui.color_edit_button_rgb(&mut color).changed() {
+                rule.color = Color::srgb_from_array(color);
+            }
+            ui.add(egui::DragValue::new(&mut rule.first_column).range(0..=max_column_index).prefix(format!("{} ", tr!(localization, "column-colors-first"))));
+            ui.add(egui::DragValue::new(&mut rule.width).range(1..=column_count.max(1)).prefix(format!("{} ", tr!(localization, "column-colors-width"))));
+            ui.add(egui::DragValue::new(&mut rule.every).range(0..=column_count.max(1)).prefix(format!("{} ", tr!(localization, "column-colors-every"))));
+            if ui.button(tr!(localization, "remove")).clicked() {
+                rule_to_remove = Some(idx);
+            }
+        });
+    }
+    if let Some(idx) = rule_to_remove {
+        column_colors.remove(idx);
+    }
+    ui.horizontal(|ui| {
+        if ui.button(tr!(localization, "add-column-color")).clicked() {
+            column_colors.push(ColumnColor::default());
+        }
+        if ui.button(tr!(localization, "column-colors-thousands")).clicked() {
+            column_colors.push(ColumnColor::alternate_thousands());
+        }
+    });
+}
@@ -0,0 +1,138 @@
+//! Typing a column's value straight in: clicking a column's value text opens a small edit
+//! box on it, and what's typed there (in the abacus's base) sets just that column.
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::abacus::*;
+use crate::config_gallery::ThumbnailCamera;
+use crate::format_number_in_base;
+use crate::localization::*;
+
+/// The column being typed into.
+struct ColumnEdit {
+    abacus: Entity,
+    column: usize,
+    /// Where the box opens, in screen points.
+    position: Vec2,
+    text: String,
+    /// Focus is asked for once, so a click elsewhere can close the box.
+    focused: bool,
+}
+
+#[derive(Resource, Default)]
+struct ColumnEntry {
+    editing: Option<ColumnEdit>,
+}
+
+pub struct ColumnEntryPlugin;
+
+impl Plugin for ColumnEntryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ColumnEntry>()
+            .add_systems(Update, (open_column_entry, column_entry_ui).chain());
+    }
+}
+
+/// Opens the edit box on a click close to a visible column value text.
+fn open_column_entry(
+    mut contexts: EguiContexts,
+    mouse: Res<ButtonInput<MouseButton>>,
+    windows: Query<&Window>,
+    camera_query: Query<(&Camera, &GlobalTransform), (With<Camera3d>, Without<ThumbnailCamera>)>,
+    abacus_query: Query<(Entity, &Abacus, &GlobalTransform), Without<ReadOnlyAbacus>>,
+    text_query: Query<(&GlobalTransform, &InheritedVisibility)>,
+    long_query: Query<&AbacusLong>,
+    mut entry: ResMut<ColumnEntry>,
+) {
+    if !mouse.just_pressed(MouseButton::Left) || contexts.ctx_mut().is_pointer_over_area() {
+        return;
+    }
+    let Some(cursor) = windows.iter().find_map(Window::cursor_position) else {
+        return;
+    };
+    let Some((camera, camera_transform)) = camera_query.iter().find(|(camera, _)| camera.is_active) else {
+        return;
+    };
+
+    let mut closest: Option<(f32, ColumnEdit)> = None;
+    for (entity, abacus, abacus_transform) in &abacus_query {
+        // A label can be clicked anywhere within half a column of its middle
+        let half_column = abacus_transform.affine().transform_vector3(abacus.rod_rotation() * Vec3::X * abacus.proportions.column_spacing / 2.0);
+        for (column, &text_entity) in abacus.column_texts.iter().enumerate() {
+            let Ok((text_transform, visibility)) = text_query.get(text_entity) else {
+                continue;
+            };
+            if !visibility.get() {
+                continue;
+            }
+            let center = text_transform.translation();
+            let (Ok(screen), Ok(edge)) = (
+                camera.world_to_viewport(camera_transform, center),
+                camera.world_to_viewport(camera_transform, center + half_column),
+            ) else {
+                continue;
+            };
+            let distance = screen.distance(cursor);
+            if distance > screen.distance(edge) || closest.as_ref().is_some_and(|(closest, _)| *closest <= distance) {
+                continue;
+            }
+            let text = format_number_in_base(abacus.get_column_value(column, &long_query), abacus.abacus_base);
+            closest = Some((distance, ColumnEdit { abacus: entity, column, position: screen, text, focused: false }));
+        }
+    }
+    if let Some((_, edit)) = closest {
+        entry.editing = Some(edit);
+    }
+}
+
+fn column_entry_ui(
+    mut contexts: EguiContexts,
+    mut entry: ResMut<ColumnEntry>,
+    abacus_query: Query<&Abacus>,
+    mut long_query: Query<&mut AbacusLong>,
+    mut commands: Commands,
+    localization: Res<Localization>,
+) {
+    let Some(edit) = entry.editing.as_mut() else {
+        return;
+    };
+    let Ok(abacus) = abacus_query.get(edit.abacus) else {
+        entry.editing = None;
+        return;
+    };
+    if edit.column >= abacus.columns.len() {
+        entry.editing = None;
+        return;
+    }
+
+    let mut close = false;
+    egui::Area::new(egui::Id::new("column_entry"))
+        .fixed_pos([edit.position.x, edit.position.y])
+        .pivot(egui::Align2::CENTER_CENTER)
+        .show(contexts.ctx_mut(), |ui| {
+            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                let response = ui.add(egui::TextEdit::singleline(&mut edit.text)
+                    .desired_width(60.0)
+                    .hint_text(tr!(localization, "column-entry-hint", base = abacus.abacus_base)));
+                if !edit.focused {
+                    response.request_focus();
+                    edit.focused = true;
+                }
+                if response.changed() {
+                    let digits = edit.text.trim();
+                    // Bases past 36 have no digits to type
+                    if let Some(value) = (2..=36).contains(&abacus.abacus_base)
+                        .then(|| u64::from_str_radix(digits, abacus.abacus_base as u32).ok())
+                        .flatten()
+                    {
+                        abacus.set_column_value(edit.column, value, &mut long_query, &mut commands);
+                    }
+                }
+                close = response.lost_focus();
+            });
+        });
+    if close {
+        entry.editing = None;
+    }
+}
@@ -0,0 +1,144 @@
+//! A glow over a column that has just changed, or just been picked as the active column,
+//! so the change can be seen from the back of a classroom. Beads share one material, so
+//! the glow is a box of its own around the column that fades out.
+
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+use crate::abacus::*;
+use crate::active_column::{column_box, ActiveColumn};
+use crate::scene_layout::SelectedAbacus;
+
+/// Seconds a glow takes to fade out.
+const GLOW_SECONDS: f32 = 0.8;
+const GLOW_COLOR: LinearRgba = LinearRgba::rgb(1.0, 0.8, 0.3);
+/// Emissive strength at the start of a glow.
+const GLOW_STRENGTH: f32 = 4.0;
+/// The glow sits just inside the active column's cursor.
+const GLOW_MARGIN: f32 = 0.2;
+
+/// The box every glow is drawn with.
+#[derive(Resource)]
+struct GlowMesh(Handle<Mesh>);
+
+impl FromWorld for GlowMesh {
+    fn from_world(world: &mut World) -> Self {
+        Self(world.resource_mut::<Assets<Mesh>>().add(Cuboid::new(1.0, 1.0, 1.0)))
+    }
+}
+
+#[derive(Component)]
+struct ColumnGlow {
+    abacus: Entity,
+    column: usize,
+    remaining: f32,
+}
+
+pub struct ColumnGlowPlugin;
+
+impl Plugin for ColumnGlowPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GlowMesh>()
+            .add_systems(Update, (
+                (glow_changed_columns.run_if(on_event::<AbacusChanged>), glow_active_column),
+                fade_column_glows,
+            ).chain());
+    }
+}
+
+/// Starts a glow on the column, or restarts the one already there.
+fn start_glow(
+    abacus: Entity,
+    column: usize,
+    glows: &mut Query<&mut ColumnGlow>,
+    commands: &mut Commands,
+    mesh: &GlowMesh,
+    materials: &mut Assets<StandardMaterial>,
+) {
+    if let Some(mut glow) = glows.iter_mut().find(|glow| glow.abacus == abacus && glow.column == column) {
+        glow.remaining = GLOW_SECONDS;
+        return;
+    }
+    // Each glow fades on its own, so it needs its own material
+    commands.spawn((
+        ColumnGlow { abacus, column, remaining: GLOW_SECONDS },
+        Mesh3d(mesh.0.clone()),
+        MeshMaterial3d(materials.add(StandardMaterial {
+            base_color: Color::NONE,
+            emissive: GLOW_COLOR * GLOW_STRENGTH,
+            alpha_mode: AlphaMode::Add,
+            ..default()
+        })),
+        Transform::default(),
+        Visibility::Hidden,
+    ));
+}
+
+/// Glows every column whose value differs from the last time the abaci changed.
+fn glow_changed_columns(
+    abacus_query: Query<(Entity, &Abacus)>,
+    long_query: Query<&AbacusLong>,
+    mut glows: Query<&mut ColumnGlow>,
+    mut last_values: Local<HashMap<Entity, Vec<u64>>>,
+    mut commands: Commands,
+    mesh: Res<GlowMesh>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    last_values.retain(|entity, _| abacus_query.contains(*entity));
+    for (entity, abacus) in &abacus_query {
+        let values: Vec<u64> = (0..abacus.columns.len()).map(|i| abacus.get_column_value(i, &long_query)).collect();
+        let Some(previous) = last_values.insert(entity, values.clone()) else {
+            // Newly built, so nothing has changed yet
+            continue;
+        };
+        if previous.len() != values.len() {
+            continue;
+        }
+        for (column, (before, after)) in previous.iter().zip(&values).enumerate() {
+            if before != after {
+                start_glow(entity, column, &mut glows, &mut commands, &mesh, &mut materials);
+            }
+        }
+    }
+}
+
+fn glow_active_column(
+    active: Res<ActiveColumn>,
+    selected: Res<SelectedAbacus>,
+    mut glows: Query<&mut ColumnGlow>,
+    mut last_active: Local<Option<(Entity, usize)>>,
+    mut commands: Commands,
+    mesh: Res<GlowMesh>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let current = selected.0.zip(active.column);
+    if std::mem::replace(&mut *last_active, current) == current {
+        return;
+    }
+    if let Some((abacus, column)) = current {
+        start_glow(abacus, column, &mut glows, &mut commands, &mesh, &mut materials);
+    }
+}
+
+/// Keeps each glow around its column while it fades, then removes it.
+fn fade_column_glows(
+    time: Res<Time>,
+    abacus_query: Query<(&Abacus, &Transform), Without<ColumnGlow>>,
+    mut glows: Query<(Entity, &mut ColumnGlow, &mut Transform, &mut Visibility, &MeshMaterial3d<StandardMaterial>)>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut commands: Commands,
+) {
+    for (entity, mut glow, mut transform, mut visibility, material) in &mut glows {
+        glow.remaining -= time.delta_secs();
+        let abacus = abacus_query.get(glow.abacus).ok().filter(|(abacus, _)| glow.column < abacus.columns.len());
+        let Some((abacus, abacus_transform)) = abacus.filter(|_| glow.remaining > 0.0) else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+        *transform = column_box(abacus, abacus_transform, glow.column, GLOW_MARGIN);
+        *visibility = Visibility::Inherited;
+        if let Some(material) = materials.get_mut(&material.0) {
+            material.emissive = GLOW_COLOR * (GLOW_STRENGTH * glow.remaining / GLOW_SECONDS);
+        }
+    }
+}
@@ -0,0 +1,173 @@
+//! Hovering a column: with the pointer over any of its beads or its rod, the whole column
+//! takes the hover color and a tooltip gives its place value. Pointer events from the
+//! beads and rods bubble up to the column's longs, which track what's under the pointer.
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use std::collections::HashMap;
+
+use crate::abacus::*;
+use crate::column_colors::{resting_bead_material, ColumnColored};
+use crate::currency::format_money;
+use crate::localization::*;
+use crate::wood_grain::AbacusMaterial;
+use crate::{group_digits, AbacusSettings};
+
+/// The column under the pointer.
+#[derive(Resource, Default)]
+pub struct HoveredColumn {
+    /// Parts of each column under the pointer; moving between two parts can send the new
+    /// part's Over before the old part's Out.
+    parts: HashMap<(Entity, usize), usize>,
+    /// The column drawn with the hover color.
+    shown: Option<(Entity, usize)>,
+}
+
+impl HoveredColumn {
+    pub fn column(&self) -> Option<(Entity, usize)> {
+        self.parts.keys().next().copied()
+    }
+}
+
+pub struct ColumnHoverPlugin;
+
+impl Plugin for ColumnHoverPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HoveredColumn>()
+            .add_systems(Update, (color_hovered_column, column_tooltip));
+    }
+}
+
+/// The abacus and column a long belongs to.
+fn column_of(long: Entity, parents: &Query<&ChildOf>, abaci: &Query<&Abacus>) -> Option<(Entity, usize)> {
+    let abacus_entity = parents.get(long).ok()?.parent();
+    let abacus = abaci.get(abacus_entity).ok()?;
+    let (column, _) = abacus.deck_of(long)?;
+    Some((abacus_entity, column))
+}
+
+pub fn hover_column(
+    trigger: Trigger<Pointer<Over>>,
+    parents: Query<&ChildOf>,
+    abaci: Query<&Abacus>,
+    mut hovered: ResMut<HoveredColumn>,
+) {
+    if let Some(column) = column_of(trigger.target(), &parents, &abaci) {
+        *hovered.parts.entry(column).or_default() += 1;
+    }
+}
+
+pub fn unhover_column(
+    trigger: Trigger<Pointer<Out>>,
+    parents: Query<&ChildOf>,
+    abaci: Query<&Abacus>,
+    mut hovered: ResMut<HoveredColumn>,
+) {
+    let Some(column) = column_of(trigger.target(), &parents, &abaci) else {
+        return;
+    };
+    if let Some(parts) = hovered.parts.get_mut(&column) {
+        *parts -= 1;
+        if *parts == 0 {
+            hovered.parts.remove(&column);
+        }
+    }
+}
+
+/// Swaps the beads and rods of a column to the hover material, or back from it to their
+/// own, leaving any part drawn with something else (a lesson highlight, an overflow flash)
+/// alone. Two-tone and colored columns' beads come back to their own color.
+fn swap_column_materials(
+    abacus: &Abacus,
+    column: usize,
+    hovered: bool,
+    settings: &AbacusSettings,
+    children_query: &Query<&Children>,
+    material_query: &mut Query<(&mut MeshMaterial3d<AbacusMaterial>, Has<AbacusBead>, Has<AltBead>, Option<&ColumnColored>)>,
+) {
+    let hover = &settings.bead_hover_material;
+    for &long in &abacus.longs[column] {
+        let Ok(children) = children_query.get(long) else {
+            continue;
+        };
+        for &child in &children[..] {
+            let Ok((mut material, is_bead, is_alt, colored)) = material_query.get_mut(child) else {
+                continue;
+            };
+            let resting = if is_bead { resting_bead_material(settings, is_alt, colored) } else { &settings.frame_material };
+            let (from, to) = if hovered { (resting, hover) } else { (hover, resting) };
+            if material.0 == *from {
+                material.0 = to.clone();
+            }
+        }
+    }
+}
+
+fn color_hovered_column(
+    mut hovered: ResMut<HoveredColumn>,
+    settings: Res<AbacusSettings>,
+    abacus_query: Query<&Abacus>,
+    children_query: Query<&Children>,
+    mut material_query: Query<(&mut MeshMaterial3d<AbacusMaterial>, Has<AbacusBead>, Has<AltBead>, Option<&ColumnColored>)>,
+) {
+    // Parts of an abacus rebuilt under the pointer never send their Out
+    hovered.parts.retain(|(abacus, column), _| abacus_query.get(*abacus).is_ok_and(|abacus| *column < abacus.columns.len()));
+    let column = hovered.column();
+    if column == hovered.shown {
+        return;
+    }
+    if let Some((abacus, column)) = hovered.shown {
+        if let Some(abacus) = abacus_query.get(abacus).ok().filter(|abacus| column < abacus.columns.len()) {
+            swap_column_materials(abacus, column, false, &settings, &children_query, &mut material_query);
+        }
+    }
+    if let Some((abacus, column)) = column {
+        if let Ok(abacus) = abacus_query.get(abacus) {
+            swap_column_materials(abacus, column, true, &settings, &children_query, &mut material_query);
+        }
+    }
+    hovered.shown = column;
+}
+
+/// Shows the hovered column's place value next to the pointer.
+fn column_tooltip(
+    mut contexts: EguiContexts,
+    hovered: Res<HoveredColumn>,
+    abacus_query: Query<&Abacus>,
+    settings: Res<AbacusSettings>,
+    localization: Res<Localization>,
+) {
+    let Some((abacus, column)) = hovered.column() else {
+        return;
+    };
+    let Ok(abacus) = abacus_query.get(abacus) else {
+        return;
+    };
+    let ctx = contexts.ctx_mut();
+    let Some(pointer) = ctx.pointer_hover_pos() else {
+        return;
+    };
+    let place_value = abacus.place_value(column);
+    let separator = settings.digit_grouping.separator(&localization);
+    // In money mode the ones column is worth a cent
+    let value = if settings.currency_mode && abacus.abacus_base == 10 {
+        format_money(place_value, &settings.currency_symbol, &separator)
+    } else {
+        group_digits(&place_value.to_string(), &separator)
+    };
+    egui::Area::new(egui::Id::new("column_tooltip"))
+        .order(egui::Order::Tooltip)
+        .fixed_pos(pointer + egui::vec2(16.0, 16.0))
+        .interactable(false)
+        .show(ctx, |ui| {
+            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                // A power of the base only where every column shares it
+                let text = if abacus.value_model == ValueModel::PlaceValue && !abacus.is_mixed_radix() {
+                    tr!(localization, "column-place-value", value = value, base = abacus.abacus_base, power = column)
+                } else {
+                    tr!(localization, "column-unit-value", value = value)
+                };
+                ui.label(text);
+            });
+        });
+}
@@ -0,0 +1,61 @@
+//! Keyboard-only bead work: the arrow keys pick the active column and move it up or down
+//! one bead-step, without carrying, and the number keys set it to a digit outright.
+
+use bevy::prelude::*;
+
+use crate::abacus::*;
+use crate::active_column::ActiveColumn;
+use crate::keybindings::{ActionInput, InputAction};
+use crate::scene_layout::SelectedAbacus;
+
+pub struct ColumnKeysPlugin;
+
+impl Plugin for ColumnKeysPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, column_keys);
+    }
+}
+
+fn column_keys(
+    input: ActionInput,
+    selected: Res<SelectedAbacus>,
+    abacus_query: Query<&Abacus>,
+    mut long_query: Query<&mut AbacusLong>,
+    mut active: ResMut<ActiveColumn>,
+    mut commands: Commands,
+) {
+    let Some(abacus) = selected.0.and_then(|entity| abacus_query.get(entity).ok()) else {
+        return;
+    };
+    if input.just_pressed(InputAction::ColumnLeft) {
+        active.step(abacus.left_step(), abacus.columns.len());
+    }
+    if input.just_pressed(InputAction::ColumnRight) {
+        active.step(-abacus.left_step(), abacus.columns.len());
+    }
+    if input.just_pressed(InputAction::DeselectColumn) {
+        active.column = None;
+    }
+    let Some(column) = active.column.filter(|&column| column < abacus.columns.len()) else {
+        return;
+    };
+
+    let layout = &abacus.columns[column];
+    let current_value = abacus.get_column_value(column, &long_query.as_readonly());
+    let mut target_value = None;
+    if input.just_pressed(InputAction::ColumnUp) {
+        target_value = current_value.checked_add(layout.bead_value_multiplier).filter(|&value| value <= layout.max_value());
+    }
+    if input.just_pressed(InputAction::ColumnDown) {
+        target_value = current_value.checked_sub(layout.bead_value_multiplier);
+    }
+    for digit in 0..=9 {
+        if input.just_pressed(InputAction::Digit(digit)) {
+            // Digits the column can't show stop at its largest value
+            target_value = Some(u64::from(digit));
+        }
+    }
+    if let Some(value) = target_value.filter(|&value| value != current_value) {
+        abacus.set_column_value(column, value, &mut long_query, &mut commands);
+    }
+}
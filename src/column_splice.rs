@@ -0,0 +1,136 @@
+//! Adding and removing single columns without rebuilding the abacus: the new column's
+//! longs are spliced into the frame and fade in, the others slide over to make room, and
+//! the total is kept wherever the new layout can still show it.
+
+use bevy::prelude::*;
+
+use crate::abacus::*;
+use crate::active_column::ActiveColumn;
+use crate::layout_transitions::{slide_into_place, FadeIn};
+use crate::operations::ColumnOpQueue;
+use crate::scene_layout::SelectedAbacus;
+use crate::{AbacusSettings, AbacusStructure};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ColumnSplice {
+    /// A column at the viewer's left.
+    AddLeft,
+    /// A column at the viewer's right.
+    AddRight,
+    /// The active column, or the highest place if none is active.
+    Remove,
+}
+
+#[derive(Event)]
+pub struct ColumnSpliceRequested {
+    pub abacus: Entity,
+    pub splice: ColumnSplice,
+}
+
+pub struct ColumnSplicePlugin;
+
+impl Plugin for ColumnSplicePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ColumnSpliceRequested>()
+            .add_systems(Update, splice_columns);
+    }
+}
+
+fn splice_columns(
+    mut requests: EventReader<ColumnSpliceRequested>,
+    mut settings: ResMut<AbacusSettings>,
+    selected: Res<SelectedAbacus>,
+    mut active: ResMut<ActiveColumn>,
+    mut abacus_query: Query<(&mut Abacus, &mut AbacusStructure)>,
+    mut long_query: Query<&mut AbacusLong>,
+    mut op_queue: ResMut<ColumnOpQueue>,
+    mut commands: Commands,
+) {
+    for request in requests.read() {
+        let Ok((mut abacus, mut structure)) = abacus_query.get_mut(request.abacus) else {
+            continue;
+        };
+        let column_count = abacus.columns.len();
+        let is_selected = selected.0 == Some(request.abacus);
+        // Queued moves name columns by their old places
+        op_queue.clear_abacus(request.abacus);
+        let total = abacus.beads_total_value(&long_query.as_readonly());
+
+        match request.splice {
+            ColumnSplice::AddLeft | ColumnSplice::AddRight => {
+                if column_count >= MAX_COLUMNS {
+                    continue;
+                }
+                // The ones end is on the right unless the ones column is on the left
+                let at_ones_end = (request.splice == ColumnSplice::AddLeft) == abacus.ones_on_left;
+                let column = if at_ones_end { 0 } else { column_count };
+                if at_ones_end {
+                    for column_override in &mut structure.column_overrides {
+                        column_override.column += 1;
+                    }
+                }
+                structure.column_count += 1;
+
+                // The new column is laid out as a rebuild would lay it out
+                abacus.columns.insert(column, structure.column_layout(column));
+                abacus.longs.insert(column, Vec::new());
+                let values = abacus.column_values_for_total(total);
+                let long_values = abacus.columns[column].long_values(values[column]);
+                let longs = spawn_column_longs(&mut commands, &abacus, column, &settings, &long_values);
+                let text = spawn_column_text(&mut commands, &abacus, column, &settings);
+                for &part in longs.iter().chain([&text]) {
+                    commands.entity(part).insert(FadeIn::default());
+                }
+                commands.entity(request.abacus).add_children(&longs).add_child(text);
+                abacus.longs[column] = longs;
+                abacus.column_texts.insert(column, text);
+
+                if is_selected && at_ones_end {
+                    active.column = active.column.map(|active| active + 1);
+                }
+            }
+            ColumnSplice::Remove => {
+                if column_count <= 1 {
+                    continue;
+                }
+                let column = active.column
+                    .filter(|&active| is_selected && active < column_count)
+                    .unwrap_or(column_count - 1);
+                structure.column_overrides.retain(|column_override| column_override.column != column);
+                for column_override in &mut structure.column_overrides {
+                    if column_override.column > column {
+                        column_override.column -= 1;
+                    }
+                }
+                structure.column_count -= 1;
+
+                for long in abacus.longs.remove(column) {
+                    commands.entity(long).despawn();
+                }
+                commands.entity(abacus.column_texts.remove(column)).despawn();
+                abacus.columns.remove(column);
+
+                if is_selected {
+                    active.column = active.column.map(|active| if active > column { active - 1 } else { active.min(column_count - 2) });
+                }
+            }
+        }
+
+        // Everything left slides to where the new layout puts it
+        slide_into_place(&mut commands, &abacus, &settings);
+
+        // Newly spawned longs already show their values; the rest are set now
+        let values = abacus.column_values_for_total(total);
+        for (column, value) in values.into_iter().enumerate() {
+            if abacus.longs[column].first().is_some_and(|&long| long_query.contains(long)) {
+                abacus.set_column_value(column, value, &mut long_query, &mut commands);
+            }
+        }
+        abacus.total_value = total.min(abacus.max_total_value());
+
+        if is_selected {
+            structure.apply_to(&mut settings);
+        }
+        commands.send_event(AbacusChanged);
+    }
+}
@@ -0,0 +1,220 @@
+//! Configuration files the user picks with the system's open and save dialogs, or drops
+//! onto the window, for moving an abacus between machines. This is separate from the
+//! saved configurations, which are stored automatically. Only the desktop build reads files.
+
+use bevy::prelude::*;
+#[cfg(not(target_arch = "wasm32"))]
+use bevy_egui::{egui, EguiContexts};
+#[cfg(not(target_arch = "wasm32"))]
+use bevy::tasks::{block_on, futures_lite::future, IoTaskPool, Task};
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::PathBuf;
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::abacus::*;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::localization::*;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::operations::ColumnOpQueue;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::scene_layout::SelectedAbacus;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::startup::read_config_file;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::wood_grain::AbacusMaterial;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::{apply_config, rebuild_selected_abacus, sanitize_config, AbacusSettings, SavableAbacusConfig, UserConfigurations};
+
+/// Asks for a dialog to import a configuration file, or to export the current settings as one.
+#[derive(Event, Clone, Copy)]
+pub enum ConfigFileRequest {
+    Import,
+    Export,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+enum ConfigFileOutcome {
+    Imported(SavableAbacusConfig),
+    Exported(PathBuf),
+}
+
+/// The open or save dialog being shown, if any. `None` from the task means it was cancelled.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Resource, Default)]
+struct ConfigFileDialog(Option<Task<Option<Result<ConfigFileOutcome, String>>>>);
+
+/// A configuration dropped onto the window, waiting for the user to apply it or not.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Resource, Default)]
+struct DroppedConfig(Option<SavableAbacusConfig>);
+
+/// Adds `config` to the saved configurations, replacing one of the same name, and
+/// selects it ready to load.
+#[cfg(not(target_arch = "wasm32"))]
+fn add_user_config(user_configs: &mut UserConfigurations, config: SavableAbacusConfig) {
+    user_configs.selected_config_name_to_load = config.name.clone();
+    match user_configs.configs.iter().position(|c| c.name == config.name) {
+        Some(existing_idx) => user_configs.configs[existing_idx] = config,
+        None => user_configs.configs.push(config),
+    }
+    user_configs.configs_changed = true;
+}
+
+pub struct ConfigFilesPlugin;
+
+impl Plugin for ConfigFilesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ConfigFileRequest>();
+        #[cfg(not(target_arch = "wasm32"))]
+        app.init_resource::<ConfigFileDialog>()
+            .init_resource::<DroppedConfig>()
+            .add_systems(Update, (
+                (open_config_dialogs, finish_config_dialogs).chain(),
+                (receive_dropped_configs, dropped_config_ui_system).chain(),
+            ));
+    }
+}
+
+/// Shows the dialog off the main thread, so the app keeps drawing while it's open.
+#[cfg(not(target_arch = "wasm32"))]
+fn open_config_dialogs(
+    mut requests: EventReader<ConfigFileRequest>,
+    mut dialog: ResMut<ConfigFileDialog>,
+    settings: Res<AbacusSettings>,
+) {
+    let Some(&request) = requests.read().last() else {
+        return;
+    };
+    // One dialog at a time
+    if dialog.0.is_some() {
+        return;
+    }
+    let settings = SavableAbacusConfig::from_settings(String::new(), &settings);
+    let task = match request {
+        ConfigFileRequest::Import => IoTaskPool::get().spawn(async move {
+            let file = rfd::AsyncFileDialog::new()
+                .add_filter("RON", &["ron"])
+                .add_filter("JSON", &["json"])
+                .pick_file()
+                .await?;
+            Some(read_config_file(file.path()).map(ConfigFileOutcome::Imported))
+        }),
+        ConfigFileRequest::Export => IoTaskPool::get().spawn(async move {
+            let file = rfd::AsyncFileDialog::new()
+                .add_filter("RON", &["ron"])
+                .set_file_name("abacus.ron")
+                .save_file()
+                .await?;
+            let path = file.path().to_path_buf();
+            // The file's name names the configuration when it's imported again
+            let name = path.file_stem().map_or_else(String::new, |stem| stem.to_string_lossy().into_owned());
+            let config = SavableAbacusConfig { name, ..settings };
+            Some(
+                ron::ser::to_string_pretty(&config, ron::ser::PrettyConfig::default())
+                    .map_err(|error| error.to_string())
+                    .and_then(|text| std::fs::write(&path, text).map_err(|error| error.to_string()))
+                    .map(|()| ConfigFileOutcome::Exported(path)),
+            )
+        }),
+    };
+    dialog.0 = Some(task);
+}
+
+/// Adds an imported configuration to the saved ones, selected ready to load.
+#[cfg(not(target_arch = "wasm32"))]
+fn finish_config_dialogs(
+    mut dialog: ResMut<ConfigFileDialog>,
+    mut user_configs: ResMut<UserConfigurations>,
+    localization: Res<Localization>,
+) {
+    let Some(task) = &mut dialog.0 else {
+        return;
+    };
+    let Some(outcome) = block_on(future::poll_once(task)) else {
+        return;
+    };
+    dialog.0 = None;
+    match outcome {
+        None => {}
+        Some(Ok(ConfigFileOutcome::Imported(mut config))) => {
+            sanitize_config(&mut config, &localization);
+            info!("{}", tr!(localization, "log-config-imported", name = config.name.as_str()));
+            add_user_config(&mut user_configs, config);
+        }
+        Some(Ok(ConfigFileOutcome::Exported(path))) => {
+            info!("{}", tr!(localization, "log-config-exported", path = path.display().to_string()));
+        }
+        Some(Err(error)) => warn!("{}", tr!(localization, "log-config-file-failed", error = error)),
+    }
+}
+
+/// Saves a `.ron` or `.json` configuration dropped onto the window, then offers to apply it.
+#[cfg(not(target_arch = "wasm32"))]
+fn receive_dropped_configs(
+    mut drops: EventReader<FileDragAndDrop>,
+    mut dropped: ResMut<DroppedConfig>,
+    mut user_configs: ResMut<UserConfigurations>,
+    localization: Res<Localization>,
+) {
+    for drop in drops.read() {
+        let FileDragAndDrop::DroppedFile { path_buf, .. } = drop else {
+            continue;
+        };
+        if !path_buf.extension().is_some_and(|extension| extension == "ron" || extension == "json") {
+            warn!("{}", tr!(localization, "log-dropped-file-ignored", path = path_buf.display().to_string()));
+            continue;
+        }
+        match read_config_file(path_buf) {
+            Ok(mut config) => {
+                sanitize_config(&mut config, &localization);
+                add_user_config(&mut user_configs, config.clone());
+                dropped.0 = Some(config);
+            }
+            Err(error) => warn!("{}", tr!(localization, "log-config-file-failed", error = error)),
+        }
+    }
+}
+
+/// Asks whether to apply the configuration just dropped onto the window.
+#[cfg(not(target_arch = "wasm32"))]
+fn dropped_config_ui_system(
+    mut contexts: EguiContexts,
+    mut dropped: ResMut<DroppedConfig>,
+    mut commands: Commands,
+    mut materials: ResMut<Assets<AbacusMaterial>>,
+    mut settings: ResMut<AbacusSettings>,
+    mut selected: ResMut<SelectedAbacus>,
+    abacus_query: Query<(Entity, &Abacus)>,
+    mut op_queue: ResMut<ColumnOpQueue>,
+    localization: Res<Localization>,
+) {
+    let Some(config) = &dropped.0 else {
+        return;
+    };
+    let mut apply = None;
+    egui::Window::new(tr!(localization, "dropped-config-window"))
+        .id(egui::Id::new("dropped_config"))
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .collapsible(false)
+        .resizable(false)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.label(tr!(localization, "dropped-config-question", name = config.name.as_str()));
+            ui.horizontal(|ui| {
+                if ui.button(tr!(localization, "dropped-config-apply")).clicked() {
+                    apply = Some(true);
+                }
+                if ui.button(tr!(localization, "dropped-config-keep")).clicked() {
+                    apply = Some(false);
+                }
+            });
+        });
+
+    let Some(apply) = apply else {
+        return;
+    };
+    if let Some(config) = dropped.0.take().filter(|_| apply) {
+        apply_config(&mut settings, &mut materials, &config);
+        info!("{}", tr!(localization, "log-config-loaded", name = config.name.as_str()));
+        rebuild_selected_abacus(&mut commands, &settings, &mut selected, &abacus_query, &mut op_queue);
+    }
+}
@@ -0,0 +1,244 @@
+//! Pictures of the saved and built-in configurations for the gallery that the settings
+//! panel picks them from. Each is a small model of the configuration's abacus, at rest,
+//! rendered off screen on a layer of its own; one is rendered at a time, and again only
+//! when its configuration is saved over.
+
+use bevy::asset::RenderAssetUsages;
+use bevy::prelude::*;
+use bevy::render::camera::RenderTarget;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat, TextureUsages};
+use bevy::render::view::RenderLayers;
+use bevy_egui::{egui, EguiContexts};
+use std::collections::HashMap;
+use std::f32::consts::PI;
+
+use crate::abacus::*;
+use crate::surfaces::SurfaceFinish;
+use crate::wood_grain::{abacus_material, AbacusMaterial, WoodGrain};
+use crate::{AbacusSettings, AbacusStructure, SavableAbacusConfig, UserConfigurations};
+
+/// Layer the models are drawn on, out of sight of the main cameras.
+const THUMBNAIL_LAYER: usize = 7;
+pub const THUMBNAIL_SIZE: UVec2 = UVec2::new(128, 96);
+/// Frames a model is kept on its layer; its meshes and materials are ready after the
+/// first, and the picture stays in its image once the camera is gone.
+const RENDER_FRAMES: u32 = 3;
+
+/// The camera rendering a picture, kept out of the queries that look for the main one.
+#[derive(Component)]
+pub struct ThumbnailCamera;
+
+/// The model and light in front of the camera.
+#[derive(Component)]
+struct ThumbnailPart;
+
+struct Thumbnail {
+    /// The configuration as it was pictured, to tell when it has been saved over.
+    config: SavableAbacusConfig,
+    image: Handle<Image>,
+    texture: egui::TextureId,
+}
+
+#[derive(Resource, Default)]
+pub struct ConfigThumbnails {
+    thumbnails: HashMap<String, Thumbnail>,
+    /// The configuration being pictured, and how many frames it has been drawn.
+    rendering: Option<(String, u32)>,
+    bead_meshes: HashMap<BeadShape, Handle<Mesh>>,
+}
+
+impl ConfigThumbnails {
+    /// The picture of the configuration called `name`, once it has been drawn.
+    pub fn texture(&self, name: &str) -> Option<egui::TextureId> {
+        self.thumbnails.get(name)
+            .filter(|_| self.rendering.as_ref().is_none_or(|(rendering, _)| rendering != name))
+            .map(|thumbnail| thumbnail.texture)
+    }
+}
+
+pub struct ConfigGalleryPlugin;
+
+impl Plugin for ConfigGalleryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ConfigThumbnails>()
+            .add_systems(Update, render_thumbnails);
+    }
+}
+
+fn thumbnail_image() -> Image {
+    let size = Extent3d { width: THUMBNAIL_SIZE.x, height: THUMBNAIL_SIZE.y, depth_or_array_layers: 1 };
+    let mut image = Image::new_fill(size, TextureDimension::D2, &[0, 0, 0, 0], TextureFormat::Bgra8UnormSrgb, RenderAssetUsages::default());
+    image.texture_descriptor.usage = TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST | TextureUsages::RENDER_ATTACHMENT;
+    image
+}
+
+/// Pictures the next configuration that has none or has changed, after clearing away the
+/// last one's model and forgetting configurations that were deleted.
+fn render_thumbnails(
+    mut commands: Commands,
+    mut contexts: EguiContexts,
+    mut thumbnails: ResMut<ConfigThumbnails>,
+    user_configs: Res<UserConfigurations>,
+    settings: Res<AbacusSettings>,
+    mut images: ResMut<Assets<Image>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<AbacusMaterial>>,
+    parts: Query<Entity, Or<(With<ThumbnailPart>, With<ThumbnailCamera>)>>,
+) {
+    let thumbnails = &mut *thumbnails;
+    if let Some((_, frames)) = &mut thumbnails.rendering {
+        *frames += 1;
+        if *frames < RENDER_FRAMES {
+            return;
+        }
+        for entity in &parts {
+            commands.entity(entity).despawn();
+        }
+        thumbnails.rendering = None;
+    }
+
+    thumbnails.thumbnails.retain(|name, thumbnail| {
+        let kept = user_configs.configs.iter().any(|config| config.name == *name);
+        if !kept {
+            contexts.remove_image(&thumbnail.image);
+        }
+        kept
+    });
+    let Some(config) = user_configs.configs.iter()
+        .find(|config| thumbnails.thumbnails.get(&config.name).is_none_or(|thumbnail| thumbnail.config != **config))
+    else {
+        return;
+    };
+
+    let image = match thumbnails.thumbnails.get_mut(&config.name) {
+        Some(thumbnail) => {
+            thumbnail.config = config.clone();
+            thumbnail.image.clone()
+        }
+        None => {
+            let image = images.add(thumbnail_image());
+            let texture = contexts.add_image(image.clone_weak());
+            thumbnails.thumbnails.insert(config.name.clone(), Thumbnail { config: config.clone(), image: image.clone(), texture });
+            image
+        }
+    };
+    thumbnails.rendering = Some((config.name.clone(), 0));
+
+    let resolution = settings.graphics_quality.bead_resolution();
+    let bead_mesh_handle = thumbnails.bead_meshes.entry(config.bead_shape)
+        .or_insert_with(|| meshes.add(bead_mesh(config.bead_shape, resolution)))
+        .clone();
+    let size = spawn_model(&mut commands, config, &bead_mesh_handle, &settings.rod_mesh, &mut materials);
+
+    // Back far enough for the whole frame, looking from the front as the main camera does
+    let half_fov = PerspectiveProjection::default().fov / 2.0;
+    let aspect = THUMBNAIL_SIZE.x as f32 / THUMBNAIL_SIZE.y as f32;
+    let distance = (size.y / 2.0 / half_fov.tan()).max(size.x / 2.0 / (half_fov.tan() * aspect)) * 1.15 + 1.0;
+    let layer = RenderLayers::layer(THUMBNAIL_LAYER);
+    commands.spawn((
+        ThumbnailCamera,
+        Camera3d::default(),
+        Camera {
+            order: -1,
+            target: RenderTarget::Image(image.into()),
+            ..default()
+        },
+        Transform::from_xyz(0.0, distance * 0.3, -distance).looking_at(Vec3::ZERO, Vec3::Y),
+        layer.clone(),
+    ));
+    commands.spawn((
+        ThumbnailPart,
+        DirectionalLight {
+            illuminance: 4000.0,
+            ..default()
+        },
+        Transform::from_xyz(3.0, 8.0, -6.0).looking_at(Vec3::ZERO, Vec3::Y),
+        layer,
+    ));
+}
+
+/// Spawns the configuration's abacus, its beads at rest, as bare meshes on the thumbnail
+/// layer. Returns the size of its frame.
+fn spawn_model(
+    commands: &mut Commands,
+    config: &SavableAbacusConfig,
+    bead_mesh: &Handle<Mesh>,
+    rod_mesh: &Handle<Mesh>,
+    materials: &mut Assets<AbacusMaterial>,
+) -> Vec2 {
+    let structure = AbacusStructure::from_config(config);
+    let proportions = Proportions { model_scale: 1.0, ..config.proportions };
+    // Laid out as the real one is, with no parts of its own
+    let abacus = Abacus {
+        longs: Vec::new(),
+        column_texts: Vec::new(),
+        total_text: Entity::PLACEHOLDER,
+        words_text: Entity::PLACEHOLDER,
+        columns: (0..config.column_count).map(|column| structure.column_layout(column)).collect(),
+        abacus_base: config.abacus_base,
+        value_model: config.value_model,
+        horizontal: config.horizontal_rods,
+        ones_on_left: config.ones_on_left,
+        proportions,
+        total_value: 0,
+        slot: 0,
+    };
+
+    let mut material = |color: Color, finish: &SurfaceFinish| {
+        let mut material = abacus_material(color);
+        material.base.metallic = finish.metallic;
+        material.base.perceptual_roughness = finish.roughness;
+        material.extension = WoodGrain::from(&config.wood_grain);
+        materials.add(material)
+    };
+    let bead_finish = &config.surfaces.bead;
+    let bead_material = material(config.ui_bead_color, bead_finish);
+    let alt_material = material(Color::from(BEAD_ALT_COLOR), bead_finish);
+    let column_materials: Vec<Handle<AbacusMaterial>> = config.column_colors.iter().map(|rule| material(rule.color, bead_finish)).collect();
+    let frame_material = material(config.ui_frame_color, &config.surfaces.frame);
+
+    let layer = RenderLayers::layer(THUMBNAIL_LAYER);
+    for (column, layout) in abacus.columns.iter().enumerate() {
+        let rule = config.column_colors.iter().rposition(|rule| rule.covers(column));
+        for deck in 0..layout.decks.len() {
+            let bead_count = layout.deck(deck).bead_count;
+            if bead_count == 0 {
+                continue;
+            }
+            let long = Transform::from_translation(abacus.long_translation(column, deck)).with_rotation(abacus.rod_rotation());
+            commands.spawn((
+                ThumbnailPart,
+                Mesh3d(rod_mesh.clone()),
+                MeshMaterial3d(frame_material.clone()),
+                long * rod_transform(bead_count, &proportions, config.grooved),
+                layer.clone(),
+            ));
+            // Beads away from the bar sit past the gap, as `move_all_abacus_beads` has them
+            let upper_count = layout.long_value(deck, 0) as usize;
+            let mut y = 0.0;
+            for i in 0..bead_count {
+                if i == upper_count {
+                    y += LONG_SPACING;
+                }
+                let alt = config.two_tone_beads && ((bead_count - 1 - i) as u64 / TWO_TONE_GROUP) % 2 == 1;
+                let material = match rule {
+                    Some(rule) => &column_materials[rule],
+                    None if alt => &alt_material,
+                    None => &bead_material,
+                };
+                let bead = Transform::from_xyz(0.0, y, 0.0)
+                    .with_rotation(Quat::from_rotation_x(PI / 2.0))
+                    .with_scale(proportions.bead_scale());
+                commands.spawn((
+                    ThumbnailPart,
+                    Mesh3d(bead_mesh.clone()),
+                    MeshMaterial3d(material.clone()),
+                    long * bead,
+                    layer.clone(),
+                ));
+                y += proportions.bead_spacing;
+            }
+        }
+    }
+    abacus.size()
+}
@@ -0,0 +1,114 @@
+//! Debug check that what the abacus says matches what its beads show. Once every bead of
+//! an abacus has settled, the total is worked out again from where the beads actually
+//! are and compared with `Abacus::total_value`, the longs, and the total and column
+//! texts. Only built with the `consistency-check` feature.
+
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+use crate::abacus::*;
+use crate::localization::*;
+use crate::{format_number_in_base, AbacusSettings};
+
+/// Frames a divergence has to last before it's logged. The texts and total follow the
+/// beads a frame or two behind, which isn't a bug.
+const MISMATCH_FRAMES: u32 = 3;
+
+pub struct ConsistencyCheckPlugin;
+
+impl Plugin for ConsistencyCheckPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PostUpdate, check_abacus_consistency);
+    }
+}
+
+/// The long's value as its beads stand: how many sit below the gap, each at its own
+/// spacing rather than lifted past the gap.
+fn long_value_from_beads(beads: &[Entity], bead_spacing: f32, bead_query: &Query<(&AbacusBead, &Transform)>) -> Option<u64> {
+    let mut value = 0;
+    for (i, &bead) in beads.iter().enumerate() {
+        let (_, transform) = bead_query.get(bead).ok()?;
+        if transform.translation.y < i as f32 * bead_spacing + LONG_SPACING / 2.0 {
+            value += 1;
+        }
+    }
+    Some(value)
+}
+
+/// What's wrong with `abacus`, if its beads have settled and something disagrees with them.
+fn find_divergence(
+    entity: Entity,
+    abacus: &Abacus,
+    long_query: &Query<(&AbacusLong, Option<&BeadsOf>)>,
+    bead_query: &Query<(&AbacusBead, &Transform)>,
+    text_query: &Query<&Text2d>,
+    localization: &Localization,
+) -> Option<String> {
+    let mut bead_total: u64 = 0;
+    for (column, layout) in abacus.columns.iter().enumerate() {
+        let mut deck_values = Vec::new();
+        for &long in &abacus.longs[column] {
+            let (abacus_long, beads) = long_query.get(long).ok()?;
+            let beads = beads.map_or(&[][..], |beads| &beads[..]);
+            // Still moving, so there's nothing settled to compare yet
+            if beads.iter().any(|&bead| bead_query.get(bead).is_ok_and(|(bead, transform)| transform.translation != bead.target)) {
+                return None;
+            }
+            let from_beads = long_value_from_beads(beads, abacus.proportions.bead_spacing, bead_query)?;
+            if from_beads != abacus_long.value {
+                return Some(tr!(localization, "log-inconsistent-long", abacus = entity.to_string(), column = column, beads = from_beads, long = abacus_long.value));
+            }
+            deck_values.push(from_beads);
+        }
+        let column_value = layout.value(&deck_values);
+        if let Ok(text) = text_query.get(abacus.column_texts[column]) {
+            if text.0 != format_number_in_base(column_value, abacus.text_base()) {
+                return Some(tr!(localization, "log-inconsistent-column", abacus = entity.to_string(), column = column, beads = column_value, text = text.0.clone()));
+            }
+        }
+        let place_value = abacus.place_value(column);
+        bead_total = column_value.saturating_mul(place_value).saturating_add(bead_total);
+    }
+
+    // The total text is grouped for reading; only its digits count
+    let text_total = text_query.get(abacus.total_text).ok()
+        .and_then(|text| text.0.chars().filter(char::is_ascii_digit).collect::<String>().parse::<u64>().ok());
+    if bead_total != abacus.total_value || text_total != Some(bead_total) {
+        let text = text_query.get(abacus.total_text).map_or(String::new(), |text| text.0.clone());
+        return Some(tr!(localization, "log-inconsistent-total", abacus = entity.to_string(), beads = bead_total, total = abacus.total_value, text = text));
+    }
+    None
+}
+
+/// Logs each abacus whose total, longs or texts have drifted from its beads, once per
+/// divergence.
+fn check_abacus_consistency(
+    settings: Res<AbacusSettings>,
+    abacus_query: Query<(Entity, &Abacus)>,
+    long_query: Query<(&AbacusLong, Option<&BeadsOf>)>,
+    bead_query: Query<(&AbacusBead, &Transform)>,
+    text_query: Query<&Text2d>,
+    localization: Res<Localization>,
+    mut divergences: Local<HashMap<Entity, (String, u32)>>,
+) {
+    // Simulated beads never sit exactly on their targets
+    if settings.physics_enabled {
+        return;
+    }
+    divergences.retain(|entity, _| abacus_query.contains(*entity));
+    for (entity, abacus) in &abacus_query {
+        let Some(divergence) = find_divergence(entity, abacus, &long_query, &bead_query, &text_query, &localization) else {
+            divergences.remove(&entity);
+            continue;
+        };
+        let (last, frames) = divergences.entry(entity).or_insert_with(|| (divergence.clone(), 0));
+        if *last != divergence {
+            *last = divergence;
+            *frames = 0;
+        }
+        *frames += 1;
+        if *frames == MISMATCH_FRAMES {
+            error!("{}", last);
+        }
+    }
+}
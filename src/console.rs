@@ -0,0 +1,273 @@
+use bevy::prelude::*;
+use bevy_egui::egui::text::{CCursor, CCursorRange};
+use bevy_egui::{egui, EguiContexts};
+
+use crate::abacus::*;
+use crate::localization::*;
+use crate::operations::{AbacusOperation, ClearRequested, ColumnOpQueue, OperationRequested};
+use crate::scene_layout::SelectedAbacus;
+use crate::wood_grain::AbacusMaterial;
+use crate::{apply_config, rebuild_selected_abacus, AbacusSettings, UserConfigurations};
+
+/// Command names, for `help` and tab completion.
+const COMMANDS: [&str; 13] = ["set", "add", "sub", "columns", "base", "top", "bottom", "preset", "clear", "scripts", "run", "stop", "help"];
+/// Output lines kept before the oldest scroll away.
+const MAX_OUTPUT: usize = 200;
+
+/// Asks the scripting engine (the `scripting` feature) to list, run or stop scripts.
+#[derive(Event)]
+pub enum ScriptRequest {
+    List,
+    /// Runs the named script from `assets/scripts` on an abacus.
+    Run { name: String, abacus: Entity },
+    Stop,
+}
+
+enum ConsoleCommand {
+    Operation(AbacusOperation),
+    Columns(usize),
+    Base(u64),
+    Top(usize),
+    Bottom(usize),
+    Preset(String),
+    Clear,
+    Scripts,
+    Run(String),
+    Stop,
+    Help,
+}
+
+fn parse_command(line: &str, localization: &Localization) -> Result<ConsoleCommand, String> {
+    let (name, argument) = line.trim().split_once(' ').map_or((line.trim(), ""), |(name, argument)| (name, argument.trim()));
+    let number = || argument.parse::<u64>().map_err(|_| tr!(localization, "console-needs-number", command = name.to_string()));
+    // Structure commands take the same ranges as the settings panel's sliders
+    let in_range = |min: u64, max: u64| {
+        let value = number()?;
+        if (min..=max).contains(&value) {
+            Ok(value)
+        } else {
+            Err(tr!(localization, "console-out-of-range", command = name.to_string(), min = min, max = max))
+        }
+    };
+    Ok(match name.to_lowercase().as_str() {
+        "set" => ConsoleCommand::Operation(AbacusOperation::Set(number()?)),
+        "add" => ConsoleCommand::Operation(AbacusOperation::Add(number()?)),
+        "sub" | "subtract" => ConsoleCommand::Operation(AbacusOperation::Subtract(number()?)),
+        "columns" => ConsoleCommand::Columns(in_range(1, MAX_COLUMNS as u64)? as usize),
+        "base" => ConsoleCommand::Base(in_range(2, 36)?),
+        "top" => ConsoleCommand::Top(in_range(0, MAX_TOP_BEADS as u64)? as usize),
+        "bottom" => ConsoleCommand::Bottom(in_range(1, 10)? as usize),
+        "preset" if !argument.is_empty() => ConsoleCommand::Preset(argument.to_string()),
+        "preset" => return Err(tr!(localization, "console-needs-preset")),
+        "clear" => ConsoleCommand::Clear,
+        "scripts" => ConsoleCommand::Scripts,
+        "run" if !argument.is_empty() => ConsoleCommand::Run(argument.to_string()),
+        "run" => return Err(tr!(localization, "console-needs-script")),
+        "stop" => ConsoleCommand::Stop,
+        "help" => ConsoleCommand::Help,
+        _ => return Err(tr!(localization, "console-unknown", command = name.to_string())),
+    })
+}
+
+/// A typed-command console for the selected abacus. Commands go through the same
+/// operation requests and settings as the panel's controls. Scripts print here too.
+#[derive(Resource, Default)]
+pub struct Console {
+    input: String,
+    output: Vec<String>,
+    history: Vec<String>,
+    /// Position while stepping through `history` with the arrow keys.
+    history_index: Option<usize>,
+}
+
+impl Console {
+    pub fn print(&mut self, line: String) {
+        self.output.push(line);
+        if self.output.len() > MAX_OUTPUT {
+            self.output.remove(0);
+        }
+    }
+
+    /// Completes the command name, or the preset name after `preset`. Several
+    /// candidates are listed instead.
+    fn complete(&mut self, preset_names: &[&str]) {
+        let (candidates, prefix): (Vec<&str>, &str) = match self.input.split_once(' ') {
+            Some((name, partial)) if name.eq_ignore_ascii_case("preset") => {
+                let partial = partial.trim_start().to_lowercase();
+                (preset_names.iter().copied().filter(|preset| preset.to_lowercase().starts_with(&partial)).collect(), "preset ")
+            }
+            Some(_) => return,
+            None => {
+                let partial = self.input.to_lowercase();
+                (COMMANDS.iter().copied().filter(|command| command.starts_with(&partial)).collect(), "")
+            }
+        };
+        match candidates.as_slice() {
+            [] => {}
+            [only] => self.input = format!("{}{} ", prefix, only),
+            several => {
+                let listed = several.join(", ");
+                self.print(listed);
+            }
+        }
+    }
+
+    fn step_history(&mut self, back: bool) {
+        if self.history.is_empty() {
+            return;
+        }
+        let index = match (self.history_index, back) {
+            (None, true) => Some(self.history.len() - 1),
+            (None, false) => None,
+            (Some(i), true) => Some(i.saturating_sub(1)),
+            (Some(i), false) => (i + 1 < self.history.len()).then_some(i + 1),
+        };
+        self.history_index = index;
+        self.input = index.map(|i| self.history[i].clone()).unwrap_or_default();
+    }
+}
+
+pub fn console_ui_system(
+    mut contexts: EguiContexts,
+    mut console: ResMut<Console>,
+    mut settings: ResMut<AbacusSettings>,
+    user_configs: Res<UserConfigurations>,
+    mut commands: Commands,
+    mut materials: ResMut<Assets<AbacusMaterial>>,
+    mut selected: ResMut<SelectedAbacus>,
+    abacus_query: Query<(Entity, &Abacus)>,
+    mut op_queue: ResMut<ColumnOpQueue>,
+    mut requests: EventWriter<OperationRequested>,
+    mut clear_requests: EventWriter<ClearRequested>,
+    mut script_requests: EventWriter<ScriptRequest>,
+    localization: Res<Localization>,
+) {
+    let localization = &*localization;
+    let console = &mut *console;
+    let mut submitted = None;
+
+    egui::Window::new(tr!(localization, "console-window"))
+        .id(egui::Id::new("console"))
+        .default_pos([10.0, 880.0])
+        .default_open(false)
+        .show(contexts.ctx_mut(), |ui| {
+            egui::ScrollArea::vertical().max_height(200.0).stick_to_bottom(true).show(ui, |ui| {
+                for line in &console.output {
+                    ui.monospace(line);
+                }
+            });
+
+            let output = egui::TextEdit::singleline(&mut console.input)
+                .font(egui::TextStyle::Monospace)
+                .hint_text(tr!(localization, "console-hint"))
+                .desired_width(f32::INFINITY)
+                // Keep Tab for completion rather than moving focus
+                .lock_focus(true)
+                .show(ui);
+            let response = output.response;
+
+            if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                submitted = Some(std::mem::take(&mut console.input));
+                response.request_focus();
+            } else if response.has_focus() {
+                let input_before = console.input.clone();
+                if ui.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Tab)) {
+                    let preset_names: Vec<&str> = user_configs.configs.iter().map(|config| config.name.as_str()).collect();
+                    console.complete(&preset_names);
+                }
+                if ui.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp)) {
+                    console.step_history(true);
+                }
+                if ui.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown)) {
+                    console.step_history(false);
+                }
+                // Put the cursor after text the console filled in
+                if console.input != input_before {
+                    let mut state = output.state;
+                    let end = CCursor::new(console.input.chars().count());
+                    state.cursor.set_char_range(Some(CCursorRange::one(end)));
+                    state.store(ui.ctx(), response.id);
+                }
+            }
+        });
+
+    let Some(line) = submitted.filter(|line| !line.trim().is_empty()) else {
+        return;
+    };
+    console.print(format!("> {}", line));
+    if console.history.last() != Some(&line) {
+        console.history.push(line.clone());
+    }
+    console.history_index = None;
+
+    let command = match parse_command(&line, localization) {
+        Ok(command) => command,
+        Err(error) => {
+            console.print(error);
+            return;
+        }
+    };
+    let selected_abacus = selected.0.filter(|&entity| abacus_query.contains(entity));
+    let mut rebuild = false;
+    match command {
+        ConsoleCommand::Help => console.print(tr!(localization, "console-help", commands = COMMANDS.join(", "))),
+        ConsoleCommand::Operation(operation) => match selected_abacus {
+            Some(abacus) => {
+                requests.write(OperationRequested { abacus, operation });
+            }
+            None => console.print(tr!(localization, "console-no-abacus")),
+        },
+        ConsoleCommand::Clear => {
+            clear_requests.write(ClearRequested { abacus: selected_abacus });
+        }
+        ConsoleCommand::Scripts | ConsoleCommand::Run(_) | ConsoleCommand::Stop if cfg!(not(feature = "scripting")) => {
+            console.print(tr!(localization, "console-scripting-disabled"));
+        }
+        ConsoleCommand::Scripts => {
+            script_requests.write(ScriptRequest::List);
+        }
+        ConsoleCommand::Run(name) => match selected_abacus {
+            Some(abacus) => {
+                script_requests.write(ScriptRequest::Run { name, abacus });
+            }
+            None => console.print(tr!(localization, "console-no-abacus")),
+        },
+        ConsoleCommand::Stop => {
+            script_requests.write(ScriptRequest::Stop);
+        }
+        ConsoleCommand::Columns(count) => {
+            settings.column_count = count;
+            settings.column_overrides.retain(|column_override| column_override.column < count);
+            rebuild = true;
+        }
+        ConsoleCommand::Base(base) => {
+            settings.abacus_base = base;
+            rebuild = true;
+        }
+        ConsoleCommand::Top(count) => {
+            settings.top_bead_count = count;
+            rebuild = true;
+        }
+        ConsoleCommand::Bottom(count) => {
+            settings.bottom_bead_count = count;
+            rebuild = true;
+        }
+        ConsoleCommand::Preset(name) => match user_configs.find_preset(&name) {
+            Ok(config) => {
+                apply_config(&mut settings, &mut materials, config);
+                console.print(tr!(localization, "log-config-loaded", name = config.name.as_str()));
+                rebuild = true;
+            }
+            Err(matching) if matching.is_empty() => console.print(tr!(localization, "console-no-preset", name = name)),
+            Err(matching) => {
+                let names: Vec<&str> = matching.iter().map(|config| config.name.as_str()).collect();
+                console.print(tr!(localization, "console-several-presets", names = names.join(", ")));
+            }
+        },
+    }
+
+    if rebuild {
+        info!("{}", tr!(localization, "log-rebuilding"));
+        rebuild_selected_abacus(&mut commands, &settings, &mut selected, &abacus_query, &mut op_queue);
+    }
+}
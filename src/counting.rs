@@ -0,0 +1,119 @@
+//! "Count to…": the selected abacus steps from its value to a target by a fixed amount at
+//! a steady rate, animating every carry on the way, for showing rollovers like 999 → 1000.
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::abacus::*;
+use crate::localization::*;
+use crate::operations::{AbacusOperation, OperationRequested};
+use crate::scene_layout::SelectedAbacus;
+
+#[derive(Resource)]
+struct CountTo {
+    target: u64,
+    step: u64,
+    /// Steps per second.
+    rate: f32,
+    /// The abacus counting and the value it has been sent to; `None` when idle.
+    counting: Option<(Entity, u64)>,
+    timer: Timer,
+}
+
+impl Default for CountTo {
+    fn default() -> Self {
+        Self {
+            target: 1000,
+            step: 1,
+            rate: 2.0,
+            counting: None,
+            timer: Timer::from_seconds(0.5, TimerMode::Repeating),
+        }
+    }
+}
+
+pub struct CountToPlugin;
+
+impl Plugin for CountToPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CountTo>()
+            .add_systems(Update, (count_to_ui_system, run_count_to).chain());
+    }
+}
+
+fn count_to_ui_system(
+    mut contexts: EguiContexts,
+    mut count_to: ResMut<CountTo>,
+    selected: Res<SelectedAbacus>,
+    abacus_query: Query<&Abacus>,
+    localization: Res<Localization>,
+) {
+    let localization = &*localization;
+    let selected_abacus = selected.0.and_then(|entity| abacus_query.get(entity).ok().map(|abacus| (entity, abacus)));
+
+    egui::Window::new(tr!(localization, "count-to-window"))
+        .id(egui::Id::new("count_to"))
+        .default_pos([10.0, 1120.0])
+        .default_open(false)
+        .show(contexts.ctx_mut(), |ui| {
+            let idle = count_to.counting.is_none();
+            ui.add_enabled_ui(idle, |ui| {
+                egui::Grid::new("count_to_grid").num_columns(2).show(ui, |ui| {
+                    ui.label(tr!(localization, "count-to-target"));
+                    ui.add(egui::DragValue::new(&mut count_to.target));
+                    ui.end_row();
+                    ui.label(tr!(localization, "count-to-step"));
+                    ui.add(egui::DragValue::new(&mut count_to.step).range(1..=u64::MAX));
+                    ui.end_row();
+                    ui.label(tr!(localization, "count-to-rate"));
+                    ui.add(egui::Slider::new(&mut count_to.rate, 0.2..=20.0).logarithmic(true));
+                    ui.end_row();
+                });
+            });
+            match count_to.counting {
+                Some((_, value)) => {
+                    ui.label(tr!(localization, "count-to-progress", value = value, target = count_to.target));
+                    if ui.button(tr!(localization, "count-to-stop")).clicked() {
+                        count_to.counting = None;
+                    }
+                }
+                None => {
+                    if ui.add_enabled(selected_abacus.is_some(), egui::Button::new(tr!(localization, "count-to-start"))).clicked() {
+                        if let Some((entity, abacus)) = selected_abacus {
+                            count_to.target = count_to.target.min(abacus.max_total_value());
+                            count_to.counting = Some((entity, abacus.total_value));
+                            let interval = 1.0 / count_to.rate;
+                            count_to.timer = Timer::from_seconds(interval, TimerMode::Repeating);
+                        }
+                    }
+                }
+            }
+        });
+}
+
+/// Sends the next step on each tick, up or down towards the target, never past it.
+fn run_count_to(
+    mut count_to: ResMut<CountTo>,
+    time: Res<Time>,
+    abacus_query: Query<&Abacus>,
+    mut requests: EventWriter<OperationRequested>,
+) {
+    let Some((entity, value)) = count_to.counting else {
+        return;
+    };
+    if !abacus_query.contains(entity) {
+        // Removed or rebuilt mid-count
+        count_to.counting = None;
+        return;
+    }
+    if !count_to.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+    let next = if count_to.target >= value {
+        value.saturating_add(count_to.step).min(count_to.target)
+    } else {
+        value.saturating_sub(count_to.step).max(count_to.target)
+    };
+    requests.write(OperationRequested { abacus: entity, operation: AbacusOperation::Set(next) });
+    count_to.counting = (next != count_to.target).then_some((entity, next));
+}
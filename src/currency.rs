@@ -0,0 +1,68 @@
+//! Money mode, for teaching money sums: the total of a base 10 abacus reads as an amount,
+//! its last two columns as the cents, and the Money window adds or takes away common
+//! coins and notes.
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::localization::*;
+use crate::operations::{AbacusOperation, OperationRequested};
+use crate::scene_layout::SelectedAbacus;
+use crate::{group_digits, AbacusSettings};
+
+pub const DEFAULT_CURRENCY_SYMBOL: &str = "$";
+const CENTS_PER_UNIT: u64 = 100;
+/// The quick amounts, in cents.
+const COMMON_AMOUNTS: [u64; 8] = [1, 5, 10, 25, 100, 500, 1000, 2000];
+
+/// `cents` as an amount, like "$1,234.56".
+pub fn format_money(cents: u64, symbol: &str, separator: &str) -> String {
+    let units = group_digits(&(cents / CENTS_PER_UNIT).to_string(), separator);
+    format!("{}{}.{:02}", symbol, units, cents % CENTS_PER_UNIT)
+}
+
+pub struct CurrencyPlugin;
+
+impl Plugin for CurrencyPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, money_ui_system);
+    }
+}
+
+fn money_ui_system(
+    mut contexts: EguiContexts,
+    settings: Res<AbacusSettings>,
+    selected: Res<SelectedAbacus>,
+    mut requests: EventWriter<OperationRequested>,
+    localization: Res<Localization>,
+) {
+    let localization = &*localization;
+    egui::Window::new(tr!(localization, "money-window"))
+        .id(egui::Id::new("money"))
+        .default_pos([10.0, 1420.0])
+        .default_open(false)
+        .show(contexts.ctx_mut(), |ui| {
+            if !settings.currency_mode {
+                ui.label(tr!(localization, "money-mode-off"));
+            }
+            let Some(abacus) = selected.0 else {
+                return;
+            };
+            let separator = settings.digit_grouping.separator(localization);
+            egui::Grid::new("money_amounts").show(ui, |ui| {
+                for subtract in [false, true] {
+                    for row in COMMON_AMOUNTS.chunks(4) {
+                        for &cents in row {
+                            let amount = format_money(cents, &settings.currency_symbol, &separator);
+                            let label = if subtract { format!("−{}", amount) } else { format!("+{}", amount) };
+                            if ui.button(label).clicked() {
+                                let operation = if subtract { AbacusOperation::Subtract(cents) } else { AbacusOperation::Add(cents) };
+                                requests.write(OperationRequested { abacus, operation });
+                            }
+                        }
+                        ui.end_row();
+                    }
+                }
+            });
+        });
+}
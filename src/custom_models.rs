@@ -0,0 +1,170 @@
+//! Bead and rod meshes from glTF files, for historically accurate or branded abaci. A
+//! configuration can name a `.glb` under `assets/` for either piece; the first mesh in it
+//! replaces the shared procedural one, which stays in place if the file can't be loaded.
+//! Pieces are modelled Y-up around the rod at the size of the built-in ones: beads one
+//! unit across and 0.4 high, rods one unit long and centred. They keep the abacus colors.
+
+use bevy::asset::LoadState;
+use bevy::gltf::GltfAssetLabel;
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use serde::{Deserialize, Serialize};
+use std::f32::consts::PI;
+
+use crate::abacus::*;
+use crate::localization::*;
+use crate::AbacusSettings;
+
+/// Paths of the glTF files the pieces are drawn with, relative to `assets/`. An empty
+/// path keeps the procedural mesh.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CustomModels {
+    pub bead: String,
+    pub rod: String,
+}
+
+/// Meshes still being loaded, and the paths last asked for.
+#[derive(Resource, Default)]
+struct ModelLoads {
+    requested: Option<CustomModels>,
+    bead: Option<Handle<Mesh>>,
+    rod: Option<Handle<Mesh>>,
+}
+
+/// The paths being typed into the Models window, applied with its Load button.
+#[derive(Resource, Default)]
+struct ModelPathInputs {
+    bead: String,
+    rod: String,
+    shown: Option<CustomModels>,
+}
+
+pub struct CustomModelsPlugin;
+
+impl Plugin for CustomModelsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ModelLoads>()
+            .init_resource::<ModelPathInputs>()
+            .add_systems(Update, (
+                request_custom_models.run_if(resource_changed::<AbacusSettings>),
+                apply_loaded_models,
+                custom_models_ui,
+            ).chain());
+    }
+}
+
+/// Starts loading the pieces whose paths changed. The procedural mesh is put back while
+/// they load, so a missing or broken file leaves the abacus as it would be without one.
+fn request_custom_models(
+    settings: Res<AbacusSettings>,
+    asset_server: Res<AssetServer>,
+    mut loads: ResMut<ModelLoads>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    let models = &settings.custom_models;
+    let previous = loads.requested.replace(models.clone());
+    if previous.as_ref() == Some(models) {
+        return;
+    }
+    let load = |path: &str| (!path.is_empty()).then(|| asset_server.load(GltfAssetLabel::Primitive { mesh: 0, primitive: 0 }.from_asset(path.to_string())));
+
+    if previous.as_ref().map(|previous| previous.bead.as_str()) != Some(models.bead.as_str()) {
+        if previous.is_some() {
+            if let Some(mesh) = meshes.get_mut(&settings.bead_mesh) {
+                *mesh = bead_mesh(settings.bead_shape, settings.graphics_quality.bead_resolution());
+            }
+            if let Some(mesh) = meshes.get_mut(&settings.bead_lod_mesh) {
+                *mesh = bead_mesh(settings.bead_shape, settings.graphics_quality.bead_lod_resolution());
+            }
+        }
+        loads.bead = load(&models.bead);
+    }
+    if previous.as_ref().map(|previous| previous.rod.as_str()) != Some(models.rod.as_str()) {
+        if previous.is_some() {
+            if let Some(mesh) = meshes.get_mut(&settings.rod_mesh) {
+                *mesh = rod_mesh();
+            }
+        }
+        loads.rod = load(&models.rod);
+    }
+}
+
+/// Swaps each piece's loaded mesh into the ones every bead or rod shares. A custom bead has
+/// no coarser version, so wide abaci draw it as it is.
+fn apply_loaded_models(
+    settings: Res<AbacusSettings>,
+    asset_server: Res<AssetServer>,
+    mut loads: ResMut<ModelLoads>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    localization: Res<Localization>,
+) {
+    let loads = &mut *loads;
+    let bead_meshes = [&settings.bead_mesh, &settings.bead_lod_mesh];
+    let rod_meshes = [&settings.rod_mesh];
+    for (pending, shared) in [(&mut loads.bead, &bead_meshes[..]), (&mut loads.rod, &rod_meshes[..])] {
+        let Some(handle) = pending.as_ref() else {
+            continue;
+        };
+        match asset_server.load_state(handle) {
+            LoadState::Loaded => {
+                // glTF is Y-up; the pieces run along their local Z
+                if let Some(mesh) = meshes.get(handle).cloned() {
+                    let mesh = mesh.rotated_by(Quat::from_rotation_x(PI / 2.0));
+                    for &target in shared {
+                        if let Some(target) = meshes.get_mut(target) {
+                            *target = mesh.clone();
+                        }
+                    }
+                }
+                *pending = None;
+            }
+            LoadState::Failed(error) => {
+                warn!("{}", tr!(localization, "log-custom-model-failed", error = error.to_string()));
+                *pending = None;
+            }
+            _ => {}
+        }
+    }
+}
+
+fn custom_models_ui(
+    mut contexts: EguiContexts,
+    mut settings: ResMut<AbacusSettings>,
+    mut inputs: ResMut<ModelPathInputs>,
+    localization: Res<Localization>,
+) {
+    let inputs = &mut *inputs;
+    // A loaded configuration brings its own paths
+    if inputs.shown.as_ref() != Some(&settings.custom_models) {
+        inputs.bead = settings.custom_models.bead.clone();
+        inputs.rod = settings.custom_models.rod.clone();
+        inputs.shown = Some(settings.custom_models.clone());
+    }
+    egui::Window::new(tr!(localization, "models-window"))
+        .id(egui::Id::new("custom_models"))
+        .default_pos([10.0, 1480.0])
+        .default_open(false)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.label(tr!(localization, "models-hint"));
+            egui::Grid::new("model_paths").show(ui, |ui| {
+                ui.label(tr!(localization, "bead-model"));
+                ui.add(egui::TextEdit::singleline(&mut inputs.bead).hint_text("models/bead.glb"));
+                ui.end_row();
+                ui.label(tr!(localization, "rod-model"));
+                ui.add(egui::TextEdit::singleline(&mut inputs.rod).hint_text("models/rod.glb"));
+                ui.end_row();
+            });
+            ui.horizontal(|ui| {
+                if ui.button(tr!(localization, "load-models")).clicked() {
+                    settings.custom_models = CustomModels {
+                        bead: inputs.bead.trim().to_string(),
+                        rod: inputs.rod.trim().to_string(),
+                    };
+                }
+                if ui.button(tr!(localization, "built-in-models")).clicked() {
+                    settings.custom_models = CustomModels::default();
+                }
+            });
+        });
+}
@@ -0,0 +1,83 @@
+//! Typing a value straight onto the selected abacus: digit keys build up a number, shown
+//! at the bottom of the screen, and Enter sets the abacus to it. While a column is active
+//! the digits go to that column instead.
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::active_column::ActiveColumn;
+use crate::keybindings::{ActionInput, InputAction};
+use crate::localization::*;
+use crate::operations::{AbacusOperation, OperationRequested};
+use crate::scene_layout::SelectedAbacus;
+
+/// Seconds without a key press before a half-typed value is dropped.
+const ENTRY_TIMEOUT: f32 = 5.0;
+
+#[derive(Resource, Default)]
+struct DigitEntry {
+    digits: String,
+    idle: f32,
+}
+
+pub struct DigitEntryPlugin;
+
+impl Plugin for DigitEntryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DigitEntry>()
+            .add_systems(Update, (type_digits, show_digit_entry).chain());
+    }
+}
+
+fn type_digits(
+    input: ActionInput,
+    time: Res<Time>,
+    selected: Res<SelectedAbacus>,
+    active: Res<ActiveColumn>,
+    mut entry: ResMut<DigitEntry>,
+    mut requests: EventWriter<OperationRequested>,
+) {
+    if active.column.is_some() {
+        entry.digits.clear();
+        return;
+    }
+    let mut typed = false;
+    for digit in 0..=9 {
+        // Leading zeros add nothing, and a u64 has room for 19 digits
+        if input.just_pressed(InputAction::Digit(digit)) && !(digit == 0 && entry.digits.is_empty()) && entry.digits.len() < 19 {
+            entry.digits.push(char::from(b'0' + digit));
+            typed = true;
+        }
+    }
+    if input.just_pressed(InputAction::EraseDigit) && entry.digits.pop().is_some() {
+        typed = true;
+    }
+    if input.just_pressed(InputAction::EnterDigits) && !entry.digits.is_empty() {
+        if let (Some(abacus), Ok(value)) = (selected.0, entry.digits.parse()) {
+            requests.write(OperationRequested { abacus, operation: AbacusOperation::Set(value) });
+        }
+        entry.digits.clear();
+    }
+
+    if typed {
+        entry.idle = 0.0;
+    } else if !entry.digits.is_empty() {
+        entry.idle += time.delta_secs();
+        if entry.idle >= ENTRY_TIMEOUT {
+            entry.digits.clear();
+        }
+    }
+}
+
+fn show_digit_entry(mut contexts: EguiContexts, entry: Res<DigitEntry>, localization: Res<Localization>) {
+    if entry.digits.is_empty() {
+        return;
+    }
+    egui::Area::new(egui::Id::new("digit_entry"))
+        .anchor(egui::Align2::CENTER_BOTTOM, [0.0, -40.0])
+        .show(contexts.ctx_mut(), |ui| {
+            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                ui.label(egui::RichText::new(tr!(localization, "digit-entry", digits = entry.digits.clone())).size(24.0));
+            });
+        });
+}
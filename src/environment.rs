@@ -0,0 +1,395 @@
+//! Lighting and sky: the ambient light, the key light above the abaci and a fill light
+//! across from it, and what's drawn behind them (the background color, a gradient sky, or
+//! an environment map that lights the beads too). The Lighting window sets them, and its
+//! presets set them all at once; configurations save them.
+
+use bevy::asset::RenderAssetUsages;
+use bevy::color::{ColorToComponents, ColorToPacked, Mix};
+use bevy::core_pipeline::Skybox;
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat, TextureViewDescriptor, TextureViewDimension};
+use bevy_egui::{egui, EguiContexts};
+use serde::{Deserialize, Serialize};
+
+use crate::graphics::GraphicsQuality;
+use crate::localization::*;
+use crate::surfaces::asset_path_field;
+use crate::table::table_controls;
+use crate::AbacusSettings;
+
+/// Pixels along each face of the gradient sky's cubemap. The gradient is smooth, so
+/// this can be small.
+const SKY_FACE_SIZE: u32 = 32;
+/// Sky brightness that shows its colors as picked at the default exposure.
+const SKY_BRIGHTNESS: f32 = 1000.0;
+
+/// What's drawn behind the abaci.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SkyStyle {
+    /// The background color.
+    #[default]
+    Color,
+    /// A gradient from the horizon color up to the top color.
+    Gradient,
+    /// The environment map's specular cubemap.
+    EnvironmentMap,
+}
+
+impl SkyStyle {
+    pub const ALL: [SkyStyle; 3] = [SkyStyle::Color, SkyStyle::Gradient, SkyStyle::EnvironmentMap];
+
+    /// Localization id of the style's display name.
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            SkyStyle::Color => "sky-color",
+            SkyStyle::Gradient => "sky-gradient",
+            SkyStyle::EnvironmentMap => "sky-environment-map",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Environment {
+    pub ambient_color: Color,
+    pub ambient_brightness: f32,
+    pub key_color: Color,
+    /// Multiplies the key light's brightness for the graphics quality.
+    pub key_brightness: f32,
+    pub fill_color: Color,
+    /// In lux; zero turns the fill light off.
+    pub fill_illuminance: f32,
+    pub sky: SkyStyle,
+    pub sky_top: Color,
+    pub sky_horizon: Color,
+    /// Prefiltered KTX2 cubemaps under `assets/`, as made by glTF IBL Sampler. The
+    /// specular one is also drawn as the sky.
+    pub diffuse_map: String,
+    pub specular_map: String,
+    pub environment_intensity: f32,
+}
+
+impl Default for Environment {
+    /// The single white light and plain background the simulator has always had.
+    fn default() -> Self {
+        Self {
+            ambient_color: Color::WHITE,
+            ambient_brightness: 80.0,
+            key_color: Color::WHITE,
+            key_brightness: 1.0,
+            fill_color: Color::WHITE,
+            fill_illuminance: 0.0,
+            sky: SkyStyle::Color,
+            sky_top: Color::srgb(0.35, 0.55, 0.85),
+            sky_horizon: Color::srgb(0.85, 0.88, 0.92),
+            diffuse_map: String::new(),
+            specular_map: String::new(),
+            environment_intensity: 900.0,
+        }
+    }
+}
+
+/// A complete lighting setup and the background that goes with it.
+pub struct EnvironmentPreset {
+    /// Localization id of the preset's display name.
+    pub message_id: &'static str,
+    pub background_color: Color,
+    pub environment: Environment,
+}
+
+pub const ENVIRONMENT_PRESETS: [EnvironmentPreset; 3] = [
+    EnvironmentPreset {
+        // Bright and even, like a lit room, so nothing is lost on a projector
+        message_id: "environment-classroom",
+        background_color: Color::srgb(0.88, 0.9, 0.92),
+        environment: Environment {
+            ambient_color: Color::srgb(1.0, 0.97, 0.92),
+            ambient_brightness: 400.0,
+            key_color: Color::WHITE,
+            key_brightness: 0.8,
+            fill_color: Color::srgb(0.9, 0.95, 1.0),
+            fill_illuminance: 4000.0,
+            sky: SkyStyle::Gradient,
+            sky_top: Color::srgb(0.55, 0.72, 0.92),
+            sky_horizon: Color::srgb(0.93, 0.94, 0.95),
+            diffuse_map: String::new(),
+            specular_map: String::new(),
+            environment_intensity: 900.0,
+        },
+    },
+    EnvironmentPreset {
+        message_id: "environment-studio",
+        background_color: Color::srgb(0.3, 0.3, 0.32),
+        environment: Environment {
+            ambient_color: Color::WHITE,
+            ambient_brightness: 150.0,
+            key_color: Color::WHITE,
+            key_brightness: 1.2,
+            fill_color: Color::WHITE,
+            fill_illuminance: 2500.0,
+            sky: SkyStyle::Gradient,
+            sky_top: Color::srgb(0.12, 0.12, 0.13),
+            sky_horizon: Color::srgb(0.45, 0.45, 0.47),
+            diffuse_map: String::new(),
+            specular_map: String::new(),
+            environment_intensity: 900.0,
+        },
+    },
+    EnvironmentPreset {
+        // A warm light on the abaci and nothing else
+        message_id: "environment-dark-stage",
+        background_color: Color::BLACK,
+        environment: Environment {
+            ambient_color: Color::WHITE,
+            ambient_brightness: 10.0,
+            key_color: Color::srgb(1.0, 0.88, 0.7),
+            key_brightness: 1.5,
+            fill_color: Color::WHITE,
+            fill_illuminance: 0.0,
+            sky: SkyStyle::Color,
+            sky_top: Color::BLACK,
+            sky_horizon: Color::BLACK,
+            diffuse_map: String::new(),
+            specular_map: String::new(),
+            environment_intensity: 900.0,
+        },
+    },
+];
+
+/// The light above and in front of the abaci, which casts their shadows.
+#[derive(Component)]
+pub struct KeyLight;
+
+/// A shadowless light from the other side, softening the key light's shadows.
+#[derive(Component)]
+struct FillLight;
+
+/// The gradient sky's cubemap, redrawn when its colors change.
+#[derive(Resource)]
+struct GradientSky(Handle<Image>);
+
+impl FromWorld for GradientSky {
+    fn from_world(world: &mut World) -> Self {
+        let environment = Environment::default();
+        let image = gradient_cubemap(environment.sky_top, environment.sky_horizon);
+        Self(world.resource_mut::<Assets<Image>>().add(image))
+    }
+}
+
+pub struct EnvironmentPlugin;
+
+impl Plugin for EnvironmentPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GradientSky>()
+            .add_systems(Startup, spawn_lights)
+            .add_systems(Update, (
+                apply_environment.run_if(resource_changed::<AbacusSettings>),
+                environment_ui,
+            ));
+    }
+}
+
+fn spawn_lights(mut commands: Commands) {
+    commands.spawn((
+        KeyLight,
+        PointLight {
+            shadows_enabled: true,
+            intensity: 10_000_000.,
+            range: 100.0,
+            shadow_depth_bias: 0.2,
+            ..default()
+        },
+        Transform::from_xyz(8.0, 16.0, -8.0),
+    ));
+    commands.spawn((
+        FillLight,
+        DirectionalLight {
+            illuminance: 0.0,
+            shadows_enabled: false,
+            ..default()
+        },
+        Transform::from_xyz(-8.0, 6.0, -4.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+}
+
+/// A cubemap shading from `horizon` up to `top`; below the horizon stays `horizon`.
+fn gradient_cubemap(top: Color, horizon: Color) -> Image {
+    let size = SKY_FACE_SIZE;
+    let mut data = Vec::with_capacity((size * size * 6 * 4) as usize);
+    for face in 0..6 {
+        for y in 0..size {
+            for x in 0..size {
+                let u = (x as f32 + 0.5) / size as f32 * 2.0 - 1.0;
+                let v = (y as f32 + 0.5) / size as f32 * 2.0 - 1.0;
+                // +X, -X, +Y, -Y, +Z, -Z, with rows running downwards on the side faces
+                let direction = match face {
+                    0 => Vec3::new(1.0, -v, -u),
+                    1 => Vec3::new(-1.0, -v, u),
+                    2 => Vec3::new(u, 1.0, v),
+                    3 => Vec3::new(u, -1.0, -v),
+                    4 => Vec3::new(u, -v, 1.0),
+                    _ => Vec3::new(-u, -v, -1.0),
+                };
+                let height = direction.normalize().y.max(0.0);
+                data.extend_from_slice(&horizon.mix(&top, height).to_srgba().to_u8_array());
+            }
+        }
+    }
+    let mut image = Image::new(
+        Extent3d { width: size, height: size, depth_or_array_layers: 6 },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    image.texture_view_descriptor = Some(TextureViewDescriptor {
+        dimension: Some(TextureViewDimension::Cube),
+        ..default()
+    });
+    image
+}
+
+/// Sets the lights and sky from the settings. The key light follows the graphics quality
+/// too, which dims it when there are no shadows.
+fn apply_environment(
+    mut commands: Commands,
+    settings: Res<AbacusSettings>,
+    asset_server: Res<AssetServer>,
+    gradient_sky: Res<GradientSky>,
+    mut applied: Local<Option<(Environment, GraphicsQuality)>>,
+    mut images: ResMut<Assets<Image>>,
+    mut ambient_light: ResMut<AmbientLight>,
+    mut key_lights: Query<&mut PointLight, With<KeyLight>>,
+    mut fill_lights: Query<&mut DirectionalLight, With<FillLight>>,
+    cameras: Query<Entity, With<Camera3d>>,
+) {
+    let environment = &settings.environment;
+    let current = (environment.clone(), settings.graphics_quality);
+    if applied.as_ref() == Some(&current) {
+        return;
+    }
+    let previous = applied.replace(current);
+
+    ambient_light.color = environment.ambient_color;
+    ambient_light.brightness = environment.ambient_brightness;
+    for mut light in &mut key_lights {
+        light.color = environment.key_color;
+        light.intensity = settings.graphics_quality.light_intensity() * environment.key_brightness;
+    }
+    for mut light in &mut fill_lights {
+        light.color = environment.fill_color;
+        light.illuminance = environment.fill_illuminance;
+    }
+
+    let recolored = previous.is_none_or(|(previous, _)| previous.sky_top != environment.sky_top || previous.sky_horizon != environment.sky_horizon);
+    if recolored {
+        if let Some(image) = images.get_mut(&gradient_sky.0) {
+            *image = gradient_cubemap(environment.sky_top, environment.sky_horizon);
+        }
+    }
+    let has_maps = !environment.diffuse_map.is_empty() && !environment.specular_map.is_empty();
+    let specular_map = has_maps.then(|| asset_server.load(environment.specular_map.clone()));
+    let sky = match environment.sky {
+        SkyStyle::Color => None,
+        SkyStyle::Gradient => Some(gradient_sky.0.clone()),
+        SkyStyle::EnvironmentMap => specular_map.clone(),
+    };
+    for camera in &cameras {
+        let mut camera = commands.entity(camera);
+        match &sky {
+            Some(image) => camera.insert(Skybox { image: image.clone(), brightness: SKY_BRIGHTNESS, ..default() }),
+            None => camera.remove::<Skybox>(),
+        };
+        match &specular_map {
+            Some(specular_map) => camera.insert(EnvironmentMapLight {
+                diffuse_map: asset_server.load(environment.diffuse_map.clone()),
+                specular_map: specular_map.clone(),
+                intensity: environment.environment_intensity,
+                ..default()
+            }),
+            None => camera.remove::<EnvironmentMapLight>(),
+        };
+    }
+}
+
+fn color_row(ui: &mut egui::Ui, color: &mut Color, label: String) {
+    ui.horizontal(|ui| {
+        let mut rgb = color.to_srgba().to_f32_array_no_alpha();
+        let color_response = ui.color_edit_button_rgb(&mut rgb);
+        if color_response.changed() {
+            *color = Color::srgb_from_array(rgb);
+        }
+        let label = ui.label(label);
+        color_response.labelled_by(label.id);
+    });
+}
+
+fn environment_ui(
+    mut contexts: EguiContexts,
+    mut settings: ResMut<AbacusSettings>,
+    mut clear_color: ResMut<ClearColor>,
+    localization: Res<Localization>,
+) {
+    egui::Window::new(tr!(localization, "environment-window"))
+        .id(egui::Id::new("environment"))
+        .default_pos([10.0, 1540.0])
+        .default_open(false)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.horizontal_wrapped(|ui| {
+                ui.label(tr!(localization, "presets"));
+                for preset in &ENVIRONMENT_PRESETS {
+                    if ui.button(tr!(localization, preset.message_id)).clicked() {
+                        settings.environment = preset.environment.clone();
+                        settings.ui_background_color = preset.background_color;
+                        clear_color.0 = preset.background_color;
+                    }
+                }
+            });
+            ui.separator();
+
+            let settings = &mut *settings;
+            ui.horizontal(|ui| {
+                let mut background = settings.ui_background_color.to_srgba().to_f32_array_no_alpha();
+                let color_response = ui.color_edit_button_rgb(&mut background);
+                if color_response.changed() {
+                    settings.ui_background_color = Color::srgb_from_array(background);
+                    clear_color.0 = settings.ui_background_color;
+                }
+                let label = ui.label(tr!(localization, "background-color"));
+                color_response.labelled_by(label.id);
+            });
+            let environment = &mut settings.environment;
+            color_row(ui, &mut environment.ambient_color, tr!(localization, "ambient-color"));
+            ui.add(egui::Slider::new(&mut environment.ambient_brightness, 0.0..=1000.0).text(tr!(localization, "ambient-brightness")));
+            color_row(ui, &mut environment.key_color, tr!(localization, "key-light-color"));
+            ui.add(egui::Slider::new(&mut environment.key_brightness, 0.0..=3.0).text(tr!(localization, "key-light-brightness")));
+            color_row(ui, &mut environment.fill_color, tr!(localization, "fill-light-color"));
+            ui.add(egui::Slider::new(&mut environment.fill_illuminance, 0.0..=10_000.0).text(tr!(localization, "fill-light-brightness")));
+            ui.separator();
+
+            egui::ComboBox::from_label(tr!(localization, "sky"))
+                .selected_text(tr!(localization, environment.sky.message_id()))
+                .show_ui(ui, |ui| {
+                    for sky in SkyStyle::ALL {
+                        ui.selectable_value(&mut environment.sky, sky, tr!(localization, sky.message_id()));
+                    }
+                });
+            if environment.sky == SkyStyle::Gradient {
+                color_row(ui, &mut environment.sky_top, tr!(localization, "sky-top"));
+                color_row(ui, &mut environment.sky_horizon, tr!(localization, "sky-horizon"));
+            }
+            ui.label(tr!(localization, "environment-map-hint"));
+            egui::Grid::new("environment_maps").show(ui, |ui| {
+                ui.label(tr!(localization, "diffuse-map"));
+                asset_path_field(ui, &mut environment.diffuse_map, "environment_maps/room_diffuse.ktx2");
+                ui.end_row();
+                ui.label(tr!(localization, "specular-map"));
+                asset_path_field(ui, &mut environment.specular_map, "environment_maps/room_specular.ktx2");
+                ui.end_row();
+            });
+            ui.add(egui::Slider::new(&mut environment.environment_intensity, 0.0..=5000.0).text(tr!(localization, "environment-intensity")));
+            ui.separator();
+
+            table_controls(ui, &mut settings.table, &localization);
+        });
+}
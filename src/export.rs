@@ -0,0 +1,322 @@
+//! Exports of an abacus for use outside the app. The SVG diagram draws the beads where
+//! they are as a black-and-white schematic, for printing in worksheets; it doesn't depend
+//! on the renderer. The glTF model (`.glb`) keeps the 3D meshes and colours with the beads
+//! baked in where they are, for slides, Blender or AR viewers.
+
+#[cfg(not(target_arch = "wasm32"))]
+use bevy::asset::AssetId;
+use bevy::prelude::*;
+#[cfg(not(target_arch = "wasm32"))]
+use bevy::render::mesh::{Indices, PrimitiveTopology, VertexAttributeValues};
+#[cfg(not(target_arch = "wasm32"))]
+use serde_json::{json, Value};
+#[cfg(not(target_arch = "wasm32"))]
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::abacus::*;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::localization::*;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::scene_layout::SelectedAbacus;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::wood_grain::AbacusMaterial;
+
+/// Distance between neighbouring rods.
+const SVG_COLUMN_WIDTH: f32 = 48.0;
+/// Height each bead takes on its rod, including the gap to the next.
+const SVG_BEAD_SLOT: f32 = 22.0;
+const SVG_BEAD_WIDTH: f32 = 40.0;
+const SVG_BEAD_HEIGHT: f32 = 19.0;
+const SVG_FRAME: f32 = 8.0;
+const SVG_MARGIN: f32 = 20.0;
+/// Room above the frame for the total, and below it for the column values.
+const SVG_TOTAL_HEIGHT: f32 = 40.0;
+const SVG_COLUMN_TEXT_HEIGHT: f32 = 28.0;
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+fn bead(svg: &mut String, x: f32, y: f32, active: bool) {
+    let fill = if active { "#333" } else { "#fff" };
+    let _ = writeln!(svg, r##"  <ellipse cx="{x}" cy="{y}" rx="{}" ry="{}" fill="{fill}" stroke="#333" stroke-width="2"/>"##,
+        SVG_BEAD_WIDTH / 2.0, SVG_BEAD_HEIGHT / 2.0);
+}
+
+/// A schematic of `abacus` as it stands: frame, beam, rods and beads, with beads that
+/// count (those against the beam) filled in. `total` is written above the frame and each
+/// column's value below its rod, in the abacus's base.
+pub fn abacus_svg(abacus: &Abacus, long_query: &Query<&AbacusLong>, total: &str) -> String {
+    let columns = abacus.columns.len();
+    let deck_count = abacus.columns.iter().map(|layout| layout.decks.len()).max().unwrap_or(0);
+    let tallest: Vec<usize> = (0..deck_count)
+        .map(|deck| abacus.columns.iter().map(|layout| layout.deck(deck).bead_count).max().unwrap_or(0))
+        .collect();
+
+    // Each deck leaves one bead's gap for the beads to move into. Empty decks above the
+    // bottom one take no room and get no beam.
+    let frame_left = SVG_MARGIN;
+    let frame_top = SVG_MARGIN + SVG_TOTAL_HEIGHT;
+    let inner_top = frame_top + SVG_FRAME;
+    let mut deck_spans = vec![(inner_top, inner_top); deck_count];
+    let mut beams = Vec::new();
+    let mut y = inner_top;
+    for (deck, &beads) in tallest.iter().enumerate() {
+        let is_bottom = deck + 1 == deck_count;
+        if beads == 0 && !is_bottom {
+            continue;
+        }
+        deck_spans[deck] = (y, y + (beads + 1) as f32 * SVG_BEAD_SLOT);
+        y = deck_spans[deck].1;
+        if !is_bottom {
+            beams.push(y);
+            y += SVG_FRAME;
+        }
+    }
+    let inner_bottom = y;
+    let frame_width = columns as f32 * SVG_COLUMN_WIDTH + SVG_FRAME * 2.0;
+    let frame_height = inner_bottom + SVG_FRAME - frame_top;
+    let width = frame_width + SVG_MARGIN * 2.0;
+    let height = frame_top + frame_height + SVG_COLUMN_TEXT_HEIGHT + SVG_MARGIN;
+
+    let mut svg = String::new();
+    let _ = writeln!(svg, r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" font-family="sans-serif">"#);
+    let _ = writeln!(svg, r#"  <rect width="{width}" height="{height}" fill="white"/>"#);
+    let _ = writeln!(svg, r#"  <text x="{}" y="{}" font-size="24" text-anchor="middle">{}</text>"#,
+        width / 2.0, SVG_MARGIN + SVG_TOTAL_HEIGHT / 2.0 + 8.0, xml_escape(total));
+
+    for (i, layout) in abacus.columns.iter().enumerate() {
+        // Column 0 is the ones column, on the right unless the abacus has it on the left
+        let slot = if abacus.ones_on_left { i } else { columns - 1 - i };
+        let x = frame_left + SVG_FRAME + slot as f32 * SVG_COLUMN_WIDTH + SVG_COLUMN_WIDTH / 2.0;
+        let _ = writeln!(svg, r##"  <line x1="{x}" y1="{inner_top}" x2="{x}" y2="{inner_bottom}" stroke="#888" stroke-width="3"/>"##);
+
+        for (deck, deck_layout) in layout.decks.iter().enumerate() {
+            let long_value = abacus.longs.get(i).and_then(|longs| longs.get(deck))
+                .and_then(|&long| long_query.get(long).ok()).map_or(0, |long| long.value);
+            let counted = layout.counted_beads(deck, long_value) as usize;
+            let idle = deck_layout.bead_count - counted;
+            let (deck_top, deck_bottom) = deck_spans[deck];
+            if deck + 1 == layout.decks.len() {
+                // Bottom beads count when pushed up against the beam above them
+                for k in 0..counted {
+                    bead(&mut svg, x, deck_top + SVG_BEAD_SLOT / 2.0 + k as f32 * SVG_BEAD_SLOT, true);
+                }
+                for j in 0..idle {
+                    bead(&mut svg, x, deck_bottom - SVG_BEAD_SLOT / 2.0 - j as f32 * SVG_BEAD_SLOT, false);
+                }
+            } else {
+                // The other decks' beads count when pushed down against the beam below them
+                for j in 0..idle {
+                    bead(&mut svg, x, deck_top + SVG_BEAD_SLOT / 2.0 + j as f32 * SVG_BEAD_SLOT, false);
+                }
+                for k in 0..counted {
+                    bead(&mut svg, x, deck_bottom - SVG_BEAD_SLOT / 2.0 - k as f32 * SVG_BEAD_SLOT, true);
+                }
+            }
+        }
+
+        let column_value = crate::format_number_in_base(abacus.get_column_value(i, long_query), abacus.text_base());
+        let _ = writeln!(svg, r#"  <text x="{x}" y="{}" font-size="16" text-anchor="middle">{}</text>"#,
+            frame_top + frame_height + SVG_COLUMN_TEXT_HEIGHT - 6.0, xml_escape(&column_value));
+    }
+
+    // Frame and beams go over the rod ends
+    let _ = writeln!(svg, r#"  <rect x="{}" y="{}" width="{}" height="{}" fill="none" stroke="black" stroke-width="{SVG_FRAME}"/>"#,
+        frame_left + SVG_FRAME / 2.0, frame_top + SVG_FRAME / 2.0, frame_width - SVG_FRAME, frame_height - SVG_FRAME);
+    for beam_top in beams {
+        let _ = writeln!(svg, r#"  <rect x="{frame_left}" y="{beam_top}" width="{frame_width}" height="{SVG_FRAME}" fill="black"/>"#);
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Asks for the selected abacus to be exported as a glTF model. Only the desktop build
+/// writes the file.
+#[derive(Event)]
+pub struct GltfExportRequested;
+
+pub struct ExportPlugin;
+
+impl Plugin for ExportPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<GltfExportRequested>();
+        #[cfg(not(target_arch = "wasm32"))]
+        app.add_systems(Update, export_gltf.run_if(on_event::<GltfExportRequested>));
+    }
+}
+
+/// Binary glTF being put together: the JSON document's arrays and the buffer they point into.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Default)]
+struct GltfBuilder {
+    buffer: Vec<u8>,
+    buffer_views: Vec<Value>,
+    accessors: Vec<Value>,
+    meshes: Vec<Value>,
+    materials: Vec<Value>,
+    nodes: Vec<Value>,
+    mesh_indices: HashMap<AssetId<Mesh>, usize>,
+    material_indices: HashMap<AssetId<AbacusMaterial>, usize>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl GltfBuilder {
+    /// Appends `bytes` to the buffer as a view, returning the view's index.
+    fn push_view(&mut self, bytes: &[u8], target: u32) -> usize {
+        self.buffer_views.push(json!({
+            "buffer": 0, "byteOffset": self.buffer.len(), "byteLength": bytes.len(), "target": target,
+        }));
+        self.buffer.extend_from_slice(bytes);
+        self.buffer_views.len() - 1
+    }
+
+    fn push_vec3_accessor(&mut self, values: &[[f32; 3]], with_bounds: bool) -> usize {
+        let bytes: Vec<u8> = values.iter().flatten().flat_map(|value| value.to_le_bytes()).collect();
+        let view = self.push_view(&bytes, 34962);
+        let mut accessor = json!({ "bufferView": view, "componentType": 5126, "count": values.len(), "type": "VEC3" });
+        // Positions need their bounds
+        if with_bounds {
+            let (min, max) = values.iter().map(|&value| Vec3::from(value))
+                .fold((Vec3::INFINITY, Vec3::NEG_INFINITY), |(min, max), value| (min.min(value), max.max(value)));
+            accessor["min"] = json!(min.to_array());
+            accessor["max"] = json!(max.to_array());
+        }
+        self.accessors.push(accessor);
+        self.accessors.len() - 1
+    }
+
+    fn push_index_accessor(&mut self, indices: &[u32]) -> usize {
+        let bytes: Vec<u8> = indices.iter().flat_map(|index| index.to_le_bytes()).collect();
+        let view = self.push_view(&bytes, 34963);
+        self.accessors.push(json!({ "bufferView": view, "componentType": 5125, "count": indices.len(), "type": "SCALAR" }));
+        self.accessors.len() - 1
+    }
+
+    /// The glTF mesh for `id`, added the first time it's used. Only triangle lists are exported.
+    fn mesh(&mut self, id: AssetId<Mesh>, mesh: &Mesh, material: usize) -> Option<usize> {
+        if let Some(&index) = self.mesh_indices.get(&id) {
+            return Some(index);
+        }
+        if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+            return None;
+        }
+        let Some(VertexAttributeValues::Float32x3(positions)) = mesh.attribute(Mesh::ATTRIBUTE_POSITION) else {
+            return None;
+        };
+        let mut attributes = json!({ "POSITION": self.push_vec3_accessor(positions, true) });
+        if let Some(VertexAttributeValues::Float32x3(normals)) = mesh.attribute(Mesh::ATTRIBUTE_NORMAL) {
+            attributes["NORMAL"] = json!(self.push_vec3_accessor(normals, false));
+        }
+        let indices: Vec<u32> = match mesh.indices() {
+            Some(Indices::U16(indices)) => indices.iter().map(|&index| index as u32).collect(),
+            Some(Indices::U32(indices)) => indices.clone(),
+            None => (0..positions.len() as u32).collect(),
+        };
+        let indices = self.push_index_accessor(&indices);
+        self.meshes.push(json!({ "primitives": [{ "attributes": attributes, "indices": indices, "material": material }] }));
+        self.mesh_indices.insert(id, self.meshes.len() - 1);
+        Some(self.meshes.len() - 1)
+    }
+
+    fn material(&mut self, id: AssetId<AbacusMaterial>, material: &AbacusMaterial) -> usize {
+        if let Some(&index) = self.material_indices.get(&id) {
+            return index;
+        }
+        let color = material.base.base_color.to_linear();
+        let alpha_mode = if color.alpha < 1.0 { "BLEND" } else { "OPAQUE" };
+        self.materials.push(json!({
+            "pbrMetallicRoughness": {
+                "baseColorFactor": [color.red, color.green, color.blue, color.alpha],
+                "metallicFactor": material.base.metallic,
+                "roughnessFactor": material.base.perceptual_roughness,
+            },
+            "alphaMode": alpha_mode,
+        }));
+        self.material_indices.insert(id, self.materials.len() - 1);
+        self.materials.len() - 1
+    }
+
+    /// The finished `.glb`: a header, then the JSON and binary chunks, each padded to four bytes.
+    fn into_glb(self) -> Result<Vec<u8>, String> {
+        let children: Vec<usize> = (1..=self.nodes.len()).collect();
+        let mut nodes = vec![json!({ "name": "Abacus", "children": children })];
+        nodes.extend(self.nodes);
+        let document = json!({
+            "asset": { "version": "2.0", "generator": "Abacus Simulator" },
+            "scene": 0,
+            "scenes": [{ "nodes": [0] }],
+            "nodes": nodes,
+            "meshes": self.meshes,
+            "materials": self.materials,
+            "accessors": self.accessors,
+            "bufferViews": self.buffer_views,
+            "buffers": [{ "byteLength": self.buffer.len() }],
+        });
+        let mut json_chunk = serde_json::to_vec(&document).map_err(|error| error.to_string())?;
+        json_chunk.resize(json_chunk.len().next_multiple_of(4), b' ');
+        let mut bin_chunk = self.buffer;
+        bin_chunk.resize(bin_chunk.len().next_multiple_of(4), 0);
+
+        let length = 12 + 8 + json_chunk.len() + 8 + bin_chunk.len();
+        let mut glb = Vec::with_capacity(length);
+        glb.extend_from_slice(b"glTF");
+        glb.extend_from_slice(&2u32.to_le_bytes());
+        glb.extend_from_slice(&(length as u32).to_le_bytes());
+        glb.extend_from_slice(&(json_chunk.len() as u32).to_le_bytes());
+        glb.extend_from_slice(b"JSON");
+        glb.extend_from_slice(&json_chunk);
+        glb.extend_from_slice(&(bin_chunk.len() as u32).to_le_bytes());
+        glb.extend_from_slice(b"BIN\0");
+        glb.extend_from_slice(&bin_chunk);
+        Ok(glb)
+    }
+}
+
+/// Writes the selected abacus's meshes, as they're placed right now, to a `.glb` in the
+/// exports folder. The model is centred on the abacus, facing the way it was built.
+#[cfg(not(target_arch = "wasm32"))]
+fn export_gltf(
+    selected: Res<SelectedAbacus>,
+    children_query: Query<&Children>,
+    global_transforms: Query<&GlobalTransform>,
+    mesh_query: Query<(&Mesh3d, &MeshMaterial3d<AbacusMaterial>, &GlobalTransform)>,
+    meshes: Res<Assets<Mesh>>,
+    materials: Res<Assets<AbacusMaterial>>,
+    localization: Res<Localization>,
+) {
+    let Some(abacus) = selected.0 else {
+        return;
+    };
+    let Ok(abacus_transform) = global_transforms.get(abacus) else {
+        return;
+    };
+    let to_abacus = abacus_transform.affine().inverse();
+
+    let mut gltf = GltfBuilder::default();
+    for entity in children_query.iter_descendants(abacus) {
+        let Ok((mesh_handle, material_handle, transform)) = mesh_query.get(entity) else {
+            continue;
+        };
+        let (Some(mesh), Some(material)) = (meshes.get(&mesh_handle.0), materials.get(&material_handle.0)) else {
+            continue;
+        };
+        let material = gltf.material(material_handle.0.id(), material);
+        let Some(mesh) = gltf.mesh(mesh_handle.0.id(), mesh, material) else {
+            continue;
+        };
+        let matrix = Mat4::from(to_abacus * transform.affine());
+        gltf.nodes.push(json!({ "mesh": mesh, "matrix": matrix.to_cols_array() }));
+    }
+
+    let result = gltf.into_glb().and_then(|glb| {
+        let path = crate::storage::export_path("abacus.glb")?;
+        std::fs::write(&path, glb).map_err(|error| error.to_string())?;
+        Ok(path)
+    });
+    match result {
+        Ok(path) => info!("{}", tr!(localization, "log-gltf-exported", path = path.display().to_string())),
+        Err(error) => warn!("{}", tr!(localization, "log-gltf-failed", error = error)),
+    }
+}
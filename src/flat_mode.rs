@@ -0,0 +1,82 @@
+//! Flat 2D mode for Chromebooks and old phones: the camera looks straight on through an
+//! orthographic projection, beads are drawn as flat quads rather than extruded discs,
+//! and the light casts no shadows (see `graphics`). It can be switched on and off at any time.
+
+use bevy::prelude::*;
+use std::f32::consts::PI;
+
+use crate::abacus::*;
+use crate::scene_layout::flat_projection;
+use crate::AbacusSettings;
+
+/// The quad every bead shares in flat mode. It's built facing the camera once the bead's
+/// own rotation is applied.
+#[derive(Resource)]
+struct FlatBeadMesh(Handle<Mesh>);
+
+impl FromWorld for FlatBeadMesh {
+    fn from_world(world: &mut World) -> Self {
+        let mesh = Mesh::from(Rectangle::new(1.0, BEAD_HEIGHT)).rotated_by(Quat::from_rotation_x(PI / 2.0));
+        Self(world.resource_mut::<Assets<Mesh>>().add(mesh))
+    }
+}
+
+/// A bead's extruded mesh, put back when flat mode is switched off.
+#[derive(Component)]
+struct SolidMesh(Handle<Mesh>);
+
+pub struct FlatModePlugin;
+
+impl Plugin for FlatModePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FlatBeadMesh>()
+            .add_systems(Update, (
+                switch_flat_view.run_if(resource_changed::<AbacusSettings>),
+                swap_bead_meshes,
+            ));
+    }
+}
+
+/// Switches the cameras' projection when flat mode is toggled, and turns the abaci to face
+/// the front.
+fn switch_flat_view(
+    settings: Res<AbacusSettings>,
+    mut active: Local<bool>,
+    mut projection_query: Query<&mut Projection, With<Camera>>,
+    mut abacus_query: Query<&mut Transform, With<Abacus>>,
+) {
+    if settings.flat_mode == *active {
+        return;
+    }
+    *active = settings.flat_mode;
+    for mut projection in &mut projection_query {
+        *projection = if settings.flat_mode { flat_projection() } else { Projection::from(PerspectiveProjection::default()) };
+    }
+    if settings.flat_mode {
+        for mut transform in &mut abacus_query {
+            transform.rotation = Quat::IDENTITY;
+        }
+    }
+}
+
+/// Gives beads the flat quad in flat mode, including beads spawned later, and their own
+/// mesh back otherwise.
+fn swap_bead_meshes(
+    mut commands: Commands,
+    settings: Res<AbacusSettings>,
+    flat_mesh: Res<FlatBeadMesh>,
+    mut solid_beads: Query<(Entity, &mut Mesh3d), (With<AbacusBead>, Without<SolidMesh>)>,
+    mut flat_beads: Query<(Entity, &mut Mesh3d, &SolidMesh), With<AbacusBead>>,
+) {
+    if settings.flat_mode {
+        for (entity, mut mesh) in &mut solid_beads {
+            let solid = std::mem::replace(&mut mesh.0, flat_mesh.0.clone());
+            commands.entity(entity).insert(SolidMesh(solid));
+        }
+    } else {
+        for (entity, mut mesh, solid) in &mut flat_beads {
+            mesh.0 = solid.0.clone();
+            commands.entity(entity).remove::<SolidMesh>();
+        }
+    }
+}
@@ -0,0 +1,118 @@
+//! Fonts from `assets/fonts` for the 3D value texts and the panels, picked under
+//! Appearance. A font with Chinese or Japanese glyphs is what the numerals in those
+//! languages need; any other makes nicer worksheet screenshots. The web build can't list
+//! the folder, so it keeps the default font.
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use std::sync::Arc;
+
+use crate::localization::*;
+use crate::AbacusSettings;
+
+/// Where text fonts are looked for.
+#[cfg(not(target_arch = "wasm32"))]
+const FONTS_DIR: &str = "assets/fonts";
+
+/// Font files egui is built from: the chosen text font first, then the CJK fallback.
+#[derive(Resource, Default)]
+pub struct EguiFontData {
+    pub text: Option<Vec<u8>>,
+    pub cjk: Option<Vec<u8>>,
+}
+
+pub struct FontsPlugin;
+
+impl Plugin for FontsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EguiFontData>()
+            .add_systems(Update, (
+                load_chosen_font.run_if(resource_changed::<AbacusSettings>),
+                apply_egui_fonts.run_if(resource_changed::<EguiFontData>),
+            ).chain());
+    }
+}
+
+/// The font files in `assets/fonts`, by file name.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn available_fonts() -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(FONTS_DIR) else {
+        return Vec::new();
+    };
+    let mut fonts: Vec<String> = entries
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter(|name| {
+            let name = name.to_lowercase();
+            [".ttf", ".otf", ".ttc"].iter().any(|extension| name.ends_with(extension))
+        })
+        .collect();
+    fonts.sort();
+    fonts
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn available_fonts() -> Vec<String> {
+    Vec::new()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn read_font(name: &str) -> Result<Vec<u8>, String> {
+    std::fs::read(std::path::Path::new(FONTS_DIR).join(name)).map_err(|error| error.to_string())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn read_font(_name: &str) -> Result<Vec<u8>, String> {
+    Err("fonts can't be read from the web build".to_string())
+}
+
+/// Loads the font picked in the settings for the 3D texts and egui; an empty name goes
+/// back to the default font.
+fn load_chosen_font(
+    mut settings: ResMut<AbacusSettings>,
+    mut loaded: Local<String>,
+    mut font_assets: ResMut<Assets<Font>>,
+    mut egui_fonts: ResMut<EguiFontData>,
+    localization: Res<Localization>,
+) {
+    if settings.text_font_name == *loaded {
+        return;
+    }
+    *loaded = settings.text_font_name.clone();
+    if settings.text_font_name.is_empty() {
+        settings.text_font = Handle::default();
+        egui_fonts.text = None;
+        return;
+    }
+    let font = read_font(&settings.text_font_name).and_then(|bytes| {
+        let font = Font::try_from_bytes(bytes.clone()).map_err(|error| error.to_string())?;
+        Ok((font, bytes))
+    });
+    match font {
+        Ok((font, bytes)) => {
+            settings.text_font = font_assets.add(font);
+            egui_fonts.text = Some(bytes);
+            info!("{}", tr!(localization, "log-font-loaded", name = settings.text_font_name.clone()));
+        }
+        Err(error) => {
+            warn!("{}", tr!(localization, "log-font-failed", name = settings.text_font_name.clone(), error = error));
+            settings.text_font_name.clear();
+            loaded.clear();
+        }
+    }
+}
+
+fn apply_egui_fonts(mut contexts: EguiContexts, egui_fonts: Res<EguiFontData>) {
+    let mut fonts = egui::FontDefinitions::default();
+    if let Some(text) = &egui_fonts.text {
+        fonts.font_data.insert("text".to_owned(), Arc::new(egui::FontData::from_owned(text.clone())));
+        fonts.families.entry(egui::FontFamily::Proportional).or_default().insert(0, "text".to_owned());
+    }
+    // egui's built-in fonts have no Chinese or Japanese glyphs
+    if let Some(cjk) = &egui_fonts.cjk {
+        fonts.font_data.insert("cjk".to_owned(), Arc::new(egui::FontData::from_owned(cjk.clone())));
+        for family in [egui::FontFamily::Proportional, egui::FontFamily::Monospace] {
+            fonts.families.entry(family).or_default().push("cjk".to_owned());
+        }
+    }
+    contexts.ctx_mut().set_fonts(fonts);
+}
@@ -0,0 +1,129 @@
+//! A title engraved along the top of each frame, such as a school's or a student's name,
+//! so it shows in screenshots and turntable GIFs. Unlike the value texts it lies flat in the
+//! frame rather than turning to the camera. Configurations save it.
+
+use bevy::prelude::*;
+use bevy_egui::egui;
+use serde::{Deserialize, Serialize};
+
+use crate::abacus::*;
+use crate::localization::*;
+use crate::AbacusSettings;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FrameTitle {
+    /// Empty for no title.
+    pub text: String,
+    pub color: Color,
+    pub size: f32,
+}
+
+impl Default for FrameTitle {
+    /// Dark, like letters cut into the wood.
+    fn default() -> Self {
+        Self {
+            text: String::new(),
+            color: Color::srgb(0.2, 0.12, 0.06),
+            size: 48.0,
+        }
+    }
+}
+
+impl FrameTitle {
+    pub fn is_shown(&self) -> bool {
+        !self.text.trim().is_empty()
+    }
+}
+
+/// The title on an abacus, parented to it.
+#[derive(Component)]
+struct FrameTitleText;
+
+pub struct FrameTitlePlugin;
+
+impl Plugin for FrameTitlePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (add_frame_titles, update_frame_titles).chain());
+    }
+}
+
+/// Gives every abacus a title; a rebuilt abacus loses its old one with its children.
+fn add_frame_titles(
+    mut commands: Commands,
+    settings: Res<AbacusSettings>,
+    abacus_query: Query<(Entity, Option<&Children>), With<Abacus>>,
+    title_query: Query<(), With<FrameTitleText>>,
+) {
+    for (abacus, children) in &abacus_query {
+        if children.map(|children| &children[..]).unwrap_or_default().iter().any(|&child| title_query.contains(child)) {
+            continue;
+        }
+        let title = commands.spawn((
+            FrameTitleText,
+            Text2d::new(settings.frame_title.text.trim()),
+            TextFont {
+                font: settings.text_font.clone(),
+                font_size: settings.frame_title.size,
+                ..default()
+            },
+            TextColor(settings.frame_title.color),
+            // Placed by `update_frame_titles` once it's a child
+            Transform::from_scale(Vec3::new(-0.01, 0.01, 0.01)),
+            Visibility::Hidden,
+            InheritedVisibility::default(),
+        )).id();
+        commands.entity(abacus).add_child(title);
+    }
+}
+
+/// Keeps the titles' text and style in step with the settings, and each one on the top of
+/// its frame, where the words line and total would otherwise be.
+fn update_frame_titles(
+    settings: Res<AbacusSettings>,
+    cjk_font: Option<Res<CjkFont>>,
+    abacus_query: Query<Ref<Abacus>>,
+    mut title_query: Query<(Ref<ChildOf>, &mut Text2d, &mut TextFont, &mut TextColor, &mut Transform, &mut Visibility), With<FrameTitleText>>,
+) {
+    for (parent, mut text, mut font, mut color, mut transform, mut visibility) in &mut title_query {
+        let Ok(abacus) = abacus_query.get(parent.parent()) else {
+            continue;
+        };
+        if !settings.is_changed() && !abacus.is_changed() && !parent.is_added() && !cjk_font.as_ref().is_some_and(|font| font.is_changed()) {
+            continue;
+        }
+        let title = &settings.frame_title;
+        if text.0 != title.text.trim() {
+            text.0 = title.text.trim().to_string();
+        }
+        // Names are often written in CJK, which the default font has no glyphs for
+        font.font = match &cjk_font {
+            Some(cjk_font) if !title.text.is_ascii() && settings.text_font_name.is_empty() => cjk_font.0.clone(),
+            _ => settings.text_font.clone(),
+        };
+        font.font_size = title.size;
+        color.0 = title.color;
+        transform.translation.y = abacus.words_y();
+        visibility.set_if_neq(if title.is_shown() { Visibility::Inherited } else { Visibility::Hidden });
+    }
+}
+
+/// The title's text, color and size, shown under Appearance.
+pub fn frame_title_controls(ui: &mut egui::Ui, title: &mut FrameTitle, localization: &Localization) {
+    ui.horizontal(|ui| {
+        let label = ui.label(tr!(localization, "frame-title"));
+        ui.add(egui::TextEdit::singleline(&mut title.text).hint_text(tr!(localization, "frame-title-hint"))).labelled_by(label.id);
+    });
+    ui.add_enabled_ui(title.is_shown(), |ui| {
+        ui.horizontal(|ui| {
+            let mut color = title.color.to_srgba().to_f32_array_no_alpha();
+            let color_response = ui.color_edit_button_rgb(&mut color);
+            if color_response.changed() {
+                title.color = Color::srgb_from_array(color);
+            }
+            let label = ui.label(tr!(localization, "frame-title-color"));
+            color_response.labelled_by(label.id);
+        });
+        ui.add(egui::Slider::new(&mut title.size, 16.0..=120.0).text(tr!(localization, "frame-title-size")));
+    });
+}
@@ -0,0 +1,98 @@
+//! Full screen with the panels still there, from the Controls panel or F10 (rebindable
+//! under Keybindings). The desktop build goes borderless on the current monitor and, on
+//! the way back, puts the window at the size and place it had; the web build asks the
+//! browser through its Fullscreen API, which keeps track of the page itself.
+
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+#[cfg(not(target_arch = "wasm32"))]
+use bevy::window::{MonitorSelection, WindowMode, WindowPosition};
+use bevy_egui::{egui, EguiContexts};
+
+use crate::keybindings::{ActionInput, InputAction};
+#[cfg(target_arch = "wasm32")]
+use crate::localization::*;
+use crate::AbacusSettings;
+
+const TOGGLE_ID: &str = "fullscreen_toggle";
+const ACTIVE_ID: &str = "fullscreen_active";
+
+pub struct FullscreenPlugin;
+
+impl Plugin for FullscreenPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, switch_fullscreen);
+    }
+}
+
+/// Goes full screen, or back, on the next frame.
+pub fn request_fullscreen_toggle(ctx: &egui::Context) {
+    ctx.data_mut(|data| data.insert_temp(egui::Id::new(TOGGLE_ID), true));
+}
+
+/// Whether the app was full screen as of the last frame.
+pub fn is_fullscreen(ctx: &egui::Context) -> bool {
+    ctx.data(|data| data.get_temp(egui::Id::new(ACTIVE_ID))).unwrap_or(false)
+}
+
+/// Whether the shortcut or the button asked to switch this frame. Also tells the button
+/// whether the app is `active`ly full screen.
+fn toggle_requested(input: &ActionInput, settings: &AbacusSettings, contexts: &mut EguiContexts, active: bool) -> bool {
+    let clicked = contexts.try_ctx_mut().is_some_and(|ctx| ctx.data_mut(|data| {
+        data.insert_temp(egui::Id::new(ACTIVE_ID), active);
+        data.remove_temp::<bool>(egui::Id::new(TOGGLE_ID)).is_some()
+    }));
+    // Presentation mode has the screen to itself, and its own way out
+    !settings.presentation_mode && (clicked || input.just_pressed(InputAction::Fullscreen))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn switch_fullscreen(
+    input: ActionInput,
+    settings: Res<AbacusSettings>,
+    mut contexts: EguiContexts,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+    mut windowed: Local<Option<(Vec2, WindowPosition)>>,
+) {
+    let Ok(mut window) = window_query.single_mut() else {
+        return;
+    };
+    let active = window.mode != WindowMode::Windowed;
+    if !toggle_requested(&input, &settings, &mut contexts, active) {
+        return;
+    }
+    if active {
+        window.mode = WindowMode::Windowed;
+        // The resolution has followed the monitor's meanwhile, and would stay that large
+        if let Some((size, position)) = windowed.take() {
+            window.resolution.set(size.x, size.y);
+            window.position = position;
+        }
+    } else {
+        *windowed = Some((window.resolution.size(), window.position));
+        window.mode = WindowMode::BorderlessFullscreen(MonitorSelection::Current);
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn switch_fullscreen(
+    input: ActionInput,
+    settings: Res<AbacusSettings>,
+    mut contexts: EguiContexts,
+    localization: Res<Localization>,
+) {
+    let Some(document) = web_sys::window().and_then(|window| window.document()) else {
+        return;
+    };
+    // Escape and the browser's own controls leave full screen too, so ask it every frame
+    let active = document.fullscreen_element().is_some();
+    if !toggle_requested(&input, &settings, &mut contexts, active) {
+        return;
+    }
+    if active {
+        document.exit_fullscreen();
+    } else if let Some(Err(error)) = document.document_element().map(|element| element.request_fullscreen()) {
+        // Some phone browsers have no Fullscreen API for pages
+        warn!("{}", tr!(localization, "log-fullscreen-failed", error = format!("{:?}", error)));
+    }
+}
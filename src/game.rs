@@ -0,0 +1,321 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::abacus::*;
+use crate::localization::*;
+use crate::scene_layout::{Billboard, SelectedAbacus};
+use crate::{format_number_in_base, AbacusSettings};
+
+/// How far above the total the target is drawn.
+const TARGET_TEXT_LIFT: f32 = 0.9;
+const TARGET_TEXT_COLOR: Color = Color::srgb(1.0, 0.75, 0.2);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GameDifficulty {
+    #[default]
+    Easy,
+    Medium,
+    /// The target is written in the abacus's own base rather than decimal.
+    Hard,
+}
+
+impl GameDifficulty {
+    pub const ALL: [GameDifficulty; 3] = [GameDifficulty::Easy, GameDifficulty::Medium, GameDifficulty::Hard];
+
+    /// Localization id of the difficulty's display name.
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            GameDifficulty::Easy => "game-easy",
+            GameDifficulty::Medium => "game-medium",
+            GameDifficulty::Hard => "game-hard",
+        }
+    }
+
+    /// Digits in the target, counted in the base it is written in.
+    fn digit_count(&self) -> u32 {
+        match self {
+            GameDifficulty::Easy => 2,
+            GameDifficulty::Medium => 4,
+            GameDifficulty::Hard => 5,
+        }
+    }
+
+    /// Seconds allowed for each target.
+    pub fn seconds(&self) -> f32 {
+        match self {
+            GameDifficulty::Easy => 30.0,
+            GameDifficulty::Medium => 45.0,
+            GameDifficulty::Hard => 60.0,
+        }
+    }
+
+    /// The base targets are written in for an abacus counting in `abacus_base`.
+    fn target_base(&self, abacus_base: u64) -> u64 {
+        match self {
+            GameDifficulty::Hard => abacus_base,
+            _ => 10,
+        }
+    }
+}
+
+/// Sent when a round is solved, runs out of time or is skipped.
+#[derive(Event)]
+pub struct RoundFinished {
+    pub solved: bool,
+    pub seconds: f32,
+}
+
+enum RoundResult {
+    Solved { seconds: f32, points: u64 },
+    TimeUp { target: u64 },
+}
+
+/// Marks the target number floating above the game's abacus.
+#[derive(Component)]
+pub struct GameTargetText;
+
+/// The target-matching game: set the shown number on the abacus before time runs out.
+#[derive(Resource, Default)]
+pub struct GameState {
+    /// The abacus being played on; `None` when no game is running.
+    abacus: Option<Entity>,
+    pub difficulty: GameDifficulty,
+    target: u64,
+    timer: Timer,
+    streak: u32,
+    best_streak: u32,
+    score: u64,
+    last_round: Option<RoundResult>,
+    rng: u64,
+    target_text: Option<Entity>,
+}
+
+impl GameState {
+    /// xorshift64*; seeded from the clock when a game starts, which is plenty for picking targets.
+    fn next_random(&mut self) -> u64 {
+        self.rng ^= self.rng >> 12;
+        self.rng ^= self.rng << 25;
+        self.rng ^= self.rng >> 27;
+        self.rng.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    fn target_base(&self, abacus: &Abacus) -> u64 {
+        self.difficulty.target_base(abacus.abacus_base)
+    }
+
+    /// Picks a fresh target with the difficulty's digit count that the abacus can show
+    /// and that it isn't showing already, and restarts the countdown.
+    fn new_round(&mut self, abacus: &Abacus) {
+        let base = self.target_base(abacus);
+        let digits = self.difficulty.digit_count();
+        let max = base.saturating_pow(digits).saturating_sub(1).min(abacus.max_total_value());
+        let min = base.saturating_pow(digits - 1).min(max);
+
+        let span = max - min + 1;
+        let mut target = min + self.next_random() % span;
+        if target == abacus.total_value && span > 1 {
+            target = min + (target - min + 1) % span;
+        }
+        self.target = target;
+        self.timer = Timer::from_seconds(self.difficulty.seconds(), TimerMode::Once);
+    }
+
+    fn start(&mut self, abacus_entity: Entity, abacus: &Abacus, seed: u64) {
+        self.abacus = Some(abacus_entity);
+        self.rng = seed | 1;
+        self.streak = 0;
+        self.score = 0;
+        self.last_round = None;
+        self.new_round(abacus);
+    }
+
+    fn stop(&mut self) {
+        self.abacus = None;
+    }
+}
+
+/// Scores the round as soon as the beads show the target.
+pub fn check_game_target(
+    mut game: ResMut<GameState>,
+    abacus_query: Query<&Abacus>,
+    mut rounds: EventWriter<RoundFinished>,
+) {
+    let Some(abacus) = game.abacus.and_then(|entity| abacus_query.get(entity).ok()) else {
+        return;
+    };
+    if abacus.total_value != game.target {
+        return;
+    }
+
+    game.streak += 1;
+    game.best_streak = game.best_streak.max(game.streak);
+    // Longer targets and longer streaks are worth more
+    let points = 10 * game.difficulty.digit_count() as u64 * game.streak as u64;
+    game.score += points;
+    let seconds = game.timer.elapsed_secs();
+    game.last_round = Some(RoundResult::Solved { seconds, points });
+    rounds.write(RoundFinished { solved: true, seconds });
+    game.new_round(abacus);
+}
+
+/// Keeps the target floating above the game's abacus, and removes it when the game ends.
+pub fn update_game_target_text(
+    mut game: ResMut<GameState>,
+    settings: Res<AbacusSettings>,
+    localization: Res<Localization>,
+    cjk_font: Option<Res<CjkFont>>,
+    abacus_query: Query<&Abacus>,
+    transform_query: Query<&Transform, Without<GameTargetText>>,
+    mut text_query: Query<(&mut Text2d, &mut TextFont, &mut Transform), With<GameTargetText>>,
+    mut commands: Commands,
+) {
+    let restyle = settings.is_changed() || localization.is_changed();
+    if !game.is_changed() && !restyle {
+        return;
+    }
+
+    let Some(abacus) = game.abacus.and_then(|entity| abacus_query.get(entity).ok()) else {
+        if let Some(text) = game.target_text.take() {
+            commands.entity(text).try_despawn();
+        }
+        return;
+    };
+
+    let label = tr!(localization, "game-target", target = format_number_in_base(game.target, game.target_base(abacus)));
+    let font = TextFont {
+        font_size: settings.text_size * settings.ui_scale,
+        // The default font has no CJK glyphs
+        font: match &cjk_font {
+            Some(cjk_font) if localization.language.uses_cjk() && settings.text_font_name.is_empty() => cjk_font.0.clone(),
+            _ => settings.text_font.clone(),
+        },
+        ..default()
+    };
+    let total_y = transform_query.get(abacus.total_text).map(|transform| transform.translation.y).unwrap_or_default();
+    let transform = Transform::from_xyz(0.0, total_y + TARGET_TEXT_LIFT, 0.0).with_scale(Vec3::new(-0.01, 0.01, 0.01));
+
+    match game.target_text.and_then(|entity| text_query.get_mut(entity).ok()) {
+        Some((mut text, mut text_font, mut text_transform)) => {
+            // The countdown changes the game every frame; only touch the text when it differs
+            if text.0 != label {
+                text.0 = label;
+            }
+            if restyle {
+                *text_font = font;
+                *text_transform = transform;
+            }
+        }
+        None => {
+            let text = commands.spawn((
+                GameTargetText,
+                Billboard,
+                Text2d::new(label),
+                font,
+                TextColor(TARGET_TEXT_COLOR),
+                transform,
+                Visibility::Inherited,
+                InheritedVisibility::default(),
+            )).id();
+            if let Some(abacus_entity) = game.abacus {
+                commands.entity(abacus_entity).add_child(text);
+            }
+            game.target_text = Some(text);
+        }
+    }
+}
+
+pub fn game_ui_system(
+    mut contexts: EguiContexts,
+    mut game: ResMut<GameState>,
+    time: Res<Time>,
+    abacus_query: Query<&Abacus>,
+    selected: Res<SelectedAbacus>,
+    localization: Res<Localization>,
+    mut rounds: EventWriter<RoundFinished>,
+) {
+    let localization = &*localization;
+
+    // The abacus was removed or rebuilt out from under the game
+    let game_abacus = game.abacus.and_then(|entity| abacus_query.get(entity).ok());
+    if game.abacus.is_some() && game_abacus.is_none() {
+        game.stop();
+    }
+
+    if let Some(abacus) = game_abacus {
+        game.timer.tick(time.delta());
+        if game.timer.just_finished() {
+            game.streak = 0;
+            game.last_round = Some(RoundResult::TimeUp { target: game.target });
+            rounds.write(RoundFinished { solved: false, seconds: game.timer.elapsed_secs() });
+            game.new_round(abacus);
+        }
+    }
+
+    egui::Window::new(tr!(localization, "game-window"))
+        .id(egui::Id::new("target_game"))
+        .default_pos([10.0, 460.0])
+        .default_open(false)
+        .show(contexts.ctx_mut(), |ui| {
+            match game_abacus {
+                Some(abacus) => {
+                    let base = game.target_base(abacus);
+                    ui.heading(tr!(localization, "game-target", target = format_number_in_base(game.target, base)));
+                    if base != 10 {
+                        ui.label(tr!(localization, "game-target-base", base = base));
+                    }
+                    ui.label(tr!(localization, "game-time-left", seconds = game.timer.remaining_secs().ceil() as u64));
+                    ui.label(tr!(localization, "game-streak", streak = game.streak, best = game.best_streak));
+                    ui.label(tr!(localization, "game-score", score = game.score));
+
+                    match &game.last_round {
+                        Some(RoundResult::Solved { seconds, points }) => {
+                            ui.colored_label(egui::Color32::LIGHT_GREEN, tr!(localization, "game-solved",
+                                seconds = format!("{:.1}", seconds), points = *points));
+                        }
+                        Some(RoundResult::TimeUp { target }) => {
+                            ui.colored_label(egui::Color32::LIGHT_RED, tr!(localization, "game-time-up",
+                                target = format_number_in_base(*target, base)));
+                        }
+                        None => {}
+                    }
+
+                    ui.horizontal(|ui| {
+                        if ui.button(tr!(localization, "game-skip")).clicked() {
+                            rounds.write(RoundFinished { solved: false, seconds: game.timer.elapsed_secs() });
+                            game.streak = 0;
+                            game.last_round = None;
+                            game.new_round(abacus);
+                        }
+                        if ui.button(tr!(localization, "game-stop")).clicked() {
+                            game.stop();
+                        }
+                    });
+                }
+                None => {
+                    ui.label(tr!(localization, "game-intro"));
+                    let mut difficulty = game.difficulty;
+                    egui::ComboBox::from_label(tr!(localization, "game-difficulty"))
+                        .selected_text(tr!(localization, difficulty.message_id()))
+                        .show_ui(ui, |ui| {
+                            for option in GameDifficulty::ALL {
+                                ui.selectable_value(&mut difficulty, option, tr!(localization, option.message_id()));
+                            }
+                        });
+                    if difficulty != game.difficulty {
+                        game.difficulty = difficulty;
+                    }
+
+                    let selected_abacus = selected.0.and_then(|entity| abacus_query.get(entity).ok().map(|abacus| (entity, abacus)));
+                    if ui.add_enabled(selected_abacus.is_some(), egui::Button::new(tr!(localization, "game-start"))).clicked() {
+                        if let Some((entity, abacus)) = selected_abacus {
+                            game.start(entity, abacus, time.elapsed().as_nanos() as u64);
+                            info!("{}", tr!(localization, "log-game-started"));
+                        }
+                    }
+                    if game.best_streak > 0 {
+                        ui.label(tr!(localization, "game-best", best = game.best_streak, score = game.score));
+                    }
+                }
+            }
+        });
+}
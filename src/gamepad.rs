@@ -0,0 +1,94 @@
+//! Gamepad controls, for a TV or a Steam Deck: the d-pad picks a column and changes it,
+//! the shoulder buttons add or subtract one, Select clears and the right stick turns the
+//! selected abacus.
+
+use bevy::prelude::*;
+
+use crate::abacus::*;
+use crate::active_column::ActiveColumn;
+use crate::operations::{AbacusOperation, ClearRequested, ColumnOpQueue, OperationRequested};
+use crate::scene_layout::SelectedAbacus;
+use crate::AbacusSettings;
+
+/// Radians per second at full stick.
+const STICK_ROTATION_SPEED: f32 = 2.0;
+/// Stick travel ignored, so a worn stick doesn't drift.
+const STICK_DEAD_ZONE: f32 = 0.15;
+
+pub struct GamepadControlsPlugin;
+
+impl Plugin for GamepadControlsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (gamepad_buttons, gamepad_rotation));
+    }
+}
+
+fn gamepad_buttons(
+    gamepads: Query<&Gamepad>,
+    selected: Res<SelectedAbacus>,
+    abacus_query: Query<&Abacus>,
+    long_query: Query<&AbacusLong>,
+    mut active: ResMut<ActiveColumn>,
+    mut op_queue: ResMut<ColumnOpQueue>,
+    mut requests: EventWriter<OperationRequested>,
+    mut clear_requests: EventWriter<ClearRequested>,
+) {
+    let Some((entity, abacus)) = selected.0.and_then(|entity| abacus_query.get(entity).ok().map(|abacus| (entity, abacus))) else {
+        return;
+    };
+    for gamepad in &gamepads {
+        if gamepad.just_pressed(GamepadButton::DPadLeft) {
+            active.step(abacus.left_step(), abacus.columns.len());
+        }
+        if gamepad.just_pressed(GamepadButton::DPadRight) {
+            active.step(-abacus.left_step(), abacus.columns.len());
+        }
+
+        let up = gamepad.just_pressed(GamepadButton::DPadUp);
+        if up || gamepad.just_pressed(GamepadButton::DPadDown) {
+            let column = *active.column.get_or_insert(0);
+            // Step from where any queued ripple will end up, as scrolling a column does
+            let current_value = op_queue.pending_total_value(entity, abacus, &long_query);
+            if let Some(new_value) = abacus.stepped_total_value(column, up, current_value) {
+                op_queue.queue_total_value(entity, abacus, new_value, &long_query);
+            }
+        }
+
+        if gamepad.just_pressed(GamepadButton::RightTrigger) {
+            requests.write(OperationRequested { abacus: entity, operation: AbacusOperation::Add(1) });
+        }
+        if gamepad.just_pressed(GamepadButton::LeftTrigger) {
+            requests.write(OperationRequested { abacus: entity, operation: AbacusOperation::Subtract(1) });
+        }
+        if gamepad.just_pressed(GamepadButton::Select) {
+            clear_requests.write(ClearRequested { abacus: Some(entity) });
+        }
+    }
+}
+
+/// Turns the selected abacus with the right stick, as dragging with the right mouse button does.
+fn gamepad_rotation(
+    gamepads: Query<&Gamepad>,
+    time: Res<Time>,
+    selected: Res<SelectedAbacus>,
+    settings: Res<AbacusSettings>,
+    mut abacus_query: Query<&mut Transform, With<Abacus>>,
+) {
+    // Flat mode always faces the front
+    if settings.flat_mode {
+        return;
+    }
+    let Some(mut transform) = selected.0.and_then(|entity| abacus_query.get_mut(entity).ok()) else {
+        return;
+    };
+    for gamepad in &gamepads {
+        let stick = gamepad.right_stick();
+        if stick.length() < STICK_DEAD_ZONE {
+            continue;
+        }
+        let angle = stick * STICK_ROTATION_SPEED * time.delta_secs();
+        // Stick up tilts the top away, like dragging the mouse up
+        transform.rotate_y(angle.x);
+        transform.rotate_x(angle.y);
+    }
+}
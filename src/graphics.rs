@@ -0,0 +1,113 @@
+//! Graphics quality presets. Low is for phones and old laptops, High is the full look;
+//! each preset sets the shadows, anti-aliasing, bead roundness and light together.
+
+use bevy::prelude::*;
+
+use crate::abacus::*;
+use crate::environment::KeyLight;
+use crate::AbacusSettings;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GraphicsQuality {
+    Low,
+    Medium,
+    High,
+}
+
+impl Default for GraphicsQuality {
+    /// Mobile browsers start on Low, everything else on High.
+    fn default() -> Self {
+        if is_mobile_device() { GraphicsQuality::Low } else { GraphicsQuality::High }
+    }
+}
+
+impl GraphicsQuality {
+    pub const ALL: [GraphicsQuality; 3] = [GraphicsQuality::Low, GraphicsQuality::Medium, GraphicsQuality::High];
+
+    /// Localization id of the preset's display name.
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            GraphicsQuality::Low => "quality-low",
+            GraphicsQuality::Medium => "quality-medium",
+            GraphicsQuality::High => "quality-high",
+        }
+    }
+
+    pub fn shadows(&self) -> bool {
+        *self == GraphicsQuality::High
+    }
+
+    /// WebGL2 only offers 1 or 4 samples, so nothing goes past 4.
+    pub fn msaa(&self) -> Msaa {
+        match self {
+            GraphicsQuality::Low => Msaa::Off,
+            GraphicsQuality::Medium | GraphicsQuality::High => Msaa::Sample4,
+        }
+    }
+
+    /// Segments around each bead's rim.
+    pub fn bead_resolution(&self) -> u32 {
+        match self {
+            GraphicsQuality::Low => 12,
+            GraphicsQuality::Medium => 20,
+            GraphicsQuality::High => 32,
+        }
+    }
+
+    /// Segments around the rim of the beads of wide abaci, too small on screen to show more.
+    pub fn bead_lod_resolution(&self) -> u32 {
+        (self.bead_resolution() / 2).max(8)
+    }
+
+    /// The key light is dimmed a little without shadows, so unshaded beads don't glare.
+    /// The lighting settings scale it from here.
+    pub fn light_intensity(&self) -> f32 {
+        match self {
+            GraphicsQuality::Low => 6_000_000.,
+            GraphicsQuality::Medium => 8_000_000.,
+            GraphicsQuality::High => 10_000_000.,
+        }
+    }
+}
+
+pub struct GraphicsPlugin;
+
+impl Plugin for GraphicsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, apply_graphics_quality.run_if(resource_changed::<AbacusSettings>));
+    }
+}
+
+/// Applies the preset when it, flat mode or the bead shape changes. Beads all share one
+/// mesh (or, on wide abaci, its coarser stand-in), so both are rebuilt in place at the
+/// preset's resolutions.
+fn apply_graphics_quality(
+    settings: Res<AbacusSettings>,
+    mut applied: Local<Option<(GraphicsQuality, bool, BeadShape)>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut camera_query: Query<&mut Msaa, With<Camera>>,
+    mut light_query: Query<&mut PointLight, With<KeyLight>>,
+) {
+    let quality = settings.graphics_quality;
+    let previous = applied.replace((quality, settings.flat_mode, settings.bead_shape));
+    if previous == Some((quality, settings.flat_mode, settings.bead_shape)) {
+        return;
+    }
+    for mut msaa in &mut camera_query {
+        *msaa = quality.msaa();
+    }
+    for mut light in &mut light_query {
+        // Flat mode never has shadows, whatever the preset
+        light.shadows_enabled = quality.shadows() && !settings.flat_mode;
+    }
+    // A custom bead model stands in for the shape
+    let reshaped = previous.is_none_or(|(previous_quality, _, previous_shape)| previous_quality != quality || previous_shape != settings.bead_shape);
+    if reshaped && settings.custom_models.bead.is_empty() {
+        if let Some(mesh) = meshes.get_mut(&settings.bead_mesh) {
+            *mesh = bead_mesh(settings.bead_shape, quality.bead_resolution());
+        }
+        if let Some(mesh) = meshes.get_mut(&settings.bead_lod_mesh) {
+            *mesh = bead_mesh(settings.bead_shape, quality.bead_lod_resolution());
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use bevy::prelude::*;
+
+use crate::abacus::AbacusBead;
+use crate::audio::AbacusCleared;
+use crate::operations::ColumnCarried;
+use crate::AbacusSettings;
+
+/// Vibration length for tapping a bead, in milliseconds.
+const TAP_PULSE_MS: u32 = 15;
+/// Longer pulse for carries and clearing the whole abacus.
+const CARRY_PULSE_MS: u32 = 60;
+
+#[cfg(target_arch = "wasm32")]
+fn vibrate(duration_ms: u32) {
+    if let Some(window) = web_sys::window() {
+        // Browsers without the Vibration API simply report false
+        let _ = window.navigator().vibrate_with_duration(duration_ms);
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn vibrate(_duration_ms: u32) {
+    // Native builds have no vibration motor to drive
+}
+
+pub fn haptic_feedback(
+    settings: Res<AbacusSettings>,
+    mut clicks: EventReader<Pointer<Click>>,
+    mut carries: EventReader<ColumnCarried>,
+    mut clears: EventReader<AbacusCleared>,
+    beads: Query<(), With<AbacusBead>>,
+) {
+    let tapped_bead = clicks.read().any(|click| beads.contains(click.target));
+    let carried = carries.read().count() > 0;
+    let cleared = clears.read().count() > 0;
+
+    if !settings.haptics_enabled {
+        return;
+    }
+
+    if carried || cleared {
+        vibrate(CARRY_PULSE_MS);
+    } else if tapped_bead {
+        vibrate(TAP_PULSE_MS);
+    }
+}
@@ -0,0 +1,156 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use std::collections::{HashMap, VecDeque};
+
+use crate::abacus::*;
+use crate::keybindings::{ActionInput, InputAction};
+use crate::localization::*;
+use crate::operations::OperationRequested;
+use crate::scene_layout::SelectedAbacus;
+use crate::snapshots::{deck_values, move_to_decks};
+
+/// Values kept per abacus before the oldest are dropped.
+const MAX_HISTORY: usize = 1000;
+
+struct HistoryEntry {
+    total: u64,
+    decks: Vec<Vec<u64>>,
+}
+
+#[derive(Default)]
+struct AbacusHistory {
+    entries: VecDeque<HistoryEntry>,
+    /// The entry the scrubber has moved the abacus to; `None` while following the latest value.
+    cursor: Option<usize>,
+}
+
+impl AbacusHistory {
+    /// Moves the abacus's beads to the entry at `position`.
+    fn scrub_to(
+        &mut self,
+        position: usize,
+        entity: Entity,
+        abacus: &Abacus,
+        long_query: &Query<&AbacusLong>,
+        requests: &mut EventWriter<OperationRequested>,
+    ) {
+        move_to_decks(entity, &deck_values(abacus, long_query), &self.entries[position].decks, requests);
+        self.cursor = (position + 1 != self.entries.len()).then_some(position);
+    }
+}
+
+/// Every value each abacus has held this session, by slot, for scrubbing back and forth.
+#[derive(Resource, Default)]
+pub struct ValueHistory {
+    abaci: HashMap<usize, AbacusHistory>,
+}
+
+/// Logs each abacus's beads whenever they come to show something new. Moves made by
+/// the scrubber itself aren't logged; any other move is, and goes back to following it.
+pub fn record_value_history(
+    mut history: ResMut<ValueHistory>,
+    abacus_query: Query<&Abacus>,
+    long_query: Query<&AbacusLong>,
+) {
+    for abacus in &abacus_query {
+        let decks = deck_values(abacus, &long_query);
+        let log = history.abaci.entry(abacus.slot).or_default();
+        // Rebuilt with another number of columns, so the old layouts no longer apply
+        if log.entries.back().is_some_and(|entry| entry.decks.len() != decks.len()) {
+            *log = AbacusHistory::default();
+        }
+        if log.entries.back().is_some_and(|entry| entry.decks == decks) {
+            continue;
+        }
+        if log.cursor.is_some_and(|i| log.entries[i].decks == decks) {
+            continue;
+        }
+
+        log.entries.push_back(HistoryEntry { total: abacus.beads_total_value(&long_query), decks });
+        if log.entries.len() > MAX_HISTORY {
+            log.entries.pop_front();
+        }
+        log.cursor = None;
+    }
+}
+
+pub fn history_ui_system(
+    mut contexts: EguiContexts,
+    mut history: ResMut<ValueHistory>,
+    abacus_query: Query<&Abacus>,
+    long_query: Query<&AbacusLong>,
+    selected: Res<SelectedAbacus>,
+    mut requests: EventWriter<OperationRequested>,
+    localization: Res<Localization>,
+) {
+    let localization = &*localization;
+    let selected_abacus = selected.0.and_then(|entity| abacus_query.get(entity).ok().map(|abacus| (entity, abacus)));
+
+    egui::Window::new(tr!(localization, "history-window"))
+        .id(egui::Id::new("value_history"))
+        .default_pos([10.0, 760.0])
+        .default_open(false)
+        .show(contexts.ctx_mut(), |ui| {
+            let Some((entity, abacus)) = selected_abacus else {
+                return;
+            };
+            let Some(log) = history.abaci.get_mut(&abacus.slot).filter(|log| log.entries.len() > 1) else {
+                ui.label(tr!(localization, "history-empty"));
+                return;
+            };
+
+            let last = log.entries.len() - 1;
+            let current = log.cursor.unwrap_or(last);
+            let mut position = current;
+            ui.add(egui::Slider::new(&mut position, 0..=last).show_value(false));
+            ui.horizontal(|ui| {
+                if ui.button("⏮").on_hover_text(tr!(localization, "history-first")).clicked() {
+                    position = 0;
+                }
+                if ui.button("◀").on_hover_text(tr!(localization, "history-back")).clicked() {
+                    position = position.saturating_sub(1);
+                }
+                if ui.button("▶").on_hover_text(tr!(localization, "history-forward")).clicked() {
+                    position = (position + 1).min(last);
+                }
+                if ui.button("⏭").on_hover_text(tr!(localization, "history-latest")).clicked() {
+                    position = last;
+                }
+            });
+            ui.label(tr!(localization, "history-position",
+                step = position + 1, count = log.entries.len(), value = log.entries[position].total));
+
+            if position != current {
+                log.scrub_to(position, entity, abacus, &long_query, &mut requests);
+            }
+        });
+}
+
+/// Steps the selected abacus back and forth through its history on the Undo and Redo
+/// shortcuts, as the scrubber's ◀ and ▶ do.
+pub fn undo_redo_system(
+    input: ActionInput,
+    mut history: ResMut<ValueHistory>,
+    abacus_query: Query<&Abacus>,
+    long_query: Query<&AbacusLong>,
+    selected: Res<SelectedAbacus>,
+    mut requests: EventWriter<OperationRequested>,
+) {
+    let offset = match (input.just_pressed(InputAction::Undo), input.just_pressed(InputAction::Redo)) {
+        (true, false) => -1,
+        (false, true) => 1,
+        _ => return,
+    };
+    let Some((entity, abacus)) = selected.0.and_then(|entity| abacus_query.get(entity).ok().map(|abacus| (entity, abacus))) else {
+        return;
+    };
+    let Some(log) = history.abaci.get_mut(&abacus.slot).filter(|log| log.entries.len() > 1) else {
+        return;
+    };
+    let last = log.entries.len() - 1;
+    let current = log.cursor.unwrap_or(last);
+    let position = current.saturating_add_signed(offset).min(last);
+    if position != current {
+        log.scrub_to(position, entity, abacus, &long_query, &mut requests);
+    }
+}
@@ -0,0 +1,44 @@
+//! The selected abacus's total as a large fixed line at the top of the screen, for
+//! projecting in a classroom where the in-world total is too small or turned away.
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::abacus::*;
+use crate::localization::*;
+use crate::scene_layout::SelectedAbacus;
+use crate::{format_total, AbacusSettings};
+
+pub struct HudPlugin;
+
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, hud_total_ui);
+    }
+}
+
+fn hud_total_ui(
+    mut contexts: EguiContexts,
+    settings: Res<AbacusSettings>,
+    selected: Res<SelectedAbacus>,
+    abacus_query: Query<&Abacus>,
+    localization: Res<Localization>,
+) {
+    if !settings.hud_total {
+        return;
+    }
+    let Some(abacus) = selected.0.and_then(|entity| abacus_query.get(entity).ok()) else {
+        return;
+    };
+    let total = format_total(abacus, &settings, &localization);
+    let [r, g, b, _] = settings.text_color.to_srgba().to_u8_array();
+
+    // Behind the windows, and clicks go through to the beads
+    egui::Area::new(egui::Id::new("hud_total"))
+        .anchor(egui::Align2::CENTER_TOP, [0.0, 8.0])
+        .order(egui::Order::Background)
+        .interactable(false)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.label(egui::RichText::new(total).size(settings.hud_text_size).color(egui::Color32::from_rgb(r, g, b)).strong());
+        });
+}
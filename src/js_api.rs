@@ -0,0 +1,137 @@
+//! JavaScript API of the web build, for e-learning pages that embed the canvas.
+//! Pages drive the selected abacus with `set_value` and `load_config_json`, read it
+//! with `get_value`, and hear about every change through `on_abacus_changed` or, when
+//! embedded in an iframe, a `{ type: "abacus-changed", abacus, value }` message posted
+//! to the parent page.
+
+use bevy::prelude::*;
+use std::cell::{Cell, RefCell};
+use wasm_bindgen::prelude::*;
+
+use crate::abacus::*;
+use crate::localization::*;
+use crate::operations::{AbacusOperation, ColumnOpQueue, OperationRequested};
+use crate::scene_layout::SelectedAbacus;
+use crate::wood_grain::AbacusMaterial;
+use crate::{apply_config, rebuild_selected_abacus, AbacusSettings, SavableAbacusConfig};
+
+enum JsCommand {
+    SetValue(u64),
+    LoadConfig(SavableAbacusConfig),
+}
+
+thread_local! {
+    /// Calls from the page, waiting for the next frame to apply them.
+    static INBOX: RefCell<Vec<JsCommand>> = const { RefCell::new(Vec::new()) };
+    /// The selected abacus's total, as of the last frame.
+    static VALUE: Cell<u64> = const { Cell::new(0) };
+    static CHANGE_CALLBACK: RefCell<Option<js_sys::Function>> = const { RefCell::new(None) };
+}
+
+/// Sets the selected abacus to `value`, rippling the beads like the Set button.
+#[wasm_bindgen]
+pub fn set_value(value: f64) -> Result<(), JsValue> {
+    // JS numbers are exact up to 2^53, far past what an abacus shows
+    if !(value.is_finite() && value >= 0.0 && value.fract() == 0.0) {
+        return Err(JsValue::from_str("value must be a non-negative integer"));
+    }
+    INBOX.with_borrow_mut(|inbox| inbox.push(JsCommand::SetValue(value as u64)));
+    Ok(())
+}
+
+/// The selected abacus's total.
+#[wasm_bindgen]
+pub fn get_value() -> f64 {
+    VALUE.get() as f64
+}
+
+/// Rebuilds the selected abacus from a configuration in the same JSON form the app
+/// saves its configurations in. Fields outside the settings panel's ranges are an error.
+#[wasm_bindgen]
+pub fn load_config_json(json: &str) -> Result<(), JsValue> {
+    let mut config: SavableAbacusConfig = serde_json::from_str(json)
+        .map_err(|error| JsValue::from_str(&format!("invalid configuration: {}", error)))?;
+    let clamped = config.sanitize();
+    if !clamped.is_empty() {
+        return Err(JsValue::from_str(&format!("configuration out of range: {}", clamped.join(", "))));
+    }
+    INBOX.with_borrow_mut(|inbox| inbox.push(JsCommand::LoadConfig(config)));
+    Ok(())
+}
+
+/// Calls `callback(value, abacus)` whenever the selected abacus's total changes.
+/// Pass `null` to stop.
+#[wasm_bindgen]
+pub fn on_abacus_changed(callback: Option<js_sys::Function>) {
+    CHANGE_CALLBACK.set(callback);
+}
+
+pub struct JsApiPlugin;
+
+impl Plugin for JsApiPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (
+            apply_js_commands,
+            notify_js_of_changes.run_if(on_event::<AbacusChanged>),
+        ));
+    }
+}
+
+fn apply_js_commands(
+    mut commands: Commands,
+    mut materials: ResMut<Assets<AbacusMaterial>>,
+    mut settings: ResMut<AbacusSettings>,
+    mut selected: ResMut<SelectedAbacus>,
+    abacus_query: Query<(Entity, &Abacus)>,
+    mut op_queue: ResMut<ColumnOpQueue>,
+    mut requests: EventWriter<OperationRequested>,
+) {
+    for command in INBOX.with_borrow_mut(std::mem::take) {
+        match command {
+            JsCommand::SetValue(value) => {
+                if let Some(abacus) = selected.0 {
+                    requests.write(OperationRequested { abacus, operation: AbacusOperation::Set(value) });
+                }
+            }
+            JsCommand::LoadConfig(config) => {
+                apply_config(&mut settings, &mut materials, &config);
+                rebuild_selected_abacus(&mut commands, &settings, &mut selected, &abacus_query, &mut op_queue);
+            }
+        }
+    }
+}
+
+fn notify_js_of_changes(selected: Res<SelectedAbacus>, abacus_query: Query<&Abacus>, localization: Res<Localization>) {
+    let Some(abacus) = selected.0.and_then(|entity| abacus_query.get(entity).ok()) else {
+        return;
+    };
+    if abacus.total_value == VALUE.get() {
+        return;
+    }
+    VALUE.set(abacus.total_value);
+
+    let value = JsValue::from_f64(abacus.total_value as f64);
+    let slot = JsValue::from_f64(abacus.slot as f64);
+    CHANGE_CALLBACK.with_borrow(|callback| {
+        if let Some(callback) = callback {
+            if let Err(error) = callback.call2(&JsValue::NULL, &value, &slot) {
+                warn!("{}", tr!(localization, "log-js-callback-failed", error = format!("{:?}", error)));
+            }
+        }
+    });
+
+    // An embedding page hears about changes without needing a reference to this module
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    let Some(parent) = window.parent().ok().flatten().filter(|parent| parent != &window) else {
+        return;
+    };
+    let message = js_sys::Object::new();
+    let _ = js_sys::Reflect::set(&message, &"type".into(), &"abacus-changed".into());
+    let _ = js_sys::Reflect::set(&message, &"abacus".into(), &slot);
+    let _ = js_sys::Reflect::set(&message, &"value".into(), &value);
+    if let Err(error) = parent.post_message(&message, "*") {
+        warn!("{}", tr!(localization, "log-js-post-failed", error = format!("{:?}", error)));
+    }
+}
@@ -0,0 +1,326 @@
+//! Rebindable shortcuts: every keyboard (and mouse button) shortcut is an `InputAction`,
+//! looked up through `ActionInput` instead of a hard-coded key, and the Keybindings window
+//! changes them. Bindings are saved with the active profile's data.
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use serde::{Deserialize, Serialize};
+
+use crate::localization::*;
+
+const CONTROL_KEYS: [KeyCode; 4] = [KeyCode::ControlLeft, KeyCode::ControlRight, KeyCode::SuperLeft, KeyCode::SuperRight];
+/// Keys that only modify others, so pressing one doesn't end a rebinding.
+const MODIFIER_KEYS: [KeyCode; 8] = [
+    KeyCode::ControlLeft, KeyCode::ControlRight, KeyCode::SuperLeft, KeyCode::SuperRight,
+    KeyCode::ShiftLeft, KeyCode::ShiftRight, KeyCode::AltLeft, KeyCode::AltRight,
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum InputAction {
+    Clear,
+    Undo,
+    Redo,
+    /// Held while dragging to turn the selected abacus.
+    RotateDrag,
+    RotateLeft,
+    RotateRight,
+    TiltUp,
+    TiltDown,
+    ResetRotation,
+    CycleCameraFocus,
+    Presentation,
+    ExitPresentation,
+    Screenshot,
+    Fullscreen,
+    /// Moves the active column towards the higher places.
+    ColumnLeft,
+    ColumnRight,
+    ColumnUp,
+    ColumnDown,
+    DeselectColumn,
+    /// Sets the active column to the digit, or with no active column types a digit of a
+    /// value to set the selected abacus to.
+    Digit(u8),
+    EnterDigits,
+    EraseDigit,
+}
+
+impl InputAction {
+    pub const ALL: [InputAction; 31] = [
+        InputAction::Clear, InputAction::Undo, InputAction::Redo,
+        InputAction::RotateDrag, InputAction::RotateLeft, InputAction::RotateRight, InputAction::TiltUp,
+        InputAction::TiltDown, InputAction::ResetRotation, InputAction::CycleCameraFocus,
+        InputAction::Presentation, InputAction::ExitPresentation, InputAction::Screenshot, InputAction::Fullscreen,
+        InputAction::ColumnLeft, InputAction::ColumnRight, InputAction::ColumnUp, InputAction::ColumnDown,
+        InputAction::DeselectColumn,
+        InputAction::Digit(0), InputAction::Digit(1), InputAction::Digit(2), InputAction::Digit(3),
+        InputAction::Digit(4), InputAction::Digit(5), InputAction::Digit(6), InputAction::Digit(7),
+        InputAction::Digit(8), InputAction::Digit(9), InputAction::EnterDigits, InputAction::EraseDigit,
+    ];
+
+    /// The action's display name.
+    pub fn label(&self, localization: &Localization) -> String {
+        match self {
+            InputAction::Clear => tr!(localization, "action-clear"),
+            InputAction::Undo => tr!(localization, "action-undo"),
+            InputAction::Redo => tr!(localization, "action-redo"),
+            InputAction::RotateDrag => tr!(localization, "action-rotate-drag"),
+            InputAction::RotateLeft => tr!(localization, "action-rotate-left"),
+            InputAction::RotateRight => tr!(localization, "action-rotate-right"),
+            InputAction::TiltUp => tr!(localization, "action-tilt-up"),
+            InputAction::TiltDown => tr!(localization, "action-tilt-down"),
+            InputAction::ResetRotation => tr!(localization, "action-reset-rotation"),
+            InputAction::CycleCameraFocus => tr!(localization, "action-camera-focus"),
+            InputAction::Presentation => tr!(localization, "action-presentation"),
+            InputAction::ExitPresentation => tr!(localization, "action-exit-presentation"),
+            InputAction::Screenshot => tr!(localization, "action-screenshot"),
+            InputAction::Fullscreen => tr!(localization, "action-fullscreen"),
+            InputAction::ColumnLeft => tr!(localization, "action-column-left"),
+            InputAction::ColumnRight => tr!(localization, "action-column-right"),
+            InputAction::ColumnUp => tr!(localization, "action-column-up"),
+            InputAction::ColumnDown => tr!(localization, "action-column-down"),
+            InputAction::DeselectColumn => tr!(localization, "action-deselect-column"),
+            InputAction::Digit(digit) => tr!(localization, "action-digit", digit = *digit),
+            InputAction::EnterDigits => tr!(localization, "action-enter-digits"),
+            InputAction::EraseDigit => tr!(localization, "action-erase-digit"),
+        }
+    }
+
+    pub fn default_bindings(&self) -> Vec<InputBinding> {
+        use InputBinding::*;
+        match self {
+            InputAction::Clear => vec![Key(KeyCode::KeyC), Key(KeyCode::Delete)],
+            InputAction::Undo => vec![CtrlKey(KeyCode::KeyZ)],
+            InputAction::Redo => vec![CtrlKey(KeyCode::KeyY)],
+            InputAction::RotateDrag => vec![Mouse(MouseButton::Right)],
+            InputAction::RotateLeft => vec![Key(KeyCode::KeyA)],
+            InputAction::RotateRight => vec![Key(KeyCode::KeyD)],
+            InputAction::TiltUp => vec![Key(KeyCode::KeyW)],
+            InputAction::TiltDown => vec![Key(KeyCode::KeyS)],
+            InputAction::ResetRotation => vec![Key(KeyCode::KeyR)],
+            InputAction::CycleCameraFocus => vec![Key(KeyCode::KeyF)],
+            InputAction::Presentation => vec![Key(KeyCode::F11)],
+            InputAction::ExitPresentation => vec![Key(KeyCode::Escape)],
+            InputAction::Screenshot => vec![Key(KeyCode::F12)],
+            InputAction::Fullscreen => vec![Key(KeyCode::F10)],
+            InputAction::ColumnLeft => vec![Key(KeyCode::ArrowLeft)],
+            InputAction::ColumnRight => vec![Key(KeyCode::ArrowRight)],
+            InputAction::ColumnUp => vec![Key(KeyCode::ArrowUp)],
+            InputAction::ColumnDown => vec![Key(KeyCode::ArrowDown)],
+            InputAction::DeselectColumn => vec![Key(KeyCode::Escape)],
+            InputAction::Digit(digit) => {
+                const ROW: [KeyCode; 10] = [
+                    KeyCode::Digit0, KeyCode::Digit1, KeyCode::Digit2, KeyCode::Digit3, KeyCode::Digit4,
+                    KeyCode::Digit5, KeyCode::Digit6, KeyCode::Digit7, KeyCode::Digit8, KeyCode::Digit9,
+                ];
+                const NUMPAD: [KeyCode; 10] = [
+                    KeyCode::Numpad0, KeyCode::Numpad1, KeyCode::Numpad2, KeyCode::Numpad3, KeyCode::Numpad4,
+                    KeyCode::Numpad5, KeyCode::Numpad6, KeyCode::Numpad7, KeyCode::Numpad8, KeyCode::Numpad9,
+                ];
+                let digit = usize::from(*digit).min(9);
+                vec![Key(ROW[digit]), Key(NUMPAD[digit])]
+            }
+            InputAction::EnterDigits => vec![Key(KeyCode::Enter), Key(KeyCode::NumpadEnter)],
+            InputAction::EraseDigit => vec![Key(KeyCode::Backspace)],
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InputBinding {
+    /// The key on its own, with neither Ctrl nor Cmd held.
+    Key(KeyCode),
+    /// The key with Ctrl (or Cmd) held.
+    CtrlKey(KeyCode),
+    Mouse(MouseButton),
+}
+
+impl InputBinding {
+    fn label(&self) -> String {
+        match self {
+            InputBinding::Key(key) => format!("{:?}", key),
+            InputBinding::CtrlKey(key) => format!("Ctrl+{:?}", key),
+            InputBinding::Mouse(button) => format!("{:?} mouse", button),
+        }
+    }
+}
+
+/// One action's bindings, as saved in the profile.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ActionBindings {
+    pub action: InputAction,
+    pub bindings: Vec<InputBinding>,
+}
+
+#[derive(Resource)]
+pub struct KeyBindings {
+    actions: Vec<ActionBindings>,
+    /// The action waiting for its next key or button press.
+    listening: Option<InputAction>,
+    /// Whether a text field has the keyboard, as of the last frame.
+    text_focused: bool,
+    pub changed: bool,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self::from_saved(Vec::new())
+    }
+}
+
+impl KeyBindings {
+    /// The defaults, with any saved actions' bindings in their place. Actions added since
+    /// the profile was saved keep their defaults.
+    pub fn from_saved(saved: Vec<ActionBindings>) -> Self {
+        let actions = InputAction::ALL.iter()
+            .map(|&action| saved.iter().find(|saved| saved.action == action).cloned()
+                .unwrap_or_else(|| ActionBindings { action, bindings: action.default_bindings() }))
+            .collect();
+        Self { actions, listening: None, text_focused: false, changed: false }
+    }
+
+    pub fn saved(&self) -> &Vec<ActionBindings> {
+        &self.actions
+    }
+
+    pub fn bindings(&self, action: InputAction) -> &[InputBinding] {
+        self.actions.iter().find(|entry| entry.action == action).map_or(&[], |entry| &entry.bindings)
+    }
+
+    fn bindings_mut(&mut self, action: InputAction) -> Option<&mut Vec<InputBinding>> {
+        self.actions.iter_mut().find(|entry| entry.action == action).map(|entry| &mut entry.bindings)
+    }
+}
+
+/// Asks whether an action's key or button is down, ignoring the keyboard while a text
+/// field has it or a binding is being changed.
+#[derive(SystemParam)]
+pub struct ActionInput<'w> {
+    bindings: Res<'w, KeyBindings>,
+    keys: Res<'w, ButtonInput<KeyCode>>,
+    mouse: Res<'w, ButtonInput<MouseButton>>,
+}
+
+impl ActionInput<'_> {
+    pub fn just_pressed(&self, action: InputAction) -> bool {
+        self.check(action, |keys, key| keys.just_pressed(key), |mouse, button| mouse.just_pressed(button))
+    }
+
+    pub fn pressed(&self, action: InputAction) -> bool {
+        self.check(action, |keys, key| keys.pressed(key), |mouse, button| mouse.pressed(button))
+    }
+
+    fn check(
+        &self,
+        action: InputAction,
+        key_down: impl Fn(&ButtonInput<KeyCode>, KeyCode) -> bool,
+        button_down: impl Fn(&ButtonInput<MouseButton>, MouseButton) -> bool,
+    ) -> bool {
+        if self.bindings.listening.is_some() {
+            return false;
+        }
+        let ctrl = self.keys.any_pressed(CONTROL_KEYS);
+        self.bindings.bindings(action).iter().any(|binding| match *binding {
+            InputBinding::Key(key) => !self.bindings.text_focused && !ctrl && key_down(&self.keys, key),
+            InputBinding::CtrlKey(key) => !self.bindings.text_focused && ctrl && key_down(&self.keys, key),
+            InputBinding::Mouse(button) => button_down(&self.mouse, button),
+        })
+    }
+}
+
+pub struct KeyBindingsPlugin;
+
+impl Plugin for KeyBindingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<KeyBindings>()
+            .add_systems(Update, keybindings_ui_system)
+            // After this frame's shortcuts have been skipped, so the captured press doesn't also trigger one
+            .add_systems(PostUpdate, (capture_binding, track_text_focus));
+    }
+}
+
+fn keybindings_ui_system(
+    mut contexts: EguiContexts,
+    mut bindings: ResMut<KeyBindings>,
+    localization: Res<Localization>,
+) {
+    let localization = &*localization;
+    egui::Window::new(tr!(localization, "keybindings-window"))
+        .id(egui::Id::new("keybindings"))
+        .default_pos([10.0, 1360.0])
+        .default_open(false)
+        .vscroll(true)
+        .show(contexts.ctx_mut(), |ui| {
+            egui::Grid::new("keybindings_grid").num_columns(2).striped(true).show(ui, |ui| {
+                for action in InputAction::ALL {
+                    ui.label(action.label(localization));
+                    ui.horizontal(|ui| {
+                        let mut removed = None;
+                        for (i, binding) in bindings.bindings(action).iter().enumerate() {
+                            if ui.button(format!("{} ✖", binding.label()))
+                                .on_hover_text(tr!(localization, "keybindings-remove"))
+                                .clicked()
+                            {
+                                removed = Some(i);
+                            }
+                        }
+                        if let Some(i) = removed {
+                            if let Some(list) = bindings.bindings_mut(action) {
+                                list.remove(i);
+                                bindings.changed = true;
+                            }
+                        }
+                        if bindings.listening == Some(action) {
+                            ui.label(tr!(localization, "keybindings-press-key"));
+                            if ui.button(tr!(localization, "keybindings-cancel")).clicked() {
+                                bindings.listening = None;
+                            }
+                        } else if ui.button("＋").on_hover_text(tr!(localization, "keybindings-add")).clicked() {
+                            bindings.listening = Some(action);
+                        }
+                    });
+                    ui.end_row();
+                }
+            });
+            ui.separator();
+            if ui.button(tr!(localization, "keybindings-reset")).clicked() {
+                *bindings = KeyBindings { changed: true, ..KeyBindings::default() };
+            }
+        });
+}
+
+/// Takes the next key (with Ctrl if held) or mouse button pressed as a new binding for
+/// the action being changed. The left button is left to the interface.
+fn capture_binding(
+    mut bindings: ResMut<KeyBindings>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+) {
+    let Some(action) = bindings.listening else {
+        return;
+    };
+    let ctrl = keys.any_pressed(CONTROL_KEYS);
+    let binding = keys.get_just_pressed()
+        .find(|key| !MODIFIER_KEYS.contains(key))
+        .map(|&key| if ctrl { InputBinding::CtrlKey(key) } else { InputBinding::Key(key) })
+        .or_else(|| mouse.get_just_pressed().find(|&&button| button != MouseButton::Left).map(|&button| InputBinding::Mouse(button)));
+    let Some(binding) = binding else {
+        return;
+    };
+    if let Some(list) = bindings.bindings_mut(action) {
+        if !list.contains(&binding) {
+            list.push(binding);
+        }
+    }
+    bindings.listening = None;
+    bindings.changed = true;
+}
+
+fn track_text_focus(mut contexts: EguiContexts, mut bindings: ResMut<KeyBindings>) {
+    let focused = contexts.ctx_mut().wants_keyboard_input();
+    // Only on a change, so change detection stays meaningful
+    if bindings.text_focused != focused {
+        bindings.text_focused = focused;
+    }
+}
@@ -0,0 +1,83 @@
+//! Kiosk mode for exhibits, started with `--kiosk`: the app stays in presentation mode
+//! with the panels out of reach, and after `--kiosk-idle` seconds without a touch it plays
+//! the attract animation. The next touch stops it and clears the abaci for the visitor.
+
+use bevy::input::mouse::MouseWheel;
+use bevy::prelude::*;
+
+use crate::attract::Attract;
+use crate::localization::*;
+use crate::operations::ClearRequested;
+use crate::startup::StartupOptions;
+use crate::AbacusSettings;
+
+/// Seconds without a touch before the attract animation starts, unless `--kiosk-idle` says.
+pub const DEFAULT_KIOSK_IDLE_SECONDS: u64 = 60;
+
+#[derive(Resource, Default)]
+pub struct Kiosk {
+    pub enabled: bool,
+    idle_seconds: f32,
+    /// Seconds since the last touch, click or key.
+    idle: f32,
+}
+
+pub struct KioskPlugin;
+
+impl Plugin for KioskPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Kiosk>()
+            .add_systems(Startup, start_kiosk)
+            .add_systems(Update, watch_kiosk_idle);
+    }
+}
+
+fn start_kiosk(
+    options: Res<StartupOptions>,
+    mut kiosk: ResMut<Kiosk>,
+    mut settings: ResMut<AbacusSettings>,
+    localization: Res<Localization>,
+) {
+    if !options.kiosk {
+        return;
+    }
+    let idle_seconds = options.kiosk_idle.unwrap_or(DEFAULT_KIOSK_IDLE_SECONDS).max(1);
+    kiosk.enabled = true;
+    kiosk.idle_seconds = idle_seconds as f32;
+    settings.presentation_mode = true;
+    info!("{}", tr!(localization, "log-kiosk-started", seconds = idle_seconds));
+}
+
+/// Starts the attract animation once nobody has touched the abacus for a while, and on
+/// the next touch stops it and clears every abacus.
+fn watch_kiosk_idle(
+    mut kiosk: ResMut<Kiosk>,
+    mut attract: ResMut<Attract>,
+    time: Res<Time>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    touches: Res<Touches>,
+    mut wheel: EventReader<MouseWheel>,
+    mut clear_requests: EventWriter<ClearRequested>,
+) {
+    if !kiosk.enabled {
+        return;
+    }
+    let scrolled = wheel.read().count() > 0;
+    let touched = scrolled
+        || mouse_buttons.get_just_pressed().next().is_some()
+        || keys.get_just_pressed().next().is_some()
+        || touches.any_just_pressed();
+    if touched {
+        kiosk.idle = 0.0;
+        if attract.playing {
+            attract.playing = false;
+            clear_requests.write(ClearRequested { abacus: None });
+        }
+        return;
+    }
+    kiosk.idle += time.delta_secs();
+    if kiosk.idle >= kiosk.idle_seconds && !attract.playing {
+        attract.start(time.elapsed().as_nanos() as u64);
+    }
+}
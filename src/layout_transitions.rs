@@ -0,0 +1,272 @@
+//! Structure changes that animate rather than pop: when the selected abacus is rebuilt,
+//! the parts that are still there slide from where they were to where the new layout puts
+//! them, and parts that are new fade in. Columns spliced in and out move the same way, as
+//! do the parts of every abacus when the spacing and size settings change.
+
+use bevy::color::Alpha;
+use bevy::prelude::*;
+use bevy::transform::TransformSystem;
+
+use crate::abacus::*;
+use crate::scene_layout::Billboard;
+use crate::wood_grain::AbacusMaterial;
+use crate::AbacusSettings;
+
+/// How quickly parts slide to their new places; higher is snappier.
+const SLIDE_SPEED: f32 = 12.0;
+/// Seconds a new part takes to fade in.
+const FADE_SECONDS: f32 = 0.4;
+
+/// Moves part of an abacus to a new place in its frame over a few frames.
+#[derive(Component)]
+pub struct Slide {
+    pub target: Transform,
+}
+
+/// Fades a newly spawned part of an abacus in. Beads and rods share their materials, so
+/// each fading part is drawn with a copy until it is fully shown.
+#[derive(Component, Default)]
+pub struct FadeIn {
+    elapsed: f32,
+    started: bool,
+    /// Each faded mesh and the material it goes back to.
+    parts: Vec<(Entity, Handle<AbacusMaterial>)>,
+    /// Each material the part is drawn with, its fading copy, and the alpha it ends at.
+    copies: Vec<(Handle<AbacusMaterial>, Handle<AbacusMaterial>, f32)>,
+    text_color: Option<Color>,
+}
+
+/// An abacus that has just been rebuilt, kept hidden for a frame so the parts of its
+/// replacement can start where its own parts were.
+#[derive(Component)]
+pub struct Replaced {
+    pub by: Entity,
+    pub longs: Vec<Vec<Entity>>,
+    pub column_texts: Vec<Entity>,
+    pub total_text: Entity,
+    pub words_text: Entity,
+}
+
+impl Replaced {
+    /// Takes the old abacus out of the scene, leaving its parts in place until `by` is
+    /// laid out from them.
+    pub fn mark(commands: &mut Commands, entity: Entity, abacus: &Abacus, by: Entity) {
+        commands.entity(entity)
+            .remove::<(Abacus, crate::AbacusStructure)>()
+            .insert((
+                Replaced {
+                    by,
+                    longs: abacus.longs.clone(),
+                    column_texts: abacus.column_texts.clone(),
+                    total_text: abacus.total_text,
+                    words_text: abacus.words_text,
+                },
+                Visibility::Hidden,
+            ));
+    }
+}
+
+pub struct LayoutTransitionsPlugin;
+
+impl Plugin for LayoutTransitionsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (apply_proportions.run_if(resource_changed::<AbacusSettings>), slide_parts, fade_in_parts).chain())
+            // Rebuilds are spawned during Update, so their replacements exist by now
+            .add_systems(PostUpdate, carry_over_layout.before(TransformSystem::TransformPropagate));
+    }
+}
+
+/// Starts each part of a rebuilt abacus where its counterpart was, sliding it to its new
+/// place, and fades in the parts that have no counterpart.
+fn carry_over_layout(
+    replaced_query: Query<(Entity, &Replaced, &Transform)>,
+    abacus_query: Query<&Abacus>,
+    mut transform_query: Query<&mut Transform, Without<Replaced>>,
+    mut commands: Commands,
+) {
+    for (entity, replaced, old_transform) in &replaced_query {
+        commands.entity(entity).despawn();
+        let Ok(abacus) = abacus_query.get(replaced.by) else {
+            continue;
+        };
+        // The new abacus keeps the old one's turn
+        if let Ok(mut transform) = transform_query.get_mut(replaced.by) {
+            transform.rotation = old_transform.rotation;
+        }
+        for (column, longs) in abacus.longs.iter().enumerate() {
+            let old_longs = replaced.longs.get(column).map(Vec::as_slice).unwrap_or_default();
+            for (deck, &long) in longs.iter().enumerate() {
+                // Decks are matched from the bottom, since extra decks stack on top
+                let old_long = old_longs.len().checked_sub(longs.len() - deck).map(|old_deck| old_longs[old_deck]);
+                carry_over(long, old_long, &mut transform_query, &mut commands);
+            }
+        }
+        for (column, &text) in abacus.column_texts.iter().enumerate() {
+            carry_over(text, replaced.column_texts.get(column).copied(), &mut transform_query, &mut commands);
+        }
+        carry_over(abacus.total_text, Some(replaced.total_text), &mut transform_query, &mut commands);
+        carry_over(abacus.words_text, Some(replaced.words_text), &mut transform_query, &mut commands);
+    }
+}
+
+fn carry_over(
+    part: Entity,
+    old_part: Option<Entity>,
+    transform_query: &mut Query<&mut Transform, Without<Replaced>>,
+    commands: &mut Commands,
+) {
+    let start = old_part.and_then(|old_part| transform_query.get(old_part).ok()).copied();
+    let Ok(mut transform) = transform_query.get_mut(part) else {
+        return;
+    };
+    match start {
+        Some(start) => {
+            commands.entity(part).insert(Slide { target: *transform });
+            *transform = start;
+        }
+        None => {
+            commands.entity(part).insert(FadeIn::default());
+        }
+    }
+}
+
+/// Slides each long and text of `abacus` to where its layout puts it.
+pub fn slide_into_place(commands: &mut Commands, abacus: &Abacus, settings: &AbacusSettings) {
+    let rotation = abacus.rod_rotation();
+    let text_scale = Vec3::new(-0.01, 0.01, 0.01);
+    for (column, longs) in abacus.longs.iter().enumerate() {
+        for (deck, &long) in longs.iter().enumerate() {
+            let target = Transform::from_translation(abacus.long_translation(column, deck)).with_rotation(rotation);
+            commands.entity(long).insert(Slide { target });
+        }
+    }
+    for (column, &text) in abacus.column_texts.iter().enumerate() {
+        let target = Transform::from_translation(abacus.column_text_translation(column)).with_scale(text_scale);
+        commands.entity(text).insert(Slide { target });
+    }
+    let words_y = abacus.words_y() + settings.title_lift();
+    commands.entity(abacus.words_text).insert(Slide { target: Transform::from_xyz(0.0, words_y, 0.0).with_scale(text_scale) });
+    commands.entity(abacus.total_text).insert(Slide { target: Transform::from_xyz(0.0, words_y + settings.total_text_lift(), 0.0).with_scale(text_scale) });
+}
+
+/// Moves every abacus to the spacing and size in the settings. Rods stretch and slide to
+/// their new places and beads follow them; `move_all_abacus_beads` spaces the beads out.
+fn apply_proportions(
+    settings: Res<AbacusSettings>,
+    mut abacus_query: Query<(&mut Abacus, &crate::AbacusStructure, &mut Transform)>,
+    children_query: Query<&Children>,
+    rod_query: Query<(), (With<Mesh3d>, Without<AbacusBead>)>,
+    mut bead_query: Query<&mut Transform, (With<AbacusBead>, Without<Abacus>)>,
+    mut commands: Commands,
+) {
+    let proportions = settings.proportions;
+    for (mut abacus, structure, mut transform) in &mut abacus_query {
+        if abacus.proportions == proportions {
+            continue;
+        }
+        abacus.proportions = proportions;
+        transform.scale = Vec3::splat(proportions.model_scale);
+        slide_into_place(&mut commands, &abacus, &settings);
+        for (layout, longs) in abacus.columns.iter().zip(&abacus.longs) {
+            for (deck, &long) in longs.iter().enumerate() {
+                for &part in children_query.get(long).map(|children| &children[..]).unwrap_or_default() {
+                    if let Ok(mut bead_transform) = bead_query.get_mut(part) {
+                        bead_transform.scale = proportions.bead_scale();
+                    } else if rod_query.contains(part) {
+                        let target = rod_transform(layout.deck(deck).bead_count, &proportions, structure.grooved);
+                        commands.entity(part).insert(Slide { target });
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn slide_parts(
+    time: Res<Time>,
+    settings: Res<AbacusSettings>,
+    mut query: Query<(Entity, &mut Transform, &Slide, Has<Billboard>)>,
+    mut commands: Commands,
+) {
+    let follow = if settings.reduced_motion {
+        1.0
+    } else {
+        1.0 - (-SLIDE_SPEED * time.delta_secs()).exp()
+    };
+    for (entity, mut transform, slide, is_billboard) in &mut query {
+        let mut target = slide.target;
+        // Texts are turned to the camera every frame instead
+        if is_billboard {
+            target.rotation = transform.rotation;
+        }
+        transform.translation = transform.translation.lerp(target.translation, follow);
+        transform.rotation = transform.rotation.slerp(target.rotation, follow);
+        transform.scale = transform.scale.lerp(target.scale, follow);
+        if transform.translation.distance(target.translation) < 0.001 && transform.rotation.angle_between(target.rotation) < 0.001 {
+            *transform = target;
+            commands.entity(entity).remove::<Slide>();
+        }
+    }
+}
+
+fn fade_in_parts(
+    time: Res<Time>,
+    settings: Res<AbacusSettings>,
+    mut query: Query<(Entity, &mut FadeIn, Option<&Children>, Option<&mut TextColor>)>,
+    mut mesh_materials: Query<&mut MeshMaterial3d<AbacusMaterial>>,
+    mut materials: ResMut<Assets<AbacusMaterial>>,
+    mut commands: Commands,
+) {
+    for (entity, mut fade, children, mut text_color) in &mut query {
+        if !fade.started {
+            fade.started = true;
+            fade.text_color = text_color.as_ref().map(|color| color.0);
+            // One copy per material the part is drawn with, not one per bead
+            for &child in children.map(|children| &children[..]).unwrap_or_default() {
+                let Ok(mut material) = mesh_materials.get_mut(child) else {
+                    continue;
+                };
+                let original = material.0.clone();
+                let copy = match fade.copies.iter().find(|(from, _, _)| *from == original) {
+                    Some((_, copy, _)) => copy.clone(),
+                    None => {
+                        let Some(mut faded) = materials.get(&original).cloned() else {
+                            continue;
+                        };
+                        let alpha = faded.base.base_color.alpha();
+                        faded.base.alpha_mode = AlphaMode::Blend;
+                        faded.base.base_color.set_alpha(0.0);
+                        let copy = materials.add(faded);
+                        fade.copies.push((original.clone(), copy.clone(), alpha));
+                        copy
+                    }
+                };
+                material.0 = copy;
+                fade.parts.push((child, original));
+            }
+        }
+
+        fade.elapsed += time.delta_secs();
+        let shown = if settings.reduced_motion { 1.0 } else { (fade.elapsed / FADE_SECONDS).min(1.0) };
+        for (_, copy, alpha) in &fade.copies {
+            if let Some(material) = materials.get_mut(copy) {
+                material.base.base_color.set_alpha(alpha * shown);
+            }
+        }
+        if let (Some(text_color), Some(from)) = (text_color.as_mut(), fade.text_color) {
+            text_color.0 = from.with_alpha(from.alpha() * shown);
+        }
+
+        if shown >= 1.0 {
+            // Parts drawn with something else in the meantime, like a hover, keep it
+            for (part, original) in &fade.parts {
+                if let Ok(mut material) = mesh_materials.get_mut(*part) {
+                    if fade.copies.iter().any(|(_, copy, _)| *copy == material.0) {
+                        material.0 = original.clone();
+                    }
+                }
+            }
+            commands.entity(entity).remove::<FadeIn>();
+        }
+    }
+}
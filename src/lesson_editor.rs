@@ -0,0 +1,179 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::abacus::*;
+use crate::lesson_files::{LessonFile, LessonFileStep};
+use crate::localization::*;
+use crate::scene_layout::SelectedAbacus;
+
+/// A lesson being recorded: the teacher moves beads, then annotates and keeps each step.
+#[derive(Resource, Default)]
+pub struct LessonRecorder {
+    /// The abacus being recorded from; `None` when not recording.
+    abacus: Option<Entity>,
+    title: String,
+    base: u64,
+    steps: Vec<LessonFileStep>,
+    /// Column values when the last step was kept, to tell which columns the next one touches.
+    previous_columns: Vec<u64>,
+    step_text: String,
+    /// One hint per line.
+    step_hints: String,
+    status: Option<String>,
+}
+
+impl LessonRecorder {
+    fn start(&mut self, abacus_entity: Entity, abacus: &Abacus, long_query: &Query<&AbacusLong>) {
+        self.abacus = Some(abacus_entity);
+        self.base = abacus.abacus_base;
+        self.steps.clear();
+        self.previous_columns = column_values(abacus, long_query);
+        self.step_text.clear();
+        self.step_hints.clear();
+        self.status = None;
+    }
+
+    /// Keeps the abacus's current value as a step, highlighting the columns moved since the last one.
+    fn keep_step(&mut self, abacus: &Abacus, long_query: &Query<&AbacusLong>) {
+        let columns = column_values(abacus, long_query);
+        let highlight_columns = columns.iter().enumerate()
+            .filter(|&(i, value)| self.previous_columns.get(i) != Some(value))
+            .map(|(i, _)| i)
+            .collect();
+        self.steps.push(LessonFileStep {
+            text: std::mem::take(&mut self.step_text).trim().to_string(),
+            target: abacus.total_value,
+            highlight_columns,
+            hints: self.step_hints.lines().map(str::trim).filter(|hint| !hint.is_empty()).map(str::to_string).collect(),
+        });
+        self.step_hints.clear();
+        self.previous_columns = columns;
+    }
+
+    fn lesson_file(&self) -> LessonFile {
+        LessonFile {
+            title: self.title.trim().to_string(),
+            base: Some(self.base),
+            steps: self.steps.clone(),
+        }
+    }
+}
+
+fn column_values(abacus: &Abacus, long_query: &Query<&AbacusLong>) -> Vec<u64> {
+    (0..abacus.longs.len()).map(|i| abacus.get_column_value(i, long_query)).collect()
+}
+
+fn lesson_ron(lesson: &LessonFile) -> Result<String, String> {
+    ron::ser::to_string_pretty(lesson, ron::ser::PrettyConfig::default()).map_err(|error| error.to_string())
+}
+
+/// Writes the lesson next to the bundled ones, where the lesson loader finds it on the next start.
+#[cfg(not(target_arch = "wasm32"))]
+fn export_lesson(lesson: &LessonFile) -> Result<String, String> {
+    let file_name: String = lesson.title.chars()
+        .map(|c| if c.is_alphanumeric() { c.to_ascii_lowercase() } else { '-' })
+        .collect();
+    let path = std::path::Path::new("assets/lessons").join(format!("{}.lesson.ron", file_name.trim_matches('-')));
+    std::fs::create_dir_all("assets/lessons").map_err(|error| error.to_string())?;
+    std::fs::write(&path, lesson_ron(lesson)?).map_err(|error| error.to_string())?;
+    Ok(path.display().to_string())
+}
+
+pub fn lesson_editor_ui_system(
+    mut contexts: EguiContexts,
+    mut recorder: ResMut<LessonRecorder>,
+    abacus_query: Query<&Abacus>,
+    long_query: Query<&AbacusLong>,
+    selected: Res<SelectedAbacus>,
+    localization: Res<Localization>,
+) {
+    let localization = &*localization;
+    let recorder = &mut *recorder;
+    let ctx = contexts.ctx_mut();
+
+    // The abacus was removed or rebuilt while recording
+    let recording = recorder.abacus.and_then(|entity| abacus_query.get(entity).ok());
+    if recorder.abacus.is_some() && recording.is_none() {
+        recorder.abacus = None;
+        recorder.status = Some(tr!(localization, "editor-abacus-gone"));
+    }
+
+    egui::Window::new(tr!(localization, "editor-window"))
+        .id(egui::Id::new("lesson_editor"))
+        .default_pos([10.0, 580.0])
+        .default_open(false)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                let title_label = ui.label(tr!(localization, "editor-title"));
+                ui.text_edit_singleline(&mut recorder.title).labelled_by(title_label.id);
+            });
+
+            match recording {
+                None => {
+                    ui.label(tr!(localization, "editor-intro"));
+                    let selected_abacus = selected.0.and_then(|entity| abacus_query.get(entity).ok().map(|abacus| (entity, abacus)));
+                    if ui.add_enabled(selected_abacus.is_some(), egui::Button::new(tr!(localization, "editor-start"))).clicked() {
+                        if let Some((entity, abacus)) = selected_abacus {
+                            recorder.start(entity, abacus, &long_query);
+                        }
+                    }
+                }
+                Some(abacus) => {
+                    ui.label(tr!(localization, "editor-current-value", value = abacus.total_value));
+                    ui.label(tr!(localization, "editor-step-text"));
+                    ui.add(egui::TextEdit::multiline(&mut recorder.step_text).desired_rows(2));
+                    ui.label(tr!(localization, "editor-step-hints"));
+                    ui.add(egui::TextEdit::multiline(&mut recorder.step_hints).desired_rows(1));
+                    if ui.add_enabled(!recorder.step_text.trim().is_empty(), egui::Button::new(tr!(localization, "editor-keep-step"))).clicked() {
+                        recorder.keep_step(abacus, &long_query);
+                    }
+
+                    ui.separator();
+                    let mut step_to_remove = None;
+                    egui::ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+                        for (i, step) in recorder.steps.iter_mut().enumerate() {
+                            ui.horizontal(|ui| {
+                                ui.label(format!("{}. → {}", i + 1, step.target));
+                                ui.text_edit_singleline(&mut step.text);
+                                if ui.small_button("✖").on_hover_text(tr!(localization, "remove")).clicked() {
+                                    step_to_remove = Some(i);
+                                }
+                            });
+                        }
+                    });
+                    if let Some(i) = step_to_remove {
+                        recorder.steps.remove(i);
+                    }
+
+                    ui.separator();
+                    let exportable = !recorder.steps.is_empty() && !recorder.title.trim().is_empty();
+                    ui.horizontal(|ui| {
+                        #[cfg(not(target_arch = "wasm32"))]
+                        if ui.add_enabled(exportable, egui::Button::new(tr!(localization, "editor-export"))).clicked() {
+                            recorder.status = Some(match export_lesson(&recorder.lesson_file()) {
+                                Ok(path) => tr!(localization, "editor-exported", path = path),
+                                Err(error) => tr!(localization, "editor-export-failed", error = error),
+                            });
+                        }
+                        // The web build can't write files, so the lesson is handed over through the clipboard
+                        if ui.add_enabled(exportable, egui::Button::new(tr!(localization, "editor-copy"))).clicked() {
+                            recorder.status = Some(match lesson_ron(&recorder.lesson_file()) {
+                                Ok(text) => {
+                                    ui.ctx().copy_text(text);
+                                    tr!(localization, "editor-copied")
+                                }
+                                Err(error) => tr!(localization, "editor-export-failed", error = error),
+                            });
+                        }
+                        if ui.button(tr!(localization, "editor-stop")).clicked() {
+                            recorder.abacus = None;
+                        }
+                    });
+                }
+            }
+
+            if let Some(status) = &recorder.status {
+                ui.label(status);
+            }
+        });
+}
@@ -0,0 +1,136 @@
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext, LoadedFolder};
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::lessons::{Lesson, LessonStep};
+
+/// Folder under `assets/` scanned for lesson files.
+const LESSON_FOLDER: &str = "lessons";
+
+/// One step of a lesson file, as a teacher writes it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LessonFileStep {
+    pub text: String,
+    /// The total the abacus should show once the step is done.
+    pub target: u64,
+    /// Columns to draw attention to, ones column first.
+    #[serde(default)]
+    pub highlight_columns: Vec<usize>,
+    /// Hints shown beside the explanation.
+    #[serde(default)]
+    pub hints: Vec<String>,
+}
+
+/// A lesson authored as a `.lesson.ron` or `.lesson.json` file under `assets/lessons/`,
+/// so teachers can write content without recompiling.
+#[derive(Asset, TypePath, Clone, Debug, Serialize, Deserialize)]
+pub struct LessonFile {
+    pub title: String,
+    /// The base the targets assume; the lesson refuses to start on an abacus counting in another.
+    #[serde(default)]
+    pub base: Option<u64>,
+    pub steps: Vec<LessonFileStep>,
+}
+
+impl LessonFile {
+    pub fn to_lesson(&self) -> Lesson {
+        Lesson {
+            title: self.title.clone(),
+            steps: self.steps.iter().map(|step| LessonStep {
+                text: step.text.clone(),
+                total_value: step.target,
+                highlight_columns: step.highlight_columns.clone(),
+                details: step.hints.clone(),
+            }).collect(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum LessonFileError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+    Json(serde_json::Error),
+    NoSteps,
+}
+
+impl fmt::Display for LessonFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LessonFileError::Io(error) => write!(f, "couldn't read lesson file: {}", error),
+            LessonFileError::Ron(error) => write!(f, "invalid RON lesson: {}", error),
+            LessonFileError::Json(error) => write!(f, "invalid JSON lesson: {}", error),
+            LessonFileError::NoSteps => write!(f, "lesson has no steps"),
+        }
+    }
+}
+
+impl std::error::Error for LessonFileError {}
+
+#[derive(Default)]
+pub struct LessonFileLoader;
+
+impl AssetLoader for LessonFileLoader {
+    type Asset = LessonFile;
+    type Settings = ();
+    type Error = LessonFileError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<LessonFile, LessonFileError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await.map_err(LessonFileError::Io)?;
+
+        let is_ron = load_context.path().extension().is_some_and(|extension| extension == "ron");
+        let lesson: LessonFile = if is_ron {
+            ron::de::from_bytes(&bytes).map_err(LessonFileError::Ron)?
+        } else {
+            serde_json::from_slice(&bytes).map_err(LessonFileError::Json)?
+        };
+        if lesson.steps.is_empty() {
+            return Err(LessonFileError::NoSteps);
+        }
+        Ok(lesson)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["lesson.ron", "lesson.json"]
+    }
+}
+
+/// Keeps the lesson folder loaded so its files can be listed in the Lessons window.
+#[derive(Resource)]
+pub struct LessonLibrary(Handle<LoadedFolder>);
+
+pub fn load_lesson_library(asset_server: Res<AssetServer>, mut commands: Commands) {
+    commands.insert_resource(LessonLibrary(asset_server.load_folder(LESSON_FOLDER)));
+}
+
+/// The lesson files found so far, for listing in the Lessons window.
+#[derive(SystemParam)]
+pub struct LessonFiles<'w> {
+    library: Option<Res<'w, LessonLibrary>>,
+    folders: Res<'w, Assets<LoadedFolder>>,
+    files: Res<'w, Assets<LessonFile>>,
+}
+
+impl LessonFiles<'_> {
+    /// Loaded lessons, sorted by title.
+    pub fn lessons(&self) -> Vec<&LessonFile> {
+        let Some(folder) = self.library.as_ref().and_then(|library| self.folders.get(&library.0)) else {
+            return Vec::new();
+        };
+        let mut lessons: Vec<&LessonFile> = folder.handles.iter()
+            .filter_map(|handle| handle.clone().try_typed::<LessonFile>().ok())
+            .filter_map(|handle| self.files.get(&handle))
+            .collect();
+        lessons.sort_by(|a, b| a.title.cmp(&b.title));
+        lessons
+    }
+}
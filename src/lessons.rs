@@ -0,0 +1,562 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use std::ops::Range;
+
+use crate::abacus::*;
+use crate::operations::ColumnOpQueue;
+use crate::{format_number_in_base, AbacusSettings};
+use crate::localization::*;
+use crate::lesson_files::LessonFiles;
+use crate::scene_layout::SelectedAbacus;
+
+/// One step of a scripted lesson: the value the abacus should show afterwards,
+/// which columns to draw attention to, and the explanation shown to the student.
+#[derive(Clone, Debug)]
+pub struct LessonStep {
+    pub text: String,
+    pub total_value: u64,
+    pub highlight_columns: Vec<usize>,
+    /// Running figures (e.g. the remainder) shown in a side panel next to the explanation.
+    pub details: Vec<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Lesson {
+    pub title: String,
+    pub steps: Vec<LessonStep>,
+}
+
+/// A contiguous block of columns assigned to one operand. `start` is the
+/// least significant column of the block.
+#[derive(Clone, Copy, Debug)]
+pub struct ColumnRegion {
+    pub start: usize,
+    pub width: usize,
+}
+
+impl ColumnRegion {
+    pub fn columns(&self) -> Range<usize> {
+        self.start..self.start + self.width
+    }
+
+    /// The abacus total contribution of `value` placed in this region.
+    pub fn place(&self, value: u64, base: u64) -> u64 {
+        value * base.pow(self.start as u32)
+    }
+}
+
+/// Splits `value` into its digits in `base`, least significant first.
+fn digits_in_base(value: u64, base: u64) -> Vec<u64> {
+    let mut digits = Vec::new();
+    let mut n = value;
+    loop {
+        digits.push(n % base);
+        n /= base;
+        if n == 0 {
+            break;
+        }
+    }
+    digits
+}
+
+/// Builds the classical multiplication-on-the-board walkthrough. The multiplicand
+/// and multiplier sit on the left, separated by an empty column, and partial
+/// products are accumulated on the right-hand (least significant) columns.
+pub fn multiplication_lesson(
+    multiplicand: u64,
+    multiplier: u64,
+    base: u64,
+    column_count: usize,
+    localization: &Localization,
+) -> Result<Lesson, String> {
+    let multiplicand_digits = digits_in_base(multiplicand, base);
+    let multiplier_digits = digits_in_base(multiplier, base);
+
+    let product_region = ColumnRegion { start: 0, width: multiplicand_digits.len() + multiplier_digits.len() };
+    let multiplier_region = ColumnRegion { start: product_region.width + 1, width: multiplier_digits.len() };
+    let multiplicand_region = ColumnRegion { start: multiplier_region.start + multiplier_region.width + 1, width: multiplicand_digits.len() };
+
+    let required_columns = multiplicand_region.start + multiplicand_region.width;
+    if required_columns > column_count {
+        return Err(tr!(localization, "lesson-error-columns", required = required_columns, available = column_count));
+    }
+    if base.checked_pow(required_columns as u32).is_none() {
+        return Err(tr!(localization, "lesson-error-too-large"));
+    }
+
+    let fmt = |value: u64| format_number_in_base(value, base);
+    let operands_total = multiplicand_region.place(multiplicand, base) + multiplier_region.place(multiplier, base);
+    let mut steps = vec![
+        LessonStep {
+            text: tr!(localization, "multiplication-set-multiplicand", multiplicand = fmt(multiplicand)),
+            total_value: multiplicand_region.place(multiplicand, base),
+            highlight_columns: multiplicand_region.columns().collect(),
+            details: Vec::new(),
+        },
+        LessonStep {
+            text: tr!(localization, "multiplication-set-multiplier", multiplier = fmt(multiplier)),
+            total_value: operands_total,
+            highlight_columns: multiplier_region.columns().collect(),
+            details: Vec::new(),
+        },
+    ];
+
+    let mut product = 0;
+    for (i, &a) in multiplicand_digits.iter().enumerate().rev() {
+        for (j, &b) in multiplier_digits.iter().enumerate().rev() {
+            let partial = a * b;
+            let place = i + j;
+            product += partial * base.pow(place as u32);
+            steps.push(LessonStep {
+                text: tr!(localization, "multiplication-partial",
+                    a = fmt(a), b = fmt(b), partial = fmt(partial), place = place + 1,
+                ),
+                total_value: operands_total + product_region.place(product, base),
+                highlight_columns: vec![multiplicand_region.start + i, multiplier_region.start + j, place, place + 1],
+                details: Vec::new(),
+            });
+        }
+    }
+
+    steps.push(LessonStep {
+        text: tr!(localization, "multiplication-done", multiplicand = fmt(multiplicand), multiplier = fmt(multiplier), product = fmt(product)),
+        total_value: operands_total + product_region.place(product, base),
+        highlight_columns: product_region.columns().collect(),
+        details: Vec::new(),
+    });
+
+    Ok(Lesson {
+        title: tr!(localization, "multiplication-title", multiplicand = fmt(multiplicand), multiplier = fmt(multiplier)),
+        steps,
+    })
+}
+
+/// Builds the traditional short-division walkthrough. The divisor sits on the left,
+/// the quotient is built up in the middle, and the dividend on the right is worn
+/// down to the remainder by one trial quotient and subtraction per digit.
+pub fn division_lesson(
+    dividend: u64,
+    divisor: u64,
+    base: u64,
+    column_count: usize,
+    localization: &Localization,
+) -> Result<Lesson, String> {
+    if divisor == 0 {
+        return Err(tr!(localization, "lesson-error-divide-by-zero"));
+    }
+
+    let dividend_digits = digits_in_base(dividend, base);
+    let divisor_digits = digits_in_base(divisor, base);
+
+    let working_region = ColumnRegion { start: 0, width: dividend_digits.len() };
+    let quotient_region = ColumnRegion { start: working_region.width + 1, width: dividend_digits.len() };
+    let divisor_region = ColumnRegion { start: quotient_region.start + quotient_region.width + 1, width: divisor_digits.len() };
+
+    let required_columns = divisor_region.start + divisor_region.width;
+    if required_columns > column_count {
+        return Err(tr!(localization, "lesson-error-columns", required = required_columns, available = column_count));
+    }
+    if base.checked_pow(required_columns as u32).is_none() {
+        return Err(tr!(localization, "lesson-error-too-large"));
+    }
+
+    let fmt = |value: u64| format_number_in_base(value, base);
+    let divisor_total = divisor_region.place(divisor, base);
+    let mut steps = vec![
+        LessonStep {
+            text: tr!(localization, "division-set-divisor", divisor = fmt(divisor)),
+            total_value: divisor_total,
+            highlight_columns: divisor_region.columns().collect(),
+            details: Vec::new(),
+        },
+        LessonStep {
+            text: tr!(localization, "division-set-dividend", dividend = fmt(dividend)),
+            total_value: divisor_total + working_region.place(dividend, base),
+            highlight_columns: working_region.columns().collect(),
+            details: Vec::new(),
+        },
+    ];
+
+    let mut working = dividend;
+    let mut quotient = 0;
+    for place in (0..dividend_digits.len()).rev() {
+        let place_value = base.pow(place as u32);
+        let prefix = working / place_value;
+        let trial_quotient = prefix / divisor;
+        if trial_quotient == 0 {
+            continue;
+        }
+
+        // Columns of the working region that the current prefix occupies
+        let prefix_columns: Vec<usize> = (place..working_region.width).collect();
+
+        quotient += trial_quotient * place_value;
+        steps.push(LessonStep {
+            text: tr!(localization, "division-trial",
+                divisor = fmt(divisor), prefix = fmt(prefix), quotient = fmt(trial_quotient), times = trial_quotient,
+            ),
+            total_value: divisor_total + quotient_region.place(quotient, base) + working_region.place(working, base),
+            highlight_columns: [prefix_columns.clone(), vec![quotient_region.start + place]].concat(),
+            details: Vec::new(),
+        });
+
+        let subtrahend = trial_quotient * divisor;
+        working -= subtrahend * place_value;
+        steps.push(LessonStep {
+            text: tr!(localization, "division-subtract",
+                quotient = fmt(trial_quotient), divisor = fmt(divisor), subtrahend = fmt(subtrahend),
+                prefix = fmt(prefix), difference = fmt(prefix - subtrahend),
+            ),
+            total_value: divisor_total + quotient_region.place(quotient, base) + working_region.place(working, base),
+            highlight_columns: prefix_columns,
+            details: Vec::new(),
+        });
+    }
+
+    steps.push(LessonStep {
+        text: tr!(localization, "division-done",
+            dividend = fmt(dividend), divisor = fmt(divisor), quotient = fmt(quotient), remainder = fmt(working),
+        ),
+        total_value: divisor_total + quotient_region.place(quotient, base) + working_region.place(working, base),
+        highlight_columns: [quotient_region.columns().collect::<Vec<_>>(), working_region.columns().collect()].concat(),
+        details: Vec::new(),
+    });
+
+    Ok(Lesson {
+        title: tr!(localization, "division-title", dividend = fmt(dividend), divisor = fmt(divisor)),
+        steps,
+    })
+}
+
+/// Builds the digit-by-digit square root extraction used on the soroban. The
+/// radicand sits on the right in pairs of digits and the root grows on the left;
+/// each pair brings down a trial digit d, and (2·root·base + d)·d is subtracted.
+pub fn square_root_lesson(
+    radicand: u64,
+    base: u64,
+    column_count: usize,
+    localization: &Localization,
+) -> Result<Lesson, String> {
+    let radicand_digits = digits_in_base(radicand, base);
+    let pair_count = radicand_digits.len().div_ceil(2);
+
+    let working_region = ColumnRegion { start: 0, width: pair_count * 2 };
+    let root_region = ColumnRegion { start: working_region.width + 1, width: pair_count };
+
+    let required_columns = root_region.start + root_region.width;
+    if required_columns > column_count {
+        return Err(tr!(localization, "lesson-error-columns", required = required_columns, available = column_count));
+    }
+    if base.checked_pow(required_columns as u32).is_none() {
+        return Err(tr!(localization, "lesson-error-too-large"));
+    }
+
+    let fmt = |value: u64| format_number_in_base(value, base);
+    let mut steps = vec![LessonStep {
+        text: tr!(localization, "square-root-set-radicand", radicand = fmt(radicand)),
+        total_value: working_region.place(radicand, base),
+        highlight_columns: working_region.columns().collect(),
+        details: vec![tr!(localization, "lesson-remainder", value = fmt(radicand)), tr!(localization, "lesson-root-so-far", value = fmt(0))],
+    }];
+
+    let mut working = radicand;
+    let mut root: u64 = 0;
+    for pair in (0..pair_count).rev() {
+        let place = pair * 2;
+        let place_value = base.pow(place as u32);
+        let prefix = working / place_value;
+        let pair_columns: Vec<usize> = (place..working_region.width).collect();
+
+        // Largest digit d with (2·root·base + d)·d not exceeding the current prefix
+        let doubled_root = 2 * root * base;
+        let digit = (0..base).rev()
+            .find(|&d| (doubled_root + d) * d <= prefix)
+            .unwrap_or(0);
+        let subtrahend = (doubled_root + digit) * digit;
+        root = root * base + digit;
+
+        let root_total = root_region.place(root, base);
+        steps.push(LessonStep {
+            text: tr!(localization, "square-root-digit",
+                doubled_root = fmt(doubled_root), prefix = fmt(prefix), digit = fmt(digit),
+            ),
+            total_value: root_total + working_region.place(working, base),
+            highlight_columns: [pair_columns.clone(), vec![root_region.start + pair]].concat(),
+            details: vec![tr!(localization, "lesson-remainder", value = fmt(prefix)), tr!(localization, "lesson-root-so-far", value = fmt(root))],
+        });
+
+        if subtrahend == 0 {
+            continue;
+        }
+        working -= subtrahend * place_value;
+        steps.push(LessonStep {
+            text: tr!(localization, "square-root-subtract",
+                doubled_root = fmt(doubled_root), digit = fmt(digit), subtrahend = fmt(subtrahend),
+                prefix = fmt(prefix), difference = fmt(prefix - subtrahend),
+            ),
+            total_value: root_total + working_region.place(working, base),
+            highlight_columns: pair_columns,
+            details: vec![tr!(localization, "lesson-remainder", value = fmt(prefix - subtrahend)), tr!(localization, "lesson-root-so-far", value = fmt(root))],
+        });
+    }
+
+    steps.push(LessonStep {
+        text: tr!(localization, "square-root-done",
+            radicand = fmt(radicand), root = fmt(root), remainder = fmt(working),
+        ),
+        total_value: root_region.place(root, base) + working_region.place(working, base),
+        highlight_columns: root_region.columns().collect(),
+        details: vec![tr!(localization, "lesson-remainder", value = fmt(working)), tr!(localization, "lesson-root", value = fmt(root))],
+    });
+
+    Ok(Lesson {
+        title: tr!(localization, "square-root-title", radicand = fmt(radicand)),
+        steps,
+    })
+}
+
+#[derive(Resource, Default)]
+pub struct LessonState {
+    pub active: Option<Lesson>,
+    pub current_step: usize,
+    /// The abacus the lesson was started on; it stays there when another is selected.
+    pub abacus: Option<Entity>,
+    multiplicand_input: String,
+    multiplier_input: String,
+    dividend_input: String,
+    divisor_input: String,
+    radicand_input: String,
+    error: Option<String>,
+    /// Titles of the lessons worked through to the last step, kept per profile.
+    pub completed: Vec<String>,
+    pub completed_changed: bool,
+}
+
+/// Parses operands typed in the abacus base and builds a lesson from them.
+fn start_lesson(
+    lesson_state: &mut LessonState,
+    inputs: &[String],
+    base: u64,
+    localization: &Localization,
+    build: impl FnOnce(&[u64]) -> Result<Lesson, String>,
+) -> bool {
+    let operands: Result<Vec<u64>, _> = inputs.iter()
+        .map(|input| u64::from_str_radix(input.trim(), base as u32))
+        .collect();
+    let lesson = match operands {
+        Ok(operands) => build(&operands),
+        Err(_) => Err(tr!(localization, "lesson-error-base", base = base)),
+    };
+
+    match lesson {
+        Ok(lesson) => {
+            info!("{}", tr!(localization, "log-lesson-started", title = lesson.title.as_str()));
+            lesson_state.active = Some(lesson);
+            lesson_state.error = None;
+            true
+        }
+        Err(message) => {
+            lesson_state.error = Some(message);
+            false
+        }
+    }
+}
+
+/// Moves the abacus to the given lesson step and highlights its columns.
+fn show_lesson_step(
+    step: &LessonStep,
+    abacus_entity: Option<Entity>,
+    abacus_query: &Query<(Entity, &Abacus)>,
+    long_query: &Query<&AbacusLong>,
+    op_queue: &mut ColumnOpQueue,
+    highlight: &mut ColumnHighlight,
+) {
+    if let Some((abacus_entity, abacus)) = abacus_entity.and_then(|entity| abacus_query.get(entity).ok()) {
+        op_queue.queue_total_value(abacus_entity, abacus, step.total_value, long_query);
+    }
+    highlight.abacus = abacus_entity;
+    highlight.columns = step.highlight_columns.clone();
+}
+
+pub fn lesson_ui_system(
+    mut contexts: EguiContexts,
+    mut lesson_state: ResMut<LessonState>,
+    settings: Res<AbacusSettings>,
+    abacus_query: Query<(Entity, &Abacus)>,
+    long_query: Query<&AbacusLong>,
+    mut op_queue: ResMut<ColumnOpQueue>,
+    mut highlight: ResMut<ColumnHighlight>,
+    localization: Res<Localization>,
+    selected: Res<SelectedAbacus>,
+    lesson_files: LessonFiles,
+) {
+    let ctx = contexts.ctx_mut();
+    let localization = &*localization;
+    let lesson_state = &mut *lesson_state;
+    let base = settings.abacus_base;
+    let column_count = settings.column_count;
+
+    egui::Window::new(tr!(localization, "lessons-window"))
+        .id(egui::Id::new("lessons"))
+        .default_pos([10.0, 400.0])
+        .default_open(false)
+        .show(ctx, |ui| {
+            let mut step_to_show: Option<usize> = None;
+            let mut exit_lesson = false;
+
+            if let Some(lesson) = &lesson_state.active {
+                // Students can perform the next step themselves; once the beads show
+                // the expected value the lesson moves on without pressing anything.
+                if let Some(next_step) = lesson.steps.get(lesson_state.current_step + 1) {
+                    let current_total = lesson.steps[lesson_state.current_step].total_value;
+                    if op_queue.pending_count() == 0
+                        && next_step.total_value != current_total
+                        && lesson_state.abacus
+                            .and_then(|entity| abacus_query.get(entity).ok())
+                            .is_some_and(|(_, abacus)| abacus.total_value == next_step.total_value) {
+                        lesson_state.current_step += 1;
+                        highlight.columns = next_step.highlight_columns.clone();
+                    }
+                }
+
+                ui.heading(&lesson.title);
+                let step = &lesson.steps[lesson_state.current_step];
+                ui.label(tr!(localization, "lesson-step-count", step = lesson_state.current_step + 1, total = lesson.steps.len()));
+                if step.details.is_empty() {
+                    ui.label(&step.text);
+                } else {
+                    ui.horizontal(|ui| {
+                        ui.vertical(|ui| {
+                            ui.set_max_width(280.0);
+                            ui.label(&step.text);
+                        });
+                        ui.separator();
+                        ui.vertical(|ui| {
+                            for detail in &step.details {
+                                ui.monospace(detail);
+                            }
+                        });
+                    });
+                }
+                ui.add_space(10.0);
+
+                ui.horizontal(|ui| {
+                    if ui.add_enabled(lesson_state.current_step > 0, egui::Button::new(tr!(localization, "lesson-previous"))).clicked() {
+                        step_to_show = Some(lesson_state.current_step - 1);
+                    }
+                    if ui.add_enabled(lesson_state.current_step + 1 < lesson.steps.len(), egui::Button::new(tr!(localization, "lesson-show-me")))
+                        .on_hover_text(tr!(localization, "lesson-show-me-hint"))
+                        .clicked() {
+                        step_to_show = Some(lesson_state.current_step + 1);
+                    }
+                    if ui.button(tr!(localization, "lesson-restart")).clicked() {
+                        step_to_show = Some(0);
+                    }
+                    if ui.button(tr!(localization, "lesson-exit")).clicked() {
+                        exit_lesson = true;
+                    }
+                });
+            } else {
+                ui.collapsing(tr!(localization, "lesson-multiplication"), |ui| {
+                    ui.horizontal(|ui| {
+                        ui.add_sized([80.0, ui.available_height()], egui::TextEdit::singleline(&mut lesson_state.multiplicand_input).hint_text(tr!(localization, "multiplicand")));
+                        ui.label("×");
+                        ui.add_sized([80.0, ui.available_height()], egui::TextEdit::singleline(&mut lesson_state.multiplier_input).hint_text(tr!(localization, "multiplier")));
+                    });
+                    if ui.button(tr!(localization, "lesson-start")).clicked() {
+                        let inputs = [lesson_state.multiplicand_input.clone(), lesson_state.multiplier_input.clone()];
+                        if start_lesson(lesson_state, &inputs, base, localization, |ops| multiplication_lesson(ops[0], ops[1], base, column_count, localization)) {
+                            step_to_show = Some(0);
+                        }
+                    }
+                });
+
+                ui.collapsing(tr!(localization, "lesson-division"), |ui| {
+                    ui.horizontal(|ui| {
+                        ui.add_sized([80.0, ui.available_height()], egui::TextEdit::singleline(&mut lesson_state.dividend_input).hint_text(tr!(localization, "dividend")));
+                        ui.label("÷");
+                        ui.add_sized([80.0, ui.available_height()], egui::TextEdit::singleline(&mut lesson_state.divisor_input).hint_text(tr!(localization, "divisor")));
+                    });
+                    if ui.button(tr!(localization, "lesson-start")).clicked() {
+                        let inputs = [lesson_state.dividend_input.clone(), lesson_state.divisor_input.clone()];
+                        if start_lesson(lesson_state, &inputs, base, localization, |ops| division_lesson(ops[0], ops[1], base, column_count, localization)) {
+                            step_to_show = Some(0);
+                        }
+                    }
+                });
+
+                ui.collapsing(tr!(localization, "lesson-square-root"), |ui| {
+                    ui.label(tr!(localization, "lesson-square-root-hint"));
+                    ui.horizontal(|ui| {
+                        ui.label("√");
+                        ui.add_sized([80.0, ui.available_height()], egui::TextEdit::singleline(&mut lesson_state.radicand_input).hint_text(tr!(localization, "radicand")));
+                    });
+                    if ui.button(tr!(localization, "lesson-start")).clicked() {
+                        let inputs = [lesson_state.radicand_input.clone()];
+                        if start_lesson(lesson_state, &inputs, base, localization, |ops| square_root_lesson(ops[0], base, column_count, localization)) {
+                            step_to_show = Some(0);
+                        }
+                    }
+                });
+
+                let file_lessons = lesson_files.lessons();
+                if !file_lessons.is_empty() {
+                    ui.collapsing(tr!(localization, "lesson-files"), |ui| {
+                        for file in file_lessons {
+                            if ui.button(&file.title).clicked() {
+                                match file.base {
+                                    Some(file_base) if file_base != base => {
+                                        lesson_state.error = Some(tr!(localization, "lesson-error-file-base", base = file_base));
+                                    }
+                                    _ => {
+                                        let lesson = file.to_lesson();
+                                        info!("{}", tr!(localization, "log-lesson-started", title = lesson.title.as_str()));
+                                        lesson_state.active = Some(lesson);
+                                        lesson_state.error = None;
+                                        step_to_show = Some(0);
+                                    }
+                                }
+                            }
+                        }
+                    });
+                }
+
+                if let Some(error) = &lesson_state.error {
+                    ui.colored_label(egui::Color32::LIGHT_RED, error);
+                }
+
+                if !lesson_state.completed.is_empty() {
+                    ui.collapsing(tr!(localization, "lessons-completed", count = lesson_state.completed.len()), |ui| {
+                        for title in &lesson_state.completed {
+                            ui.label(format!("✓ {}", title));
+                        }
+                    });
+                }
+            }
+
+            if let Some(step_index) = step_to_show {
+                if let Some(lesson) = &lesson_state.active {
+                    if lesson_state.abacus.is_none() {
+                        lesson_state.abacus = selected.0;
+                    }
+                    lesson_state.current_step = step_index;
+                    show_lesson_step(&lesson.steps[step_index], lesson_state.abacus, &abacus_query, &long_query, &mut op_queue, &mut highlight);
+                }
+            }
+
+            // Reaching the last step, by the student or with Show Me, completes the lesson
+            if let Some(lesson) = &lesson_state.active {
+                if lesson_state.current_step + 1 == lesson.steps.len() && !lesson_state.completed.contains(&lesson.title) {
+                    lesson_state.completed.push(lesson.title.clone());
+                    lesson_state.completed_changed = true;
+                }
+            }
+
+            if exit_lesson {
+                lesson_state.active = None;
+                lesson_state.current_step = 0;
+                lesson_state.abacus = None;
+                highlight.columns.clear();
+            }
+        });
+}
@@ -0,0 +1,4 @@
+//! The parts of the simulator that need no window or GPU, kept in a library so the
+//! benchmarks in `benches/` can reach them. The app itself is in `main.rs`.
+
+pub mod value_engine;
@@ -0,0 +1,205 @@
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+use crate::abacus::*;
+use crate::operations::*;
+
+/// Pairs of abaci (by slot, so links survive a rebuild) that always show the same
+/// total, e.g. a base-10 suanpan beside a binary counter to watch base conversion live.
+#[derive(Resource, Default)]
+pub struct AbacusLinks {
+    pairs: Vec<[usize; 2]>,
+    /// Total of each abacus when links were last synced, to tell which one the user moved.
+    last_totals: HashMap<usize, u64>,
+}
+
+impl AbacusLinks {
+    /// The abacus `slot` is linked with, if any. Each abacus has at most one partner.
+    pub fn partner(&self, slot: usize) -> Option<usize> {
+        self.pairs.iter().find_map(|&[a, b]| {
+            if a == slot {
+                Some(b)
+            } else if b == slot {
+                Some(a)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Links two abaci, replacing any links either of them had. `slot` counts as
+    /// changed at the next sync, so `partner` takes its total.
+    pub fn link(&mut self, slot: usize, partner: usize) {
+        self.unlink(slot);
+        self.unlink(partner);
+        if slot != partner {
+            self.pairs.push([slot, partner]);
+            self.last_totals.remove(&slot);
+        }
+    }
+
+    pub fn unlink(&mut self, slot: usize) {
+        self.pairs.retain(|pair| !pair.contains(&slot));
+    }
+}
+
+/// The total `abacus` will actually show for `target`, after clamping and rounding to its beads.
+fn representable_total(abacus: &Abacus, target: u64) -> u64 {
+    abacus.column_values_for_total(target).iter().enumerate().fold(0u64, |total, (i, &value)| {
+        total.saturating_add(value.saturating_mul(abacus.place_value(i)))
+    })
+}
+
+/// Runs after totals are recomputed: whichever abacus of a linked pair changed
+/// pushes its total to the other.
+pub fn sync_linked_abaci(
+    mut links: ResMut<AbacusLinks>,
+    mut abacus_query: Query<(Entity, &mut Abacus)>,
+    mut long_query: Query<&mut AbacusLong>,
+    mut commands: Commands,
+) {
+    let entities_by_slot: HashMap<usize, Entity> = abacus_query.iter().map(|(entity, abacus)| (abacus.slot, entity)).collect();
+
+    let mut changed = Vec::new();
+    for (_, abacus) in &abacus_query {
+        if links.last_totals.insert(abacus.slot, abacus.total_value) != Some(abacus.total_value) {
+            changed.push((abacus.slot, abacus.total_value));
+        }
+    }
+
+    for (slot, total) in changed {
+        let Some(partner_entity) = links.partner(slot).and_then(|partner| entities_by_slot.get(&partner)) else {
+            continue;
+        };
+        let Ok((_, mut partner)) = abacus_query.get_mut(*partner_entity) else {
+            continue;
+        };
+
+        let expected = representable_total(&partner, total);
+        if expected == partner.total_value {
+            continue;
+        }
+        // Recorded up front so the partner's own change isn't pushed back; if it can't
+        // show the whole value, the abacus the user moved keeps what they set
+        links.last_totals.insert(partner.slot, expected);
+        partner.set_total_value(total, &mut long_query, &mut commands);
+    }
+}
+
+/// Abaci chained like an odometer extension (by slot): when one overflows its
+/// maximum, the excess carries into the next.
+#[derive(Resource, Default)]
+pub struct AbacusChains {
+    carries_into: HashMap<usize, usize>,
+}
+
+impl AbacusChains {
+    pub fn carries_into(&self, slot: usize) -> Option<usize> {
+        self.carries_into.get(&slot).copied()
+    }
+
+    /// Whether `slot` can carry into `next` without closing a loop. Each abacus
+    /// is carried into by at most one other.
+    pub fn can_chain(&self, slot: usize, next: usize) -> bool {
+        if slot == next || self.carries_into.iter().any(|(&lower, &upper)| upper == next && lower != slot) {
+            return false;
+        }
+        let mut current = next;
+        while let Some(upper) = self.carries_into(current) {
+            if upper == slot {
+                return false;
+            }
+            current = upper;
+        }
+        true
+    }
+
+    pub fn chain(&mut self, slot: usize, next: usize) {
+        if self.can_chain(slot, next) {
+            self.carries_into.insert(slot, next);
+        }
+    }
+
+    pub fn unchain(&mut self, slot: usize) {
+        self.carries_into.remove(&slot);
+    }
+
+    /// Drops every chain through `slot`, e.g. when its abacus is removed.
+    pub fn remove(&mut self, slot: usize) {
+        self.carries_into.retain(|&lower, &mut upper| lower != slot && upper != slot);
+    }
+}
+
+/// `start` followed by every abacus it carries into, in order.
+fn chain_members<'a>(
+    chains: &AbacusChains,
+    start: Entity,
+    abacus_query: &'a Query<(Entity, &Abacus)>,
+) -> Vec<(Entity, &'a Abacus)> {
+    let by_slot: HashMap<usize, (Entity, &Abacus)> = abacus_query.iter().map(|(entity, abacus)| (abacus.slot, (entity, abacus))).collect();
+    let Ok(first) = abacus_query.get(start) else {
+        return Vec::new();
+    };
+
+    let mut members = vec![first];
+    while let Some(&next) = chains.carries_into(members[members.len() - 1].1.slot).and_then(|slot| by_slot.get(&slot)) {
+        members.push(next);
+    }
+    members
+}
+
+/// The combined total of `start` and the abaci it carries into, once queued moves have played out.
+pub fn pending_chained_total(
+    queue: &ColumnOpQueue,
+    chains: &AbacusChains,
+    start: Entity,
+    abacus_query: &Query<(Entity, &Abacus)>,
+    abacus_long_query: &Query<&AbacusLong>,
+) -> u64 {
+    let mut total = 0u64;
+    let mut place_value = 1u64;
+    for (entity, abacus) in chain_members(chains, start, abacus_query) {
+        total = total.saturating_add(queue.pending_total_value(entity, abacus, abacus_long_query).saturating_mul(place_value));
+        place_value = place_value.saturating_mul(abacus.max_total_value().saturating_add(1));
+    }
+    total
+}
+
+/// Largest combined total `start` and the abaci it carries into can hold.
+fn max_chained_total(members: &[(Entity, &Abacus)]) -> u64 {
+    let mut total = 0u64;
+    let mut place_value = 1u64;
+    for (_, abacus) in members {
+        total = total.saturating_add(abacus.max_total_value().saturating_mul(place_value));
+        place_value = place_value.saturating_mul(abacus.max_total_value().saturating_add(1));
+    }
+    total
+}
+
+/// Queues `start` to show `target_total_value`, carrying whatever it can't hold into
+/// the abaci it is chained to. The last abacus of the chain clamps as usual; when it
+/// has to, the chain's maximum is returned so the clamp can be pointed out.
+pub fn queue_chained_total(
+    queue: &mut ColumnOpQueue,
+    chains: &AbacusChains,
+    start: Entity,
+    target_total_value: u64,
+    abacus_query: &Query<(Entity, &Abacus)>,
+    abacus_long_query: &Query<&AbacusLong>,
+) -> Option<u64> {
+    let members = chain_members(chains, start, abacus_query);
+    let max = max_chained_total(&members);
+    let last = members.len().saturating_sub(1);
+    let mut remaining = target_total_value;
+    for (i, (entity, abacus)) in members.into_iter().enumerate() {
+        let value = if i == last {
+            remaining
+        } else {
+            let (kept, carried) = abacus.split_overflow(remaining);
+            remaining = carried;
+            kept
+        };
+        queue.queue_total_value(entity, abacus, value, abacus_long_query);
+    }
+    (target_total_value > max).then_some(max)
+}
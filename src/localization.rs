@@ -0,0 +1,205 @@
+use bevy::prelude::*;
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource};
+use unic_langid::LanguageIdentifier;
+
+use crate::fonts::EguiFontData;
+
+/// Looks up a localized message, optionally filling in named Fluent variables:
+/// `tr!(localization, "pending-moves", count = 3)`.
+macro_rules! tr {
+    ($localization:expr, $id:expr) => {
+        $localization.message($id, None)
+    };
+    ($localization:expr, $id:expr, $($name:ident = $value:expr),+ $(,)?) => {{
+        let mut args = fluent_bundle::FluentArgs::new();
+        $(args.set(stringify!($name), $value);)+
+        $localization.message($id, Some(&args))
+    }};
+}
+pub(crate) use tr;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Language {
+    #[default]
+    English,
+    Chinese,
+    Japanese,
+}
+
+impl Language {
+    pub const ALL: [Language; 3] = [Language::English, Language::Chinese, Language::Japanese];
+
+    /// Written in the language itself, so it can be found without reading the current one.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::Chinese => "中文",
+            Language::Japanese => "日本語",
+        }
+    }
+
+    fn identifier(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Chinese => "zh-CN",
+            Language::Japanese => "ja",
+        }
+    }
+
+    /// The Fluent messages are embedded so the web build doesn't need a separate assets folder.
+    fn source(&self) -> &'static str {
+        match self {
+            Language::English => include_str!("../assets/locales/en/main.ftl"),
+            Language::Chinese => include_str!("../assets/locales/zh-CN/main.ftl"),
+            Language::Japanese => include_str!("../assets/locales/ja/main.ftl"),
+        }
+    }
+
+    pub fn uses_cjk(&self) -> bool {
+        matches!(self, Language::Chinese | Language::Japanese)
+    }
+}
+
+/// How the digits of the total are grouped in threes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DigitGrouping {
+    Off,
+    /// The separator the current language uses (`digit-group-separator`).
+    #[default]
+    Locale,
+    Comma,
+    Space,
+}
+
+impl DigitGrouping {
+    pub const ALL: [DigitGrouping; 4] = [DigitGrouping::Off, DigitGrouping::Locale, DigitGrouping::Comma, DigitGrouping::Space];
+
+    /// Localization id of the grouping's display name.
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            DigitGrouping::Off => "grouping-off",
+            DigitGrouping::Locale => "grouping-locale",
+            DigitGrouping::Comma => "grouping-comma",
+            DigitGrouping::Space => "grouping-space",
+        }
+    }
+
+    pub fn separator(&self, localization: &Localization) -> String {
+        match self {
+            DigitGrouping::Off => String::new(),
+            DigitGrouping::Locale => tr!(localization, "digit-group-separator"),
+            DigitGrouping::Comma => ",".to_string(),
+            DigitGrouping::Space => " ".to_string(), // Plain space; the default 3D text font has no thin spaces
+        }
+    }
+}
+
+fn load_bundle(language: Language) -> FluentBundle<FluentResource> {
+    let identifier: LanguageIdentifier = language.identifier().parse().expect("valid language identifier");
+    let mut bundle = FluentBundle::new_concurrent(vec![identifier]);
+    // egui draws Unicode isolation marks as boxes
+    bundle.set_use_isolating(false);
+
+    let resource = FluentResource::try_new(language.source().to_string()).unwrap_or_else(|(resource, errors)| {
+        error!("Errors parsing {} messages: {:?}", language.identifier(), errors);
+        resource
+    });
+    if let Err(errors) = bundle.add_resource(resource) {
+        error!("Errors adding {} messages: {:?}", language.identifier(), errors);
+    }
+    bundle
+}
+
+/// Every user-facing string, in the language picked in the settings window.
+#[derive(Resource)]
+pub struct Localization {
+    pub language: Language,
+    bundles: Vec<FluentBundle<FluentResource>>,
+}
+
+impl Default for Localization {
+    fn default() -> Self {
+        Self {
+            language: Language::default(),
+            bundles: Language::ALL.iter().map(|&language| load_bundle(language)).collect(),
+        }
+    }
+}
+
+impl Localization {
+    /// Formats message `id` in the current language, falling back to English for
+    /// messages a translation hasn't caught up with. Use the `tr!` macro rather than calling this.
+    pub fn message(&self, id: &str, args: Option<&FluentArgs>) -> String {
+        let english = &self.bundles[Language::English as usize];
+        let current = &self.bundles[self.language as usize];
+        let found = [current, english].into_iter()
+            .find_map(|bundle| bundle.get_message(id).and_then(|message| message.value()).map(|pattern| (bundle, pattern)));
+
+        let Some((bundle, pattern)) = found else {
+            warn!("Missing localized message '{}'", id);
+            return id.to_string();
+        };
+        let mut errors = Vec::new();
+        let text = bundle.format_pattern(pattern, args, &mut errors).into_owned();
+        if !errors.is_empty() {
+            warn!("Errors formatting localized message '{}': {:?}", id, errors);
+        }
+        text
+    }
+}
+
+/// Places a CJK font is commonly installed on desktop systems.
+#[cfg(not(target_arch = "wasm32"))]
+const CJK_FONT_PATHS: &[&str] = &[
+    "assets/fonts/cjk.ttf",
+    "C:\\Windows\\Fonts\\msyh.ttc",
+    "/System/Library/Fonts/PingFang.ttc",
+    "/System/Library/Fonts/Hiragino Sans GB.ttc",
+    "/usr/share/fonts/opentype/noto/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/noto-cjk/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/google-noto-cjk/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/truetype/wqy/wqy-microhei.ttc",
+];
+
+#[cfg(not(target_arch = "wasm32"))]
+fn find_cjk_font() -> Option<Vec<u8>> {
+    CJK_FONT_PATHS.iter().find_map(|path| std::fs::read(path).ok())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn find_cjk_font() -> Option<Vec<u8>> {
+    None // The browser's fonts aren't visible to egui's canvas
+}
+
+/// The CJK font found by `install_cjk_font`, for 3D text that needs it.
+#[derive(Resource)]
+pub struct CjkFont(pub Handle<Font>);
+
+/// egui's built-in fonts have no Chinese or Japanese glyphs, so the first time
+/// one of those languages is picked a system CJK font is added as a fallback.
+pub fn install_cjk_font(
+    mut egui_fonts: ResMut<EguiFontData>,
+    localization: Res<Localization>,
+    mut installed: Local<bool>,
+    mut font_assets: ResMut<Assets<Font>>,
+    mut commands: Commands,
+) {
+    if *installed || !localization.language.uses_cjk() {
+        return;
+    }
+    *installed = true;
+
+    let Some(font_bytes) = find_cjk_font() else {
+        warn!("No CJK font found; Chinese and Japanese text may not render. Place one at assets/fonts/cjk.ttf.");
+        return;
+    };
+
+    match Font::try_from_bytes(font_bytes.clone()) {
+        Ok(font) => commands.insert_resource(CjkFont(font_assets.add(font))),
+        Err(error) => warn!("CJK font can't be used for 3D text: {}", error),
+    }
+
+    egui_fonts.cjk = Some(font_bytes);
+    info!("CJK font installed");
+}
@@ -0,0 +1,249 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use serde::{Deserialize, Serialize};
+
+use crate::abacus::*;
+use crate::localization::*;
+use crate::operations::{AbacusOperation, ColumnOpQueue, OperationPerformed, OperationRequested};
+use crate::scene_layout::SelectedAbacus;
+
+/// Seconds between replayed operations at 1× speed, once the previous one has finished moving.
+const MACRO_STEP_SECONDS: f32 = 0.6;
+const MIN_SPEED: f32 = 0.25;
+const MAX_SPEED: f32 = 4.0;
+
+/// A named sequence of operations recorded from an abacus, replayable on any abacus.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Macro {
+    pub name: String,
+    pub operations: Vec<AbacusOperation>,
+}
+
+struct Playback {
+    abacus: Entity,
+    name: String,
+    operations: Vec<AbacusOperation>,
+    /// Index of the next operation to send.
+    next: usize,
+    timer: Timer,
+}
+
+/// Saved macros plus the one being recorded or played, if any. Each profile keeps its own macros.
+#[derive(Resource)]
+pub struct MacroLibrary {
+    pub macros: Vec<Macro>,
+    /// Set when `macros` changes, so the profile's copy is saved.
+    pub changed: bool,
+    /// The abacus being recorded from and what it has done so far.
+    recording: Option<(Entity, Vec<AbacusOperation>)>,
+    name: String,
+    playback: Option<Playback>,
+    speed: f32,
+    status: Option<String>,
+}
+
+impl Default for MacroLibrary {
+    fn default() -> Self {
+        Self {
+            macros: Vec::new(),
+            changed: false,
+            recording: None,
+            name: String::new(),
+            playback: None,
+            speed: 1.0,
+            status: None,
+        }
+    }
+}
+
+impl MacroLibrary {
+    pub fn is_playing(&self) -> bool {
+        self.playback.is_some()
+    }
+
+    pub fn play(&mut self, index: usize, abacus: Entity) {
+        let Some(recorded) = self.macros.get(index) else {
+            return;
+        };
+        self.playback = Some(Playback {
+            abacus,
+            name: recorded.name.clone(),
+            operations: recorded.operations.clone(),
+            next: 0,
+            // The first operation goes out straight away
+            timer: Timer::from_seconds(0.0, TimerMode::Once),
+        });
+    }
+
+    fn finish_recording(&mut self, localization: &Localization) {
+        let Some((_, operations)) = self.recording.take() else {
+            return;
+        };
+        if operations.is_empty() {
+            return;
+        }
+        let name = match self.name.trim() {
+            "" => tr!(localization, "macros-default-name", number = self.macros.len() + 1),
+            name => name.to_string(),
+        };
+        self.name.clear();
+        self.macros.push(Macro { name, operations });
+        self.changed = true;
+    }
+}
+
+/// Adds what the recorded abacus does to the macro being recorded. Operations sent by a
+/// playing macro are left out, so playing one while recording doesn't nest it.
+pub fn record_macro_operations(
+    mut performed: EventReader<OperationPerformed>,
+    mut library: ResMut<MacroLibrary>,
+) {
+    let library = &mut *library;
+    let playing = library.playback.is_some();
+    let Some((abacus, operations)) = &mut library.recording else {
+        performed.clear();
+        return;
+    };
+    for event in performed.read() {
+        if event.abacus == *abacus && !playing {
+            operations.push(event.operation.clone());
+        }
+    }
+}
+
+/// Sends the playing macro's operations one at a time, waiting for each to finish
+/// rippling across the columns before the next.
+pub fn play_macros(
+    time: Res<Time>,
+    mut library: ResMut<MacroLibrary>,
+    queue: Res<ColumnOpQueue>,
+    abacus_query: Query<&Abacus>,
+    mut requests: EventWriter<OperationRequested>,
+    localization: Res<Localization>,
+) {
+    let library = &mut *library;
+    let Some(playback) = &mut library.playback else {
+        return;
+    };
+    if abacus_query.get(playback.abacus).is_err() {
+        library.playback = None;
+        library.status = Some(tr!(localization, "macros-abacus-gone"));
+        return;
+    }
+    if queue.pending_count() > 0 {
+        return;
+    }
+    playback.timer.tick(time.delta());
+    if !playback.timer.finished() {
+        return;
+    }
+
+    match playback.operations.get(playback.next) {
+        Some(operation) => {
+            requests.write(OperationRequested { abacus: playback.abacus, operation: operation.clone() });
+            playback.next += 1;
+            playback.timer = Timer::from_seconds(MACRO_STEP_SECONDS / library.speed, TimerMode::Once);
+        }
+        None => library.playback = None,
+    }
+}
+
+pub fn macros_ui_system(
+    mut contexts: EguiContexts,
+    mut library: ResMut<MacroLibrary>,
+    abacus_query: Query<&Abacus>,
+    selected: Res<SelectedAbacus>,
+    localization: Res<Localization>,
+) {
+    let localization = &*localization;
+    let library = &mut *library;
+
+    // The abacus was removed or rebuilt while recording
+    if library.recording.as_ref().is_some_and(|(entity, _)| abacus_query.get(*entity).is_err()) {
+        library.recording = None;
+        library.status = Some(tr!(localization, "macros-abacus-gone"));
+    }
+    let selected_abacus = selected.0.filter(|&entity| abacus_query.get(entity).is_ok());
+
+    egui::Window::new(tr!(localization, "macros-window"))
+        .id(egui::Id::new("macros"))
+        .default_pos([10.0, 640.0])
+        .default_open(false)
+        .show(contexts.ctx_mut(), |ui| {
+            match library.recording.as_ref().map(|(_, operations)| operations.len()) {
+                None => {
+                    ui.label(tr!(localization, "macros-intro"));
+                    if ui.add_enabled(selected_abacus.is_some(), egui::Button::new(tr!(localization, "macros-record"))).clicked() {
+                        if let Some(entity) = selected_abacus {
+                            library.recording = Some((entity, Vec::new()));
+                            library.status = None;
+                        }
+                    }
+                }
+                Some(count) => {
+                    ui.label(tr!(localization, "macros-recording", count = count));
+                    ui.horizontal(|ui| {
+                        let name_label = ui.label(tr!(localization, "macros-name"));
+                        ui.text_edit_singleline(&mut library.name).labelled_by(name_label.id);
+                    });
+                    ui.horizontal(|ui| {
+                        if ui.button(tr!(localization, "macros-save")).clicked() {
+                            library.finish_recording(localization);
+                        }
+                        if ui.button(tr!(localization, "macros-discard")).clicked() {
+                            library.recording = None;
+                        }
+                    });
+                }
+            }
+
+            ui.separator();
+            ui.add(egui::Slider::new(&mut library.speed, MIN_SPEED..=MAX_SPEED)
+                .logarithmic(true)
+                .suffix("×")
+                .text(tr!(localization, "macros-speed")));
+
+            let mut stop_playback = false;
+            if let Some(playback) = &library.playback {
+                ui.horizontal(|ui| {
+                    ui.label(tr!(localization, "macros-playing",
+                        name = playback.name.clone(), step = playback.next, total = playback.operations.len()));
+                    stop_playback = ui.button(tr!(localization, "macros-stop")).clicked();
+                });
+            }
+            if stop_playback {
+                library.playback = None;
+            }
+
+            if library.macros.is_empty() {
+                ui.label(tr!(localization, "macros-empty"));
+            }
+            let can_play = library.playback.is_none() && library.recording.is_none() && selected_abacus.is_some();
+            let mut to_play = None;
+            let mut to_remove = None;
+            egui::ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+                for (i, recorded) in library.macros.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        if ui.add_enabled(can_play, egui::Button::new(tr!(localization, "macros-play"))).clicked() {
+                            to_play = Some(i);
+                        }
+                        ui.label(tr!(localization, "macros-entry", name = recorded.name.clone(), count = recorded.operations.len()));
+                        if ui.small_button("✖").on_hover_text(tr!(localization, "remove")).clicked() {
+                            to_remove = Some(i);
+                        }
+                    });
+                }
+            });
+            if let (Some(i), Some(entity)) = (to_play, selected_abacus) {
+                library.play(i, entity);
+            }
+            if let Some(i) = to_remove {
+                library.macros.remove(i);
+                library.changed = true;
+            }
+
+            if let Some(status) = &library.status {
+                ui.label(status);
+            }
+        });
+}
@@ -2,26 +2,216 @@ use bevy::prelude::*;
 use bevy_egui::{egui, EguiContexts, EguiPlugin};
 use bevy::winit::{WinitSettings, UpdateMode};
 use bevy::input::mouse::MouseMotion;
+use bevy::color::{ColorToComponents, Mix};
+use bevy::ecs::system::SystemParam;
+use bevy::transform::TransformSystem;
+use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
 use abacus::*;
+use operations::*;
+use operation_log::*;
+use overflow::*;
+use picking::*;
+use lessons::*;
+use lesson_files::*;
+use lesson_editor::*;
+use audio::*;
+use column_colors::*;
+use column_entry::*;
+use column_glow::*;
+use column_hover::*;
+use column_keys::*;
+use column_splice::*;
+use config_files::*;
+use config_gallery::*;
+use console::*;
+use counting::*;
+use currency::*;
+use custom_models::*;
+use digit_entry::*;
+use environment::*;
+use export::*;
+use flat_mode::*;
+use fonts::*;
+use frame_title::*;
+use fullscreen::*;
+use game::*;
+use gamepad::*;
+use graphics::*;
+use haptics::*;
+use history::*;
+use hud::*;
+use keybindings::*;
+use kiosk::*;
+use layout_transitions::*;
+use shake::*;
+use scene_files::*;
+use screenshot::*;
+use share_link::*;
+use snapshots::*;
+use startup::*;
+use stats::*;
+use stopwatch::*;
+use stress::*;
+use surfaces::*;
+use table::*;
+use tally::*;
+use themes::*;
+use touch_gestures::*;
+use window_layout::*;
+use wood_grain::*;
+use presentation::*;
+use profiles::*;
+use replay::*;
+use a11y::*;
+use active_column::*;
+use appearance::*;
+use attract::*;
+use localization::*;
+use number_words::*;
+use scene_layout::*;
+use links::*;
+use macros::*;
 
+mod a11y;
 mod abacus;
+mod active_column;
+mod appearance;
+mod attract;
+mod audio;
+mod bottom_sheet;
+#[cfg(all(feature = "remote", not(target_arch = "wasm32")))]
+mod broadcast;
+mod column_colors;
+mod column_entry;
+mod column_glow;
+mod column_hover;
+mod column_keys;
+mod column_splice;
+mod config_files;
+mod config_gallery;
+#[cfg(feature = "consistency-check")]
+mod consistency;
+mod console;
+mod counting;
+mod currency;
+mod custom_models;
+mod digit_entry;
+mod environment;
+mod export;
+mod flat_mode;
+mod fonts;
+mod frame_title;
+mod fullscreen;
+mod game;
+mod gamepad;
+mod graphics;
+mod haptics;
+mod history;
+mod hud;
+#[cfg(target_arch = "wasm32")]
+mod js_api;
+mod keybindings;
+mod kiosk;
+mod layout_transitions;
+mod lesson_editor;
+mod lesson_files;
+mod lessons;
+mod links;
+mod localization;
+mod macros;
+#[cfg(all(feature = "midi", not(target_arch = "wasm32")))]
+mod midi;
+mod number_words;
+mod operation_log;
+mod operations;
+#[cfg(all(feature = "osc", not(target_arch = "wasm32")))]
+mod osc;
+mod overflow;
+#[cfg(feature = "physics")]
+mod physics;
+mod picking;
+mod presentation;
+mod profiles;
+#[cfg(all(feature = "remote", not(target_arch = "wasm32")))]
+mod remote;
+mod replay;
+mod scene_files;
+mod scene_layout;
+mod screenshot;
+#[cfg(feature = "scripting")]
+mod scripting;
+mod shake;
+mod share_link;
+mod snapshots;
+mod startup;
+mod stats;
+mod stopwatch;
+mod storage;
+mod stress;
+mod surfaces;
+mod table;
+mod tally;
+mod themes;
+mod touch_gestures;
+#[cfg(not(target_arch = "wasm32"))]
+mod turntable;
+mod window_layout;
+#[cfg(target_arch = "wasm32")]
+mod window_resize;
+mod wood_grain;
 
 // Configuration that can be saved/loaded
-#[derive(Clone, Debug, PartialEq)] // PartialEq for potential future comparisons
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)] // PartialEq for potential future comparisons
 struct SavableAbacusConfig {
     name: String, // Name will be part of this struct for simplicity here
     column_count: usize,
     top_bead_count: usize,
     bottom_bead_count: usize,
     top_bead_base_value: u64,
+    bead_value_multiplier: u64,
+    column_overrides: Vec<ColumnOverride>,
     abacus_base: u64,
+    #[serde(default)]
+    value_model: ValueModel,
+    #[serde(default)]
+    horizontal_rods: bool,
+    #[serde(default)]
+    two_tone_beads: bool,
+    #[serde(default)]
+    grooved: bool,
+    #[serde(default)]
+    extra_decks: Vec<DeckLayout>,
+    #[serde(default)]
+    ones_on_left: bool,
     show_top_text: bool,
     show_column_texts: bool,
     ui_bead_color: Color,
     ui_bead_hover_color: Color,
     ui_frame_color: Color,
+    #[serde(default)]
+    proportions: Proportions,
+    #[serde(default)]
+    bead_shape: BeadShape,
+    #[serde(default)]
+    custom_models: CustomModels,
+    #[serde(default)]
+    surfaces: Surfaces,
+    #[serde(default)]
+    wood_grain: WoodGrainSettings,
+    #[serde(default)]
+    environment: Environment,
+    #[serde(default)]
+    table: TableSurface,
+    #[serde(default)]
+    frame_title: FrameTitle,
+    #[serde(default)]
+    column_colors: Vec<ColumnColor>,
+    /// Name of the theme file the look comes from; empty for none.
+    #[serde(default)]
+    theme: String,
+    sound_theme: SoundTheme,
 }
 
 // Resource to hold all user-saved configurations and UI state for saving/loading
@@ -32,25 +222,46 @@ struct UserConfigurations {
     selected_config_name_to_load: String, 
     set_value_input: String,
     modify_value_input: String, // New field for Add/Subtract input
+    /// Saved or deleted since the profile's configurations were last written to storage.
+    configs_changed: bool,
 }
 
-impl FromWorld for UserConfigurations {
-    fn from_world(_world: &mut World) -> Self {
-        // Pre-populate with some default configurations
-        let default_configs = vec![
+impl SavableAbacusConfig {
+    /// The presets every profile starts with.
+    fn built_in() -> Vec<SavableAbacusConfig> {
+        vec![
             SavableAbacusConfig {
                 name: "Suanpan (Chinese 2/5) - Base 10".to_string(),
                 column_count: 9,
                 top_bead_count: 2, // 2 beads in the upper deck
                 bottom_bead_count: 5, // 5 beads in the lower deck
                 top_bead_base_value: 5, // Each upper bead is worth 5 (when moved against the bar)
+                bead_value_multiplier: 1,
+                column_overrides: Vec::new(),
                 abacus_base: 10, // Typically used for decimal calculations
+                value_model: ValueModel::PlaceValue,
+                horizontal_rods: false,
+                two_tone_beads: false,
+                grooved: false,
+                extra_decks: Vec::new(),
+                ones_on_left: false,
                 show_top_text: true,
                 show_column_texts: true,
                 // Placeholder colors - you can refine these to match typical abacus colors
                 ui_bead_color: Color::srgb(0.6, 0.3, 0.1), // Brownish beads
                 ui_bead_hover_color: Color::srgb(0.7, 0.4, 0.2),
                 ui_frame_color: Color::srgb(0.3, 0.2, 0.1), // Dark wood frame
+                proportions: Proportions::default(),
+                bead_shape: BeadShape::Bicone,
+                custom_models: CustomModels::default(),
+                surfaces: Surfaces::default(),
+                wood_grain: WoodGrainSettings::default(),
+                environment: Environment::default(),
+                table: TableSurface::default(),
+                frame_title: FrameTitle::default(),
+                column_colors: Vec::new(),
+                theme: String::new(),
+                sound_theme: SoundTheme::Wood,
             },
             SavableAbacusConfig {
                 name: "Suanpan (Chinese 2/5) - Base 16".to_string(),
@@ -58,13 +269,32 @@ impl FromWorld for UserConfigurations {
                 top_bead_count: 2, // 2 beads in the upper deck
                 bottom_bead_count: 5, // 5 beads in the lower deck
                 top_bead_base_value: 5, // Each upper bead is worth 5 (when moved against the bar)
+                bead_value_multiplier: 1,
+                column_overrides: Vec::new(),
                 abacus_base: 16,
+                value_model: ValueModel::PlaceValue,
+                horizontal_rods: false,
+                two_tone_beads: false,
+                grooved: false,
+                extra_decks: Vec::new(),
+                ones_on_left: false,
                 show_top_text: true,
                 show_column_texts: true,
                 // Placeholder colors - you can refine these to match typical abacus colors
                 ui_bead_color: Color::srgb(0.6, 0.3, 0.1), // Brownish beads
                 ui_bead_hover_color: Color::srgb(0.7, 0.4, 0.2),
                 ui_frame_color: Color::srgb(0.3, 0.2, 0.1), // Dark wood frame
+                proportions: Proportions::default(),
+                bead_shape: BeadShape::Bicone,
+                custom_models: CustomModels::default(),
+                surfaces: Surfaces::default(),
+                wood_grain: WoodGrainSettings::default(),
+                environment: Environment::default(),
+                table: TableSurface::default(),
+                frame_title: FrameTitle::default(),
+                column_colors: Vec::new(),
+                theme: String::new(),
+                sound_theme: SoundTheme::Wood,
             },
             SavableAbacusConfig {
                 name: "Soroban (Japanese 1/4)".to_string(),
@@ -72,12 +302,31 @@ impl FromWorld for UserConfigurations {
                 top_bead_count: 1,   // 1 bead in the upper deck
                 bottom_bead_count: 4, // 4 beads in the lower deck
                 top_bead_base_value: 5, // Upper bead is worth 5
+                bead_value_multiplier: 1,
+                column_overrides: Vec::new(),
                 abacus_base: 10, // Decimal system
+                value_model: ValueModel::PlaceValue,
+                horizontal_rods: false,
+                two_tone_beads: false,
+                grooved: false,
+                extra_decks: Vec::new(),
+                ones_on_left: false,
                 show_top_text: true,
                 show_column_texts: true,
                 ui_bead_color: Color::srgb(0.2, 0.2, 0.2), // Dark beads
                 ui_bead_hover_color: Color::srgb(0.4, 0.4, 0.4),
                 ui_frame_color: Color::srgb(0.5, 0.5, 0.5), // Lighter frame
+                proportions: Proportions::default(),
+                bead_shape: BeadShape::Bicone,
+                custom_models: CustomModels::default(),
+                surfaces: Surfaces::default(),
+                wood_grain: WoodGrainSettings::default(),
+                environment: Environment::default(),
+                table: TableSurface::default(),
+                frame_title: FrameTitle::default(),
+                column_colors: Vec::new(),
+                theme: String::new(),
+                sound_theme: SoundTheme::Wood,
             },
             SavableAbacusConfig {
                 name: "Binary Counter (1/1)".to_string(),
@@ -85,15 +334,175 @@ impl FromWorld for UserConfigurations {
                 top_bead_count: 0,
                 bottom_bead_count: 1,
                 top_bead_base_value: 1,
+                bead_value_multiplier: 1,
+                column_overrides: Vec::new(),
                 abacus_base: 2,
+                value_model: ValueModel::PlaceValue,
+                horizontal_rods: false,
+                two_tone_beads: false,
+                grooved: false,
+                extra_decks: Vec::new(),
+                ones_on_left: false,
                 show_top_text: true,
                 show_column_texts: true,
                 ui_bead_color: Color::srgb(0.1, 0.5, 0.1), // Green beads
                 ui_bead_hover_color: Color::srgb(0.2, 0.7, 0.2),
                 ui_frame_color: Color::srgb(0.4, 0.4, 0.4), 
+                proportions: Proportions::default(),
+                bead_shape: BeadShape::Disk,
+                custom_models: CustomModels::default(),
+                surfaces: Surfaces::default(),
+                wood_grain: WoodGrainSettings::default(),
+                environment: Environment::default(),
+                table: TableSurface::default(),
+                frame_title: FrameTitle::default(),
+                column_colors: Vec::new(),
+                theme: String::new(),
+                sound_theme: SoundTheme::Glass,
+            },
+            SavableAbacusConfig {
+                name: "Rekenrek (2 × 10)".to_string(),
+                column_count: 2, // Two rods of ten
+                top_bead_count: 0,
+                bottom_bead_count: 10,
+                top_bead_base_value: 1,
+                bead_value_multiplier: 1,
+                column_overrides: Vec::new(),
+                abacus_base: 10,
+                value_model: ValueModel::Counting, // Beads moved to the left are simply counted
+                horizontal_rods: true,
+                two_tone_beads: true, // Five red, five white
+                grooved: false,
+                extra_decks: Vec::new(),
+                ones_on_left: false,
+                show_top_text: true,
+                show_column_texts: true,
+                ui_bead_color: Color::srgb(0.8, 0.1, 0.1), // Red beads
+                ui_bead_hover_color: Color::srgb(0.9, 0.3, 0.3),
+                ui_frame_color: Color::srgb(0.3, 0.3, 0.35),
+                proportions: Proportions::default(),
+                bead_shape: BeadShape::Sphere,
+                custom_models: CustomModels::default(),
+                surfaces: Surfaces::default(),
+                wood_grain: WoodGrainSettings::default(),
+                environment: Environment::default(),
+                table: TableSurface::default(),
+                frame_title: FrameTitle::default(),
+                column_colors: Vec::new(),
+                theme: String::new(),
+                sound_theme: SoundTheme::Wood,
+            },
+            SavableAbacusConfig {
+                name: "Hundred Frame (10 × 10)".to_string(),
+                column_count: 10, // Ten rods of ten, for counting to a hundred
+                top_bead_count: 0,
+                bottom_bead_count: 10,
+                top_bead_base_value: 1,
+                bead_value_multiplier: 1,
+                column_overrides: Vec::new(),
+                abacus_base: 10,
+                value_model: ValueModel::Counting,
+                horizontal_rods: true,
+                two_tone_beads: true, // Color changes every five beads
+                grooved: false,
+                extra_decks: Vec::new(),
+                ones_on_left: false,
+                show_top_text: true,
+                show_column_texts: true,
+                ui_bead_color: Color::srgb(0.1, 0.3, 0.8), // Blue beads
+                ui_bead_hover_color: Color::srgb(0.3, 0.5, 0.9),
+                ui_frame_color: Color::srgb(0.55, 0.4, 0.25), // Light wood frame
+                proportions: Proportions::default(),
+                bead_shape: BeadShape::Sphere,
+                custom_models: CustomModels::default(),
+                surfaces: Surfaces::default(),
+                wood_grain: WoodGrainSettings::default(),
+                environment: Environment::default(),
+                table: TableSurface::default(),
+                frame_title: FrameTitle::default(),
+                column_colors: Vec::new(),
+                theme: String::new(),
+                sound_theme: SoundTheme::Wood,
+            },
+SavableAbacusConfig {
+                name: "Roman Hand Abacus".to_string(),
+                // Seven whole columns (ones up to a million) over the uncia column and the
+                // semuncia and sicilicus slots, so the total counts in 48ths of an as
+                column_count: 10,
+                top_bead_count: 1,
+                bottom_bead_count: 4,
+                top_bead_base_value: 5,
+                bead_value_multiplier: 1,
+                column_overrides: vec![
+                    ColumnOverride { column: 0, top_bead_count: 0, bottom_bead_count: 1, bead_value_multiplier: 1, top_bead_base_value: None, radix: Some(2) },
+                    ColumnOverride { column: 1, top_bead_count: 0, bottom_bead_count: 1, bead_value_multiplier: 1, top_bead_base_value: None, radix: Some(2) },
+                    // Twelve unciae to the as: five single beads and one worth six
+                    ColumnOverride { column: 2, top_bead_count: 1, bottom_bead_count: 5, bead_value_multiplier: 1, top_bead_base_value: Some(6), radix: Some(12) },
+                ],
+                abacus_base: 10,
+                value_model: ValueModel::PlaceValue,
+                horizontal_rods: false,
+                two_tone_beads: false,
+                grooved: true, // Bronze plate with slots rather than rods
+                extra_decks: Vec::new(),
+                ones_on_left: false,
+                show_top_text: true,
+                show_column_texts: true,
+                ui_bead_color: Color::srgb(0.75, 0.6, 0.3), // Brass studs
+                ui_bead_hover_color: Color::srgb(0.9, 0.75, 0.4),
+                ui_frame_color: Color::srgb(0.35, 0.25, 0.15), // Dark bronze
+                proportions: Proportions::default(),
+                bead_shape: BeadShape::Disk,
+                custom_models: CustomModels::default(),
+                surfaces: Surfaces::default(),
+                wood_grain: WoodGrainSettings::default(),
+                environment: Environment::default(),
+                table: TableSurface::default(),
+                frame_title: FrameTitle::default(),
+                column_colors: Vec::new(),
+                theme: String::new(),
+                sound_theme: SoundTheme::Metal,
+            },
+            SavableAbacusConfig {
+                name: "Nepōhualtzintzin (Aztec 3/4) - Base 20".to_string(),
+                column_count: 13, // Thirteen columns, after the 13 × 7 = 91 beads of the calendar reading
+                top_bead_count: 3, // Three upper beads worth 5 each
+                bottom_bead_count: 4, // Four lower beads worth 1, so a column counts to 19
+                top_bead_base_value: 5,
+                bead_value_multiplier: 1,
+                column_overrides: Vec::new(),
+                abacus_base: 20, // Vigesimal, like Nahuatl counting
+                value_model: ValueModel::PlaceValue,
+                horizontal_rods: false,
+                two_tone_beads: false,
+                grooved: false,
+                extra_decks: Vec::new(),
+                ones_on_left: false,
+                show_top_text: true,
+                show_column_texts: true,
+                ui_bead_color: Color::srgb(0.1, 0.55, 0.45), // Jade beads
+                ui_bead_hover_color: Color::srgb(0.3, 0.75, 0.6),
+                ui_frame_color: Color::srgb(0.45, 0.2, 0.1), // Reddish wood
+                proportions: Proportions::default(),
+                bead_shape: BeadShape::Sphere,
+                custom_models: CustomModels::default(),
+                surfaces: Surfaces::default(),
+                wood_grain: WoodGrainSettings::default(),
+                environment: Environment::default(),
+                table: TableSurface::default(),
+                frame_title: FrameTitle::default(),
+                column_colors: Vec::new(),
+                theme: String::new(),
+                sound_theme: SoundTheme::Wood,
             },
             // Add more predefined configurations as needed
-        ];
+        ]
+    }
+}
+
+impl FromWorld for UserConfigurations {
+    fn from_world(_world: &mut World) -> Self {
+        let default_configs = SavableAbacusConfig::built_in();
 
         // Set the first config as initially selected if available
         let initial_selection = if !default_configs.is_empty() {
@@ -108,66 +517,396 @@ impl FromWorld for UserConfigurations {
             selected_config_name_to_load: initial_selection,
             set_value_input: String::new(),
             modify_value_input: String::new(), // Initialize
+            configs_changed: false,
         }
     }
 }
 
-#[derive(Resource)]
+impl UserConfigurations {
+    /// The configuration a preset name picks, for the console and `--preset`: the one with
+    /// that name, ignoring case, or else the only one whose name contains it. Otherwise
+    /// every configuration that matches, which is none for an unknown name.
+    fn find_preset(&self, name: &str) -> Result<&SavableAbacusConfig, Vec<&SavableAbacusConfig>> {
+        let wanted = name.to_lowercase();
+        if let Some(config) = self.configs.iter().find(|config| config.name.to_lowercase() == wanted) {
+            return Ok(config);
+        }
+        let mut matching: Vec<_> = self.configs.iter().filter(|config| config.name.to_lowercase().contains(&wanted)).collect();
+        if matching.len() == 1 { Ok(matching.remove(0)) } else { Err(matching) }
+    }
+}
+
+/// The structural part of the settings, kept on each abacus so selecting another
+/// abacus brings its own layout back into the settings panel.
+#[derive(Component, Clone, PartialEq, Reflect, Serialize, Deserialize)]
+#[reflect(Component)]
+struct AbacusStructure {
+    column_count: usize,
+    top_bead_count: usize,
+    bottom_bead_count: usize,
+    top_bead_base_value: u64,
+    bead_value_multiplier: u64,
+    column_overrides: Vec<ColumnOverride>,
+    abacus_base: u64,
+    #[serde(default)]
+    value_model: ValueModel,
+    #[serde(default)]
+    horizontal_rods: bool,
+    #[serde(default)]
+    two_tone_beads: bool,
+    #[serde(default)]
+    grooved: bool,
+    #[serde(default)]
+    extra_decks: Vec<DeckLayout>,
+    #[serde(default)]
+    ones_on_left: bool,
+}
+
+impl AbacusStructure {
+    fn from_settings(settings: &AbacusSettings) -> Self {
+        Self {
+            column_count: settings.column_count,
+            top_bead_count: settings.top_bead_count,
+            bottom_bead_count: settings.bottom_bead_count,
+            top_bead_base_value: settings.top_bead_base_value,
+            bead_value_multiplier: settings.bead_value_multiplier,
+            column_overrides: settings.column_overrides.clone(),
+            abacus_base: settings.abacus_base,
+            value_model: settings.value_model,
+            horizontal_rods: settings.horizontal_rods,
+            two_tone_beads: settings.two_tone_beads,
+            grooved: settings.grooved,
+            extra_decks: settings.extra_decks.clone(),
+            ones_on_left: settings.ones_on_left,
+        }
+    }
+
+    fn from_config(config: &SavableAbacusConfig) -> Self {
+        Self {
+            column_count: config.column_count,
+            top_bead_count: config.top_bead_count,
+            bottom_bead_count: config.bottom_bead_count,
+            top_bead_base_value: config.top_bead_base_value,
+            bead_value_multiplier: config.bead_value_multiplier,
+            column_overrides: config.column_overrides.clone(),
+            abacus_base: config.abacus_base,
+            value_model: config.value_model,
+            horizontal_rods: config.horizontal_rods,
+            two_tone_beads: config.two_tone_beads,
+            grooved: config.grooved,
+            extra_decks: config.extra_decks.clone(),
+            ones_on_left: config.ones_on_left,
+        }
+    }
+
+    /// Resolves the bead layout of a column, applying the last matching override.
+    fn column_layout(&self, column: usize) -> ColumnLayout {
+        let mut top = DeckLayout { bead_count: self.top_bead_count, bead_value: self.top_bead_base_value };
+        let mut bottom = DeckLayout { bead_count: self.bottom_bead_count, bead_value: 1 };
+        let mut bead_value_multiplier = self.bead_value_multiplier;
+        let mut radix = self.abacus_base;
+        if let Some(column_override) = self.column_overrides.iter().rev().find(|o| o.column == column) {
+            top.bead_count = column_override.top_bead_count;
+            top.bead_value = column_override.top_bead_base_value.unwrap_or(self.top_bead_base_value);
+            bottom.bead_count = column_override.bottom_bead_count;
+            bead_value_multiplier = column_override.bead_value_multiplier;
+            radix = column_override.radix.unwrap_or(self.abacus_base);
+        }
+        // Extra decks stack above the usual two
+        let decks = self.extra_decks.iter().rev().copied().chain([top, bottom]).collect();
+        ColumnLayout { decks, bead_value_multiplier, radix }
+    }
+
+    fn apply_to(&self, settings: &mut AbacusSettings) {
+        settings.column_count = self.column_count;
+        settings.top_bead_count = self.top_bead_count;
+        settings.bottom_bead_count = self.bottom_bead_count;
+        settings.top_bead_base_value = self.top_bead_base_value;
+        settings.bead_value_multiplier = self.bead_value_multiplier;
+        settings.column_overrides = self.column_overrides.clone();
+        settings.abacus_base = self.abacus_base;
+        settings.value_model = self.value_model;
+        settings.horizontal_rods = self.horizontal_rods;
+        settings.two_tone_beads = self.two_tone_beads;
+        settings.grooved = self.grooved;
+        settings.extra_decks = self.extra_decks.clone();
+        settings.ones_on_left = self.ones_on_left;
+    }
+
+    /// Brings a structure from outside the app, like a file or a share link, within the
+    /// ranges the settings panel allows, so it can't hang the app or divide by zero.
+    /// Overrides of columns it doesn't have are dropped, as is all but the last override
+    /// of a column. Returns the fields that were out of range.
+    fn sanitize(&mut self) -> Vec<&'static str> {
+        let mut clamped = Vec::new();
+        clamp_field(&mut self.column_count, 1, MAX_COLUMNS, "column_count", &mut clamped);
+        clamp_field(&mut self.top_bead_count, 0, MAX_TOP_BEADS, "top_bead_count", &mut clamped);
+        clamp_field(&mut self.bottom_bead_count, 1, 10, "bottom_bead_count", &mut clamped);
+        clamp_field(&mut self.top_bead_base_value, 1, 10, "top_bead_base_value", &mut clamped);
+        clamp_field(&mut self.bead_value_multiplier, 1, 10, "bead_value_multiplier", &mut clamped);
+        clamp_field(&mut self.abacus_base, 2, 36, "abacus_base", &mut clamped);
+
+        if self.extra_decks.len() > MAX_EXTRA_DECKS {
+            self.extra_decks.truncate(MAX_EXTRA_DECKS);
+            clamped.push("extra_decks");
+        }
+        for deck in &mut self.extra_decks {
+            clamp_field(&mut deck.bead_count, 1, 10, "extra_decks", &mut clamped);
+            clamp_field(&mut deck.bead_value, 1, 100, "extra_decks", &mut clamped);
+        }
+
+        let mut overridden = vec![false; self.column_count];
+        self.column_overrides.reverse();
+        self.column_overrides.retain(|o| o.column < overridden.len() && !std::mem::replace(&mut overridden[o.column], true));
+        self.column_overrides.reverse();
+        for o in &mut self.column_overrides {
+            clamp_field(&mut o.top_bead_count, 0, MAX_TOP_BEADS, "column_overrides", &mut clamped);
+            clamp_field(&mut o.bottom_bead_count, 1, 10, "column_overrides", &mut clamped);
+            clamp_field(&mut o.bead_value_multiplier, 1, 10, "column_overrides", &mut clamped);
+            if let Some(top_bead_base_value) = &mut o.top_bead_base_value {
+                clamp_field(top_bead_base_value, 1, 10, "column_overrides", &mut clamped);
+            }
+            if let Some(radix) = &mut o.radix {
+                clamp_field(radix, 2, 36, "column_overrides", &mut clamped);
+            }
+        }
+        clamped
+    }
+}
+
+/// Clamps `value` to `min..=max`, adding `field` to `clamped` if it was outside.
+fn clamp_field<T: Ord + Copy>(value: &mut T, min: T, max: T, field: &'static str, clamped: &mut Vec<&'static str>) {
+    let within = (*value).clamp(min, max);
+    if within != *value {
+        *value = within;
+        if !clamped.contains(&field) {
+            clamped.push(field);
+        }
+    }
+}
+
+#[derive(Resource, Clone)]
 struct AbacusSettings {
     column_count: usize,
     top_bead_count: usize,
     bottom_bead_count: usize,
     top_bead_base_value: u64,
+    bead_value_multiplier: u64,
+    column_overrides: Vec<ColumnOverride>,
     abacus_base: u64,
+    value_model: ValueModel,
+    horizontal_rods: bool, // Rods run across, rekenrek style
+    two_tone_beads: bool,  // Beads alternate color in blocks of five
+    grooved: bool,         // Beads slide in grooves instead of on rods, as on a Roman abacus
+    extra_decks: Vec<DeckLayout>, // Decks above the top deck, nearest it first
+    ones_on_left: bool,    // The ones column at the left end instead of the right
     show_top_text: bool,
     show_column_texts: bool,
 
     // Handles to shared materials
-    bead_material: Handle<StandardMaterial>,
-    bead_hover_material: Handle<StandardMaterial>, // Will be used if hover effects are re-enabled for non-mobile
-    frame_material: Handle<StandardMaterial>,
+    bead_material: Handle<AbacusMaterial>,
+    bead_hover_material: Handle<AbacusMaterial>, // Will be used if hover effects are re-enabled for non-mobile
+    bead_highlight_material: Handle<AbacusMaterial>,
+    bead_alt_material: Handle<AbacusMaterial>, // The second color of two-tone rods
+    column_bead_materials: Vec<Handle<AbacusMaterial>>, // One per column color, in the same order
+    frame_material: Handle<AbacusMaterial>,
+
+    // Handles to shared meshes; every bead uses the one bead mesh
+    bead_mesh: Handle<Mesh>,
+    bead_lod_mesh: Handle<Mesh>, // Fewer segments, for abaci wider than LOD_COLUMNS
+    rod_mesh: Handle<Mesh>,
 
     // Colors for UI pickers
     ui_bead_color: Color,
     ui_bead_hover_color: Color,
     ui_frame_color: Color,
+    ui_background_color: Color,
+    text_color: Color,
+    text_size: f32,
+    text_font_name: String, // File in assets/fonts; empty for the default font
+    text_font: Handle<Font>,
+    hud_total: bool,    // The total as a large line at the top of the screen, for projecting
+    hud_text_size: f32,
+    presentation_mode: bool, // Full screen, no panels, just the abaci and a large total
+    digit_grouping: DigitGrouping,
+    currency_mode: bool, // Base 10 totals read as money, the last two columns as cents
+    currency_symbol: String,
+    show_value_in_words: bool,
+    group_column_colors: bool, // Alternate column text color every three columns
+    arrangement: AbacusArrangement,
+    camera_focus: CameraFocus,
+    ui_scale: f32, // Multiplies egui widgets and the 3D value text
+    proportions: Proportions, // Spacing and size of the abaci, applied without a rebuild
+    bead_shape: BeadShape,
+    custom_models: CustomModels, // glTF files standing in for the bead and rod meshes
+    surfaces: Surfaces,          // Textures and finish of the bead and frame materials
+    wood_grain: WoodGrainSettings,
+    environment: Environment, // Lights and what's drawn behind the abaci
+    table: TableSurface,      // Surface the abaci stand on
+    frame_title: FrameTitle,  // Label along the top of each frame
+    column_colors: Vec<ColumnColor>,
+    theme: String,            // Theme file the look came from, until it's changed by hand
+
+    // Sound
+    sound_volume: f32,
+    sound_muted: bool,
+    sound_theme: SoundTheme,
+    haptics_enabled: bool,
+
+    // Simulated beads (only available with the `physics` feature)
+    physics_enabled: bool,
+
+    // Bead animation
+    bead_easing: BeadEasing,
+    bead_move_duration: f32, // Seconds per bead move
+    bead_stagger: f32,       // Seconds between beads moving on the same rod
+    reduced_motion: bool,    // Beads jump straight to their targets, no ripples or tumbles
+    attract_mode: bool,      // Demo mode: keep setting new values on a timer
+    attract_style: AttractStyle,
+    attract_interval: f32,   // Seconds between demo values
+    flat_mode: bool,         // Front-on orthographic view with flat beads, for low-end devices
+    graphics_quality: GraphicsQuality,
+
+    // Screenshots
+    screenshot_hides_ui: bool, // Leave the panels out, for a clean shot of the abaci
 }
 
 impl FromWorld for AbacusSettings {
     fn from_world(world: &mut World) -> Self {
-        let mut materials = world.get_resource_mut::<Assets<StandardMaterial>>().unwrap();
+        let graphics_quality = GraphicsQuality::default();
+        let mut meshes = world.resource_mut::<Assets<Mesh>>();
+        let bead_mesh = meshes.add(abacus::bead_mesh(BeadShape::default(), graphics_quality.bead_resolution()));
+        let bead_lod_mesh = meshes.add(abacus::bead_mesh(BeadShape::default(), graphics_quality.bead_lod_resolution()));
+        let rod_mesh = meshes.add(abacus::rod_mesh());
+
+        let mut materials = world.get_resource_mut::<Assets<AbacusMaterial>>().unwrap();
 
         let initial_bead_color = Color::from(abacus::BEAD_NORMAL_COLOR);
         let initial_bead_hover_color = Color::from(abacus::BEAD_HOVER_COLOR);
         let initial_frame_color = Color::from(abacus::FRAME_COLOR);
 
-        let bead_material = materials.add(StandardMaterial {
-            base_color: initial_bead_color,
-            ..default()
-        });
-        let bead_hover_material = materials.add(StandardMaterial {
-            base_color: initial_bead_hover_color,
-            ..default()
-        });
-        let frame_material = materials.add(StandardMaterial {
-            base_color: initial_frame_color,
-            ..default()
-        });
+        let bead_material = materials.add(abacus_material(initial_bead_color));
+        let bead_hover_material = materials.add(abacus_material(initial_bead_hover_color));
+        let frame_material = materials.add(abacus_material(initial_frame_color));
+        let mut bead_highlight_material = abacus_material(Color::from(abacus::BEAD_HIGHLIGHT_COLOR));
+        bead_highlight_material.base.emissive = LinearRgba::from(abacus::BEAD_HIGHLIGHT_COLOR) * 0.5;
+        let bead_highlight_material = materials.add(bead_highlight_material);
+        let bead_alt_material = materials.add(abacus_material(Color::from(abacus::BEAD_ALT_COLOR)));
 
         Self {
             column_count: 9,
             top_bead_count: 2,
             bottom_bead_count: 5,
             top_bead_base_value: 5,
+            bead_value_multiplier: 1,
+            column_overrides: Vec::new(),
             abacus_base: 10,
+            value_model: ValueModel::default(),
+            horizontal_rods: false,
+            two_tone_beads: false,
+            grooved: false,
+            extra_decks: Vec::new(),
+            ones_on_left: false,
             show_top_text: true,
             show_column_texts: true,
             bead_material,
             bead_hover_material,
+            bead_highlight_material,
+            bead_alt_material,
+            column_bead_materials: Vec::new(),
             frame_material,
+            bead_mesh,
+            bead_lod_mesh,
+            rod_mesh,
             ui_bead_color: initial_bead_color,
             ui_bead_hover_color: initial_bead_hover_color,
             ui_frame_color: initial_frame_color,
+            ui_background_color: ClearColor::default().0,
+            text_color: Color::WHITE,
+            text_size: DEFAULT_TEXT_SIZE,
+            text_font_name: String::new(),
+            text_font: Handle::default(),
+            hud_total: false,
+            hud_text_size: 96.0,
+            presentation_mode: false,
+            digit_grouping: DigitGrouping::default(),
+            currency_mode: false,
+            currency_symbol: DEFAULT_CURRENCY_SYMBOL.to_string(),
+            show_value_in_words: false,
+            group_column_colors: false,
+            arrangement: AbacusArrangement::default(),
+            camera_focus: CameraFocus::default(),
+            ui_scale: 1.0,
+            proportions: Proportions::default(),
+            bead_shape: BeadShape::default(),
+            custom_models: CustomModels::default(),
+            surfaces: Surfaces::default(),
+            wood_grain: WoodGrainSettings::default(),
+            environment: Environment::default(),
+            table: TableSurface::default(),
+            frame_title: FrameTitle::default(),
+            column_colors: Vec::new(),
+            theme: String::new(),
+            sound_volume: 0.5,
+            sound_muted: false,
+            sound_theme: SoundTheme::default(),
+            haptics_enabled: true,
+            physics_enabled: false,
+            bead_easing: BeadEasing::default(),
+            bead_move_duration: 0.15,
+            bead_stagger: 0.03,
+            reduced_motion: prefers_reduced_motion(),
+            attract_mode: false,
+            attract_style: AttractStyle::default(),
+            attract_interval: DEFAULT_ATTRACT_INTERVAL,
+            flat_mode: false,
+            graphics_quality,
+            screenshot_hides_ui: true,
+        }
+    }
+}
+
+/// Honors the browser's prefers-reduced-motion media query on the web build.
+#[cfg(target_arch = "wasm32")]
+fn prefers_reduced_motion() -> bool {
+    web_sys::window()
+        .and_then(|window| window.match_media("(prefers-reduced-motion: reduce)").ok().flatten())
+        .is_some_and(|query| query.matches())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn prefers_reduced_motion() -> bool {
+    false // No system-wide preference is read on native builds
+}
+
+impl AbacusSettings {
+    /// How far the total is lifted to make room for the value-in-words line beneath it.
+    fn total_text_lift(&self) -> f32 {
+        if !self.show_value_in_words {
+            return 0.0;
+        }
+        // Half the total's height plus half the words line; text is drawn at 0.01 scale
+        self.text_size * self.ui_scale * (1.0 + VALUE_IN_WORDS_SCALE) * 0.5 * 0.01
+    }
+
+    /// How far the words line and total are lifted to make room for the frame's title,
+    /// which takes their place on top of the frame.
+    fn title_lift(&self) -> f32 {
+        if !self.frame_title.is_shown() {
+            return 0.0;
+        }
+        // Half the title's height plus half that of the line just above it
+        let above = if self.show_value_in_words { self.text_size * VALUE_IN_WORDS_SCALE } else { self.text_size };
+        (self.frame_title.size + above * self.ui_scale) * 0.5 * 0.01
+    }
+
+    /// Value text color of a column; with grouping on, every other block of three
+    /// columns (thousands, millions, ...) is tinted so long numbers are easier to read.
+    fn column_text_color(&self, column: usize) -> Color {
+        if self.group_column_colors && (column / 3) % 2 == 1 {
+            self.text_color.mix(&Color::from(abacus::BEAD_HIGHLIGHT_COLOR), 0.5)
+        } else {
+            self.text_color
         }
     }
 }
@@ -181,14 +920,57 @@ impl SavableAbacusConfig {
             top_bead_count: settings.top_bead_count,
             bottom_bead_count: settings.bottom_bead_count,
             top_bead_base_value: settings.top_bead_base_value,
+            bead_value_multiplier: settings.bead_value_multiplier,
+            column_overrides: settings.column_overrides.clone(),
             abacus_base: settings.abacus_base,
+            value_model: settings.value_model,
+            horizontal_rods: settings.horizontal_rods,
+            two_tone_beads: settings.two_tone_beads,
+            grooved: settings.grooved,
+            extra_decks: settings.extra_decks.clone(),
+            ones_on_left: settings.ones_on_left,
             show_top_text: settings.show_top_text,
             show_column_texts: settings.show_column_texts,
             ui_bead_color: settings.ui_bead_color,
             ui_bead_hover_color: settings.ui_bead_hover_color,
             ui_frame_color: settings.ui_frame_color,
+            proportions: settings.proportions,
+            bead_shape: settings.bead_shape,
+            custom_models: settings.custom_models.clone(),
+            surfaces: settings.surfaces.clone(),
+            wood_grain: settings.wood_grain,
+            environment: settings.environment.clone(),
+            table: settings.table.clone(),
+            frame_title: settings.frame_title.clone(),
+            column_colors: settings.column_colors.clone(),
+            theme: settings.theme.clone(),
+            sound_theme: settings.sound_theme,
         }
     }
+
+    /// Sanitizes the structure like `AbacusStructure::sanitize`, returning the fields that
+    /// were out of range.
+    fn sanitize(&mut self) -> Vec<&'static str> {
+        let mut structure = AbacusStructure::from_config(self);
+        let clamped = structure.sanitize();
+        self.column_count = structure.column_count;
+        self.top_bead_count = structure.top_bead_count;
+        self.bottom_bead_count = structure.bottom_bead_count;
+        self.top_bead_base_value = structure.top_bead_base_value;
+        self.bead_value_multiplier = structure.bead_value_multiplier;
+        self.column_overrides = structure.column_overrides;
+        self.abacus_base = structure.abacus_base;
+        self.extra_decks = structure.extra_decks;
+        clamped
+    }
+}
+
+/// Sanitizes a configuration from a file, warning about the fields that were out of range.
+fn sanitize_config(config: &mut SavableAbacusConfig, localization: &Localization) {
+    let clamped = config.sanitize();
+    if !clamped.is_empty() {
+        warn!("{}", tr!(localization, "log-config-clamped", name = config.name.as_str(), fields = clamped.join(", ")));
+    }
 }
 
 #[derive(Resource)]
@@ -205,43 +987,132 @@ impl Default for WelcomeUiState {
 }
 
 fn main() {
-    App::new()
+    let (startup_options, replay_options) = parse_args();
+    let mut app = App::new();
+    app
         .add_plugins(DefaultPlugins.set(WindowPlugin {
             primary_window: Some(Window {
-                // Make it resize to the available space
-                fit_canvas_to_parent: true,
+                // The web build follows the page's size itself, once it settles (see window_resize)
+                fit_canvas_to_parent: false,
                 // Prevents issues with touch scrolling and back/forward gestures
                 prevent_default_event_handling: true,
-                // Don't allow resizing (can crash on some mobile browsers if left true)
-                resizable: false,
+                resizable: true,
+                // A headless replay runs in a window that's never shown
+                visible: !replay_options.headless,
                 ..default()
             }),
             ..default()
         }))
-        .add_plugins((MeshPickingPlugin, EguiPlugin { enable_multipass_for_primary_context: false }))
+        .add_plugins((AbacusPickingPlugin, EguiPlugin { enable_multipass_for_primary_context: false }))
+        .add_plugins((ConfigFilesPlugin, SceneFilesPlugin, ScreenshotPlugin, ExportPlugin, FlatModePlugin, GraphicsPlugin, StressTestPlugin, HudPlugin, FontsPlugin, PresentationPlugin, AttractPlugin, KioskPlugin, StopwatchPlugin, CountToPlugin, TallyPlugin))
+        .add_plugins((ActiveColumnPlugin, GamepadControlsPlugin, KeyBindingsPlugin, DigitEntryPlugin, ColumnKeysPlugin, ColumnEntryPlugin, ColumnGlowPlugin, ColumnHoverPlugin, CurrencyPlugin, ColumnSplicePlugin, LayoutTransitionsPlugin, CustomModelsPlugin, SurfacesPlugin, WoodGrainPlugin, EnvironmentPlugin))
+        .add_plugins((TablePlugin, FrameTitlePlugin, ColumnColorsPlugin, ThemesPlugin, ConfigGalleryPlugin, WindowLayoutPlugin, TouchGesturesPlugin, FullscreenPlugin))
         .add_event::<AbacusChanged>()
+        .add_event::<BeadArrived>()
+        .add_event::<BeadDeparted>()
+        .add_event::<AbacusCleared>()
+        .add_event::<ColumnCarried>()
+        .add_event::<ClearRequested>()
+        .add_event::<ValueClamped>()
+        .add_event::<RoundFinished>()
+        .add_event::<OperationRequested>()
+        .add_event::<OperationPerformed>()
+        .add_event::<ScriptRequest>()
+        .init_asset::<LessonFile>()
+        .init_asset_loader::<LessonFileLoader>()
         .init_resource::<AbacusSettings>()
         .init_resource::<UserConfigurations>()
         .init_resource::<WelcomeUiState>()
-        .add_systems(Startup, setup)
+        .init_resource::<ColumnOpQueue>()
+        .init_resource::<SoundThemes>()
+        .init_resource::<ShakeDetector>()
+        .init_resource::<AnnouncedColumns>()
+        .init_resource::<ColumnHighlight>()
+        .init_resource::<LessonState>()
+        .init_resource::<Localization>()
+        .init_resource::<SelectedAbacus>()
+        .init_resource::<CameraView>()
+        .init_resource::<AbacusLinks>()
+        .init_resource::<AbacusChains>()
+        .init_resource::<OverflowToast>()
+        .init_resource::<GameState>()
+        .init_resource::<SessionHistory>()
+        .init_resource::<Profiles>()
+        .init_resource::<LessonRecorder>()
+        .init_resource::<MacroLibrary>()
+        .init_resource::<Snapshots>()
+        .init_resource::<ValueHistory>()
+        .init_resource::<OperationLog>()
+        .init_resource::<StartupValue>()
+        .init_resource::<Console>()
+        .insert_resource(startup_options)
+        .insert_resource(replay_options)
+        .add_observer(select_clicked_abacus)
+        .add_systems(Startup, ((read_share_link, apply_startup_options, setup).chain(), spawn_announcer, load_lesson_library, init_replay_session))
         .add_systems(Update, 
             (
                 move_all_abacus_beads,
-                animate_beads,
+                (animate_beads, play_bead_sounds).chain(),
+                haptic_feedback,
                 update_text_visibility,
                 ui_system,
                 welcome_ui_system,
+                install_cjk_font,
                 abacus_rotation_system,
+                (play_replay, play_macros, perform_operations, record_macro_operations, record_replay_events, log_operations, run_column_ops).chain(),
+                (clear_shortcut_system, detect_shake, handle_clear_requests).chain(),
+                animate_tumble,
+                (lesson_ui_system, lesson_editor_ui_system, macros_ui_system, snapshots_ui_system, operation_log_ui_system, console_ui_system),
+                (record_value_history, history_ui_system, undo_redo_system).chain(),
+                apply_column_highlights,
+                (ensure_abacus_selected, apply_startup_value, load_selected_structure, arrange_abaci, focus_camera).chain(),
+                (flash_overflowing_abaci, overflow_toast_ui),
+                (game_ui_system, update_game_target_text).chain(),
+                (sync_profile_data, record_session_stats, save_session_stats, save_replay_on_exit, stats_ui_system).chain(),
             )
         )
         .add_systems(Update, 
         (
-                update_abacus_values,
-                update_abacus_texts
-            ).chain().run_if(on_event::<AbacusChanged>),
+                update_abacus_values.run_if(on_event::<AbacusChanged>),
+                sync_linked_abaci.run_if(on_event::<AbacusChanged>),
+                check_game_target.run_if(on_event::<AbacusChanged>),
+                // Grouping and language also change how the total reads
+                update_abacus_texts.run_if(
+                    on_event::<AbacusChanged>
+                        .or(resource_changed::<AbacusSettings>)
+                        .or(resource_changed::<Localization>),
+                ),
+                announce_abacus_changes.run_if(on_event::<AbacusChanged>),
+            ).chain(),
         )
-        .add_systems(Startup, init_refresh_rate)
-        .run();
+        .add_systems(PostUpdate, face_camera.before(TransformSystem::TransformPropagate))
+        .add_systems(Startup, init_refresh_rate);
+
+    #[cfg(feature = "physics")]
+    app.add_plugins(physics::BeadPhysicsPlugin);
+
+    #[cfg(feature = "scripting")]
+    app.add_plugins(scripting::ScriptingPlugin);
+
+    #[cfg(feature = "consistency-check")]
+    app.add_plugins(consistency::ConsistencyCheckPlugin);
+
+    #[cfg(target_arch = "wasm32")]
+    app.add_plugins((js_api::JsApiPlugin, window_resize::WindowResizePlugin));
+
+    #[cfg(not(target_arch = "wasm32"))]
+    app.add_plugins(turntable::TurntablePlugin);
+
+    #[cfg(all(feature = "remote", not(target_arch = "wasm32")))]
+    app.add_plugins((remote::RemotePlugin, broadcast::BroadcastPlugin));
+
+    #[cfg(all(feature = "osc", not(target_arch = "wasm32")))]
+    app.add_plugins(osc::OscPlugin);
+
+    #[cfg(all(feature = "midi", not(target_arch = "wasm32")))]
+    app.add_plugins(midi::MidiPlugin);
+
+    app.run();
 }
 
 fn init_refresh_rate(mut winit: ResMut<WinitSettings>) {
@@ -254,7 +1125,6 @@ pub struct MainCameraAnchor;
 
 fn setup(
     mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
     settings: Res<AbacusSettings>,
 ) {
     // Anchor entity — controls transform & projection
@@ -282,70 +1152,98 @@ fn setup(
         ]
     ));
 
-    commands.spawn((
-        PointLight {
-            shadows_enabled: true,
-            intensity: 10_000_000.,
-            range: 100.0,
-            shadow_depth_bias: 0.2,
-            ..default()
-        },
-        Transform::from_xyz(8.0, 16.0, -8.0),
-        Visibility::Inherited,
-        InheritedVisibility::default(),
-    ));
-    
     abacus::spawn_abacus(
         &mut commands,
-        &mut meshes,
         &settings,
+        0,
     );
 }
 
+/// Shows the structure of a newly selected abacus in the settings panel.
+fn load_selected_structure(
+    selected: Res<SelectedAbacus>,
+    structure_query: Query<&AbacusStructure>,
+    mut settings: ResMut<AbacusSettings>,
+) {
+    if !selected.is_changed() {
+        return;
+    }
+    if let Some(structure) = selected.0.and_then(|entity| structure_query.get(entity).ok()) {
+        structure.apply_to(&mut settings);
+    }
+}
+
 fn move_all_abacus_beads(
     query: Query<(&BeadsOf, &AbacusLong)>,
-    mut beads: Query<&mut AbacusBead>,
+    mut beads: Query<(&mut AbacusBead, &Transform)>,
+    mut departures: EventWriter<BeadDeparted>,
+    settings: Res<AbacusSettings>,
+    mut last_bead_spacing: Local<f32>,
 ) {
+    // Beads spreading out or closing up to a new spacing haven't left their groups
+    let respaced = std::mem::replace(&mut *last_bead_spacing, settings.proportions.bead_spacing) != settings.proportions.bead_spacing;
     for (beads_of, long) in &query {
         let upper_count = long.value as usize;
 
         let mut y = 0.0;
+        let mut moving_count = 0;
 
-        for &bead in &beads_of[..upper_count] {
-            if let Ok(mut bead) = beads.get_mut(bead) {
-                bead.target = Vec3::new(0.0, y, 0.0);
-                y += BEAD_SPACING;
+        for (i, &bead_entity) in beads_of[..].iter().enumerate() {
+            if i == upper_count {
+                y += LONG_SPACING;
             }
-        }
-
-        y += LONG_SPACING;
-
-        for &bead in &beads_of[upper_count..] {
-            if let Ok(mut bead) = beads.get_mut(bead) {
-                bead.target = Vec3::new(0.0, y, 0.0);
-                y += BEAD_SPACING;
+            if let Ok((mut bead, transform)) = beads.get_mut(bead_entity) {
+                let target = Vec3::new(0.0, y, 0.0);
+                if bead.target != target {
+                    // Freshly spawned beads settle into place silently
+                    if !bead.is_added() && !respaced {
+                        departures.write(BeadDeparted(bead_entity));
+                    }
+                    bead.target = target;
+                    bead.start = transform.translation;
+                    // Stagger beads moving on the same rod so group moves read clearly
+                    bead.elapsed = -(moving_count as f32) * settings.bead_stagger;
+                    moving_count += 1;
+                }
+                y += settings.proportions.bead_spacing;
             }
         }
     }
 }
 
 fn animate_beads(
-    mut query: Query<(&mut Transform, &AbacusBead)>,
+    mut query: Query<(Entity, &mut Transform, &mut AbacusBead)>,
+    mut arrivals: EventWriter<BeadArrived>,
+    settings: Res<AbacusSettings>,
     time: Res<Time>,
 ) {
-    let speed = 10.0; // units per second, adjust as needed
-    for (mut transform, bead) in &mut query {
-        let current = transform.translation;
-        let target = bead.target;
-        if current != target {
-            let direction = target - current;
-            let distance = direction.length();
-            let step = speed * time.delta_secs();
-            if distance <= step {
-                transform.translation = target;
-            } else {
-                transform.translation += direction.normalize() * step;
-            }
+    if settings.physics_enabled {
+        return; // The physics backend moves the beads instead
+    }
+
+    let duration = settings.bead_move_duration.max(f32::EPSILON);
+    for (entity, mut transform, mut bead) in &mut query {
+        if transform.translation == bead.target {
+            continue;
+        }
+
+        if settings.reduced_motion {
+            transform.translation = bead.target;
+            arrivals.write(BeadArrived(entity));
+            continue;
+        }
+
+        bead.elapsed += time.delta_secs();
+        if bead.elapsed <= 0.0 {
+            continue; // Still waiting for its stagger delay
+        }
+
+        let t = (bead.elapsed / duration).min(1.0);
+        if t >= 1.0 {
+            transform.translation = bead.target;
+            arrivals.write(BeadArrived(entity));
+        } else {
+            transform.translation = bead.start.lerp(bead.target, settings.bead_easing.sample(t));
         }
     }
 }
@@ -353,30 +1251,48 @@ fn animate_beads(
 fn update_abacus_values(
     mut abacus_query: Query<&mut Abacus>,
     abacus_long_query: Query<&AbacusLong>,
+    mut clears: EventWriter<AbacusCleared>,
 ) {
     for mut abacus in &mut abacus_query {
-        let _value = abacus.get_total_value(&abacus_long_query);
+        let previous_value = abacus.total_value;
+        let value = abacus.get_total_value(&abacus_long_query);
+        if previous_value != 0 && value == 0 {
+            clears.write(AbacusCleared);
+        }
     }
 }
 
 fn update_abacus_texts(
     abacus_query: Query<&Abacus>,
     abacus_long_query: Query<&AbacusLong>,
-    mut text_query: Query<&mut Text2d>,
+    mut text_query: Query<(&mut Text2d, &mut TextFont)>,
+    settings: Res<AbacusSettings>,
+    localization: Res<Localization>,
+    cjk_font: Option<Res<CjkFont>>,
 ) {
     for abacus in &abacus_query {
         // Format based on abacus numeric base
-        let base = abacus.abacus_base;
+        let base = abacus.text_base();
         
         // Update total value text
-        if let Ok(mut text) = text_query.get_mut(abacus.total_text) {
-            text.0 = abacus.total_value.to_string();
+        if let Ok((mut text, _)) = text_query.get_mut(abacus.total_text) {
+            text.0 = format_total(abacus, &settings, &localization);
+        }
+
+        // The default font has no CJK glyphs, so the words line borrows the one egui uses
+        // unless a font was chosen for the texts
+        if let Ok((mut text, mut font)) = text_query.get_mut(abacus.words_text) {
+            text.0 = number_in_words(abacus.total_value, localization.language);
+            font.font = match &cjk_font {
+                Some(cjk_font) if localization.language.uses_cjk() && settings.text_font_name.is_empty() => cjk_font.0.clone(),
+                _ => settings.text_font.clone(),
+            };
         }
         
         // Update each column's value text
         for (i, &text_entity) in abacus.column_texts.iter().enumerate() {
             let col_value = abacus.get_column_value(i, &abacus_long_query);
-            if let Ok(mut text) = text_query.get_mut(text_entity) {
+            if let Ok((mut text, _)) = text_query.get_mut(text_entity) {
                     let base_repr = format_number_in_base(col_value, base);
                     text.0 = format!("{}", base_repr);
             }
@@ -384,6 +1300,32 @@ fn update_abacus_texts(
     }
 }
 
+/// The total as the texts and panels show it: digits grouped, or in currency mode as money.
+fn format_total(abacus: &Abacus, settings: &AbacusSettings, localization: &Localization) -> String {
+    let separator = settings.digit_grouping.separator(localization);
+    // Cents only make sense in base 10
+    if settings.currency_mode && abacus.abacus_base == 10 {
+        return format_money(abacus.total_value, &settings.currency_symbol, &separator);
+    }
+    group_digits(&abacus.total_value.to_string(), &separator)
+}
+
+/// Inserts `separator` between groups of three digits, counting from the right.
+fn group_digits(digits: &str, separator: &str) -> String {
+    if separator.is_empty() {
+        return digits.to_string();
+    }
+
+    let mut grouped = String::new();
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            grouped.push_str(separator);
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
 /// Formats a number in the specified base (supports bases 2-36)
 fn format_number_in_base(value: u64, base: u64) -> String {
     match base {
@@ -421,180 +1363,299 @@ fn format_number_in_base(value: u64, base: u64) -> String {
     }
 }
 
+/// Picks another abacus by slot, or none.
+fn abacus_slot_combo(ui: &mut egui::Ui, label: String, slot: &mut Option<usize>, options: &[&Abacus], localization: &Localization) {
+    let abacus_label = |abacus: &Abacus| tr!(localization, "abacus-label", number = abacus.slot + 1, base = abacus.abacus_base);
+    let selected_text = options.iter().find(|abacus| Some(abacus.slot) == *slot)
+        .map(|abacus| abacus_label(abacus))
+        .unwrap_or_else(|| tr!(localization, "link-none"));
+    ui.add_enabled_ui(!options.is_empty() || slot.is_some(), |ui| {
+        egui::ComboBox::from_label(label)
+            .selected_text(selected_text)
+            .show_ui(ui, |ui| {
+                ui.selectable_value(slot, None, tr!(localization, "link-none"));
+                for abacus in options {
+                    ui.selectable_value(slot, Some(abacus.slot), abacus_label(abacus));
+                }
+            });
+    });
+}
+
+/// Which abacus the settings panel edits, and how the abaci relate to each other.
+#[derive(SystemParam)]
+struct SceneSelection<'w> {
+    selected: ResMut<'w, SelectedAbacus>,
+    links: ResMut<'w, AbacusLinks>,
+    chains: ResMut<'w, AbacusChains>,
+}
+
+/// What the settings panel saves and loads for the active profile, and the gallery's
+/// pictures of the configurations.
+#[derive(SystemParam)]
+struct SavedData<'w> {
+    user_configs: ResMut<'w, UserConfigurations>,
+    profiles: ResMut<'w, Profiles>,
+    thumbnails: Res<'w, ConfigThumbnails>,
+}
+
+/// Files the settings panel asks for: configurations, scenes, screenshots and models.
+#[derive(SystemParam)]
+struct FileRequests<'w> {
+    configs: EventWriter<'w, ConfigFileRequest>,
+    scenes: EventWriter<'w, SceneFileRequest>,
+    screenshots: EventWriter<'w, ScreenshotRequested>,
+    models: EventWriter<'w, GltfExportRequested>,
+}
+
+/// Whether a change to a structure control should rebuild the abacus now. Changes made by
+/// dragging wait for the release, so sweeping the Columns slider doesn't respawn the abacus
+/// on every frame; `drag_pending` remembers that a drag changed something.
+fn structure_changed(response: egui::Response, drag_pending: &mut bool) -> bool {
+    if response.changed() && response.dragged() {
+        *drag_pending = true;
+        return false;
+    }
+    if response.drag_stopped() && std::mem::take(drag_pending) {
+        return true;
+    }
+    response.changed()
+}
+
 fn ui_system(
     mut contexts: EguiContexts,
     mut settings: ResMut<AbacusSettings>,
-    mut user_configs: ResMut<UserConfigurations>,
+    saved: SavedData,
     mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut standard_materials: ResMut<Assets<StandardMaterial>>,
-    mut abacus_query: Query<&mut Abacus>,
-    mut long_query: Query<&mut AbacusLong>,
-    abacus_entity_query: Query<Entity, With<Abacus>>,
+    mut abacus_materials: ResMut<Assets<AbacusMaterial>>,
+    abacus_query: Query<(Entity, &Abacus)>,
+    long_query: Query<&AbacusLong>,
+    mut op_queue: ResMut<ColumnOpQueue>,
     mut abacus_transform_query: Query<&mut Transform, With<Abacus>>,
+    mut clear_requests: EventWriter<ClearRequested>,
+    mut file_requests: FileRequests,
+    mut clear_color: ResMut<ClearColor>,
+    mut localization: ResMut<Localization>,
+    scene: SceneSelection,
+    mut structure_drag: Local<bool>,
+    mut splice_requests: EventWriter<ColumnSpliceRequested>,
 ) {
+    let SceneSelection { mut selected, mut links, mut chains } = scene;
+    let SavedData { mut user_configs, mut profiles, thumbnails } = saved;
     let ctx = contexts.ctx_mut();
+    // Zoom rather than overriding pixels-per-point, so high-DPI screens keep their native scale
+    if ctx.zoom_factor() != settings.ui_scale {
+        ctx.set_zoom_factor(settings.ui_scale);
+    }
     
     let mut rebuild_abacus_requested = false;
     
-    egui::Window::new("Abacus Settings")
-        .default_pos([10.0, 10.0])
-        .show(ctx, |ui| {
-            ui.heading("Abacus Configuration");
-            
-            // --- Structure Section --- 
-            ui.collapsing("Structure", |ui| {
-                if ui.add(egui::Slider::new(&mut settings.column_count, 1..=20).text("Columns")).changed() { rebuild_abacus_requested = true; };
-                if ui.add(egui::Slider::new(&mut settings.top_bead_count, 0..=2).text("Top Beads (per section)")).changed() { rebuild_abacus_requested = true; };
-                if ui.add(egui::Slider::new(&mut settings.bottom_bead_count, 1..=10).text("Bottom Beads (per section)")).changed() { rebuild_abacus_requested = true; };
-                if ui.add(egui::Slider::new(&mut settings.top_bead_base_value, 1..=10).text("Top Bead Base Value")).changed() { rebuild_abacus_requested = true; };
-                if ui.add(egui::Slider::new(&mut settings.abacus_base, 2..=36).text("Abacus Numeric Base")).changed() { rebuild_abacus_requested = true; };
-            });
-
-            // --- Display Options Section --- 
-            ui.collapsing("Display Options", |ui| {
-            ui.checkbox(&mut settings.show_top_text, "Show Total Value");
-            ui.checkbox(&mut settings.show_column_texts, "Show Column Values");
-            });
-
-            // --- Appearance Section --- 
-            ui.collapsing("Appearance (Live Update)", |ui| {
-                // Directly use .as_rgba() which returns an Srgba, then access fields
-                let (mut r_b, mut g_b, mut b_b, mut a_b) = (0.0, 0.0, 0.0, 0.0); // bead_color
-                if let Color::Srgba(srgba) = settings.ui_bead_color {
-                    r_b = srgba.red;
-                    g_b = srgba.green;
-                    b_b = srgba.blue;
-                    a_b = srgba.alpha;
-                }
-                let mut bead_color_arr = [r_b, g_b, b_b, a_b];
-
-                let (mut r_bh, mut g_bh, mut b_bh, mut a_bh) = (0.0, 0.0, 0.0, 0.0); // bead_hover_color
-                if let Color::Srgba(srgba) = settings.ui_bead_hover_color {
-                    r_bh = srgba.red;
-                    g_bh = srgba.green;
-                    b_bh = srgba.blue;
-                    a_bh = srgba.alpha;
-                }
-                let mut bead_hover_color_arr = [r_bh, g_bh, b_bh, a_bh];
-
-                let (mut r_f, mut g_f, mut b_f, mut a_f) = (0.0, 0.0, 0.0, 0.0); // frame_color
-                if let Color::Srgba(srgba) = settings.ui_frame_color {
-                    r_f = srgba.red;
-                    g_f = srgba.green;
-                    b_f = srgba.blue;
-                    a_f = srgba.alpha;
-                }
-                let mut frame_color_arr = [r_f, g_f, b_f, a_f];
-                
+    // The panels read the localization as they're drawn, so a new language is applied after
+    let mut language = localization.language;
+    show_settings_panels(ctx, &localization, |panel, ui| match panel {
+        SettingsPanel::Structure => {
+            if structure_changed(ui.add(egui::Slider::new(&mut settings.column_count, 1..=MAX_COLUMNS).logarithmic(true).text(tr!(localization, "columns"))), &mut structure_drag) { rebuild_abacus_requested = true; };
+            // Single columns are spliced in and out, keeping the value
+            if let Some(abacus) = selected.0 {
                 ui.horizontal(|ui| {
-                    if ui.color_edit_button_rgba_unmultiplied(&mut bead_color_arr).changed() {
-                        settings.ui_bead_color = Color::Srgba(bevy::color::Srgba::new(bead_color_arr[0], bead_color_arr[1], bead_color_arr[2], bead_color_arr[3]));
-                        if let Some(material) = standard_materials.get_mut(&settings.bead_material) {
-                            material.base_color = settings.ui_bead_color;
+                    let column_count = settings.column_count;
+                    for (splice, message_id, enabled) in [
+                        (ColumnSplice::AddLeft, "add-column-left", column_count < MAX_COLUMNS),
+                        (ColumnSplice::AddRight, "add-column-right", column_count < MAX_COLUMNS),
+                        (ColumnSplice::Remove, "remove-column", column_count > 1),
+                    ] {
+                        if ui.add_enabled(enabled, egui::Button::new(tr!(localization, message_id))).clicked() {
+                            splice_requests.write(ColumnSpliceRequested { abacus, splice });
                         }
                     }
-                    ui.label("Bead Color");
                 });
-                ui.horizontal(|ui| {
-                    if ui.color_edit_button_rgba_unmultiplied(&mut bead_hover_color_arr).changed() {
-                        settings.ui_bead_hover_color = Color::Srgba(bevy::color::Srgba::new(bead_hover_color_arr[0], bead_hover_color_arr[1], bead_hover_color_arr[2], bead_hover_color_arr[3]));
-                        if let Some(material) = standard_materials.get_mut(&settings.bead_hover_material) {
-                            material.base_color = settings.ui_bead_hover_color;
-                        }
+            }
+            if structure_changed(ui.add(egui::Slider::new(&mut settings.top_bead_count, 0..=MAX_TOP_BEADS).text(tr!(localization, "top-beads"))), &mut structure_drag) { rebuild_abacus_requested = true; };
+            if structure_changed(ui.add(egui::Slider::new(&mut settings.bottom_bead_count, 1..=10).text(tr!(localization, "bottom-beads"))), &mut structure_drag) { rebuild_abacus_requested = true; };
+            if structure_changed(ui.add(egui::Slider::new(&mut settings.top_bead_base_value, 1..=10).text(tr!(localization, "top-bead-base-value"))), &mut structure_drag) { rebuild_abacus_requested = true; };
+            if structure_changed(ui.add(egui::Slider::new(&mut settings.abacus_base, 2..=36).text(tr!(localization, "numeric-base"))), &mut structure_drag) { rebuild_abacus_requested = true; };
+            if structure_changed(ui.add(egui::Slider::new(&mut settings.bead_value_multiplier, 1..=10).text(tr!(localization, "bead-value-multiplier"))), &mut structure_drag) { rebuild_abacus_requested = true; };
+            let value_model = settings.value_model;
+            egui::ComboBox::from_label(tr!(localization, "value-model"))
+                .selected_text(tr!(localization, settings.value_model.message_id()))
+                .show_ui(ui, |ui| {
+                    for model in ValueModel::ALL {
+                        ui.selectable_value(&mut settings.value_model, model, tr!(localization, model.message_id()));
                     }
-                    ui.label("Bead Hover (non-mobile)");
                 });
+            if settings.value_model != value_model { rebuild_abacus_requested = true; };
+            if ui.checkbox(&mut settings.horizontal_rods, tr!(localization, "horizontal-rods")).changed() { rebuild_abacus_requested = true; };
+            if ui.checkbox(&mut settings.two_tone_beads, tr!(localization, "two-tone-beads")).changed() { rebuild_abacus_requested = true; };
+            if ui.checkbox(&mut settings.grooved, tr!(localization, "grooves")).changed() { rebuild_abacus_requested = true; };
+            if ui.checkbox(&mut settings.ones_on_left, tr!(localization, "ones-on-left")).changed() { rebuild_abacus_requested = true; };
+            if let Some((_, abacus)) = selected.0.and_then(|entity| abacus_query.get(entity).ok()) {
+                ui.label(tr!(localization, "max-value", value = abacus.max_total_value()));
+            }
+
+            ui.separator();
+            ui.label(tr!(localization, "extra-decks"));
+            let mut deck_to_remove: Option<usize> = None;
+            for (idx, deck) in settings.extra_decks.iter_mut().enumerate() {
                 ui.horizontal(|ui| {
-                    if ui.color_edit_button_rgba_unmultiplied(&mut frame_color_arr).changed() {
-                        settings.ui_frame_color = Color::Srgba(bevy::color::Srgba::new(frame_color_arr[0], frame_color_arr[1], frame_color_arr[2], frame_color_arr[3]));
-                        if let Some(material) = standard_materials.get_mut(&settings.frame_material) {
-                            material.base_color = settings.ui_frame_color;
-                        }
+                    if structure_changed(ui.add(egui::DragValue::new(&mut deck.bead_count).range(1..=10).prefix(format!("{} ", tr!(localization, "deck-beads")))), &mut structure_drag) { rebuild_abacus_requested = true; };
+                    if structure_changed(ui.add(egui::DragValue::new(&mut deck.bead_value).range(1..=100).prefix("x")), &mut structure_drag) { rebuild_abacus_requested = true; };
+                    if ui.button(tr!(localization, "remove")).clicked() {
+                        deck_to_remove = Some(idx);
                     }
-                    ui.label("Frame Color");
                 });
-            });
+            }
+            if let Some(idx) = deck_to_remove {
+                settings.extra_decks.remove(idx);
+                rebuild_abacus_requested = true;
+            }
+            if ui.add_enabled(settings.extra_decks.len() < MAX_EXTRA_DECKS, egui::Button::new(tr!(localization, "add-deck"))).clicked() {
+                let bead_value = settings.extra_decks.last().map_or(settings.top_bead_base_value, |deck| deck.bead_value);
+                settings.extra_decks.push(DeckLayout { bead_count: 1, bead_value });
+                rebuild_abacus_requested = true;
+            }
 
-            // --- Controls Section --- 
-            ui.collapsing("Controls", |ui| {
-                // Reset Rotation Button
-                if ui.button("Reset Rotation").clicked() {
-                    if let Ok(mut transform) = abacus_transform_query.single_mut() {
-                        transform.rotation = Quat::IDENTITY;
-                    }
-                }
-                
-                ui.separator();
-                
-                // Set Value Input and Button
-                ui.label("Set Abacus Value:");
+            ui.separator();
+            ui.label(tr!(localization, "column-overrides"));
+            let max_column_index = settings.column_count.saturating_sub(1);
+            let (default_top_value, default_base) = (settings.top_bead_base_value, settings.abacus_base);
+            let mut override_to_remove: Option<usize> = None;
+            for (idx, column_override) in settings.column_overrides.iter_mut().enumerate() {
                 ui.horizontal(|ui| {
-                    let set_response = ui.add_sized([100.0, ui.available_height()], 
-                        egui::TextEdit::singleline(&mut user_configs.set_value_input)
-                            .hint_text("Enter value")
-                    );
-                    let set_submitted = set_response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
-                    if ui.button("Set").clicked() || set_submitted {
-                        match user_configs.set_value_input.trim().parse::<u64>() {
-                            Ok(value) => {
-                                if let Ok(mut abacus) = abacus_query.single_mut() {
-                                    info!("Setting abacus total value to: {}", value);
-                                    abacus.set_total_value(value, &mut long_query, &mut commands);
-                                }
-                            }
-                            Err(_) => { info!("Invalid input for Set: Please enter a non-negative integer."); }
-                        }
+                    if structure_changed(ui.add(egui::DragValue::new(&mut column_override.column).range(0..=max_column_index).prefix(format!("{} ", tr!(localization, "override-column")))), &mut structure_drag) { rebuild_abacus_requested = true; };
+                    if structure_changed(ui.add(egui::DragValue::new(&mut column_override.top_bead_count).range(0..=MAX_TOP_BEADS).prefix(format!("{} ", tr!(localization, "override-top")))), &mut structure_drag) { rebuild_abacus_requested = true; };
+                    if structure_changed(ui.add(egui::DragValue::new(&mut column_override.bottom_bead_count).range(1..=10).prefix(format!("{} ", tr!(localization, "override-bottom")))), &mut structure_drag) { rebuild_abacus_requested = true; };
+                    if structure_changed(ui.add(egui::DragValue::new(&mut column_override.bead_value_multiplier).range(1..=10).prefix("x")), &mut structure_drag) { rebuild_abacus_requested = true; };
+                    // Top bead value and base follow the abacus's until changed here
+                    let mut top_value = column_override.top_bead_base_value.unwrap_or(default_top_value);
+                    let response = ui.add(egui::DragValue::new(&mut top_value).range(1..=10).prefix(format!("{} ", tr!(localization, "override-top-value"))));
+                    if response.changed() { column_override.top_bead_base_value = Some(top_value); };
+                    if structure_changed(response, &mut structure_drag) { rebuild_abacus_requested = true; };
+                    let mut radix = column_override.radix.unwrap_or(default_base);
+                    let response = ui.add(egui::DragValue::new(&mut radix).range(2..=36).prefix(format!("{} ", tr!(localization, "override-base"))));
+                    if response.changed() { column_override.radix = Some(radix); };
+                    if structure_changed(response, &mut structure_drag) { rebuild_abacus_requested = true; };
+                    if ui.button(tr!(localization, "remove")).clicked() {
+                        override_to_remove = Some(idx);
                     }
                 });
+            }
+            if let Some(idx) = override_to_remove {
+                settings.column_overrides.remove(idx);
+                rebuild_abacus_requested = true;
+            }
+            if ui.button(tr!(localization, "add-column-override")).clicked() {
+                let new_override = ColumnOverride {
+                    column: max_column_index,
+                    top_bead_count: settings.top_bead_count,
+                    bottom_bead_count: settings.bottom_bead_count,
+                    bead_value_multiplier: settings.bead_value_multiplier,
+                    top_bead_base_value: None,
+                    radix: None,
+                };
+                settings.column_overrides.push(new_override);
+                rebuild_abacus_requested = true;
+            }
 
-                ui.separator();
-                
-                // Add/Subtract Value Input and Buttons
-                ui.label("Modify Abacus Value:");
+            // --- Abaci in the scene ---
+            ui.collapsing(tr!(localization, "section-scene"), |ui| {
                 ui.horizontal(|ui| {
-                    let modify_response = ui.add_sized([100.0, ui.available_height()], 
-                        egui::TextEdit::singleline(&mut user_configs.modify_value_input)
-                            .hint_text("Enter amount")
-                    );
-                    let modify_submitted_add = modify_response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)); // Treat Enter as Add
-                    
-                    let add_clicked = ui.button("Add").clicked() || modify_submitted_add;
-                    let subtract_clicked = ui.button("Subtract").clicked();
-
-                    if add_clicked || subtract_clicked {
-                        match user_configs.modify_value_input.trim().parse::<u64>() {
-                            Ok(amount) => {
-                                if let Ok(mut abacus) = abacus_query.single_mut() {
-                                    let current_value = abacus.total_value;
-                                    let new_value = if add_clicked {
-                                        current_value.saturating_add(amount)
-                                    } else { // subtract_clicked must be true
-                                        current_value.saturating_sub(amount)
-                                    };
-                                    
-                                    info!("Setting abacus total value to: {} (from {} {} {})", 
-                                        new_value, current_value, if add_clicked {"+"} else {"-"}, amount);
-                                    abacus.set_total_value(new_value, &mut long_query, &mut commands);
-                                } else {
-                                    warn!("Could not find Abacus component to modify value.");
-                                }
-                                // Optionally clear input after modifying
-                                // user_configs.modify_value_input.clear();
+                    if ui.button(tr!(localization, "add-abacus")).clicked() {
+                        let slot = abacus_query.iter().map(|(_, abacus)| abacus.slot + 1).max().unwrap_or(0);
+                        selected.0 = Some(abacus::spawn_abacus(&mut commands, &settings, slot));
+                    }
+                    if ui.add_enabled(abacus_query.iter().count() > 1, egui::Button::new(tr!(localization, "remove-abacus"))).clicked() {
+                        if let Some(entity) = selected.0.take() {
+                            if let Ok((_, abacus)) = abacus_query.get(entity) {
+                                links.unlink(abacus.slot);
+                                chains.remove(abacus.slot);
                             }
-                            Err(_) => { info!("Invalid input for Modify: Please enter a non-negative integer."); }
+                            op_queue.clear_abacus(entity);
+                            commands.entity(entity).despawn();
                         }
                     }
                 });
+                egui::ComboBox::from_label(tr!(localization, "arrangement"))
+                    .selected_text(tr!(localization, settings.arrangement.message_id()))
+                    .show_ui(ui, |ui| {
+                        for arrangement in AbacusArrangement::ALL {
+                            ui.selectable_value(&mut settings.arrangement, arrangement, tr!(localization, arrangement.message_id()));
+                        }
+                    });
+                egui::ComboBox::from_label(tr!(localization, "camera-focus"))
+                    .selected_text(tr!(localization, settings.camera_focus.message_id()))
+                    .show_ui(ui, |ui| {
+                        for focus in CameraFocus::ALL {
+                            ui.selectable_value(&mut settings.camera_focus, focus, tr!(localization, focus.message_id()));
+                        }
+                    });
+
+                // Linking shows the same total in another abacus's base; chaining carries overflow into another
+                if let Some((_, current)) = selected.0.and_then(|entity| abacus_query.get(entity).ok()) {
+                    let mut others: Vec<&Abacus> = abacus_query.iter().map(|(_, abacus)| abacus).filter(|abacus| abacus.slot != current.slot).collect();
+                    others.sort_by_key(|abacus| abacus.slot);
+
+                    let current_partner = links.partner(current.slot);
+                    let mut partner = current_partner;
+                    abacus_slot_combo(ui, tr!(localization, "link-with"), &mut partner, &others, &localization);
+                    if partner != current_partner {
+                        match partner {
+                            Some(partner) => links.link(current.slot, partner),
+                            None => links.unlink(current.slot),
+                        }
+                        // Sync right away so the newly linked abacus takes this one's total
+                        commands.send_event(AbacusChanged);
+                    }
+
+                    let current_next = chains.carries_into(current.slot);
+                    let mut next = current_next;
+                    let chainable: Vec<&Abacus> = others.iter().copied().filter(|abacus| chains.can_chain(current.slot, abacus.slot)).collect();
+                    abacus_slot_combo(ui, tr!(localization, "carries-into"), &mut next, &chainable, &localization);
+                    if next != current_next {
+                        match next {
+                            Some(next) => chains.chain(current.slot, next),
+                            None => chains.unchain(current.slot),
+                        }
+                    }
+                }
             });
 
             // --- Save/Load Configurations Section --- 
-            ui.collapsing("Save/Load Configurations", |ui| {
+            ui.collapsing(tr!(localization, "section-save-load"), |ui| {
+                profile_picker(ui, &mut profiles, &localization);
+                ui.separator();
+
+                // Teachers send students a link that opens this abacus and value in the web build
+                if ui.button(tr!(localization, "copy-share-link")).clicked() {
+                    let value = selected.0.and_then(|entity| abacus_query.get(entity).ok()).map_or(0, |(_, abacus)| abacus.total_value);
+                    ui.ctx().copy_text(share_link(&settings, value));
+                    info!("{}", tr!(localization, "log-share-link-copied"));
+                }
+                // Files for taking an abacus to another machine, through the system's dialogs
+                if cfg!(not(target_arch = "wasm32")) {
+                    ui.horizontal(|ui| {
+                        if ui.button(tr!(localization, "import-config")).clicked() {
+                            file_requests.configs.write(ConfigFileRequest::Import);
+                        }
+                        if ui.button(tr!(localization, "export-config")).clicked() {
+                            file_requests.configs.write(ConfigFileRequest::Export);
+                        }
+                    });
+                    // Every abacus with its beads, not just the settings
+                    ui.horizontal(|ui| {
+                        if ui.button(tr!(localization, "load-scene")).clicked() {
+                            file_requests.scenes.write(SceneFileRequest::Load);
+                        }
+                        if ui.button(tr!(localization, "save-scene")).clicked() {
+                            file_requests.scenes.write(SceneFileRequest::Save);
+                        }
+                    });
+                }
+                ui.separator();
+
                 ui.horizontal(|ui| {
-                    ui.label("Config Name:");
-                    ui.text_edit_singleline(&mut user_configs.new_config_name);
+                    let name_label = ui.label(tr!(localization, "config-name"));
+                    ui.text_edit_singleline(&mut user_configs.new_config_name).labelled_by(name_label.id);
                 });
-                if ui.button("Save Current Configuration").clicked() {
+                if ui.button(tr!(localization, "save-config")).clicked() {
                     let name_to_save = user_configs.new_config_name.trim().to_string(); // Clone and trim here
                     if !name_to_save.is_empty() {
                         // Prevent duplicates by name, or update existing
@@ -604,29 +1665,37 @@ fn ui_system(
                             user_configs.configs.push(SavableAbacusConfig::from_settings(name_to_save, &settings));
                         }
                         user_configs.new_config_name.clear(); // Clear the original mutable field
-                        info!("Configuration saved.");
+                        user_configs.configs_changed = true;
+                        info!("{}", tr!(localization, "log-config-saved"));
                     } else {
-                        info!("Please enter a name to save the configuration.");
+                        info!("{}", tr!(localization, "log-config-name-missing"));
                     }
                 }
 
                 ui.separator();
-                
+            
                 let mut newly_selected_name: Option<String> = None;
-                
-                egui::ComboBox::new("load_config_combobox_unique_id", "") 
-                    .selected_text(user_configs.selected_config_name_to_load.as_str())
-                    .show_ui(ui, |ui| {
-                        for conf in user_configs.configs.iter() { // Immutable borrow for iteration
-                            // selectable_value internally compares conf.name with the current selected_config_name_to_load
-                            // and updates its internal state. We capture if it was clicked.
-                            if ui.selectable_label(user_configs.selected_config_name_to_load == conf.name, &conf.name).clicked() {
+            
+                // A picture of each configuration, to pick one by sight
+                ui.label(tr!(localization, "saved-config"));
+                egui::ScrollArea::vertical().id_salt("config_gallery").max_height(260.0).show(ui, |ui| {
+                    ui.horizontal_wrapped(|ui| {
+                        let thumbnail_size = egui::vec2(THUMBNAIL_SIZE.x as f32, THUMBNAIL_SIZE.y as f32);
+                        for conf in user_configs.configs.iter() {
+                            let selected = user_configs.selected_config_name_to_load == conf.name;
+                            let button = match thumbnails.texture(&conf.name) {
+                                Some(texture) => egui::Button::image_and_text(egui::Image::new(egui::load::SizedTexture::new(texture, thumbnail_size)), conf.name.as_str()),
+                                // Not drawn yet
+                                None => egui::Button::new(conf.name.as_str()).min_size(thumbnail_size),
+                            };
+                            if ui.add(button.selected(selected)).clicked() {
                                 newly_selected_name = Some(conf.name.clone());
                             }
                         }
                     });
-                
-                // Apply the selection change after the ComboBox UI is built
+                });
+            
+                // Apply the selection change after the gallery is built
                 if let Some(name) = newly_selected_name {
                     user_configs.selected_config_name_to_load = name;
                 }
@@ -637,79 +1706,474 @@ fn ui_system(
                     user_configs.selected_config_name_to_load = user_configs.configs[0].name.clone();
                 }
 
-                if ui.button("Load Selected Configuration").clicked() {
+                if ui.button(tr!(localization, "load-config")).clicked() {
                     let name_to_load = user_configs.selected_config_name_to_load.clone();
                     if !name_to_load.is_empty() {
                         if let Some(loaded_config) = user_configs.configs.iter().find(|c| c.name == name_to_load).cloned() { // Clone the config to avoid borrow issues
                             // Use the helper function
-                            apply_config(&mut settings, &mut standard_materials, &loaded_config);
-                            
+                            apply_config(&mut settings, &mut abacus_materials, &loaded_config);
+                        
                             rebuild_abacus_requested = true;
-                            info!("Configuration '{}' loaded.", loaded_config.name);
+                            info!("{}", tr!(localization, "log-config-loaded", name = loaded_config.name.as_str()));
                         } else {
-                            info!("Selected configuration '{}' not found to load.", name_to_load);    
+                            info!("{}", tr!(localization, "log-config-not-found", name = name_to_load.as_str()));    
                         }
                     } else if !user_configs.configs.is_empty() {
                         // Attempt to load the first one
                         let first_config = user_configs.configs[0].clone(); // Clone here too
-                        apply_config(&mut settings, &mut standard_materials, &first_config);
+                        apply_config(&mut settings, &mut abacus_materials, &first_config);
                         rebuild_abacus_requested = true;
-                        info!("Loaded first available configuration '{}'.", first_config.name);
+                        info!("{}", tr!(localization, "log-first-config-loaded", name = first_config.name.as_str()));
                     } else {
-                        info!("No configuration selected or available to load.");
+                        info!("{}", tr!(localization, "log-no-config"));
                     }
                 }
                 // Optional: Delete button
-                if ui.button("Delete Selected Configuration").clicked() {
+                if ui.button(tr!(localization, "delete-config")).clicked() {
                     let name_to_delete = user_configs.selected_config_name_to_load.clone();
                     if !name_to_delete.is_empty() {
                         if let Some(pos) = user_configs.configs.iter().position(|c| c.name == name_to_delete) {
                             user_configs.configs.remove(pos);
+                            user_configs.configs_changed = true;
                             user_configs.selected_config_name_to_load.clear(); // Clear selection after delete
-                            info!("Configuration '{}' deleted.", name_to_delete);
+                            info!("{}", tr!(localization, "log-config-deleted", name = name_to_delete.as_str()));
                         } else {
-                             info!("Configuration '{}' not found to delete.", name_to_delete);
+                             info!("{}", tr!(localization, "log-config-delete-missing", name = name_to_delete.as_str()));
                         }
                     } else {
-                        info!("No configuration selected to delete.");
+                        info!("{}", tr!(localization, "log-no-config-to-delete"));
                     }
                 }
             });
-            
+
             // --- Rebuild Button --- 
             // ui.add_space(15.0);
             // if ui.button("Rebuild Abacus (Apply Structure Changes)").clicked() {
             //     rebuild_abacus_requested = true;
             // }
-        });
+        }
+        SettingsPanel::Appearance => {
+            // Directly use .as_rgba() which returns an Srgba, then access fields
+            let (mut r_b, mut g_b, mut b_b, mut a_b) = (0.0, 0.0, 0.0, 0.0); // bead_color
+            if let Color::Srgba(srgba) = settings.ui_bead_color {
+                r_b = srgba.red;
+                g_b = srgba.green;
+                b_b = srgba.blue;
+                a_b = srgba.alpha;
+            }
+            let mut bead_color_arr = [r_b, g_b, b_b, a_b];
 
-    if rebuild_abacus_requested {
-        info!("Rebuilding abacus structure");
-        for entity in abacus_entity_query.iter() {
-                    commands.entity(entity).despawn();
+            let (mut r_bh, mut g_bh, mut b_bh, mut a_bh) = (0.0, 0.0, 0.0, 0.0); // bead_hover_color
+            if let Color::Srgba(srgba) = settings.ui_bead_hover_color {
+                r_bh = srgba.red;
+                g_bh = srgba.green;
+                b_bh = srgba.blue;
+                a_bh = srgba.alpha;
+            }
+            let mut bead_hover_color_arr = [r_bh, g_bh, b_bh, a_bh];
+
+            let (mut r_f, mut g_f, mut b_f, mut a_f) = (0.0, 0.0, 0.0, 0.0); // frame_color
+            if let Color::Srgba(srgba) = settings.ui_frame_color {
+                r_f = srgba.red;
+                g_f = srgba.green;
+                b_f = srgba.blue;
+                a_f = srgba.alpha;
+            }
+            let mut frame_color_arr = [r_f, g_f, b_f, a_f];
+            
+            ui.horizontal(|ui| {
+                let color_response = ui.color_edit_button_rgba_unmultiplied(&mut bead_color_arr);
+                if color_response.changed() {
+                    settings.ui_bead_color = Color::Srgba(bevy::color::Srgba::new(bead_color_arr[0], bead_color_arr[1], bead_color_arr[2], bead_color_arr[3]));
+                    if let Some(material) = abacus_materials.get_mut(&settings.bead_material) {
+                        material.base.base_color = settings.ui_bead_color;
+                    }
                 }
-                
-                abacus::spawn_abacus(
-                    &mut commands,
-                    &mut meshes,
-            &settings, 
-                );
+                let label = ui.label(tr!(localization, "bead-color"));
+                color_response.labelled_by(label.id);
+            });
+            ui.horizontal(|ui| {
+                let color_response = ui.color_edit_button_rgba_unmultiplied(&mut bead_hover_color_arr);
+                if color_response.changed() {
+                    settings.ui_bead_hover_color = Color::Srgba(bevy::color::Srgba::new(bead_hover_color_arr[0], bead_hover_color_arr[1], bead_hover_color_arr[2], bead_hover_color_arr[3]));
+                    if let Some(material) = abacus_materials.get_mut(&settings.bead_hover_material) {
+                        material.base.base_color = settings.ui_bead_hover_color;
+                    }
+                }
+                let label = ui.label(tr!(localization, "bead-hover-color"));
+                color_response.labelled_by(label.id);
+            });
+            ui.horizontal(|ui| {
+                let color_response = ui.color_edit_button_rgba_unmultiplied(&mut frame_color_arr);
+                if color_response.changed() {
+                    settings.ui_frame_color = Color::Srgba(bevy::color::Srgba::new(frame_color_arr[0], frame_color_arr[1], frame_color_arr[2], frame_color_arr[3]));
+                    if let Some(material) = abacus_materials.get_mut(&settings.frame_material) {
+                        material.base.base_color = settings.ui_frame_color;
+                    }
+                }
+                let label = ui.label(tr!(localization, "frame-color"));
+                color_response.labelled_by(label.id);
+            });
+            ui.horizontal(|ui| {
+                let mut background_color_arr = settings.ui_background_color.to_srgba().to_f32_array();
+                let color_response = ui.color_edit_button_rgba_unmultiplied(&mut background_color_arr);
+                if color_response.changed() {
+                    settings.ui_background_color = Color::Srgba(bevy::color::Srgba::from_f32_array(background_color_arr));
+                    clear_color.0 = settings.ui_background_color;
+                }
+                let label = ui.label(tr!(localization, "background-color"));
+                color_response.labelled_by(label.id);
+            });
+            ui.collapsing(tr!(localization, "surfaces"), |ui| {
+                surface_controls(ui, &mut settings.surfaces, &localization);
+            });
+            wood_grain_controls(ui, &mut settings.wood_grain, &localization);
+            frame_title_controls(ui, &mut settings.frame_title, &localization);
+            let column_count = settings.column_count;
+            column_color_controls(ui, &mut settings.column_colors, column_count, &localization);
+            ui.add(egui::Slider::new(&mut settings.text_size, 32.0..=160.0).text(tr!(localization, "value-text-size")));
+            egui::ComboBox::from_label(tr!(localization, "bead-shape"))
+                .selected_text(tr!(localization, settings.bead_shape.message_id()))
+                .show_ui(ui, |ui| {
+                    for shape in BeadShape::ALL {
+                        ui.selectable_value(&mut settings.bead_shape, shape, tr!(localization, shape.message_id()));
+                    }
+                });
+            // Compact or exaggerated layouts, e.g. for recording videos
+            ui.add(egui::Slider::new(&mut settings.proportions.bead_spacing, BEAD_HEIGHT..=1.0).text(tr!(localization, "bead-spacing")));
+            ui.add(egui::Slider::new(&mut settings.proportions.column_spacing, 0.6..=3.0).text(tr!(localization, "column-spacing")));
+            ui.add(egui::Slider::new(&mut settings.proportions.bead_radius, 0.25..=0.75).text(tr!(localization, "bead-radius")));
+            ui.add(egui::Slider::new(&mut settings.proportions.model_scale, 0.25..=3.0).text(tr!(localization, "model-scale")));
+            // Fonts are read from assets/fonts, which the web build can't list
+            if cfg!(not(target_arch = "wasm32")) {
+                let default_font = tr!(localization, "text-font-default");
+                egui::ComboBox::from_label(tr!(localization, "text-font"))
+                    .selected_text(if settings.text_font_name.is_empty() { default_font.clone() } else { settings.text_font_name.clone() })
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut settings.text_font_name, String::new(), default_font);
+                        for font in available_fonts() {
+                            ui.selectable_value(&mut settings.text_font_name, font.clone(), font);
+                        }
+                    });
+            }
+
+            ui.horizontal(|ui| {
+                ui.label(tr!(localization, "presets"));
+                for preset in &APPEARANCE_PRESETS {
+                    let preset_name = tr!(localization, preset.message_id);
+                    if ui.button(preset_name.as_str()).clicked() {
+                        apply_appearance_preset(&mut settings, &mut abacus_materials, &mut clear_color, preset);
+                        info!("{}", tr!(localization, "preset-applied", name = preset_name));
+                    }
+                }
+            });
+
+            egui::ComboBox::from_label(tr!(localization, "sound-theme"))
+                .selected_text(tr!(localization, settings.sound_theme.message_id()))
+                .show_ui(ui, |ui| {
+                    for theme in SoundTheme::ALL {
+                        ui.selectable_value(&mut settings.sound_theme, theme, tr!(localization, theme.message_id()));
+                    }
+                });
+
+            // --- Display Options Section --- 
+            ui.collapsing(tr!(localization, "section-display"), |ui| {
+            ui.checkbox(&mut settings.show_top_text, tr!(localization, "show-total-value"));
+            ui.checkbox(&mut settings.hud_total, tr!(localization, "hud-total"));
+            if settings.hud_total {
+                ui.add(egui::Slider::new(&mut settings.hud_text_size, 24.0..=240.0).text(tr!(localization, "hud-text-size")));
+            }
+            ui.checkbox(&mut settings.presentation_mode, tr!(localization, "presentation-mode"));
+            ui.checkbox(&mut settings.show_column_texts, tr!(localization, "show-column-values"));
+            ui.checkbox(&mut settings.show_value_in_words, tr!(localization, "show-value-in-words"));
+            egui::ComboBox::from_label(tr!(localization, "digit-grouping"))
+                .selected_text(tr!(localization, settings.digit_grouping.message_id()))
+                .show_ui(ui, |ui| {
+                    for grouping in DigitGrouping::ALL {
+                        ui.selectable_value(&mut settings.digit_grouping, grouping, tr!(localization, grouping.message_id()));
+                    }
+                });
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut settings.currency_mode, tr!(localization, "money-mode"));
+                if settings.currency_mode {
+                    ui.add(egui::TextEdit::singleline(&mut settings.currency_symbol).desired_width(40.0))
+                        .on_hover_text(tr!(localization, "money-symbol"));
+                }
+            });
+            ui.checkbox(&mut settings.group_column_colors, tr!(localization, "group-column-colors"));
+            ui.add(egui::Slider::new(&mut settings.ui_scale, MIN_UI_SCALE..=MAX_UI_SCALE).text(tr!(localization, "ui-scale")));
+            ui.checkbox(&mut settings.flat_mode, tr!(localization, "flat-mode"));
+            egui::ComboBox::from_label(tr!(localization, "graphics-quality"))
+                .selected_text(tr!(localization, settings.graphics_quality.message_id()))
+                .show_ui(ui, |ui| {
+                    for quality in GraphicsQuality::ALL {
+                        ui.selectable_value(&mut settings.graphics_quality, quality, tr!(localization, quality.message_id()));
+                    }
+                });
+            ui.horizontal(|ui| {
+                if ui.button(tr!(localization, "screenshot")).clicked() {
+                    file_requests.screenshots.write(ScreenshotRequested);
+                }
+                ui.checkbox(&mut settings.screenshot_hides_ui, tr!(localization, "screenshot-hides-ui"));
+            });
+            // A printable diagram of the selected abacus's beads, for worksheets
+            if let Some((_, abacus)) = selected.0.and_then(|entity| abacus_query.get(entity).ok()) {
+                ui.horizontal(|ui| {
+                    let total = format_total(abacus, &settings, &localization);
+                    #[cfg(not(target_arch = "wasm32"))]
+                    if ui.button(tr!(localization, "export-svg")).clicked() {
+                        match storage::export_file("abacus.svg", &abacus_svg(abacus, &long_query, &total)) {
+                            Ok(path) => info!("{}", tr!(localization, "log-svg-exported", path = path.display().to_string())),
+                            Err(error) => warn!("{}", tr!(localization, "log-svg-failed", error = error)),
+                        }
+                    }
+                    // The web build can't write files, so the diagram goes through the clipboard
+                    if ui.button(tr!(localization, "copy-svg")).clicked() {
+                        ui.ctx().copy_text(abacus_svg(abacus, &long_query, &total));
+                        info!("{}", tr!(localization, "log-svg-copied"));
+                    }
+                    // The 3D model, beads where they are, for slides, Blender or AR viewers
+                    if cfg!(not(target_arch = "wasm32")) && ui.button(tr!(localization, "export-gltf")).clicked() {
+                        file_requests.models.write(GltfExportRequested);
+                    }
+                });
             }
+            });
+
+            // --- Animation Section --- 
+            ui.collapsing(tr!(localization, "section-animation"), |ui| {
+                egui::ComboBox::from_label(tr!(localization, "bead-easing"))
+                    .selected_text(tr!(localization, settings.bead_easing.message_id()))
+                    .show_ui(ui, |ui| {
+                        for easing in BeadEasing::ALL {
+                            ui.selectable_value(&mut settings.bead_easing, easing, tr!(localization, easing.message_id()));
+                        }
+                    });
+                ui.add(egui::Slider::new(&mut settings.bead_move_duration, 0.02..=1.0).text(tr!(localization, "move-duration")));
+                ui.add(egui::Slider::new(&mut settings.bead_stagger, 0.0..=0.2).text(tr!(localization, "bead-stagger")));
+                ui.checkbox(&mut settings.reduced_motion, tr!(localization, "reduced-motion"));
+                ui.checkbox(&mut settings.attract_mode, tr!(localization, "attract-mode"));
+                if settings.attract_mode {
+                    egui::ComboBox::from_label(tr!(localization, "attract-style"))
+                        .selected_text(tr!(localization, settings.attract_style.message_id()))
+                        .show_ui(ui, |ui| {
+                            for style in AttractStyle::ALL {
+                                ui.selectable_value(&mut settings.attract_style, style, tr!(localization, style.message_id()));
+                            }
+                        });
+                    ui.add(egui::Slider::new(&mut settings.attract_interval, 0.5..=10.0).text(tr!(localization, "attract-interval")));
+                }
+            });
+        }
+        SettingsPanel::Controls => {
+            egui::ComboBox::from_label(tr!(localization, "language"))
+                .selected_text(language.label())
+                .show_ui(ui, |ui| {
+                    for option in Language::ALL {
+                        ui.selectable_value(&mut language, option, option.label());
+                    }
+                });
+            // Reset Rotation Button
+            if ui.button(tr!(localization, "reset-rotation")).clicked() {
+                if let Some(mut transform) = selected.0.and_then(|entity| abacus_transform_query.get_mut(entity).ok()) {
+                    transform.rotation = Quat::IDENTITY;
+                }
+            }
+            if ui.button(tr!(localization, "reset-window-layout")).clicked() {
+                request_layout_reset(ui.ctx());
+            }
+            if ui.add(egui::Button::new(tr!(localization, "fullscreen")).selected(is_fullscreen(ui.ctx()))).clicked() {
+                request_fullscreen_toggle(ui.ctx());
+            }
+
+            // --- Sound Section --- 
+            ui.collapsing(tr!(localization, "section-sound"), |ui| {
+                ui.checkbox(&mut settings.sound_muted, tr!(localization, "mute"));
+                ui.add_enabled(!settings.sound_muted, egui::Slider::new(&mut settings.sound_volume, 0.0..=1.0).text(tr!(localization, "bead-click-volume")));
+                ui.checkbox(&mut settings.haptics_enabled, tr!(localization, "vibrate-on-tap"));
+            });
+
+            // --- Physics Section --- 
+            #[cfg(feature = "physics")]
+            ui.collapsing(tr!(localization, "section-physics"), |ui| {
+                ui.checkbox(&mut settings.physics_enabled, tr!(localization, "simulated-beads"));
+            });
+        }
+        SettingsPanel::Practice => {
+            if ui.add_sized([ui.available_width(), 28.0], egui::Button::new(tr!(localization, "clear-button"))).clicked() {
+                clear_requests.write(ClearRequested { abacus: selected.0 });
+            }
+
+            // Set Value Input and Button
+            let set_label = ui.label(tr!(localization, "set-abacus-value"));
+            ui.horizontal(|ui| {
+                let set_response = ui.add_sized([100.0, ui.available_height()], 
+                    egui::TextEdit::singleline(&mut user_configs.set_value_input)
+                        .hint_text(tr!(localization, "enter-value"))
+                ).labelled_by(set_label.id);
+                let set_submitted = set_response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                if ui.button(tr!(localization, "set")).clicked() || set_submitted {
+                    match user_configs.set_value_input.trim().parse::<u64>() {
+                        Ok(value) => {
+                            if let Some(abacus_entity) = selected.0 {
+                                commands.send_event(OperationRequested { abacus: abacus_entity, operation: AbacusOperation::Set(value) });
+                            }
+                        }
+                        Err(_) => { info!("{}", tr!(localization, "log-invalid-set")); }
+                    }
+                }
+            });
+
+            ui.separator();
+            
+            // Add/Subtract Value Input and Buttons
+            let modify_label = ui.label(tr!(localization, "modify-abacus-value"));
+            ui.horizontal(|ui| {
+                let modify_response = ui.add_sized([100.0, ui.available_height()], 
+                    egui::TextEdit::singleline(&mut user_configs.modify_value_input)
+                        .hint_text(tr!(localization, "enter-amount"))
+                ).labelled_by(modify_label.id);
+                let modify_submitted_add = modify_response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)); // Treat Enter as Add
+                
+                let add_clicked = ui.button(tr!(localization, "add")).clicked() || modify_submitted_add;
+                let subtract_clicked = ui.button(tr!(localization, "subtract")).clicked();
+
+                if add_clicked || subtract_clicked {
+                    match user_configs.modify_value_input.trim().parse::<u64>() {
+                        Ok(amount) => {
+                            if let Some(abacus_entity) = selected.0 {
+                                let operation = if add_clicked { AbacusOperation::Add(amount) } else { AbacusOperation::Subtract(amount) };
+                                commands.send_event(OperationRequested { abacus: abacus_entity, operation });
+                            } else {
+                                warn!("{}", tr!(localization, "log-abacus-missing"));
+                            }
+                            // Optionally clear input after modifying
+                            // user_configs.modify_value_input.clear();
+                        }
+                        Err(_) => { info!("{}", tr!(localization, "log-invalid-modify")); }
+                    }
+                }
+            });
+
+            // --- Playback Section --- 
+            ui.collapsing(tr!(localization, "section-playback"), |ui| {
+                let mut step_delay = op_queue.step_delay();
+                if ui.add(egui::Slider::new(&mut step_delay, 0.0..=MAX_STEP_DELAY).text(tr!(localization, "step-delay"))).changed() {
+                    op_queue.set_step_delay(step_delay);
+                }
+
+                ui.horizontal(|ui| {
+                    let play_pause_label = if op_queue.paused { tr!(localization, "play") } else { tr!(localization, "pause") };
+                    if ui.button(play_pause_label).clicked() {
+                        op_queue.paused = !op_queue.paused;
+                    }
+                    if ui.add_enabled(op_queue.paused && op_queue.pending_count() > 0, egui::Button::new(tr!(localization, "step"))).clicked() {
+                        op_queue.request_step();
+                    }
+                    if ui.add_enabled(op_queue.pending_count() > 0, egui::Button::new(tr!(localization, "cancel"))).clicked() {
+                        op_queue.clear();
+                    }
+                });
+                ui.label(tr!(localization, "pending-moves", count = op_queue.pending_count()));
+            });
+        }
+    });
+    if language != localization.language {
+        localization.language = language;
+    }
+
+    if rebuild_abacus_requested {
+        info!("{}", tr!(localization, "log-rebuilding"));
+        rebuild_selected_abacus(&mut commands, &settings, &mut selected, &abacus_query, &mut op_queue);
+    }
+}
+
+/// Respawns the selected abacus with the structure in `settings`. Only the selected
+/// abacus takes the new structure; it keeps its place in the scene.
+fn rebuild_selected_abacus(
+    commands: &mut Commands,
+    settings: &AbacusSettings,
+    selected: &mut SelectedAbacus,
+    abacus_query: &Query<(Entity, &Abacus)>,
+    op_queue: &mut ColumnOpQueue,
+) {
+    let old = selected.0.and_then(|entity| abacus_query.get(entity).ok());
+    let slot = old.map_or(0, |(_, abacus)| abacus.slot);
+    let new = abacus::spawn_abacus(
+        commands,
+        settings,
+        slot,
+    );
+    // The old parts stay a frame longer, so the new ones can move on from them
+    if let Some((entity, abacus)) = old {
+        op_queue.clear_abacus(entity);
+        Replaced::mark(commands, entity, abacus, new);
+    }
+    selected.0 = Some(new);
+}
+
+/// Clears the abacus on the Clear shortcut (C or Delete by default).
+fn clear_shortcut_system(
+    input: ActionInput,
+    mut clear_requests: EventWriter<ClearRequested>,
+    selected: Res<SelectedAbacus>,
+) {
+    if input.just_pressed(InputAction::Clear) {
+        clear_requests.write(ClearRequested { abacus: selected.0 });
+    }
 }
 
 fn update_text_visibility(
     settings: Res<AbacusSettings>,
     abacus_query: Query<&Abacus>,
     mut visibility_query: Query<&mut Visibility>,
+    mut text_style_query: Query<(&mut TextFont, &mut TextColor)>,
+    mut transform_query: Query<&mut Transform>,
 ) {
     if !settings.is_changed() {
         return;
     }
     
     for abacus in &abacus_query {
-        // Update total text visibility
+        // Keep size and color in step with the Appearance settings
+        for (i, &text_entity) in abacus.column_texts.iter().enumerate() {
+            if let Ok((mut font, mut color)) = text_style_query.get_mut(text_entity) {
+                font.font_size = settings.text_size * settings.ui_scale;
+                font.font = settings.text_font.clone();
+                color.0 = settings.column_text_color(i);
+            }
+        }
+        if let Ok((mut font, mut color)) = text_style_query.get_mut(abacus.total_text) {
+            font.font_size = settings.text_size * settings.ui_scale;
+            font.font = settings.text_font.clone();
+            color.0 = settings.text_color;
+        }
+        if let Ok((mut font, mut color)) = text_style_query.get_mut(abacus.words_text) {
+            font.font_size = settings.text_size * settings.ui_scale * VALUE_IN_WORDS_SCALE;
+            color.0 = settings.text_color;
+        }
+
+        // The words line sits where the total normally does, so the total moves above it;
+        // a title on the frame lifts them both
+        if let Ok(mut transform) = transform_query.get_mut(abacus.words_text) {
+            transform.translation.y = abacus.words_y() + settings.title_lift();
+        }
+        if let Ok(words_y) = transform_query.get(abacus.words_text).map(|transform| transform.translation.y) {
+            if let Ok(mut transform) = transform_query.get_mut(abacus.total_text) {
+                transform.translation.y = words_y + settings.total_text_lift();
+            }
+        }
+        if let Ok(mut visibility) = visibility_query.get_mut(abacus.words_text) {
+            *visibility = if settings.show_value_in_words {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            };
+        }
+
+        // Update total text visibility; the HUD shows the total instead
         if let Ok(mut visibility) = visibility_query.get_mut(abacus.total_text) {
-            *visibility = if settings.show_top_text {
+            *visibility = if settings.show_top_text && !settings.hud_total && !settings.presentation_mode {
                 Visibility::Inherited
             } else {
                 Visibility::Hidden
@@ -729,14 +2193,38 @@ fn update_text_visibility(
     }
 }
 
+/// Radians per second while a rotate or tilt key is held.
+const KEY_ROTATION_SPEED: f32 = 1.5;
+
 fn abacus_rotation_system(
     time: Res<Time>,
-    mouse_button: Res<ButtonInput<MouseButton>>,
+    input: ActionInput,
     mut mouse_motion_events: EventReader<MouseMotion>,
     mut query: Query<&mut Transform, With<Abacus>>,
+    selected: Res<SelectedAbacus>,
+    mut settings: ResMut<AbacusSettings>,
 ) {
-    // Only process motion when right mouse button is pressed
-    if mouse_button.pressed(MouseButton::Right) {
+    if input.just_pressed(InputAction::CycleCameraFocus) {
+        let next = CameraFocus::ALL.iter().position(|&focus| focus == settings.camera_focus).map_or(0, |i| i + 1);
+        settings.camera_focus = CameraFocus::ALL[next % CameraFocus::ALL.len()];
+    }
+    // Flat mode always faces the front
+    if !settings.flat_mode {
+        if let Some(mut transform) = selected.0.and_then(|entity| query.get_mut(entity).ok()) {
+            if input.just_pressed(InputAction::ResetRotation) {
+                transform.rotation = Quat::IDENTITY;
+            }
+            let turn = KEY_ROTATION_SPEED * time.delta_secs();
+            let axis = |positive: InputAction, negative: InputAction| {
+                input.pressed(positive) as i32 as f32 - input.pressed(negative) as i32 as f32
+            };
+            transform.rotate_y(axis(InputAction::RotateRight, InputAction::RotateLeft) * turn);
+            transform.rotate_x(axis(InputAction::TiltDown, InputAction::TiltUp) * turn);
+        }
+    }
+
+    // Only process motion while the drag button is held
+    if input.pressed(InputAction::RotateDrag) && !settings.flat_mode {
         let mut rotation_delta = Vec2::ZERO;
         
         // Accumulate mouse motion
@@ -751,7 +2239,7 @@ fn abacus_rotation_system(
             
             // Apply horizontal movement to Y-axis rotation (left/right)
             // Apply vertical movement to X-axis rotation (up/down)
-            if let Ok(mut transform) = query.single_mut() {
+            if let Some(mut transform) = selected.0.and_then(|entity| query.get_mut(entity).ok()) {
                 transform.rotate_y(rotation_delta.x * rotation_speed);
                 transform.rotate_x(-rotation_delta.y * rotation_speed);
             }
@@ -765,7 +2253,7 @@ fn abacus_rotation_system(
 /// Applies a saved configuration to the active settings and materials.
 fn apply_config(
     settings: &mut AbacusSettings,
-    materials: &mut Assets<StandardMaterial>,
+    materials: &mut Assets<AbacusMaterial>,
     config: &SavableAbacusConfig,
 ) {
     // Apply structural settings
@@ -773,28 +2261,77 @@ fn apply_config(
     settings.top_bead_count = config.top_bead_count;
     settings.bottom_bead_count = config.bottom_bead_count;
     settings.top_bead_base_value = config.top_bead_base_value;
+    settings.bead_value_multiplier = config.bead_value_multiplier;
+    settings.column_overrides = config.column_overrides.clone();
     settings.abacus_base = config.abacus_base;
+    settings.value_model = config.value_model;
+    settings.horizontal_rods = config.horizontal_rods;
+    settings.two_tone_beads = config.two_tone_beads;
+    settings.grooved = config.grooved;
+    settings.extra_decks = config.extra_decks.clone();
+    settings.ones_on_left = config.ones_on_left;
     settings.show_top_text = config.show_top_text;
     settings.show_column_texts = config.show_column_texts;
 
     // Apply color settings and update materials
     settings.ui_bead_color = config.ui_bead_color;
     if let Some(material) = materials.get_mut(&settings.bead_material) {
-        material.base_color = settings.ui_bead_color;
+        material.base.base_color = settings.ui_bead_color;
     }
     settings.ui_bead_hover_color = config.ui_bead_hover_color;
     if let Some(material) = materials.get_mut(&settings.bead_hover_material) {
-        material.base_color = settings.ui_bead_hover_color;
+        material.base.base_color = settings.ui_bead_hover_color;
     }
     settings.ui_frame_color = config.ui_frame_color;
     if let Some(material) = materials.get_mut(&settings.frame_material) {
-        material.base_color = settings.ui_frame_color;
+        material.base.base_color = settings.ui_frame_color;
+    }
+
+    settings.proportions = config.proportions;
+    settings.bead_shape = config.bead_shape;
+    settings.custom_models = config.custom_models.clone();
+    settings.surfaces = config.surfaces.clone();
+    settings.wood_grain = config.wood_grain;
+    settings.environment = config.environment.clone();
+    settings.table = config.table.clone();
+    settings.frame_title = config.frame_title.clone();
+    settings.column_colors = config.column_colors.clone();
+    // Applied by the themes plugin once the file has loaded
+    settings.theme = config.theme.clone();
+    settings.sound_theme = config.sound_theme;
+}
+
+/// Applies an appearance preset to the shared materials, background and value text.
+fn apply_appearance_preset(
+    settings: &mut AbacusSettings,
+    materials: &mut Assets<AbacusMaterial>,
+    clear_color: &mut ClearColor,
+    preset: &AppearancePreset,
+) {
+    settings.ui_bead_color = preset.bead_color;
+    if let Some(material) = materials.get_mut(&settings.bead_material) {
+        material.base.base_color = settings.ui_bead_color;
+    }
+    settings.ui_bead_hover_color = preset.bead_hover_color;
+    if let Some(material) = materials.get_mut(&settings.bead_hover_material) {
+        material.base.base_color = settings.ui_bead_hover_color;
+    }
+    settings.ui_frame_color = preset.frame_color;
+    if let Some(material) = materials.get_mut(&settings.frame_material) {
+        material.base.base_color = settings.ui_frame_color;
     }
+
+    settings.ui_background_color = preset.background_color;
+    clear_color.0 = preset.background_color;
+    settings.text_color = preset.text_color;
+    settings.text_size = preset.text_size;
 }
 
 fn welcome_ui_system(
     mut contexts: EguiContexts,
     mut welcome_state: ResMut<WelcomeUiState>,
+    mut profiles: ResMut<Profiles>,
+    localization: Res<Localization>,
 ) {
     if !welcome_state.show_welcome {
         return;
@@ -802,50 +2339,60 @@ fn welcome_ui_system(
 
     let ctx = contexts.ctx_mut();
     
-    egui::Window::new("Welcome to Abacus Simulator")
+    egui::Window::new(tr!(localization, "welcome-window"))
+        .id(egui::Id::new("welcome"))
         .collapsible(false)
         .resizable(true)
         .default_width(500.0)
         .default_pos([300.0, 150.0])
         .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
         .show(ctx, |ui| {
-            ui.heading("Welcome to Abacus Simulator!");
+            ui.heading(tr!(localization, "welcome-heading"));
             ui.add_space(10.0);
             
-            ui.label("This interactive simulator lets you explore different types of abaci from around the world.");
+            ui.label(tr!(localization, "welcome-intro"));
+            ui.add_space(10.0);
+
+            // Students pick who is practicing so their progress is kept apart
+            profile_picker(ui, &mut profiles, &localization);
             ui.add_space(10.0);
             
-            ui.collapsing("Controls", |ui| {
-                ui.label("• Click on beads to move them up/down");
-                ui.label("• Right-click and drag to rotate the 3D view");
-                ui.label("• Use the Reset Rotation button to return to default view");
-                ui.label("• Use the Set Value field to set a specific number");
-                ui.label("• Use Add/Subtract to perform calculations");
-                ui.label("• Numbers display in the selected numeric base (e.g., base 16 shows 10 as 'A')");
+            ui.collapsing(tr!(localization, "welcome-controls"), |ui| {
+                ui.label(tr!(localization, "welcome-click"));
+                ui.label(tr!(localization, "welcome-select"));
+                ui.label(tr!(localization, "welcome-scroll"));
+                ui.label(tr!(localization, "welcome-rotate"));
+                ui.label(tr!(localization, "welcome-touch"));
+                ui.label(tr!(localization, "welcome-reset-rotation"));
+                ui.label(tr!(localization, "welcome-set-value"));
+                ui.label(tr!(localization, "welcome-clear"));
+                ui.label(tr!(localization, "welcome-shake"));
+                ui.label(tr!(localization, "welcome-add-subtract"));
+                ui.label(tr!(localization, "welcome-bases"));
             });
 
-            ui.collapsing("Save/Load Configurations", |ui| {
-                ui.label("• Save and load different abacus configurations");
+            ui.collapsing(tr!(localization, "section-save-load"), |ui| {
+                ui.label(tr!(localization, "welcome-save-load"));
             });
             
-            ui.collapsing("Abacus Types (Available in Save/Load Configurations)", |ui| {
-                ui.label("• Suanpan (Chinese): 2 top beads worth 5 each, 5 bottom beads");
-                ui.label("• Soroban (Japanese): 1 top bead worth 5, 4 bottom beads");
-                ui.label("• Binary: Represents binary numbers (base 2)");
-                ui.label("• Custom: Create your own abacus configuration!");
+            ui.collapsing(tr!(localization, "welcome-types"), |ui| {
+                ui.label(tr!(localization, "welcome-suanpan"));
+                ui.label(tr!(localization, "welcome-soroban"));
+                ui.label(tr!(localization, "welcome-binary"));
+                ui.label(tr!(localization, "welcome-custom"));
             });
             
-            ui.collapsing("Customization", |ui| {
-                ui.label("You can customize:");
-                ui.label("• Number of columns");
-                ui.label("• Number of beads (top and bottom sections)");
-                ui.label("• Value of top beads");
-                ui.label("• Numeric base (decimal, binary, hexadecimal, etc.)");
-                ui.label("• Colors of beads and frame");
+            ui.collapsing(tr!(localization, "welcome-customization"), |ui| {
+                ui.label(tr!(localization, "welcome-you-can-customize"));
+                ui.label(tr!(localization, "welcome-custom-columns"));
+                ui.label(tr!(localization, "welcome-custom-beads"));
+                ui.label(tr!(localization, "welcome-custom-top-value"));
+                ui.label(tr!(localization, "welcome-custom-base"));
+                ui.label(tr!(localization, "welcome-custom-colors"));
             });
             
             ui.add_space(15.0);
-            if ui.button("Close").clicked() {
+            if ui.button(tr!(localization, "close")).clicked() {
                 welcome_state.show_welcome = false;
             }
         });
@@ -0,0 +1,184 @@
+//! MIDI input, enabled with the `midi` cargo feature on desktop builds, so the abacus can
+//! be played from a pad controller. From the first mapped note up, each key adds one bead
+//! to the next column of the selected abacus (ones, tens, ...), carrying like Add; the
+//! mapped control change sets the total to its value.
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use midir::{MidiInput, MidiInputConnection};
+use std::sync::mpsc::{channel, Receiver};
+use std::sync::Mutex;
+
+use crate::abacus::*;
+use crate::localization::*;
+use crate::operations::{AbacusOperation, OperationRequested};
+use crate::scene_layout::SelectedAbacus;
+
+const CLIENT_NAME: &str = "Abacus Simulator";
+const NOTE_ON: u8 = 0x90;
+const CONTROL_CHANGE: u8 = 0xB0;
+
+#[derive(Resource)]
+struct MidiMapping {
+    /// The note that counts in the ones column; each note above it, the next column.
+    first_note: u8,
+    /// The control change whose value becomes the total.
+    total_control: u8,
+    port_names: Vec<String>,
+    chosen_port: usize,
+    /// Messages from the open port, forwarded from midir's thread.
+    inbox: Option<Mutex<Receiver<Vec<u8>>>>,
+    last_message: Option<String>,
+}
+
+impl Default for MidiMapping {
+    fn default() -> Self {
+        Self {
+            // Middle C
+            first_note: 60,
+            // The modulation wheel
+            total_control: 1,
+            port_names: Vec::new(),
+            chosen_port: 0,
+            inbox: None,
+            last_message: None,
+        }
+    }
+}
+
+/// The open port. Some platforms' connections can't leave the main thread.
+#[derive(Default)]
+struct MidiLink(Option<(String, MidiInputConnection<()>)>);
+
+pub struct MidiPlugin;
+
+impl Plugin for MidiPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MidiMapping>()
+            .init_non_send_resource::<MidiLink>()
+            .add_systems(Update, (midi_ui_system, apply_midi_messages).chain());
+    }
+}
+
+fn list_ports() -> Vec<String> {
+    let Ok(input) = MidiInput::new(CLIENT_NAME) else {
+        return Vec::new();
+    };
+    input.ports().iter().filter_map(|port| input.port_name(port).ok()).collect()
+}
+
+fn connect(mapping: &mut MidiMapping, link: &mut MidiLink, name: &str) -> Result<(), String> {
+    let input = MidiInput::new(CLIENT_NAME).map_err(|error| error.to_string())?;
+    let port = input.ports().into_iter()
+        .find(|port| input.port_name(port).is_ok_and(|port_name| port_name == name))
+        .ok_or_else(|| format!("{} is no longer connected", name))?;
+    let (sender, inbox) = channel();
+    let connection = input
+        .connect(&port, "abacus-input", move |_, message, _| {
+            let _ = sender.send(message.to_vec());
+        }, ())
+        .map_err(|error| error.to_string())?;
+    link.0 = Some((name.to_string(), connection));
+    mapping.inbox = Some(Mutex::new(inbox));
+    Ok(())
+}
+
+fn midi_ui_system(
+    mut contexts: EguiContexts,
+    mut mapping: ResMut<MidiMapping>,
+    mut link: NonSendMut<MidiLink>,
+    localization: Res<Localization>,
+) {
+    let localization = &*localization;
+    let mapping = &mut *mapping;
+    egui::Window::new(tr!(localization, "midi-window"))
+        .id(egui::Id::new("midi"))
+        .default_pos([10.0, 1300.0])
+        .default_open(false)
+        .show(contexts.ctx_mut(), |ui| {
+            match link.0.as_ref().map(|(name, _)| name.clone()) {
+                Some(name) => {
+                    ui.label(tr!(localization, "midi-connected", port = name));
+                    if ui.button(tr!(localization, "midi-disconnect")).clicked() {
+                        // Dropping the connection closes the port
+                        link.0 = None;
+                        mapping.inbox = None;
+                    }
+                }
+                None => {
+                    ui.horizontal(|ui| {
+                        let selected_text = mapping.port_names.get(mapping.chosen_port).cloned()
+                            .unwrap_or_else(|| tr!(localization, "midi-no-ports"));
+                        egui::ComboBox::from_id_salt("midi_port")
+                            .selected_text(selected_text)
+                            .show_ui(ui, |ui| {
+                                for (i, name) in mapping.port_names.iter().enumerate() {
+                                    ui.selectable_value(&mut mapping.chosen_port, i, name.as_str());
+                                }
+                            });
+                        if ui.button(tr!(localization, "midi-refresh")).clicked() {
+                            mapping.port_names = list_ports();
+                            mapping.chosen_port = 0;
+                        }
+                    });
+                    let chosen = mapping.port_names.get(mapping.chosen_port).cloned();
+                    if ui.add_enabled(chosen.is_some(), egui::Button::new(tr!(localization, "midi-connect"))).clicked() {
+                        if let Some(name) = chosen {
+                            match connect(mapping, &mut link, &name) {
+                                Ok(()) => info!("{}", tr!(localization, "log-midi-connected", port = name)),
+                                Err(error) => warn!("{}", tr!(localization, "log-midi-failed", port = name, error = error)),
+                            }
+                        }
+                    }
+                }
+            }
+
+            ui.separator();
+            egui::Grid::new("midi_mapping").num_columns(2).show(ui, |ui| {
+                ui.label(tr!(localization, "midi-first-note"));
+                ui.add(egui::DragValue::new(&mut mapping.first_note).range(0..=127));
+                ui.end_row();
+                ui.label(tr!(localization, "midi-total-control"));
+                ui.add(egui::DragValue::new(&mut mapping.total_control).range(0..=127));
+                ui.end_row();
+            });
+            if let Some(message) = &mapping.last_message {
+                ui.label(tr!(localization, "midi-last-message", message = message.clone()));
+            }
+        });
+}
+
+/// Turns mapped notes and the mapped control change into operations on the selected abacus.
+fn apply_midi_messages(
+    mut mapping: ResMut<MidiMapping>,
+    selected: Res<SelectedAbacus>,
+    abacus_query: Query<&Abacus>,
+    mut requests: EventWriter<OperationRequested>,
+) {
+    let messages: Vec<Vec<u8>> = match &mapping.inbox {
+        Some(inbox) => inbox.lock().map(|inbox| inbox.try_iter().collect()).unwrap_or_default(),
+        None => return,
+    };
+    for message in messages {
+        let [status, data1, data2] = message[..] else {
+            continue;
+        };
+        mapping.last_message = Some(format!("{:02X} {} {}", status, data1, data2));
+        let Some((entity, abacus)) = selected.0.and_then(|entity| abacus_query.get(entity).ok().map(|abacus| (entity, abacus))) else {
+            continue;
+        };
+        let operation = match status & 0xF0 {
+            // A note-on with no velocity is how many controllers send note-off
+            NOTE_ON if data2 > 0 && data1 >= mapping.first_note => {
+                let column = usize::from(data1 - mapping.first_note);
+                if column >= abacus.columns.len() {
+                    continue;
+                }
+                AbacusOperation::Add(abacus.place_value(column))
+            }
+            CONTROL_CHANGE if data1 == mapping.total_control => AbacusOperation::Set(u64::from(data2)),
+            _ => continue,
+        };
+        requests.write(OperationRequested { abacus: entity, operation });
+    }
+}
@@ -0,0 +1,143 @@
+use crate::localization::Language;
+
+const ENGLISH_ONES: [&str; 20] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+    "ten", "eleven", "twelve", "thirteen", "fourteen", "fifteen", "sixteen", "seventeen", "eighteen", "nineteen",
+];
+const ENGLISH_TENS: [&str; 10] = ["", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety"];
+/// Short scale names of each group of three digits; u64 tops out in the quintillions.
+const ENGLISH_SCALES: [&str; 7] = ["", "thousand", "million", "billion", "trillion", "quadrillion", "quintillion"];
+
+const CJK_DIGITS: [&str; 10] = ["〇", "一", "二", "三", "四", "五", "六", "七", "八", "九"];
+const CJK_PLACES: [&str; 4] = ["", "十", "百", "千"];
+const CHINESE_GROUPS: [&str; 5] = ["", "万", "亿", "万亿", "亿亿"];
+const JAPANESE_GROUPS: [&str; 5] = ["", "万", "億", "兆", "京"];
+
+/// Writes `value` out in words in `language`, e.g. "twelve thousand three hundred forty-five"
+/// or 一万二千三百四十五, for early-grade number sense lessons.
+pub fn number_in_words(value: u64, language: Language) -> String {
+    match language {
+        Language::English => english_words(value),
+        Language::Chinese => chinese_words(value),
+        Language::Japanese => japanese_words(value),
+    }
+}
+
+fn english_below_thousand(value: u64) -> String {
+    let mut words = Vec::new();
+    if value >= 100 {
+        words.push(format!("{} hundred", ENGLISH_ONES[(value / 100) as usize]));
+    }
+    match value % 100 {
+        0 => {}
+        rest @ 1..=19 => words.push(ENGLISH_ONES[rest as usize].to_string()),
+        rest if rest % 10 == 0 => words.push(ENGLISH_TENS[(rest / 10) as usize].to_string()),
+        rest => words.push(format!("{}-{}", ENGLISH_TENS[(rest / 10) as usize], ENGLISH_ONES[(rest % 10) as usize])),
+    }
+    words.join(" ")
+}
+
+fn english_words(value: u64) -> String {
+    if value == 0 {
+        return ENGLISH_ONES[0].to_string();
+    }
+
+    let mut groups = Vec::new();
+    let mut remaining = value;
+    let mut scale = 0;
+    while remaining > 0 {
+        let group = remaining % 1000;
+        if group != 0 {
+            let mut words = english_below_thousand(group);
+            if scale > 0 {
+                words.push(' ');
+                words.push_str(ENGLISH_SCALES[scale]);
+            }
+            groups.push(words);
+        }
+        remaining /= 1000;
+        scale += 1;
+    }
+    groups.reverse();
+    groups.join(" ")
+}
+
+/// Splits `value` into groups of four digits (万 steps), least significant first.
+fn cjk_groups(value: u64) -> Vec<u64> {
+    let mut groups = Vec::new();
+    let mut remaining = value;
+    while remaining > 0 {
+        groups.push(remaining % 10_000);
+        remaining /= 10_000;
+    }
+    groups
+}
+
+/// Chinese reads every gap in the digits as a single 零: 一万零五, 一千零一十.
+fn chinese_words(value: u64) -> String {
+    if value == 0 {
+        return "零".to_string();
+    }
+
+    let mut words = String::new();
+    let mut pending_zero = false;
+    for (group_index, &group) in cjk_groups(value).iter().enumerate().rev() {
+        if group == 0 {
+            pending_zero = !words.is_empty();
+            continue;
+        }
+        // A group short of a thousands digit also leaves a gap after a higher group
+        if !words.is_empty() && (pending_zero || group < 1000) {
+            words.push('零');
+        }
+        pending_zero = false;
+
+        let mut zero_in_group = false;
+        for place in (0..4).rev() {
+            let digit = (group / 10u64.pow(place as u32)) % 10;
+            if digit == 0 {
+                zero_in_group = !words.is_empty() && !words.ends_with('零');
+                continue;
+            }
+            if zero_in_group && !words.ends_with('零') {
+                words.push('零');
+            }
+            zero_in_group = false;
+            words.push_str(CJK_DIGITS[digit as usize]);
+            words.push_str(CJK_PLACES[place]);
+        }
+        words.push_str(CHINESE_GROUPS[group_index]);
+    }
+
+    // 十二 rather than 一十二 at the start of a number
+    match words.strip_prefix("一十") {
+        Some(rest) => format!("十{}", rest),
+        None => words,
+    }
+}
+
+/// Japanese skips zeros entirely and drops the 一 before 十, 百 and 千: 一万五, 千十.
+fn japanese_words(value: u64) -> String {
+    if value == 0 {
+        return "零".to_string();
+    }
+
+    let mut words = String::new();
+    for (group_index, &group) in cjk_groups(value).iter().enumerate().rev() {
+        if group == 0 {
+            continue;
+        }
+        for place in (0..4).rev() {
+            let digit = (group / 10u64.pow(place as u32)) % 10;
+            if digit == 0 {
+                continue;
+            }
+            if digit != 1 || place == 0 {
+                words.push_str(CJK_DIGITS[digit as usize]);
+            }
+            words.push_str(CJK_PLACES[place]);
+        }
+        words.push_str(JAPANESE_GROUPS[group_index]);
+    }
+    words
+}
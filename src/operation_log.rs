@@ -0,0 +1,187 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use serde::Serialize;
+
+use crate::abacus::*;
+use crate::localization::*;
+use crate::operations::{AbacusOperation, OperationPerformed};
+use crate::profiles::Profiles;
+
+/// One operation as the log keeps it.
+#[derive(Clone, Debug, Serialize)]
+struct LogEntry {
+    /// Seconds into the session.
+    seconds: f32,
+    /// The profile that was active; `None` for the guest.
+    student: Option<String>,
+    /// The abacus's number as shown in the panel, starting at 1.
+    abacus: usize,
+    operation: AbacusOperation,
+    /// What the abacus showed (or was headed to) afterwards.
+    total: u64,
+}
+
+impl LogEntry {
+    fn text(&self, localization: &Localization) -> String {
+        match self.operation {
+            AbacusOperation::MoveBeads { column, .. } => tr!(localization, "oplog-beads", column = column + 1, total = self.total),
+            AbacusOperation::Set(_) => tr!(localization, "oplog-set", total = self.total),
+            AbacusOperation::Add(amount) => tr!(localization, "oplog-add", amount = amount, total = self.total),
+            AbacusOperation::Subtract(amount) => tr!(localization, "oplog-subtract", amount = amount, total = self.total),
+        }
+    }
+
+    fn csv_row(&self) -> String {
+        let (operation, column, value) = match self.operation {
+            AbacusOperation::MoveBeads { column, deck, long_value } => {
+                (format!("beads-deck-{}", deck + 1), (column + 1).to_string(), long_value)
+            }
+            AbacusOperation::Set(value) => ("set".to_string(), String::new(), value),
+            AbacusOperation::Add(amount) => ("add".to_string(), String::new(), amount),
+            AbacusOperation::Subtract(amount) => ("subtract".to_string(), String::new(), amount),
+        };
+        // Profile names are free text, so quote them
+        let student = self.student.as_deref().map(|name| format!("\"{}\"", name.replace('"', "\"\""))).unwrap_or_default();
+        format!("{:.2},{},{},{},{},{},{}", self.seconds, student, self.abacus, operation, column, value, self.total)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    #[default]
+    Csv,
+    Json,
+}
+
+impl LogFormat {
+    pub const ALL: [LogFormat; 2] = [LogFormat::Csv, LogFormat::Json];
+
+    /// Localization id of the format's display name.
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            LogFormat::Csv => "oplog-format-csv",
+            LogFormat::Json => "oplog-format-json",
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn extension(&self) -> &'static str {
+        match self {
+            LogFormat::Csv => "csv",
+            LogFormat::Json => "json",
+        }
+    }
+}
+
+/// Every operation done to any abacus this session, so teachers can review what a student did.
+#[derive(Resource, Default)]
+pub struct OperationLog {
+    entries: Vec<LogEntry>,
+    format: LogFormat,
+    status: Option<String>,
+}
+
+impl OperationLog {
+    fn export_text(&self) -> Result<String, String> {
+        match self.format {
+            LogFormat::Csv => {
+                let mut text = String::from("seconds,student,abacus,operation,column,value,total\n");
+                for entry in &self.entries {
+                    text.push_str(&entry.csv_row());
+                    text.push('\n');
+                }
+                Ok(text)
+            }
+            LogFormat::Json => serde_json::to_string_pretty(&self.entries).map_err(|error| error.to_string()),
+        }
+    }
+}
+
+pub fn log_operations(
+    time: Res<Time>,
+    mut performed: EventReader<OperationPerformed>,
+    mut log: ResMut<OperationLog>,
+    abacus_query: Query<&Abacus>,
+    profiles: Res<Profiles>,
+) {
+    for event in performed.read() {
+        let Ok(abacus) = abacus_query.get(event.abacus) else {
+            continue;
+        };
+        log.entries.push(LogEntry {
+            seconds: time.elapsed_secs(),
+            student: profiles.active.clone(),
+            abacus: abacus.slot + 1,
+            operation: event.operation.clone(),
+            total: event.total,
+        });
+    }
+}
+
+fn format_time(seconds: f32) -> String {
+    let seconds = seconds as u64;
+    format!("{:02}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60)
+}
+
+pub fn operation_log_ui_system(
+    mut contexts: EguiContexts,
+    mut log: ResMut<OperationLog>,
+    localization: Res<Localization>,
+) {
+    let localization = &*localization;
+    let log = &mut *log;
+
+    egui::Window::new(tr!(localization, "oplog-window"))
+        .id(egui::Id::new("operation_log"))
+        .default_pos([10.0, 820.0])
+        .default_open(false)
+        .show(contexts.ctx_mut(), |ui| {
+            if log.entries.is_empty() {
+                ui.label(tr!(localization, "oplog-empty"));
+            }
+            egui::ScrollArea::vertical().max_height(240.0).stick_to_bottom(true).show(ui, |ui| {
+                for entry in &log.entries {
+                    ui.label(tr!(localization, "oplog-entry",
+                        time = format_time(entry.seconds), abacus = entry.abacus, text = entry.text(localization)));
+                }
+            });
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                egui::ComboBox::from_id_salt("oplog_format")
+                    .selected_text(tr!(localization, log.format.message_id()))
+                    .show_ui(ui, |ui| {
+                        for format in LogFormat::ALL {
+                            ui.selectable_value(&mut log.format, format, tr!(localization, format.message_id()));
+                        }
+                    });
+                let exportable = !log.entries.is_empty();
+                #[cfg(not(target_arch = "wasm32"))]
+                if ui.add_enabled(exportable, egui::Button::new(tr!(localization, "oplog-export"))).clicked() {
+                    let file_name = format!("operation-log.{}", log.format.extension());
+                    log.status = Some(match log.export_text().and_then(|text| crate::storage::export_file(&file_name, &text)) {
+                        Ok(path) => tr!(localization, "oplog-exported", path = path.display().to_string()),
+                        Err(error) => tr!(localization, "oplog-export-failed", error = error),
+                    });
+                }
+                // The web build can't write files, so the log is handed over through the clipboard
+                if ui.add_enabled(exportable, egui::Button::new(tr!(localization, "oplog-copy"))).clicked() {
+                    log.status = Some(match log.export_text() {
+                        Ok(text) => {
+                            ui.ctx().copy_text(text);
+                            tr!(localization, "oplog-copied")
+                        }
+                        Err(error) => tr!(localization, "oplog-export-failed", error = error),
+                    });
+                }
+                if ui.add_enabled(exportable, egui::Button::new(tr!(localization, "oplog-clear"))).clicked() {
+                    log.entries.clear();
+                    log.status = None;
+                }
+            });
+
+            if let Some(status) = &log.status {
+                ui.label(status);
+            }
+        });
+}
@@ -0,0 +1,335 @@
+use bevy::prelude::*;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::abacus::*;
+use crate::AbacusSettings;
+use crate::links::{pending_chained_total, queue_chained_total, AbacusChains};
+use crate::localization::*;
+use crate::overflow::ValueClamped;
+
+/// Default seconds between consecutive column moves while a carry ripples across the abacus.
+pub const CARRY_STEP_DELAY: f32 = 0.15;
+pub const MAX_STEP_DELAY: f32 = 2.0;
+
+/// A single staggered `set_column_value` call.
+struct ColumnOp {
+    abacus: Entity,
+    column: usize,
+    value: u64,
+    /// The column rolls over (or borrows), pushing a carry into the next column.
+    carry: bool,
+}
+
+/// Sent when a queued move rolls a column over and carries (or borrows) into its neighbour.
+#[derive(Event)]
+pub struct ColumnCarried {
+    pub abacus: Entity,
+    pub column: usize,
+}
+
+/// Queue of column moves that are played back one at a time, so carries and
+/// borrows visibly ripple from the ones column outward instead of snapping.
+/// Playback can be paused and single-stepped so students can follow along.
+#[derive(Resource)]
+pub struct ColumnOpQueue {
+    pending: VecDeque<ColumnOp>,
+    timer: Timer,
+    pub paused: bool,
+    step_requested: bool,
+}
+
+impl Default for ColumnOpQueue {
+    fn default() -> Self {
+        Self {
+            pending: VecDeque::new(),
+            timer: Timer::from_seconds(CARRY_STEP_DELAY, TimerMode::Repeating),
+            paused: false,
+            step_requested: false,
+        }
+    }
+}
+
+impl ColumnOpQueue {
+    /// Queues the column moves that take `abacus` to `target_total_value`, ones column first.
+    /// Moves are planned from where earlier queued operations leave the abacus, so
+    /// several Set/Add/Subtract operations play back one after another.
+    pub fn queue_total_value(
+        &mut self,
+        abacus_entity: Entity,
+        abacus: &Abacus,
+        target_total_value: u64,
+        abacus_long_query: &Query<&AbacusLong>,
+    ) {
+        if self.pending.is_empty() {
+            // Nothing is playing, so the first move happens on the next tick
+            let duration = self.timer.duration();
+            self.timer.set_elapsed(duration);
+        }
+
+        let current_values = self.pending_column_values(abacus_entity, abacus, abacus_long_query);
+        let current_total = self.pending_total_value(abacus_entity, abacus, abacus_long_query);
+        let increasing = target_total_value > current_total;
+        for (column, value) in abacus.column_values_for_total(target_total_value).into_iter().enumerate() {
+            let current_value = current_values[column];
+            if current_value != value {
+                self.pending.push_back(ColumnOp {
+                    abacus: abacus_entity,
+                    column,
+                    value,
+                    // A column moving against the direction of the total must have rolled over
+                    carry: if increasing { value < current_value } else { value > current_value },
+                });
+            }
+        }
+    }
+
+    /// The value each column of `abacus` will show once its queued moves have played out.
+    fn pending_column_values(
+        &self,
+        abacus_entity: Entity,
+        abacus: &Abacus,
+        abacus_long_query: &Query<&AbacusLong>,
+    ) -> Vec<u64> {
+        let mut column_values: Vec<u64> = (0..abacus.longs.len())
+            .map(|i| abacus.get_column_value(i, abacus_long_query))
+            .collect();
+        for op in self.pending.iter().filter(|op| op.abacus == abacus_entity) {
+            column_values[op.column] = op.value;
+        }
+        column_values
+    }
+
+    /// The total `abacus` will show once its queued moves have played out.
+    pub fn pending_total_value(
+        &self,
+        abacus_entity: Entity,
+        abacus: &Abacus,
+        abacus_long_query: &Query<&AbacusLong>,
+    ) -> u64 {
+        let column_values = self.pending_column_values(abacus_entity, abacus, abacus_long_query);
+        column_values.iter().enumerate().fold(0u64, |total, (i, &value)| {
+            total.saturating_add(value.saturating_mul(abacus.place_value(i)))
+        })
+    }
+
+    pub fn pending_count(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn step_delay(&self) -> f32 {
+        self.timer.duration().as_secs_f32()
+    }
+
+    pub fn set_step_delay(&mut self, seconds: f32) {
+        self.timer.set_duration(Duration::from_secs_f32(seconds.clamp(0.0, MAX_STEP_DELAY)));
+    }
+
+    /// Plays exactly one queued move while paused.
+    pub fn request_step(&mut self) {
+        self.step_requested = true;
+    }
+
+    /// Drops every queued move, leaving the beads where they currently are.
+    pub fn clear(&mut self) {
+        self.pending.clear();
+        self.step_requested = false;
+    }
+
+    /// Drops the queued moves of one abacus, e.g. before it is rebuilt or removed.
+    pub fn clear_abacus(&mut self, abacus_entity: Entity) {
+        self.pending.retain(|op| op.abacus != abacus_entity);
+    }
+}
+
+/// Asks an abacus to return to zero, from the UI or a keyboard shortcut.
+#[derive(Event)]
+pub struct ClearRequested {
+    /// `None` clears every abacus in the scene, as shaking does.
+    pub abacus: Option<Entity>,
+}
+
+/// Clears abaci in one go, dropping any moves still queued for them.
+pub fn handle_clear_requests(
+    mut requests: EventReader<ClearRequested>,
+    mut queue: ResMut<ColumnOpQueue>,
+    // A read-only copy keeps its beads, even when every abacus is cleared
+    abacus_query: Query<(Entity, &Abacus), Without<ReadOnlyAbacus>>,
+    mut long_query: Query<&mut AbacusLong>,
+    mut commands: Commands,
+    localization: Res<Localization>,
+) {
+    for request in requests.read() {
+        for (entity, abacus) in &abacus_query {
+            if request.abacus.is_none_or(|target| target == entity) {
+                queue.clear_abacus(entity);
+                abacus.clear(&mut long_query, &mut commands);
+            }
+        }
+        info!("{}", tr!(localization, "log-cleared"));
+    }
+}
+
+/// Applies the next queued column move whenever the step delay has elapsed,
+/// or when a single step was requested while paused.
+pub fn run_column_ops(
+    time: Res<Time>,
+    settings: Res<AbacusSettings>,
+    mut queue: ResMut<ColumnOpQueue>,
+    abacus_query: Query<&Abacus>,
+    mut long_query: Query<&mut AbacusLong>,
+    mut carries: EventWriter<ColumnCarried>,
+    mut commands: Commands,
+) {
+    if queue.pending.is_empty() {
+        queue.step_requested = false;
+        return;
+    }
+
+    let ops_to_apply = if queue.paused {
+        if !queue.step_requested {
+            return;
+        }
+        queue.step_requested = false;
+        1
+    } else if settings.reduced_motion {
+        // No rippling: everything queued lands at once
+        queue.pending.len()
+    } else {
+        queue.timer.tick(time.delta());
+        if !queue.timer.just_finished() {
+            return;
+        }
+        1
+    };
+
+    for op in queue.pending.drain(..ops_to_apply) {
+        // The abacus may have been rebuilt since the move was queued
+        if let Ok(abacus) = abacus_query.get(op.abacus) {
+            abacus.set_column_value(op.column, op.value, &mut long_query, &mut commands);
+            if op.carry {
+                carries.write(ColumnCarried { abacus: op.abacus, column: op.column });
+            }
+        }
+    }
+}
+
+/// Something a user did to an abacus, in a form that can be logged, recorded and replayed.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AbacusOperation {
+    /// A bead click left `deck` of `column` (the top deck being 0) at `long_value`.
+    MoveBeads {
+        column: usize,
+        #[serde(alias = "top", deserialize_with = "deck_index")]
+        deck: usize,
+        long_value: u64,
+    },
+    Set(u64),
+    Add(u64),
+    Subtract(u64),
+}
+
+/// Reads a `MoveBeads` deck, which recordings from before there could be more than two
+/// decks give as `top: true` or `false`.
+fn deck_index<'de, D: Deserializer<'de>>(deserializer: D) -> Result<usize, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Deck {
+        Index(usize),
+        Top(bool),
+    }
+    Ok(match Deck::deserialize(deserializer)? {
+        Deck::Index(deck) => deck,
+        Deck::Top(top) => if top { 0 } else { 1 },
+    })
+}
+
+/// Asks for an operation on an abacus. The settings panel, macros and other
+/// controllers all go through this so they behave the same.
+#[derive(Event, Clone)]
+pub struct OperationRequested {
+    pub abacus: Entity,
+    pub operation: AbacusOperation,
+}
+
+/// Sent once an operation has been applied or queued, for logs and recorders.
+#[derive(Event, Clone)]
+pub struct OperationPerformed {
+    pub abacus: Entity,
+    pub operation: AbacusOperation,
+    /// The total the abacus shows (or will show, once queued moves play out) afterwards.
+    pub total: u64,
+}
+
+/// Queues `abacus_entity` (and the abaci it carries into) to show `target`, pointing
+/// out a clamp. Returns the total the abacus will reach.
+fn queue_target(
+    target: u64,
+    abacus_entity: Entity,
+    queue: &mut ColumnOpQueue,
+    chains: &AbacusChains,
+    abacus_query: &Query<(Entity, &Abacus)>,
+    abacus_long_query: &Query<&AbacusLong>,
+    clamps: &mut EventWriter<ValueClamped>,
+) -> u64 {
+    match queue_chained_total(queue, chains, abacus_entity, target, abacus_query, abacus_long_query) {
+        Some(max) => {
+            clamps.write(ValueClamped { abacus: abacus_entity, requested: target, max });
+            max
+        }
+        None => target,
+    }
+}
+
+/// Applies requested operations. Set, Add and Subtract ripple through the column
+/// queue and carry into chained abaci; bead moves land immediately, like a click.
+pub fn perform_operations(
+    mut requests: EventReader<OperationRequested>,
+    mut queue: ResMut<ColumnOpQueue>,
+    chains: Res<AbacusChains>,
+    abacus_query: Query<(Entity, &Abacus)>,
+    read_only_query: Query<(), With<ReadOnlyAbacus>>,
+    mut long_query: Query<&mut AbacusLong>,
+    mut performed: EventWriter<OperationPerformed>,
+    mut clamps: EventWriter<ValueClamped>,
+    mut commands: Commands,
+    localization: Res<Localization>,
+) {
+    for request in requests.read() {
+        let Ok((abacus_entity, abacus)) = abacus_query.get(request.abacus) else {
+            warn!("{}", tr!(localization, "log-abacus-missing"));
+            continue;
+        };
+        // Someone else's abacus moves only when they do
+        if read_only_query.contains(abacus_entity) {
+            continue;
+        }
+
+        let total = match request.operation {
+            AbacusOperation::MoveBeads { column, deck, long_value } => {
+                let Some(mut long) = abacus.longs.get(column).and_then(|longs| longs.get(deck)).and_then(|&long| long_query.get_mut(long).ok()) else {
+                    continue;
+                };
+                long.value = long_value;
+                commands.send_event(AbacusChanged);
+                abacus.beads_total_value(&long_query.as_readonly())
+            }
+            AbacusOperation::Set(value) => {
+                info!("{}", tr!(localization, "log-setting-value", value = value));
+                queue_target(value, abacus_entity, &mut queue, &chains, &abacus_query, &long_query.as_readonly(), &mut clamps)
+            }
+            AbacusOperation::Add(amount) | AbacusOperation::Subtract(amount) => {
+                // A chained abacus adds and subtracts across the whole chain, carrying and borrowing between abaci
+                let current_value = pending_chained_total(&queue, &chains, abacus_entity, &abacus_query, &long_query.as_readonly());
+                let adding = matches!(request.operation, AbacusOperation::Add(_));
+                let new_value = if adding { current_value.saturating_add(amount) } else { current_value.saturating_sub(amount) };
+                info!("{}", tr!(localization, "log-modifying-value",
+                    value = new_value, current = current_value, operator = if adding { "+" } else { "-" }, amount = amount));
+                queue_target(new_value, abacus_entity, &mut queue, &chains, &abacus_query, &long_query.as_readonly(), &mut clamps)
+            }
+        };
+        performed.write(OperationPerformed { abacus: abacus_entity, operation: request.operation.clone(), total });
+    }
+}
@@ -0,0 +1,168 @@
+//! OSC control for interactive installations, enabled with the `osc` cargo feature on
+//! desktop builds. Messages to UDP port 9000 (or `ABACUS_OSC_PORT`) drive the selected
+//! abacus:
+//!
+//! - `/abacus/set n`, `/abacus/add n` and `/abacus/subtract n`, like the buttons in Controls
+//! - `/abacus/clear` returns it to zero
+//! - `/abacus/tally` counts one trigger in the Tally Counter
+//!
+//! With `ABACUS_OSC_SEND=host:port`, every change of an abacus's total is sent there as
+//! `/abacus/value slot total`.
+
+use bevy::prelude::*;
+use rosc::{OscMessage, OscPacket, OscType};
+use std::collections::HashMap;
+use std::net::{SocketAddr, ToSocketAddrs, UdpSocket};
+
+use crate::abacus::*;
+use crate::localization::*;
+use crate::operations::{AbacusOperation, ClearRequested, OperationRequested};
+use crate::scene_layout::SelectedAbacus;
+use crate::tally::TallyTriggered;
+
+const DEFAULT_PORT: u16 = 9000;
+const PORT_ENV_VAR: &str = "ABACUS_OSC_PORT";
+const SEND_ENV_VAR: &str = "ABACUS_OSC_SEND";
+/// Large enough for any OSC packet that fits in one UDP datagram.
+const PACKET_BUFFER_SIZE: usize = 65_536;
+
+#[derive(Resource)]
+struct OscSocket {
+    socket: UdpSocket,
+    /// Where value changes are sent, if anywhere.
+    send_to: Option<SocketAddr>,
+}
+
+pub struct OscPlugin;
+
+impl Plugin for OscPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, open_osc_socket)
+            .add_systems(Update, (
+                receive_osc,
+                send_osc_changes.run_if(on_event::<AbacusChanged>),
+            ));
+    }
+}
+
+fn open_osc_socket(mut commands: Commands, localization: Res<Localization>) {
+    let port = std::env::var(PORT_ENV_VAR).ok().and_then(|port| port.parse().ok()).unwrap_or(DEFAULT_PORT);
+    let socket = match UdpSocket::bind(("0.0.0.0", port)).and_then(|socket| socket.set_nonblocking(true).map(|_| socket)) {
+        Ok(socket) => socket,
+        Err(error) => {
+            warn!("{}", tr!(localization, "log-osc-failed", port = port, error = error.to_string()));
+            return;
+        }
+    };
+    let send_to = std::env::var(SEND_ENV_VAR).ok().and_then(|target| {
+        let address = target.to_socket_addrs().ok().and_then(|mut addresses| addresses.next());
+        if address.is_none() {
+            warn!("{}", tr!(localization, "log-osc-bad-target", target = target.clone()));
+        }
+        address
+    });
+    info!("{}", tr!(localization, "log-osc-listening", port = port));
+    commands.insert_resource(OscSocket { socket, send_to });
+}
+
+/// The message's first argument as a whole number, whichever OSC type it was sent as.
+fn whole_number_arg(message: &OscMessage) -> Option<u64> {
+    match message.args.first()? {
+        OscType::Int(n) => u64::try_from(*n).ok(),
+        OscType::Long(n) => u64::try_from(*n).ok(),
+        OscType::Float(n) => (*n >= 0.0 && n.fract() == 0.0).then_some(*n as u64),
+        OscType::Double(n) => (*n >= 0.0 && n.fract() == 0.0).then_some(*n as u64),
+        OscType::String(text) => text.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+/// The messages in `packet`, bundles opened up.
+fn flatten_packet(packet: OscPacket, messages: &mut Vec<OscMessage>) {
+    match packet {
+        OscPacket::Message(message) => messages.push(message),
+        OscPacket::Bundle(bundle) => {
+            for packet in bundle.content {
+                flatten_packet(packet, messages);
+            }
+        }
+    }
+}
+
+fn receive_osc(
+    osc: Option<Res<OscSocket>>,
+    selected: Res<SelectedAbacus>,
+    mut requests: EventWriter<OperationRequested>,
+    mut clear_requests: EventWriter<ClearRequested>,
+    mut tally: EventWriter<TallyTriggered>,
+    mut buffer: Local<Vec<u8>>,
+    localization: Res<Localization>,
+) {
+    let Some(osc) = osc else {
+        return;
+    };
+    buffer.resize(PACKET_BUFFER_SIZE, 0);
+    let mut messages = Vec::new();
+    // Non-blocking, so this stops once the waiting datagrams are read
+    while let Ok((size, _)) = osc.socket.recv_from(&mut buffer) {
+        match rosc::decoder::decode_udp(&buffer[..size]) {
+            Ok((_, packet)) => flatten_packet(packet, &mut messages),
+            Err(error) => warn!("{}", tr!(localization, "log-osc-bad-packet", error = format!("{:?}", error))),
+        }
+    }
+
+    for message in messages {
+        let operation = match message.addr.as_str() {
+            "/abacus/set" => whole_number_arg(&message).map(AbacusOperation::Set),
+            "/abacus/add" => whole_number_arg(&message).map(AbacusOperation::Add),
+            "/abacus/subtract" => whole_number_arg(&message).map(AbacusOperation::Subtract),
+            "/abacus/clear" => {
+                clear_requests.write(ClearRequested { abacus: selected.0 });
+                continue;
+            }
+            "/abacus/tally" => {
+                tally.write(TallyTriggered);
+                continue;
+            }
+            _ => {
+                warn!("{}", tr!(localization, "log-osc-unknown", address = message.addr.clone()));
+                continue;
+            }
+        };
+        match (operation, selected.0) {
+            (Some(operation), Some(abacus)) => {
+                requests.write(OperationRequested { abacus, operation });
+            }
+            (None, _) => warn!("{}", tr!(localization, "log-osc-bad-argument", address = message.addr.clone())),
+            (_, None) => warn!("{}", tr!(localization, "log-abacus-missing")),
+        }
+    }
+}
+
+/// Sends `/abacus/value slot total` for each abacus whose total changed.
+fn send_osc_changes(
+    osc: Option<Res<OscSocket>>,
+    abacus_query: Query<(Entity, &Abacus)>,
+    mut sent: Local<HashMap<Entity, u64>>,
+) {
+    let Some(osc) = osc else {
+        return;
+    };
+    let Some(target) = osc.send_to else {
+        return;
+    };
+    sent.retain(|entity, _| abacus_query.contains(*entity));
+    for (entity, abacus) in &abacus_query {
+        if sent.insert(entity, abacus.total_value) == Some(abacus.total_value) {
+            continue;
+        }
+        let packet = OscPacket::Message(OscMessage {
+            addr: "/abacus/value".to_string(),
+            // OSC has no unsigned integers, so the largest totals are capped
+            args: vec![OscType::Int(abacus.slot as i32), OscType::Long(i64::try_from(abacus.total_value).unwrap_or(i64::MAX))],
+        });
+        if let Ok(bytes) = rosc::encoder::encode(&packet) {
+            let _ = osc.socket.send_to(&bytes, target);
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use bevy::prelude::*;
+use bevy::color::Mix;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::abacus::*;
+use crate::localization::*;
+use crate::wood_grain::{abacus_material, AbacusMaterial};
+use crate::AbacusSettings;
+
+/// How long the frame glows red after a value had to be clamped.
+const FLASH_SECONDS: f32 = 0.8;
+const FLASH_COLOR: Color = Color::srgb(0.85, 0.12, 0.1);
+/// How long the explanation stays on screen.
+const TOAST_SECONDS: f32 = 4.0;
+
+/// Sent when Set or Add asked for more than the abacus (or its chain) can hold,
+/// so the value was clamped to `max`.
+#[derive(Event, Clone)]
+pub struct ValueClamped {
+    pub abacus: Entity,
+    pub requested: u64,
+    pub max: u64,
+}
+
+/// Tints the rods of an abacus that just clamped a value, fading back to the frame color.
+#[derive(Component)]
+pub struct OverflowFlash {
+    timer: Timer,
+    material: Handle<AbacusMaterial>,
+}
+
+/// The most recent clamp, explained until the timer runs out.
+#[derive(Resource, Default)]
+pub struct OverflowToast {
+    clamp: Option<ValueClamped>,
+    timer: Timer,
+}
+
+/// Swaps every rod of `abacus` that uses `from` over to `to`.
+fn swap_rod_materials(
+    abacus: &Abacus,
+    from: &Handle<AbacusMaterial>,
+    to: &Handle<AbacusMaterial>,
+    children_query: &Query<&Children>,
+    material_query: &mut Query<&mut MeshMaterial3d<AbacusMaterial>, Without<AbacusBead>>,
+) {
+    for &long in abacus.longs.iter().flatten() {
+        let Ok(children) = children_query.get(long) else {
+            continue;
+        };
+        for &child in &children[..] {
+            if let Ok(mut material) = material_query.get_mut(child) {
+                if material.0 == *from {
+                    material.0 = to.clone();
+                }
+            }
+        }
+    }
+}
+
+/// Starts a flash and a toast for each clamp, and fades running flashes out.
+pub fn flash_overflowing_abaci(
+    mut clamps: EventReader<ValueClamped>,
+    time: Res<Time>,
+    settings: Res<AbacusSettings>,
+    mut toast: ResMut<OverflowToast>,
+    mut materials: ResMut<Assets<AbacusMaterial>>,
+    mut flash_query: Query<(Entity, &Abacus, Option<&mut OverflowFlash>)>,
+    children_query: Query<&Children>,
+    mut material_query: Query<&mut MeshMaterial3d<AbacusMaterial>, Without<AbacusBead>>,
+    mut commands: Commands,
+) {
+    for clamp in clamps.read() {
+        toast.clamp = Some(clamp.clone());
+        toast.timer = Timer::from_seconds(TOAST_SECONDS, TimerMode::Once);
+
+        let Ok((entity, abacus, flash)) = flash_query.get_mut(clamp.abacus) else {
+            continue;
+        };
+        match flash {
+            // Already flashing: just start over
+            Some(mut flash) => flash.timer.reset(),
+            None => {
+                let material = materials.add(abacus_material(FLASH_COLOR));
+                swap_rod_materials(abacus, &settings.frame_material, &material, &children_query, &mut material_query);
+                commands.entity(entity).insert(OverflowFlash {
+                    timer: Timer::from_seconds(FLASH_SECONDS, TimerMode::Once),
+                    material,
+                });
+            }
+        }
+    }
+
+    toast.timer.tick(time.delta());
+
+    let frame_color = settings.ui_frame_color;
+    for (entity, abacus, flash) in &mut flash_query {
+        let Some(mut flash) = flash else {
+            continue;
+        };
+        flash.timer.tick(time.delta());
+        if flash.timer.finished() {
+            swap_rod_materials(abacus, &flash.material, &settings.frame_material, &children_query, &mut material_query);
+            materials.remove(&flash.material);
+            commands.entity(entity).remove::<OverflowFlash>();
+        } else if let Some(material) = materials.get_mut(&flash.material) {
+            // Reduced motion holds the warning color instead of fading
+            let fade = if settings.reduced_motion { 0.0 } else { flash.timer.fraction() };
+            material.base.base_color = FLASH_COLOR.mix(&frame_color, fade);
+        }
+    }
+}
+
+/// Explains why the abacus stopped short of the requested value.
+pub fn overflow_toast_ui(
+    mut contexts: EguiContexts,
+    toast: Res<OverflowToast>,
+    localization: Res<Localization>,
+) {
+    let Some(clamp) = &toast.clamp else {
+        return;
+    };
+    if toast.timer.finished() {
+        return;
+    }
+
+    egui::Area::new(egui::Id::new("overflow_toast"))
+        .anchor(egui::Align2::CENTER_TOP, [0.0, 16.0])
+        .interactable(false)
+        .show(contexts.ctx_mut(), |ui| {
+            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                ui.colored_label(egui::Color32::from_rgb(230, 80, 60), tr!(localization, "overflow-toast",
+                    requested = clamp.requested, max = clamp.max));
+            });
+        });
+}
@@ -0,0 +1,167 @@
+//! Optional rigid-body bead simulation, enabled with the `physics` cargo feature.
+//! Beads slide on a prismatic joint along their rod, collide with each other and
+//! bounce off the ends of the deck, while a spring pulls them to their targets.
+//! The kinematic `animate_beads` path stays the default whenever the toggle is off.
+//! Simulated beads click when they come to rest and when they knock into each other.
+
+use avian3d::prelude::*;
+use bevy::prelude::*;
+use std::collections::HashSet;
+use std::f32::consts::PI;
+
+use crate::abacus::*;
+use crate::audio::BeadArrived;
+use crate::AbacusSettings;
+
+const BEAD_MASS: f32 = 0.05;
+/// Spring stiffness and damping of the pull towards a bead's target.
+const SPRING_STIFFNESS: f32 = 12.0;
+const SPRING_DAMPING: f32 = 0.6;
+const BEAD_FRICTION: f32 = 0.4;
+const BEAD_RESTITUTION: f32 = 0.35;
+/// Speed, in units per second, above which a bead counts as moving.
+const MOVING_SPEED: f32 = 0.05;
+
+pub struct BeadPhysicsPlugin;
+
+impl Plugin for BeadPhysicsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(PhysicsPlugins::default())
+            .add_systems(Update, (
+                add_long_bodies,
+                add_bead_bodies,
+                sync_body_mode,
+                pull_beads_to_targets,
+                sound_bead_stops,
+            ));
+    }
+}
+
+fn add_long_bodies(
+    mut commands: Commands,
+    longs: Query<Entity, Added<AbacusLong>>,
+) {
+    for long in &longs {
+        // Rods never move relative to the abacus; they only anchor the bead joints
+        commands.entity(long).insert(RigidBody::Static);
+    }
+}
+
+fn add_bead_bodies(
+    mut commands: Commands,
+    settings: Res<AbacusSettings>,
+    beads: Query<(Entity, &AbacusBead, &BelongsTo), Added<AbacusBead>>,
+) {
+    for (entity, bead, BelongsTo(long)) in &beads {
+        // Bead meshes are extruded along their local Z and rotated onto the rod,
+        // so the collider cylinder is rotated back to lie along the same axis.
+        let collider = Collider::compound(vec![(
+            Vec3::ZERO,
+            Quat::from_rotation_x(-PI / 2.0),
+            Collider::cylinder(BEAD_RADIUS, BEAD_HEIGHT),
+        )]);
+
+        commands.entity(entity).insert((
+            body_for(settings.physics_enabled),
+            collider,
+            Mass(BEAD_MASS),
+            Friction::new(BEAD_FRICTION),
+            Restitution::new(BEAD_RESTITUTION),
+            LockedAxes::ROTATION_LOCKED,
+            ExternalForce::default().with_persistence(false),
+            CollisionEventsEnabled,
+        ));
+
+        // Each bead may only travel the gap of its deck, above the beads below it
+        let lowest = (bead.value - 1) as f32 * settings.proportions.bead_spacing;
+        commands.spawn((
+            PrismaticJoint::new(*long, entity)
+                .with_free_axis(Vec3::Y)
+                .with_limits(lowest, lowest + LONG_SPACING),
+            ChildOf(entity),
+        ));
+    }
+}
+
+fn body_for(physics_enabled: bool) -> RigidBody {
+    if physics_enabled {
+        RigidBody::Dynamic
+    } else {
+        RigidBody::Kinematic
+    }
+}
+
+/// Switches beads between simulated and animated when the toggle changes.
+fn sync_body_mode(
+    settings: Res<AbacusSettings>,
+    mut beads: Query<&mut RigidBody, With<AbacusBead>>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+    let body = body_for(settings.physics_enabled);
+    for mut rigid_body in &mut beads {
+        if *rigid_body != body {
+            *rigid_body = body;
+        }
+    }
+}
+
+/// Spring-damper along the rod towards each bead's target, holding it against
+/// gravity once it arrives the way friction holds beads on a real abacus.
+fn pull_beads_to_targets(
+    settings: Res<AbacusSettings>,
+    gravity: Res<Gravity>,
+    longs: Query<&GlobalTransform, With<AbacusLong>>,
+    mut beads: Query<(&AbacusBead, &BelongsTo, &Transform, &LinearVelocity, &mut ExternalForce)>,
+) {
+    if !settings.physics_enabled {
+        return;
+    }
+
+    for (bead, BelongsTo(long), transform, velocity, mut force) in &mut beads {
+        let Ok(long_transform) = longs.get(*long) else {
+            continue;
+        };
+        let rod_axis = long_transform.up();
+        let offset = bead.target.y - transform.translation.y;
+        let speed_along_rod = velocity.0.dot(*rod_axis);
+
+        let pull = SPRING_STIFFNESS * offset - SPRING_DAMPING * speed_along_rod;
+        let hold = -gravity.0.dot(*rod_axis) * BEAD_MASS;
+        force.apply_force(*rod_axis * (pull + hold));
+    }
+}
+
+/// Clicks for simulated beads, which never reach `animate_beads`: one when a bead comes to
+/// rest, on its target or against the end of its deck, and one when it knocks into
+/// another bead.
+fn sound_bead_stops(
+    settings: Res<AbacusSettings>,
+    beads: Query<(Entity, &LinearVelocity), With<AbacusBead>>,
+    mut collisions: EventReader<CollisionStarted>,
+    mut moving: Local<HashSet<Entity>>,
+    mut arrivals: EventWriter<BeadArrived>,
+) {
+    if !settings.physics_enabled {
+        collisions.clear();
+        moving.clear();
+        return;
+    }
+
+    for (entity, velocity) in &beads {
+        if velocity.length() > MOVING_SPEED {
+            moving.insert(entity);
+        } else if moving.remove(&entity) {
+            arrivals.write(BeadArrived(entity));
+        }
+    }
+    // Beads despawned by a rebuild while moving
+    moving.retain(|entity| beads.contains(*entity));
+
+    for CollisionStarted(first, second) in collisions.read() {
+        if let Some(bead) = [*first, *second].into_iter().find(|entity| beads.contains(*entity)) {
+            arrivals.write(BeadArrived(bead));
+        }
+    }
+}
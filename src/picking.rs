@@ -0,0 +1,117 @@
+//! Picking for beads and rods, in place of raycasting every mesh in the scene. The
+//! pointer's ray is taken into each abacus's own space, where the columns it can reach
+//! follow from their spacing; only the beads and rods of those columns are tested. A
+//! 200-column abacus costs about as much to pick as a 9-column one.
+
+use bevy::math::Affine3A;
+use bevy::picking::backend::ray::RayMap;
+use bevy::picking::backend::{HitData, PointerHits};
+use bevy::picking::PickSet;
+use bevy::prelude::*;
+
+use crate::abacus::*;
+
+/// Half the bead's extruded disc, in the bead's own space; beads of other sizes are
+/// scaled by their transforms.
+const BEAD_HALF_EXTENTS: Vec3 = Vec3::new(BEAD_RADIUS, BEAD_RADIUS, BEAD_HEIGHT / 2.0);
+
+/// Half a rod one unit long; each rod's transform stretches it to its column.
+const ROD_HALF_EXTENTS: Vec3 = Vec3::new(FRAME_THICKNESS, FRAME_THICKNESS, 0.5);
+
+pub struct AbacusPickingPlugin;
+
+impl Plugin for AbacusPickingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PreUpdate, pick_beads.in_set(PickSet::Backend));
+    }
+}
+
+/// Distance along the ray to where it enters the box centered on the origin, if it does.
+fn ray_box_distance(origin: Vec3, direction: Vec3, half_extents: Vec3) -> Option<f32> {
+    let inverse = direction.recip();
+    let t1 = (-half_extents - origin) * inverse;
+    let t2 = (half_extents - origin) * inverse;
+    let near = t1.min(t2).max_element().max(0.0);
+    let far = t1.max(t2).min_element();
+    (far >= near).then_some(near)
+}
+
+/// Columns whose beads the ray can touch, from where it crosses the slab the beads sit in.
+fn reachable_columns(origin: Vec3, direction: Vec3, column_count: usize, proportions: &Proportions) -> std::ops::Range<usize> {
+    // Beads are discs on the rods, as deep as they are wide
+    let depth = proportions.bead_radius;
+    let spacing = proportions.column_spacing;
+    let (x_min, x_max) = if direction.z.abs() < f32::EPSILON {
+        if origin.z.abs() > depth {
+            return 0..0;
+        }
+        (f32::NEG_INFINITY, f32::INFINITY)
+    } else {
+        let t1 = (-depth - origin.z) / direction.z;
+        let t2 = (depth - origin.z) / direction.z;
+        let x1 = origin.x + t1.max(0.0) * direction.x;
+        let x2 = origin.x + t2.max(0.0) * direction.x;
+        (x1.min(x2), x1.max(x2))
+    };
+    // Columns are centered on the abacus, a column spacing apart
+    let center = (column_count as f32 - 1.0) / 2.0;
+    let reach = depth / spacing;
+    let first = (x_min / spacing + center - reach).ceil().max(0.0);
+    let last = (x_max / spacing + center + reach).floor().min(column_count as f32 - 1.0);
+    if first > last {
+        return 0..0;
+    }
+    first as usize..last as usize + 1
+}
+
+fn pick_beads(
+    ray_map: Res<RayMap>,
+    camera_query: Query<&Camera, With<Camera3d>>,
+    abacus_query: Query<(&Abacus, &GlobalTransform), Without<ReadOnlyAbacus>>,
+    children_query: Query<&Children, With<AbacusLong>>,
+    part_query: Query<(&GlobalTransform, &InheritedVisibility, Has<AbacusBead>), With<Mesh3d>>,
+    mut hits: EventWriter<PointerHits>,
+) {
+    for (&ray_id, ray) in ray_map.iter() {
+        let Ok(camera) = camera_query.get(ray_id.camera) else {
+            continue;
+        };
+        if !camera.is_active {
+            continue;
+        }
+        let mut picks = Vec::new();
+        for (abacus, abacus_transform) in &abacus_query {
+            // Columns are laid out across the upright abacus, before any turn onto its side
+            let to_abacus = (abacus_transform.affine() * Affine3A::from_quat(abacus.rod_rotation())).inverse();
+            let mut origin = to_abacus.transform_point3(ray.origin);
+            let mut direction = to_abacus.transform_vector3(*ray.direction);
+            // Counting columns from the other end is the same as looking from the other side
+            if abacus.ones_on_left {
+                origin.x = -origin.x;
+                direction.x = -direction.x;
+            }
+            for column in reachable_columns(origin, direction, abacus.columns.len(), &abacus.proportions) {
+                for children in abacus.longs[column].iter().filter_map(|&long| children_query.get(long).ok()) {
+                    for &part in &children[..] {
+                        let Ok((transform, visibility, is_bead)) = part_query.get(part) else {
+                            continue;
+                        };
+                        if !visibility.get() {
+                            continue;
+                        }
+                        // Distances are the same in the part's space as in the world's, since
+                        // the direction is carried over unnormalized
+                        let to_part = transform.affine().inverse();
+                        let half_extents = if is_bead { BEAD_HALF_EXTENTS } else { ROD_HALF_EXTENTS };
+                        if let Some(depth) = ray_box_distance(to_part.transform_point3(ray.origin), to_part.transform_vector3(*ray.direction), half_extents) {
+                            picks.push((part, HitData::new(ray_id.camera, depth, Some(ray.get_point(depth)), None)));
+                        }
+                    }
+                }
+            }
+        }
+        if !picks.is_empty() {
+            hits.write(PointerHits::new(ray_id.pointer, picks, camera.order as f32));
+        }
+    }
+}
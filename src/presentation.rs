@@ -0,0 +1,134 @@
+//! Presentation mode for screen sharing: F11 (or the Display Options checkbox) goes full
+//! screen with every panel hidden, leaving the abaci and a large total. F11 or Escape
+//! comes back; both can be rebound under Keybindings. Kiosk mode keeps it on.
+
+use bevy::prelude::*;
+use bevy::window::{MonitorSelection, PrimaryWindow, WindowMode};
+use bevy_egui::{egui, EguiInput, EguiPostUpdateSet, EguiPreUpdateSet, EguiRenderOutput};
+
+use crate::abacus::*;
+use crate::keybindings::{ActionInput, InputAction};
+use crate::kiosk::Kiosk;
+use crate::localization::*;
+use crate::scene_layout::SelectedAbacus;
+use crate::{format_total, AbacusSettings};
+
+/// The total shown at the top of the screen while the panels are hidden.
+#[derive(Component)]
+struct PresentationTotal;
+
+pub struct PresentationPlugin;
+
+impl Plugin for PresentationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_presentation_total)
+            .add_systems(PreUpdate, ignore_ui_input.after(EguiPreUpdateSet::ProcessInput).before(EguiPreUpdateSet::BeginPass))
+            .add_systems(Update, (
+                toggle_presentation,
+                switch_presentation.run_if(resource_changed::<AbacusSettings>),
+                update_presentation_total,
+            ).chain())
+            .add_systems(PostUpdate, hide_ui.after(EguiPostUpdateSet::ProcessOutput));
+    }
+}
+
+fn spawn_presentation_total(mut commands: Commands) {
+    commands.spawn((
+        PresentationTotal,
+        Text::new(""),
+        TextLayout::new_with_justify(JustifyText::Center),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(8.0),
+            width: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        Visibility::Hidden,
+    ));
+}
+
+fn toggle_presentation(input: ActionInput, kiosk: Res<Kiosk>, mut settings: ResMut<AbacusSettings>) {
+    if kiosk.enabled {
+        return;
+    }
+    if input.just_pressed(InputAction::Presentation) {
+        settings.presentation_mode = !settings.presentation_mode;
+    } else if input.just_pressed(InputAction::ExitPresentation) && settings.presentation_mode {
+        settings.presentation_mode = false;
+    }
+}
+
+/// Goes full screen and shows the large total on entering presentation mode, and back.
+fn switch_presentation(
+    settings: Res<AbacusSettings>,
+    mut active: Local<bool>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+    mut total_query: Query<&mut Visibility, With<PresentationTotal>>,
+) {
+    if settings.presentation_mode == *active {
+        return;
+    }
+    *active = settings.presentation_mode;
+    if let Ok(mut window) = window_query.single_mut() {
+        window.mode = if settings.presentation_mode {
+            WindowMode::BorderlessFullscreen(MonitorSelection::Current)
+        } else {
+            WindowMode::Windowed
+        };
+    }
+    for mut visibility in &mut total_query {
+        *visibility = if settings.presentation_mode { Visibility::Inherited } else { Visibility::Hidden };
+    }
+}
+
+fn update_presentation_total(
+    settings: Res<AbacusSettings>,
+    selected: Res<SelectedAbacus>,
+    abacus_query: Query<&Abacus>,
+    mut total_query: Query<(&mut Text, &mut TextFont, &mut TextColor), With<PresentationTotal>>,
+    localization: Res<Localization>,
+) {
+    if !settings.presentation_mode {
+        return;
+    }
+    let Ok((mut text, mut font, mut color)) = total_query.single_mut() else {
+        return;
+    };
+    let total = selected.0
+        .and_then(|entity| abacus_query.get(entity).ok())
+        .map(|abacus| format_total(abacus, &settings, &localization))
+        .unwrap_or_default();
+    // Only touch the text when it differs, so it isn't laid out again every frame
+    if text.0 != total {
+        text.0 = total;
+    }
+    if font.font_size != settings.hud_text_size || font.font != settings.text_font {
+        font.font_size = settings.hud_text_size;
+        font.font = settings.text_font.clone();
+    }
+    if color.0 != settings.text_color {
+        color.0 = settings.text_color;
+    }
+}
+
+/// Keeps the hidden panels from reacting to clicks and keys meant for the abaci.
+fn ignore_ui_input(settings: Res<AbacusSettings>, mut inputs: Query<&mut EguiInput>) {
+    if !settings.presentation_mode {
+        return;
+    }
+    for mut input in &mut inputs {
+        // Moving the pointer away also stops the panels from blocking bead picking
+        input.0.events.clear();
+        input.0.events.push(egui::Event::PointerGone);
+    }
+}
+
+fn hide_ui(settings: Res<AbacusSettings>, mut render_outputs: Query<&mut EguiRenderOutput>) {
+    if !settings.presentation_mode {
+        return;
+    }
+    for mut render_output in &mut render_outputs {
+        render_output.paint_jobs = Default::default();
+    }
+}
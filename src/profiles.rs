@@ -0,0 +1,186 @@
+use bevy::prelude::*;
+use bevy_egui::egui;
+use serde::{Deserialize, Serialize};
+
+use crate::keybindings::KeyBindings;
+use crate::lessons::LessonState;
+use crate::localization::*;
+use crate::macros::MacroLibrary;
+use crate::snapshots::Snapshots;
+use crate::stats::SessionHistory;
+use crate::storage;
+use crate::{AbacusSettings, SavableAbacusConfig, UserConfigurations};
+
+const PROFILES_KEY: &str = "profiles";
+const CONFIGS_KEY: &str = "configs";
+const KEYBINDINGS_KEY: &str = "keybindings";
+const LESSONS_KEY: &str = "lessons";
+const MACROS_KEY: &str = "macros";
+const SNAPSHOTS_KEY: &str = "snapshots";
+const UI_SCALE_KEY: &str = "ui-scale";
+
+#[derive(Default, Serialize, Deserialize)]
+struct SavedProfiles {
+    names: Vec<String>,
+    last_used: Option<String>,
+}
+
+/// The students using this device. Each profile keeps its own saved configurations,
+/// statistics, completed lessons and UI scale; `None` is the shared guest profile.
+#[derive(Resource)]
+pub struct Profiles {
+    names: Vec<String>,
+    pub active: Option<String>,
+    new_name: String,
+    /// The profile whose data is currently loaded, once any has been.
+    loaded: Option<Option<String>>,
+}
+
+impl FromWorld for Profiles {
+    fn from_world(world: &mut World) -> Self {
+        let localization = world.get_resource_or_init::<Localization>();
+        let saved: SavedProfiles = storage::load_json(PROFILES_KEY, &localization).unwrap_or_default();
+        Self {
+            active: saved.last_used.filter(|name| saved.names.contains(name)),
+            names: saved.names,
+            new_name: String::new(),
+            loaded: None,
+        }
+    }
+}
+
+impl Profiles {
+    fn save(&self, localization: &Localization) {
+        storage::save_json(PROFILES_KEY, &SavedProfiles {
+            names: self.names.clone(),
+            last_used: self.active.clone(),
+        }, localization);
+    }
+
+    fn create(&mut self, name: &str, localization: &Localization) {
+        let name = name.trim();
+        if name.is_empty() {
+            return;
+        }
+        if !self.names.iter().any(|existing| existing == name) {
+            self.names.push(name.to_string());
+        }
+        self.active = Some(name.to_string());
+        self.save(localization);
+    }
+}
+
+/// Profile picker and "new profile" field, shown in the welcome window and the settings panel.
+pub fn profile_picker(ui: &mut egui::Ui, profiles: &mut Profiles, localization: &Localization) {
+    let guest = tr!(localization, "profile-guest");
+    let mut active = profiles.active.clone();
+    egui::ComboBox::from_label(tr!(localization, "profile"))
+        .selected_text(active.clone().unwrap_or_else(|| guest.clone()))
+        .show_ui(ui, |ui| {
+            ui.selectable_value(&mut active, None, guest.as_str());
+            for name in &profiles.names {
+                ui.selectable_value(&mut active, Some(name.clone()), name);
+            }
+        });
+    if active != profiles.active {
+        profiles.active = active;
+        profiles.save(localization);
+    }
+
+    ui.horizontal(|ui| {
+        let response = ui.add_sized([140.0, ui.available_height()],
+            egui::TextEdit::singleline(&mut profiles.new_name).hint_text(tr!(localization, "profile-new-name")));
+        let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+        if ui.button(tr!(localization, "profile-create")).clicked() || submitted {
+            let name = std::mem::take(&mut profiles.new_name);
+            profiles.create(&name, localization);
+        }
+    });
+}
+
+/// Loads the active profile's data whenever the profile changes, saving the outgoing
+/// one first, and saves saved-configuration, lesson, macro, snapshot, keybinding and UI scale
+/// changes as they happen. The UI scale belongs to the student rather than to any
+/// configuration, so loading one leaves it alone.
+pub fn sync_profile_data(
+    mut profiles: ResMut<Profiles>,
+    mut user_configs: ResMut<UserConfigurations>,
+    mut history: ResMut<SessionHistory>,
+    mut lessons: ResMut<LessonState>,
+    mut macros: ResMut<MacroLibrary>,
+    mut snapshots: ResMut<Snapshots>,
+    mut bindings: ResMut<KeyBindings>,
+    mut settings: ResMut<AbacusSettings>,
+    mut saved_ui_scale: Local<Option<f32>>,
+    localization: Res<Localization>,
+) {
+    if profiles.loaded.as_ref() != Some(&profiles.active) {
+        let active = profiles.active.clone();
+        if let Some(previous) = profiles.loaded.take() {
+            save_profile_data(previous.as_deref(), &mut user_configs, &mut lessons, &mut macros, &mut snapshots, &mut bindings, &localization);
+            save_ui_scale(previous.as_deref(), settings.ui_scale, &mut saved_ui_scale, &localization);
+        }
+
+        let profile = active.as_deref();
+        // A new profile starts from the built-in configurations
+        user_configs.configs = storage::load_json(&storage::profile_key(profile, CONFIGS_KEY), &localization)
+            .unwrap_or_else(SavableAbacusConfig::built_in);
+        lessons.completed = storage::load_json(&storage::profile_key(profile, LESSONS_KEY), &localization).unwrap_or_default();
+        macros.macros = storage::load_json(&storage::profile_key(profile, MACROS_KEY), &localization).unwrap_or_default();
+        snapshots.saved = storage::load_json(&storage::profile_key(profile, SNAPSHOTS_KEY), &localization).unwrap_or_default();
+        *bindings = KeyBindings::from_saved(storage::load_json(&storage::profile_key(profile, KEYBINDINGS_KEY), &localization).unwrap_or_default());
+        // A profile without one keeps the scale in use, rather than shrinking it back
+        if let Some(ui_scale) = storage::load_json::<f32>(&storage::profile_key(profile, UI_SCALE_KEY), &localization) {
+            settings.ui_scale = ui_scale;
+        }
+        *saved_ui_scale = Some(settings.ui_scale);
+        history.switch_profile(profile, &localization);
+        let name = profile.map(str::to_string).unwrap_or_else(|| tr!(localization, "profile-guest"));
+        info!("{}", tr!(localization, "log-profile-loaded", name = name));
+        profiles.loaded = Some(active);
+        return;
+    }
+
+    if user_configs.configs_changed || lessons.completed_changed || macros.changed || snapshots.changed || bindings.changed {
+        save_profile_data(profiles.active.as_deref(), &mut user_configs, &mut lessons, &mut macros, &mut snapshots, &mut bindings, &localization);
+    }
+    save_ui_scale(profiles.active.as_deref(), settings.ui_scale, &mut saved_ui_scale, &localization);
+}
+
+fn save_ui_scale(profile: Option<&str>, ui_scale: f32, saved: &mut Option<f32>, localization: &Localization) {
+    if *saved != Some(ui_scale) {
+        storage::save_json(&storage::profile_key(profile, UI_SCALE_KEY), &ui_scale, localization);
+        *saved = Some(ui_scale);
+    }
+}
+
+fn save_profile_data(
+    profile: Option<&str>,
+    user_configs: &mut UserConfigurations,
+    lessons: &mut LessonState,
+    macros: &mut MacroLibrary,
+    snapshots: &mut Snapshots,
+    bindings: &mut KeyBindings,
+    localization: &Localization,
+) {
+    if user_configs.configs_changed {
+        storage::save_json(&storage::profile_key(profile, CONFIGS_KEY), &user_configs.configs, localization);
+        user_configs.configs_changed = false;
+    }
+    if lessons.completed_changed {
+        storage::save_json(&storage::profile_key(profile, LESSONS_KEY), &lessons.completed, localization);
+        lessons.completed_changed = false;
+    }
+    if macros.changed {
+        storage::save_json(&storage::profile_key(profile, MACROS_KEY), &macros.macros, localization);
+        macros.changed = false;
+    }
+    if snapshots.changed {
+        storage::save_json(&storage::profile_key(profile, SNAPSHOTS_KEY), &snapshots.saved, localization);
+        snapshots.changed = false;
+    }
+    if bindings.changed {
+        storage::save_json(&storage::profile_key(profile, KEYBINDINGS_KEY), bindings.saved(), localization);
+        bindings.changed = false;
+    }
+}
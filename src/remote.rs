@@ -0,0 +1,201 @@
+//! WebSocket remote control, enabled with the `remote` cargo feature on desktop builds.
+//! Stream overlays, classroom dashboards and home-automation scripts connect to
+//! `ws://127.0.0.1:9001` (or the address in `ABACUS_REMOTE_ADDRESS`) and send JSON:
+//!
+//! - `{ "command": "set", "value": n }`, `"add"` and `"subtract"` move the selected
+//!   abacus like the buttons in Controls
+//! - `{ "command": "get" }` answers `{ "type": "value", "abacus": slot, "value": n }`
+//! - `{ "command": "tally" }` counts one trigger in the Tally Counter
+//!
+//! Every client hears `{ "type": "abacus-changed", "abacus": slot, "value": n }` whenever
+//! an abacus's total changes.
+
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tungstenite::Message;
+
+use crate::abacus::*;
+use crate::localization::*;
+use crate::operations::{AbacusOperation, OperationRequested};
+use crate::scene_layout::SelectedAbacus;
+use crate::tally::TallyTriggered;
+
+/// Only this machine can connect unless the environment variable says otherwise.
+const DEFAULT_ADDRESS: &str = "127.0.0.1:9001";
+const ADDRESS_ENV_VAR: &str = "ABACUS_REMOTE_ADDRESS";
+/// How long a client's thread waits for a message before sending out queued ones.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+#[derive(Deserialize)]
+#[serde(tag = "command", rename_all = "lowercase")]
+enum RemoteRequest {
+    Set { value: u64 },
+    Get,
+    Add { value: u64 },
+    Subtract { value: u64 },
+    Tally,
+}
+
+/// A request from a client, with the way back to it.
+struct RemoteCommand {
+    request: RemoteRequest,
+    reply: Sender<String>,
+}
+
+/// Messages outgoing to each connected client.
+type Clients = Arc<Mutex<Vec<Sender<String>>>>;
+
+#[derive(Resource)]
+struct RemoteServer {
+    commands: Mutex<Receiver<RemoteCommand>>,
+    clients: Clients,
+}
+
+pub struct RemotePlugin;
+
+impl Plugin for RemotePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, start_remote_server)
+            .add_systems(Update, (
+                apply_remote_commands,
+                broadcast_changes.run_if(on_event::<AbacusChanged>),
+            ));
+    }
+}
+
+fn start_remote_server(mut commands: Commands, localization: Res<Localization>) {
+    let address = std::env::var(ADDRESS_ENV_VAR).unwrap_or_else(|_| DEFAULT_ADDRESS.to_string());
+    let listener = match TcpListener::bind(&address) {
+        Ok(listener) => listener,
+        Err(error) => {
+            warn!("{}", tr!(localization, "log-remote-failed", address = address, error = error.to_string()));
+            return;
+        }
+    };
+    info!("{}", tr!(localization, "log-remote-listening", address = address));
+
+    let (command_sender, command_receiver) = channel();
+    let clients = Clients::default();
+    let thread_clients = clients.clone();
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let commands = command_sender.clone();
+            let clients = thread_clients.clone();
+            std::thread::spawn(move || serve_client(stream, commands, clients));
+        }
+    });
+    commands.insert_resource(RemoteServer { commands: Mutex::new(command_receiver), clients });
+}
+
+fn error_message(message: &str) -> String {
+    serde_json::json!({ "type": "error", "message": message }).to_string()
+}
+
+fn value_message(kind: &str, abacus: &Abacus) -> String {
+    serde_json::json!({ "type": kind, "abacus": abacus.slot, "value": abacus.total_value }).to_string()
+}
+
+/// Passes one client's requests to the app and sends it whatever is queued for it, until
+/// it disconnects.
+fn serve_client(stream: TcpStream, commands: Sender<RemoteCommand>, clients: Clients) {
+    let Ok(mut socket) = tungstenite::accept(stream) else {
+        return;
+    };
+    // Reads time out so queued messages go out even while the client is quiet
+    if socket.get_ref().set_read_timeout(Some(POLL_INTERVAL)).is_err() {
+        return;
+    }
+    let (outgoing, outbox) = channel();
+    if let Ok(mut clients) = clients.lock() {
+        clients.push(outgoing.clone());
+    }
+    loop {
+        for text in outbox.try_iter() {
+            if socket.send(Message::text(text)).is_err() {
+                return;
+            }
+        }
+        match socket.read() {
+            Ok(Message::Text(text)) => match serde_json::from_str::<RemoteRequest>(text.as_str()) {
+                Ok(request) => {
+                    if commands.send(RemoteCommand { request, reply: outgoing.clone() }).is_err() {
+                        return;
+                    }
+                }
+                Err(error) => {
+                    if socket.send(Message::text(error_message(&error.to_string()))).is_err() {
+                        return;
+                    }
+                }
+            },
+            Ok(Message::Close(_)) => return,
+            Ok(_) => {}
+            Err(tungstenite::Error::Io(error)) if matches!(error.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+            Err(_) => return,
+        }
+    }
+}
+
+fn apply_remote_commands(
+    server: Option<Res<RemoteServer>>,
+    selected: Res<SelectedAbacus>,
+    abacus_query: Query<&Abacus>,
+    mut requests: EventWriter<OperationRequested>,
+    mut tally: EventWriter<TallyTriggered>,
+) {
+    let Some(server) = server else {
+        return;
+    };
+    let Ok(inbox) = server.commands.lock() else {
+        return;
+    };
+    for command in inbox.try_iter() {
+        let Some((entity, abacus)) = selected.0.and_then(|entity| abacus_query.get(entity).ok().map(|abacus| (entity, abacus))) else {
+            let _ = command.reply.send(error_message("no abacus is selected"));
+            continue;
+        };
+        let operation = match command.request {
+            RemoteRequest::Set { value } => AbacusOperation::Set(value),
+            RemoteRequest::Add { value } => AbacusOperation::Add(value),
+            RemoteRequest::Subtract { value } => AbacusOperation::Subtract(value),
+            RemoteRequest::Get => {
+                let _ = command.reply.send(value_message("value", abacus));
+                continue;
+            }
+            RemoteRequest::Tally => {
+                tally.write(TallyTriggered);
+                continue;
+            }
+        };
+        requests.write(OperationRequested { abacus: entity, operation });
+    }
+}
+
+/// Tells every client about each abacus whose total changed.
+fn broadcast_changes(
+    server: Option<Res<RemoteServer>>,
+    abacus_query: Query<(Entity, &Abacus)>,
+    mut sent: Local<HashMap<Entity, u64>>,
+) {
+    let Some(server) = server else {
+        return;
+    };
+    sent.retain(|entity, _| abacus_query.contains(*entity));
+    let Ok(mut clients) = server.clients.lock() else {
+        return;
+    };
+    for (entity, abacus) in &abacus_query {
+        if sent.insert(entity, abacus.total_value) == Some(abacus.total_value) {
+            continue;
+        }
+        let message = value_message("abacus-changed", abacus);
+        // Clients that have disconnected drop out here
+        clients.retain(|client| client.send(message.clone()).is_ok());
+    }
+}
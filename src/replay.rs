@@ -0,0 +1,218 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::abacus::*;
+use crate::localization::*;
+use crate::operations::{AbacusOperation, ColumnOpQueue, OperationPerformed, OperationRequested};
+
+/// One operation of a recorded session.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReplayEvent {
+    /// Seconds since the recording started.
+    pub seconds: f32,
+    /// Slot of the abacus it was done to, which survives restarts unlike its entity.
+    pub abacus: usize,
+    pub operation: AbacusOperation,
+}
+
+/// A recorded session: what each abacus showed at the start, every operation with its
+/// timestamp, and what each abacus showed at the end so a replay can be checked.
+/// Replays assume the same abaci (structure and slots) as when they were recorded.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ReplayFile {
+    pub start_totals: Vec<(usize, u64)>,
+    pub events: Vec<ReplayEvent>,
+    #[serde(default)]
+    pub final_totals: Vec<(usize, u64)>,
+}
+
+/// Replay flags from the command line (see `startup::parse_args`):
+/// `--record <file>` saves the session when the app closes,
+/// `--replay <file>` plays a saved session back on screen, and
+/// `--headless` plays it in a hidden window and exits when it's done, failing if the
+/// abaci don't end where they did when it was recorded.
+#[derive(Resource, Default)]
+pub struct ReplayOptions {
+    pub record: Option<PathBuf>,
+    pub replay: Option<PathBuf>,
+    pub headless: bool,
+}
+
+struct Recording {
+    path: PathBuf,
+    /// When the recording started, once there were abaci to record.
+    started: Option<f32>,
+    file: ReplayFile,
+}
+
+struct Playback {
+    file: ReplayFile,
+    /// When playback started, once the recorded abaci exist.
+    started: Option<f32>,
+    next: usize,
+}
+
+#[derive(Resource, Default)]
+pub struct ReplaySession {
+    recording: Option<Recording>,
+    playback: Option<Playback>,
+}
+
+fn totals_by_slot(abacus_query: &Query<&Abacus>) -> Vec<(usize, u64)> {
+    let mut totals: Vec<(usize, u64)> = abacus_query.iter().map(|abacus| (abacus.slot, abacus.total_value)).collect();
+    totals.sort();
+    totals
+}
+
+fn read_replay(path: &PathBuf) -> Result<ReplayFile, String> {
+    let text = std::fs::read_to_string(path).map_err(|error| error.to_string())?;
+    serde_json::from_str(&text).map_err(|error| error.to_string())
+}
+
+fn write_replay(path: &PathBuf, file: &ReplayFile) -> Result<(), String> {
+    let text = serde_json::to_string_pretty(file).map_err(|error| error.to_string())?;
+    std::fs::write(path, text).map_err(|error| error.to_string())
+}
+
+pub fn init_replay_session(options: Res<ReplayOptions>, localization: Res<Localization>, mut commands: Commands) {
+    let mut session = ReplaySession::default();
+    if let Some(path) = &options.record {
+        session.recording = Some(Recording { path: path.clone(), started: None, file: ReplayFile::default() });
+    }
+    if let Some(path) = &options.replay {
+        match read_replay(path) {
+            Ok(file) => {
+                info!("{}", tr!(localization, "log-replay-started", count = file.events.len(), path = path.display().to_string()));
+                session.playback = Some(Playback { file, started: None, next: 0 });
+            }
+            Err(error) => error!("{}", tr!(localization, "log-replay-read-failed", path = path.display().to_string(), error = error)),
+        }
+    }
+    commands.insert_resource(session);
+}
+
+/// Appends every performed operation to the recording, stamped with its time.
+pub fn record_replay_events(
+    time: Res<Time>,
+    mut performed: EventReader<OperationPerformed>,
+    mut session: ResMut<ReplaySession>,
+    abacus_query: Query<&Abacus>,
+) {
+    let Some(recording) = &mut session.recording else {
+        performed.clear();
+        return;
+    };
+    let started = match recording.started {
+        Some(started) => started,
+        None if !abacus_query.is_empty() => {
+            recording.file.start_totals = totals_by_slot(&abacus_query);
+            *recording.started.insert(time.elapsed_secs())
+        }
+        None => {
+            performed.clear();
+            return;
+        }
+    };
+    for event in performed.read() {
+        let Ok(abacus) = abacus_query.get(event.abacus) else {
+            continue;
+        };
+        recording.file.events.push(ReplayEvent {
+            seconds: time.elapsed_secs() - started,
+            abacus: abacus.slot,
+            operation: event.operation.clone(),
+        });
+    }
+}
+
+/// Saves the recording, with the totals the abaci end on, when the app closes.
+pub fn save_replay_on_exit(
+    mut exits: EventReader<AppExit>,
+    mut session: ResMut<ReplaySession>,
+    abacus_query: Query<&Abacus>,
+    localization: Res<Localization>,
+) {
+    if exits.read().count() == 0 {
+        return;
+    }
+    let Some(mut recording) = session.recording.take() else {
+        return;
+    };
+    recording.file.final_totals = totals_by_slot(&abacus_query);
+    match write_replay(&recording.path, &recording.file) {
+        Ok(()) => info!("{}", tr!(localization, "log-replay-saved", count = recording.file.events.len(), path = recording.path.display().to_string())),
+        Err(error) => error!("{}", tr!(localization, "log-replay-save-failed", path = recording.path.display().to_string(), error = error)),
+    }
+}
+
+/// Restores the recorded starting totals, then sends each recorded operation once its
+/// time comes. A headless replay exits when done, with an error if the totals differ.
+pub fn play_replay(
+    time: Res<Time>,
+    options: Res<ReplayOptions>,
+    mut session: ResMut<ReplaySession>,
+    queue: Res<ColumnOpQueue>,
+    mut abacus_query: Query<(Entity, &mut Abacus)>,
+    mut long_query: Query<&mut AbacusLong>,
+    mut requests: EventWriter<OperationRequested>,
+    mut exits: EventWriter<AppExit>,
+    mut commands: Commands,
+    localization: Res<Localization>,
+) {
+    let Some(playback) = &mut session.playback else {
+        return;
+    };
+
+    let started = match playback.started {
+        Some(started) => started,
+        None => {
+            // Wait until every recorded abacus has been spawned
+            let missing = playback.file.start_totals.iter()
+                .any(|&(slot, _)| !abacus_query.iter().any(|(_, abacus)| abacus.slot == slot));
+            if missing {
+                return;
+            }
+            for (_, mut abacus) in &mut abacus_query {
+                if let Some(&(_, total)) = playback.file.start_totals.iter().find(|&&(slot, _)| slot == abacus.slot) {
+                    abacus.set_total_value(total, &mut long_query, &mut commands);
+                }
+            }
+            *playback.started.insert(time.elapsed_secs())
+        }
+    };
+
+    let elapsed = time.elapsed_secs() - started;
+    while let Some(event) = playback.file.events.get(playback.next).filter(|event| event.seconds <= elapsed) {
+        match abacus_query.iter().find(|(_, abacus)| abacus.slot == event.abacus) {
+            Some((entity, _)) => {
+                requests.write(OperationRequested { abacus: entity, operation: event.operation.clone() });
+            }
+            None => warn!("{}", tr!(localization, "log-replay-missing-abacus", abacus = event.abacus + 1)),
+        }
+        playback.next += 1;
+    }
+
+    // Done once every operation has been sent and has finished moving the beads
+    if playback.next < playback.file.events.len() || queue.pending_count() > 0 {
+        return;
+    }
+    let Some(playback) = session.playback.take() else {
+        return;
+    };
+    // Read the beads rather than `total_value`, which catches up a frame later
+    let mut totals: Vec<(usize, u64)> = abacus_query.iter()
+        .map(|(_, abacus)| (abacus.slot, abacus.beads_total_value(&long_query.as_readonly())))
+        .collect();
+    totals.sort();
+    let matches = playback.file.final_totals.is_empty() || playback.file.final_totals == totals;
+    if matches {
+        info!("{}", tr!(localization, "log-replay-finished", totals = format!("{:?}", totals)));
+    } else {
+        error!("{}", tr!(localization, "log-replay-mismatch",
+            totals = format!("{:?}", totals), expected = format!("{:?}", playback.file.final_totals)));
+    }
+    if options.headless {
+        exits.write(if matches { AppExit::Success } else { AppExit::error() });
+    }
+}
@@ -0,0 +1,262 @@
+//! Saves every abacus in the scene, beads and all, as a Bevy scene (`.scn.ron`) and loads
+//! it back. Where a configuration only keeps the settings, a scene also keeps each
+//! abacus's place, rotation and beads. Only the desktop build has the file dialogs.
+//!
+//! Loading doesn't spawn the saved entities as they are: meshes, materials and observers
+//! aren't in the file. Each saved abacus is rebuilt from its structure instead, then its
+//! beads slide to where they were saved.
+
+use bevy::prelude::*;
+#[cfg(not(target_arch = "wasm32"))]
+use bevy::reflect::TypeRegistryArc;
+#[cfg(not(target_arch = "wasm32"))]
+use bevy::scene::serde::SceneDeserializer;
+#[cfg(not(target_arch = "wasm32"))]
+use bevy::scene::DynamicEntity;
+#[cfg(not(target_arch = "wasm32"))]
+use bevy::tasks::{block_on, futures_lite::future, IoTaskPool, Task};
+#[cfg(not(target_arch = "wasm32"))]
+use serde::de::DeserializeSeed;
+#[cfg(not(target_arch = "wasm32"))]
+use std::collections::HashMap;
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::PathBuf;
+
+use crate::abacus::*;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::localization::*;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::operations::{ColumnOpQueue, OperationRequested};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::scene_layout::SelectedAbacus;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::snapshots::{deck_values, move_to_decks};
+use crate::AbacusStructure;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::AbacusSettings;
+
+/// Asks for a dialog to save the scene to a file, or to load one.
+#[derive(Event, Clone, Copy)]
+pub enum SceneFileRequest {
+    Save,
+    Load,
+}
+
+/// An abacus read back from a scene file.
+#[cfg(not(target_arch = "wasm32"))]
+struct SavedAbacus {
+    structure: AbacusStructure,
+    slot: usize,
+    transform: Transform,
+    decks: Vec<Vec<u64>>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+enum SceneFileOutcome {
+    Saved(PathBuf),
+    Loaded(Vec<SavedAbacus>),
+}
+
+/// The save or open dialog being shown, if any. `None` from the task means it was cancelled.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Resource, Default)]
+struct SceneFileDialog(Option<Task<Option<Result<SceneFileOutcome, String>>>>);
+
+/// Loaded abaci whose beads still have to move to their saved places, once they've spawned.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Resource, Default)]
+struct PendingDecks(Vec<(Entity, Vec<Vec<u64>>)>);
+
+pub struct SceneFilesPlugin;
+
+impl Plugin for SceneFilesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<SceneFileRequest>()
+            .register_type::<Abacus>()
+            .register_type::<AbacusStructure>()
+            .register_type::<AbacusLong>()
+            .register_type::<AbacusBead>();
+        #[cfg(not(target_arch = "wasm32"))]
+        app.init_resource::<SceneFileDialog>()
+            .init_resource::<PendingDecks>()
+            .add_systems(Update, (open_scene_dialogs, finish_scene_dialogs, restore_saved_decks).chain());
+    }
+}
+
+/// The abaci with their decks and beads, as a `.scn.ron` scene.
+#[cfg(not(target_arch = "wasm32"))]
+fn serialize_scene(world: &mut World) -> Result<String, String> {
+    let entities: Vec<Entity> = world
+        .query_filtered::<Entity, Or<(With<Abacus>, With<AbacusLong>, With<AbacusBead>)>>()
+        .iter(world)
+        .collect();
+    let scene = DynamicSceneBuilder::from_world(world)
+        .deny_all()
+        .allow_component::<Abacus>()
+        .allow_component::<AbacusStructure>()
+        .allow_component::<AbacusLong>()
+        .allow_component::<AbacusBead>()
+        .allow_component::<Transform>()
+        .allow_component::<ChildOf>()
+        .extract_entities(entities.into_iter())
+        .build();
+    let registry = world.resource::<AppTypeRegistry>().read();
+    scene.serialize(&registry).map_err(|error| error.to_string())
+}
+
+/// The saved component of type `T` on `entity`, if it has one.
+#[cfg(not(target_arch = "wasm32"))]
+fn saved_component<T: FromReflect + TypePath>(entity: &DynamicEntity) -> Option<T> {
+    entity.components.iter()
+        .find(|component| component.get_represented_type_info().is_some_and(|info| info.type_path() == T::type_path()))
+        .and_then(|component| T::from_reflect(&**component))
+}
+
+/// Reads the abaci back out of a `.scn.ron` scene, with the deck values their longs held.
+#[cfg(not(target_arch = "wasm32"))]
+fn deserialize_scene(text: &str, registry: &TypeRegistryArc) -> Result<Vec<SavedAbacus>, String> {
+    let registry = registry.read();
+    let mut deserializer = ron::de::Deserializer::from_str(text).map_err(|error| error.to_string())?;
+    let scene = SceneDeserializer { type_registry: &registry }
+        .deserialize(&mut deserializer)
+        .map_err(|error| error.to_string())?;
+
+    // The abaci still name their longs by the entities they had when saved
+    let long_values: HashMap<Entity, u64> = scene.entities.iter()
+        .filter_map(|entity| Some((entity.entity, saved_component::<AbacusLong>(entity)?.value)))
+        .collect();
+    let mut abaci: Vec<SavedAbacus> = scene.entities.iter()
+        .filter_map(|entity| {
+            let abacus = saved_component::<Abacus>(entity)?;
+            let decks = abacus.longs.iter()
+                .map(|longs| longs.iter().map(|long| long_values.get(long).copied().unwrap_or(0)).collect())
+                .collect();
+            Some(SavedAbacus {
+                structure: saved_component::<AbacusStructure>(entity)?,
+                slot: abacus.slot,
+                transform: saved_component::<Transform>(entity).unwrap_or_default(),
+                decks,
+            })
+        })
+        .collect();
+    if abaci.is_empty() {
+        return Err("the scene has no abacus in it".to_string());
+    }
+    abaci.sort_by_key(|abacus| abacus.slot);
+    Ok(abaci)
+}
+
+/// Shows the dialog off the main thread, so the app keeps drawing while it's open. The
+/// scene is saved as it was when the dialog opened.
+#[cfg(not(target_arch = "wasm32"))]
+fn open_scene_dialogs(world: &mut World) {
+    let Some(request) = world.resource_mut::<Events<SceneFileRequest>>().drain().last() else {
+        return;
+    };
+    // One dialog at a time
+    if world.resource::<SceneFileDialog>().0.is_some() {
+        return;
+    }
+    let task = match request {
+        SceneFileRequest::Save => {
+            let text = serialize_scene(world);
+            IoTaskPool::get().spawn(async move {
+                let file = rfd::AsyncFileDialog::new()
+                    .add_filter("Bevy scene", &["ron"])
+                    .set_file_name("abacus.scn.ron")
+                    .save_file()
+                    .await?;
+                let path = file.path().to_path_buf();
+                Some(
+                    text.and_then(|text| std::fs::write(&path, text).map_err(|error| error.to_string()))
+                        .map(|()| SceneFileOutcome::Saved(path)),
+                )
+            })
+        }
+        SceneFileRequest::Load => {
+            let registry = world.resource::<AppTypeRegistry>().0.clone();
+            IoTaskPool::get().spawn(async move {
+                let file = rfd::AsyncFileDialog::new()
+                    .add_filter("Bevy scene", &["ron"])
+                    .pick_file()
+                    .await?;
+                Some(
+                    std::fs::read_to_string(file.path())
+                        .map_err(|error| error.to_string())
+                        .and_then(|text| deserialize_scene(&text, &registry))
+                        .map(SceneFileOutcome::Loaded),
+                )
+            })
+        }
+    };
+    world.resource_mut::<SceneFileDialog>().0 = Some(task);
+}
+
+/// Replaces the abaci in the scene with the ones loaded, each in its saved slot.
+#[cfg(not(target_arch = "wasm32"))]
+fn finish_scene_dialogs(
+    mut dialog: ResMut<SceneFileDialog>,
+    mut commands: Commands,
+    mut settings: ResMut<AbacusSettings>,
+    mut selected: ResMut<SelectedAbacus>,
+    abacus_query: Query<Entity, With<Abacus>>,
+    mut op_queue: ResMut<ColumnOpQueue>,
+    mut pending: ResMut<PendingDecks>,
+    localization: Res<Localization>,
+) {
+    let Some(task) = &mut dialog.0 else {
+        return;
+    };
+    let Some(outcome) = block_on(future::poll_once(task)) else {
+        return;
+    };
+    dialog.0 = None;
+    let abaci = match outcome {
+        None => return,
+        Some(Ok(SceneFileOutcome::Saved(path))) => {
+            info!("{}", tr!(localization, "log-scene-saved", path = path.display().to_string()));
+            return;
+        }
+        Some(Ok(SceneFileOutcome::Loaded(abaci))) => abaci,
+        Some(Err(error)) => {
+            warn!("{}", tr!(localization, "log-scene-file-failed", error = error));
+            return;
+        }
+    };
+
+    for entity in &abacus_query {
+        op_queue.clear_abacus(entity);
+        commands.entity(entity).despawn();
+    }
+    pending.0.clear();
+    for saved in &abaci {
+        saved.structure.apply_to(&mut settings);
+        let entity = spawn_abacus(&mut commands, &settings, saved.slot);
+        commands.entity(entity).insert(saved.transform);
+        pending.0.push((entity, saved.decks.clone()));
+    }
+    // The first abacus is selected, so the panel shows its structure
+    abaci[0].structure.apply_to(&mut settings);
+    selected.0 = pending.0.first().map(|(entity, _)| *entity);
+    info!("{}", tr!(localization, "log-scene-loaded", count = abaci.len()));
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn restore_saved_decks(
+    mut pending: ResMut<PendingDecks>,
+    abacus_query: Query<&Abacus>,
+    long_query: Query<&AbacusLong>,
+    mut requests: EventWriter<OperationRequested>,
+) {
+    if pending.0.is_empty() {
+        return;
+    }
+    pending.0.retain(|(entity, decks)| {
+        let Ok(abacus) = abacus_query.get(*entity) else {
+            // Not spawned yet
+            return true;
+        };
+        move_to_decks(*entity, &deck_values(abacus, &long_query), decks, &mut requests);
+        false
+    });
+}
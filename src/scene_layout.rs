@@ -0,0 +1,267 @@
+use bevy::prelude::*;
+use bevy::render::camera::ScalingMode;
+
+use crate::abacus::*;
+use crate::config_gallery::ThumbnailCamera;
+use crate::{AbacusSettings, MainCameraAnchor};
+
+/// Space left between neighbouring abaci.
+const ABACUS_GAP: f32 = 1.5;
+/// Where the camera sits relative to the point it looks at, for a single 9-column abacus.
+const CAMERA_OFFSET: Vec3 = Vec3::new(0.0, 5.0, -14.0);
+/// Where the camera sits in flat mode: straight in front, so the abaci read like a diagram.
+const FLAT_CAMERA_OFFSET: Vec3 = Vec3::new(0.0, 0.0, -14.0);
+/// Width and height of the view at `CAMERA_OFFSET`; larger scenes pull the camera back.
+const CAMERA_VIEW_SIZE: Vec2 = Vec2::new(12.0, 8.0);
+/// How quickly the camera glides to a new focus.
+const CAMERA_FOLLOW_SPEED: f32 = 5.0;
+
+/// How far the camera has been orbited and zoomed from where `focus_camera` would put it,
+/// by touch gestures.
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
+pub struct CameraView {
+    /// Turn about the vertical, in radians.
+    pub yaw: f32,
+    /// Tilt from the usual slightly raised view, in radians; up is positive.
+    pub pitch: f32,
+    /// Above one is closer.
+    pub zoom: f32,
+}
+
+impl Default for CameraView {
+    fn default() -> Self {
+        Self { yaw: 0.0, pitch: 0.0, zoom: 1.0 }
+    }
+}
+
+impl CameraView {
+    const MIN_PITCH: f32 = -1.0;
+    const MAX_PITCH: f32 = 1.0;
+    const MIN_ZOOM: f32 = 0.4;
+    const MAX_ZOOM: f32 = 4.0;
+
+    pub fn orbit(&mut self, yaw: f32, pitch: f32) {
+        self.yaw += yaw;
+        self.pitch = (self.pitch + pitch).clamp(Self::MIN_PITCH, Self::MAX_PITCH);
+    }
+
+    pub fn zoom_by(&mut self, factor: f32) {
+        self.zoom = (self.zoom * factor).clamp(Self::MIN_ZOOM, Self::MAX_ZOOM);
+    }
+
+    /// `offset` from the point the camera looks at, turned and scaled by the view.
+    fn apply(&self, offset: Vec3) -> Vec3 {
+        Quat::from_rotation_y(self.yaw) * Quat::from_rotation_x(self.pitch) * offset / self.zoom
+    }
+}
+
+/// The abacus the settings panel, value controls, lessons and screen reader act on.
+/// Clicking a bead selects its abacus.
+#[derive(Resource, Default)]
+pub struct SelectedAbacus(pub Option<Entity>);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AbacusArrangement {
+    #[default]
+    Row,
+    Grid,
+}
+
+impl AbacusArrangement {
+    pub const ALL: [AbacusArrangement; 2] = [AbacusArrangement::Row, AbacusArrangement::Grid];
+
+    /// Localization id of the arrangement's display name.
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            AbacusArrangement::Row => "arrangement-row",
+            AbacusArrangement::Grid => "arrangement-grid",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CameraFocus {
+    /// Frame every abacus at once.
+    #[default]
+    Shared,
+    /// Follow the selected abacus.
+    Selected,
+}
+
+impl CameraFocus {
+    pub const ALL: [CameraFocus; 2] = [CameraFocus::Shared, CameraFocus::Selected];
+
+    /// Localization id of the focus mode's display name.
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            CameraFocus::Shared => "camera-focus-shared",
+            CameraFocus::Selected => "camera-focus-selected",
+        }
+    }
+}
+
+/// Selects the abacus a bead was clicked on; clicks bubble from the bead up to its abacus.
+/// A read-only copy is only there to be watched, so it's never selected.
+pub fn select_clicked_abacus(
+    trigger: Trigger<Pointer<Click>>,
+    abacus_query: Query<(), (With<Abacus>, Without<ReadOnlyAbacus>)>,
+    mut selected: ResMut<SelectedAbacus>,
+) {
+    let target = trigger.target();
+    if abacus_query.contains(target) && selected.0 != Some(target) {
+        selected.0 = Some(target);
+    }
+}
+
+/// Falls back to the first abacus when the selected one was removed or rebuilt.
+pub fn ensure_abacus_selected(
+    abacus_query: Query<(Entity, &Abacus), Without<ReadOnlyAbacus>>,
+    mut selected: ResMut<SelectedAbacus>,
+) {
+    if selected.0.is_some_and(|entity| abacus_query.contains(entity)) {
+        return;
+    }
+    let first = abacus_query.iter().min_by_key(|(_, abacus)| abacus.slot).map(|(entity, _)| entity);
+    if selected.0 != first {
+        selected.0 = first;
+    }
+}
+
+/// Places every abacus in a centred row or grid, ordered by slot.
+pub fn arrange_abaci(
+    settings: Res<AbacusSettings>,
+    mut abacus_query: Query<(&Abacus, &mut Transform)>,
+) {
+    let mut abaci: Vec<(&Abacus, Mut<Transform>)> = abacus_query.iter_mut().collect();
+    abaci.sort_by_key(|(abacus, _)| abacus.slot);
+
+    let positions = layout_positions(&abaci.iter().map(|(abacus, _)| abacus.size()).collect::<Vec<_>>(), settings.arrangement);
+    for ((_, mut transform), position) in abaci.into_iter().zip(positions) {
+        if transform.translation != position {
+            transform.translation = position;
+        }
+    }
+}
+
+/// Centres of each abacus. The camera looks down +Z, so the first slot is placed
+/// at +X to appear on the left, matching the reading order of the columns.
+fn layout_positions(sizes: &[Vec2], arrangement: AbacusArrangement) -> Vec<Vec3> {
+    match arrangement {
+        AbacusArrangement::Row => {
+            let total_width: f32 = sizes.iter().map(|size| size.x + ABACUS_GAP).sum::<f32>() - ABACUS_GAP;
+            let mut left = total_width / 2.0;
+            sizes.iter().map(|size| {
+                let x = left - size.x / 2.0;
+                left -= size.x + ABACUS_GAP;
+                Vec3::new(x, 0.0, 0.0)
+            }).collect()
+        }
+        AbacusArrangement::Grid => {
+            let grid_columns = (sizes.len() as f32).sqrt().ceil().max(1.0) as usize;
+            let grid_rows = sizes.len().div_ceil(grid_columns);
+            let cell = sizes.iter().fold(Vec2::ZERO, |cell, &size| cell.max(size)) + Vec2::splat(ABACUS_GAP);
+            (0..sizes.len()).map(|i| {
+                let (row, column) = (i / grid_columns, i % grid_columns);
+                Vec3::new(
+                    ((grid_columns as f32 - 1.0) / 2.0 - column as f32) * cell.x,
+                    ((grid_rows as f32 - 1.0) / 2.0 - row as f32) * cell.y,
+                    0.0,
+                )
+            }).collect()
+        }
+    }
+}
+
+/// The flat mode's front-on view, sized like the perspective view at `CAMERA_OFFSET`.
+/// `focus_camera` scales it to fit larger scenes.
+pub fn flat_projection() -> Projection {
+    Projection::Orthographic(OrthographicProjection {
+        scaling_mode: ScalingMode::AutoMin { min_width: CAMERA_VIEW_SIZE.x, min_height: CAMERA_VIEW_SIZE.y },
+        ..OrthographicProjection::default_3d()
+    })
+}
+
+/// Glides the camera to frame all abaci, or just the selected one, from the angle and
+/// distance of the `CameraView`.
+pub fn focus_camera(
+    time: Res<Time>,
+    settings: Res<AbacusSettings>,
+    selected: Res<SelectedAbacus>,
+    view: Res<CameraView>,
+    abacus_query: Query<(Entity, &Abacus, &Transform), Without<MainCameraAnchor>>,
+    mut camera_query: Query<&mut Transform, With<MainCameraAnchor>>,
+    mut projection_query: Query<&mut Projection, (With<Camera>, Without<ThumbnailCamera>)>,
+) {
+    let Ok(mut camera_transform) = camera_query.single_mut() else {
+        return;
+    };
+
+    let focused: Vec<(&Abacus, &Transform)> = abacus_query.iter()
+        .filter(|(entity, _, _)| settings.camera_focus == CameraFocus::Shared || selected.0 == Some(*entity))
+        .map(|(_, abacus, transform)| (abacus, transform))
+        .collect();
+    if focused.is_empty() {
+        return;
+    }
+
+    let (min, max) = focused.iter().fold((Vec3::INFINITY, Vec3::NEG_INFINITY), |(min, max), (abacus, transform)| {
+        let half_size = abacus.size().extend(0.0) / 2.0;
+        (min.min(transform.translation - half_size), max.max(transform.translation + half_size))
+    });
+    let center = (min + max) / 2.0;
+    let extent = (max - min).truncate();
+    let distance_scale = (extent / CAMERA_VIEW_SIZE).max_element().max(1.0);
+    let target = if settings.flat_mode {
+        // An orthographic view doesn't shrink with distance, so it widens instead, and stays
+        // straight on
+        let scale = distance_scale / view.zoom;
+        for mut projection in &mut projection_query {
+            if matches!(&*projection, Projection::Orthographic(ortho) if ortho.scale != scale) {
+                if let Projection::Orthographic(ortho) = &mut *projection {
+                    ortho.scale = scale;
+                }
+            }
+        }
+        center + FLAT_CAMERA_OFFSET
+    } else {
+        center + view.apply(CAMERA_OFFSET * distance_scale)
+    };
+
+    if camera_transform.translation.distance(target) < 0.001 {
+        return;
+    }
+    let follow = if settings.reduced_motion {
+        1.0
+    } else {
+        1.0 - (-CAMERA_FOLLOW_SPEED * time.delta_secs()).exp()
+    };
+    camera_transform.translation = camera_transform.translation.lerp(target, follow);
+    camera_transform.look_at(center + (camera_transform.translation - target), Vec3::Y);
+}
+
+/// Text on an abacus that turns to face the camera, so it reads the right way round however
+/// the abacus is rotated.
+#[derive(Component)]
+pub struct Billboard;
+
+/// Turns every billboard to face the camera. Runs before transforms propagate so it uses
+/// this frame's camera and abacus rotations.
+pub fn face_camera(
+    camera_query: Query<&Transform, With<MainCameraAnchor>>,
+    parent_query: Query<&Transform, (Without<Billboard>, Without<MainCameraAnchor>)>,
+    mut billboard_query: Query<(&mut Transform, &ChildOf), (With<Billboard>, Without<MainCameraAnchor>)>,
+) {
+    let Ok(camera_transform) = camera_query.single() else {
+        return;
+    };
+    // Texts are drawn with a mirrored x so they read correctly from behind, where the
+    // camera looks from; the half turn keeps them facing it
+    let facing = camera_transform.rotation * Quat::from_rotation_y(std::f32::consts::PI);
+    for (mut transform, ChildOf(parent)) in &mut billboard_query {
+        let parent_rotation = parent_query.get(*parent).map_or(Quat::IDENTITY, |parent| parent.rotation);
+        let rotation = parent_rotation.inverse() * facing;
+        if transform.rotation != rotation {
+            transform.rotation = rotation;
+        }
+    }
+}
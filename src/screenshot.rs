@@ -0,0 +1,75 @@
+use bevy::prelude::*;
+use bevy::render::view::screenshot::{save_to_disk, Screenshot};
+use bevy_egui::{EguiPostUpdateSet, EguiRenderOutput};
+use std::path::PathBuf;
+
+use crate::keybindings::{ActionInput, InputAction};
+use crate::localization::*;
+use crate::AbacusSettings;
+
+/// Asks for the next frame to be saved as a PNG.
+#[derive(Event)]
+pub struct ScreenshotRequested;
+
+/// Set for a frame being captured when the panels are left out of screenshots.
+#[derive(Resource, Default)]
+pub struct HideUiThisFrame(pub bool);
+
+pub struct ScreenshotPlugin;
+
+impl Plugin for ScreenshotPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ScreenshotRequested>()
+            .init_resource::<HideUiThisFrame>()
+            .add_systems(Update, take_screenshots)
+            .add_systems(PostUpdate, hide_ui_for_screenshot.after(EguiPostUpdateSet::ProcessOutput));
+    }
+}
+
+/// Natively screenshots go to the exports folder; the web build downloads them instead.
+#[cfg(not(target_arch = "wasm32"))]
+fn screenshot_path() -> Result<PathBuf, String> {
+    let seconds = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs());
+    crate::storage::export_path(&format!("abacus-{}.png", seconds))
+}
+
+#[cfg(target_arch = "wasm32")]
+fn screenshot_path() -> Result<PathBuf, String> {
+    Ok(PathBuf::from("abacus.png"))
+}
+
+/// Captures the window on the Screenshot shortcut (F12 by default) or button.
+fn take_screenshots(
+    input: ActionInput,
+    mut requests: EventReader<ScreenshotRequested>,
+    settings: Res<AbacusSettings>,
+    mut hide_ui: ResMut<HideUiThisFrame>,
+    mut commands: Commands,
+    localization: Res<Localization>,
+) {
+    let requested = requests.read().count() > 0;
+    if !requested && !input.just_pressed(InputAction::Screenshot) {
+        return;
+    }
+    match screenshot_path() {
+        Ok(path) => {
+            info!("{}", tr!(localization, "log-screenshot-saving", path = path.display().to_string()));
+            commands.spawn(Screenshot::primary_window()).observe(save_to_disk(path));
+            // The capture is of this frame, so the panels only disappear for one frame
+            hide_ui.0 = settings.screenshot_hides_ui;
+        }
+        Err(error) => warn!("{}", tr!(localization, "log-screenshot-failed", error = error)),
+    }
+}
+
+/// Drops the panels from the frame being captured, leaving just the abaci.
+fn hide_ui_for_screenshot(mut hide_ui: ResMut<HideUiThisFrame>, mut render_outputs: Query<&mut EguiRenderOutput>) {
+    if !std::mem::take(&mut hide_ui.0) {
+        return;
+    }
+    for mut render_output in &mut render_outputs {
+        render_output.paint_jobs = Default::default();
+    }
+}
@@ -0,0 +1,314 @@
+//! Rhai scripts that drive the abacus, enabled with the `scripting` cargo feature.
+//! Scripts live in `assets/scripts/*.rhai` and run from the console with `run <name>`.
+//! They only see a small sandboxed API:
+//!
+//! - `set(n)`, `add(n)`, `sub(n)`: the same operations as the settings panel
+//! - `wait(seconds)`: pause between operations (up to a minute at a time)
+//! - `value()`, `max_value()`: what the abacus will show once the earlier operations land
+//! - `random(low, high)`: a whole number from `low` to `high`, both included
+//! - `print(text)`: writes to the console
+//!
+//! A script runs to completion straight away, planning its operations, which then play
+//! back one after another like a macro.
+
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext, LoadedFolder};
+use bevy::prelude::*;
+use rhai::{Engine, EvalAltResult};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use crate::abacus::*;
+use crate::console::{Console, ScriptRequest};
+use crate::localization::*;
+use crate::operations::{AbacusOperation, ColumnOpQueue, OperationRequested};
+
+/// Folder under `assets/` scanned for scripts.
+const SCRIPT_FOLDER: &str = "scripts";
+/// Limits that keep a runaway script from hanging the app.
+const MAX_SCRIPT_OPERATIONS: u64 = 200_000;
+const MAX_PLANNED_ACTIONS: usize = 10_000;
+const MAX_WAIT_SECONDS: f64 = 60.0;
+/// Pause after each operation once its beads have settled.
+const SCRIPT_STEP_SECONDS: f32 = 0.2;
+
+#[derive(Asset, TypePath, Debug)]
+pub struct ScriptFile {
+    source: String,
+}
+
+#[derive(Default)]
+pub struct ScriptFileLoader;
+
+impl AssetLoader for ScriptFileLoader {
+    type Asset = ScriptFile;
+    type Settings = ();
+    type Error = std::io::Error;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<ScriptFile, std::io::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let source = String::from_utf8(bytes).map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error))?;
+        Ok(ScriptFile { source })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["rhai"]
+    }
+}
+
+enum ScriptAction {
+    Operation(AbacusOperation),
+    Wait(f32),
+    Print(String),
+}
+
+/// What a script has planned so far, and the value the abacus will reach by then.
+struct ScriptPlan {
+    actions: Vec<ScriptAction>,
+    value: u64,
+    max_value: u64,
+    rng: u64,
+}
+
+impl ScriptPlan {
+    fn push(&mut self, action: ScriptAction) -> Result<(), Box<EvalAltResult>> {
+        if self.actions.len() >= MAX_PLANNED_ACTIONS {
+            return Err(format!("scripts can plan at most {} steps", MAX_PLANNED_ACTIONS).into());
+        }
+        self.actions.push(action);
+        Ok(())
+    }
+
+    fn operation(&mut self, operation: AbacusOperation) -> Result<(), Box<EvalAltResult>> {
+        self.value = match operation {
+            AbacusOperation::Set(value) => value,
+            AbacusOperation::Add(amount) => self.value.saturating_add(amount),
+            AbacusOperation::Subtract(amount) => self.value.saturating_sub(amount),
+            AbacusOperation::MoveBeads { .. } => self.value,
+        }
+        .min(self.max_value);
+        self.push(ScriptAction::Operation(operation))
+    }
+
+    /// xorshift64*, as the target game uses.
+    fn next_random(&mut self) -> u64 {
+        self.rng ^= self.rng >> 12;
+        self.rng ^= self.rng << 25;
+        self.rng ^= self.rng >> 27;
+        self.rng.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+}
+
+fn non_negative(n: i64) -> Result<u64, Box<EvalAltResult>> {
+    u64::try_from(n).map_err(|_| format!("expected a non-negative number, got {}", n).into())
+}
+
+/// The pause `wait(seconds)` asks for, within the limits. NaN and the infinities are
+/// errors, as no timer can run for them.
+fn wait_seconds(seconds: f64) -> Result<f32, Box<EvalAltResult>> {
+    if !seconds.is_finite() {
+        return Err(format!("wait needs a number of seconds, got {}", seconds).into());
+    }
+    Ok(seconds.clamp(0.0, MAX_WAIT_SECONDS) as f32)
+}
+
+/// Runs `source` against the sandboxed API and returns the steps it planned.
+fn plan_script(source: &str, start_value: u64, max_value: u64, seed: u64) -> Result<Vec<ScriptAction>, String> {
+    let plan = Rc::new(RefCell::new(ScriptPlan { actions: Vec::new(), value: start_value, max_value, rng: seed | 1 }));
+
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_SCRIPT_OPERATIONS);
+    engine.set_max_call_levels(32);
+    engine.set_max_expr_depths(64, 32);
+    engine.set_max_string_size(10_000);
+    engine.set_max_array_size(10_000);
+    engine.set_max_map_size(1_000);
+    engine.disable_symbol("eval");
+
+    let p = plan.clone();
+    engine.register_fn("set", move |n: i64| p.borrow_mut().operation(AbacusOperation::Set(non_negative(n)?)));
+    let p = plan.clone();
+    engine.register_fn("add", move |n: i64| p.borrow_mut().operation(AbacusOperation::Add(non_negative(n)?)));
+    let p = plan.clone();
+    engine.register_fn("sub", move |n: i64| p.borrow_mut().operation(AbacusOperation::Subtract(non_negative(n)?)));
+    let p = plan.clone();
+    engine.register_fn("wait", move |seconds: f64| p.borrow_mut().push(ScriptAction::Wait(wait_seconds(seconds)?)));
+    let p = plan.clone();
+    engine.register_fn("wait", move |seconds: i64| p.borrow_mut().push(ScriptAction::Wait(wait_seconds(seconds as f64)?)));
+    let p = plan.clone();
+    engine.register_fn("value", move || p.borrow().value as i64);
+    let p = plan.clone();
+    engine.register_fn("max_value", move || p.borrow().max_value.min(i64::MAX as u64) as i64);
+    let p = plan.clone();
+    engine.register_fn("random", move |low: i64, high: i64| -> Result<i64, Box<EvalAltResult>> {
+        if high < low {
+            return Err(format!("random({}, {}) has an empty range", low, high).into());
+        }
+        let span = high.abs_diff(low).saturating_add(1);
+        Ok(low.wrapping_add((p.borrow_mut().next_random() % span) as i64))
+    });
+    let p = plan.clone();
+    engine.on_print(move |text| {
+        // Past the step limit the script fails on its next operation anyway
+        let _ = p.borrow_mut().push(ScriptAction::Print(text.to_string()));
+    });
+
+    engine.run(source).map_err(|error| error.to_string())?;
+    drop(engine);
+    let plan = Rc::try_unwrap(plan).map_err(|_| "script plan still in use".to_string())?;
+    Ok(plan.into_inner().actions)
+}
+
+/// Keeps the script folder loaded so `run` can find scripts by name.
+#[derive(Resource)]
+struct ScriptLibrary(Handle<LoadedFolder>);
+
+struct RunningScript {
+    name: String,
+    abacus: Entity,
+    actions: VecDeque<ScriptAction>,
+    timer: Timer,
+}
+
+#[derive(Resource, Default)]
+struct ScriptRunner(Option<RunningScript>);
+
+pub struct ScriptingPlugin;
+
+impl Plugin for ScriptingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<ScriptFile>()
+            .init_asset_loader::<ScriptFileLoader>()
+            .init_resource::<ScriptRunner>()
+            .add_systems(Startup, load_script_library)
+            .add_systems(Update, (handle_script_requests, play_scripts).chain());
+    }
+}
+
+fn load_script_library(asset_server: Res<AssetServer>, mut commands: Commands) {
+    commands.insert_resource(ScriptLibrary(asset_server.load_folder(SCRIPT_FOLDER)));
+}
+
+/// Loaded scripts by name (file name without `.rhai`), sorted.
+fn loaded_scripts<'a>(
+    library: &ScriptLibrary,
+    folders: &Assets<LoadedFolder>,
+    files: &'a Assets<ScriptFile>,
+) -> Vec<(String, &'a ScriptFile)> {
+    let Some(folder) = folders.get(&library.0) else {
+        return Vec::new();
+    };
+    let mut scripts: Vec<(String, &ScriptFile)> = folder.handles.iter()
+        .filter_map(|handle| {
+            let name = handle.path()?.path().file_stem()?.to_string_lossy().into_owned();
+            let file = files.get(&handle.clone().try_typed::<ScriptFile>().ok()?)?;
+            Some((name, file))
+        })
+        .collect();
+    scripts.sort_by(|a, b| a.0.cmp(&b.0));
+    scripts
+}
+
+fn handle_script_requests(
+    time: Res<Time>,
+    mut requests: EventReader<ScriptRequest>,
+    mut runner: ResMut<ScriptRunner>,
+    mut console: ResMut<Console>,
+    library: Res<ScriptLibrary>,
+    folders: Res<Assets<LoadedFolder>>,
+    files: Res<Assets<ScriptFile>>,
+    abacus_query: Query<&Abacus>,
+    localization: Res<Localization>,
+) {
+    for request in requests.read() {
+        let scripts = loaded_scripts(&library, &folders, &files);
+        match request {
+            ScriptRequest::List if scripts.is_empty() => console.print(tr!(localization, "console-no-scripts")),
+            ScriptRequest::List => {
+                let names: Vec<&str> = scripts.iter().map(|(name, _)| name.as_str()).collect();
+                console.print(tr!(localization, "console-scripts", names = names.join(", ")));
+            }
+            ScriptRequest::Stop => {
+                if let Some(script) = runner.0.take() {
+                    console.print(tr!(localization, "console-script-stopped", name = script.name));
+                }
+            }
+            ScriptRequest::Run { name, abacus } => {
+                let Some((_, file)) = scripts.iter().find(|(script, _)| script.eq_ignore_ascii_case(name)) else {
+                    console.print(tr!(localization, "console-script-missing", name = name.clone()));
+                    continue;
+                };
+                let Ok(target) = abacus_query.get(*abacus) else {
+                    continue;
+                };
+                match plan_script(&file.source, target.total_value, target.max_total_value(), time.elapsed().as_nanos() as u64) {
+                    Ok(actions) => {
+                        console.print(tr!(localization, "console-script-started", name = name.clone(), count = actions.len()));
+                        runner.0 = Some(RunningScript {
+                            name: name.clone(),
+                            abacus: *abacus,
+                            actions: actions.into(),
+                            timer: Timer::from_seconds(0.0, TimerMode::Once),
+                        });
+                    }
+                    Err(error) => console.print(tr!(localization, "console-script-failed", name = name.clone(), error = error)),
+                }
+            }
+        }
+    }
+}
+
+/// Plays the running script's steps, letting each operation's beads settle before the next.
+fn play_scripts(
+    time: Res<Time>,
+    mut runner: ResMut<ScriptRunner>,
+    queue: Res<ColumnOpQueue>,
+    abacus_query: Query<&Abacus>,
+    mut requests: EventWriter<OperationRequested>,
+    mut console: ResMut<Console>,
+    localization: Res<Localization>,
+) {
+    let Some(script) = &mut runner.0 else {
+        return;
+    };
+    if !abacus_query.contains(script.abacus) {
+        runner.0 = None;
+        return;
+    }
+    if queue.pending_count() > 0 {
+        return;
+    }
+    script.timer.tick(time.delta());
+    if !script.timer.finished() {
+        return;
+    }
+
+    // Prints go out together with the step that follows them
+    loop {
+        match script.actions.pop_front() {
+            Some(ScriptAction::Print(text)) => console.print(text),
+            Some(ScriptAction::Operation(operation)) => {
+                requests.write(OperationRequested { abacus: script.abacus, operation });
+                script.timer = Timer::from_seconds(SCRIPT_STEP_SECONDS, TimerMode::Once);
+                return;
+            }
+            Some(ScriptAction::Wait(seconds)) => {
+                script.timer = Timer::from_seconds(seconds, TimerMode::Once);
+                return;
+            }
+            None => {
+                let name = script.name.clone();
+                runner.0 = None;
+                console.print(tr!(localization, "console-script-finished", name = name));
+                return;
+            }
+        }
+    }
+}
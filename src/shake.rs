@@ -0,0 +1,139 @@
+use bevy::prelude::*;
+use bevy::input::mouse::MouseMotion;
+use bevy::picking::hover::HoverMap;
+use bevy::picking::pointer::PointerId;
+use bevy_egui::EguiContexts;
+use std::collections::VecDeque;
+use std::f32::consts::PI;
+
+use crate::abacus::Abacus;
+use crate::keybindings::{ActionInput, InputAction};
+use crate::operations::ClearRequested;
+use crate::AbacusSettings;
+
+/// Direction changes needed within `SHAKE_WINDOW` seconds to count as a shake.
+const SHAKE_REVERSALS: usize = 4;
+const SHAKE_WINDOW: f32 = 1.0;
+/// Horizontal travel (in pixels) a drag must cover before a reversal counts.
+const SHAKE_MIN_TRAVEL: f32 = 40.0;
+
+const TUMBLE_DURATION: f32 = 0.8;
+const TUMBLE_ANGLE: f32 = PI / 10.0;
+const TUMBLE_WOBBLES: f32 = 3.0;
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::prelude::*;
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+extern "C" {
+    /// Set by the page's devicemotion listener; returns true once per detected shake.
+    fn take_device_shake() -> bool;
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn take_device_shake() -> bool {
+    false // No accelerometer on native builds
+}
+
+/// Tracks the back-and-forth of pointer drags to recognise a shake. Only a left drag that
+/// started on empty space counts, so turning an abacus or dragging a bead or a slider back
+/// and forth doesn't clear everything.
+#[derive(Resource, Default)]
+pub struct ShakeDetector {
+    /// Whether the left button went down on nothing: not an abacus, nor a panel.
+    on_empty_space: bool,
+    direction: f32,
+    travel: f32,
+    reversals: VecDeque<f32>,
+}
+
+/// Wobbles the abacus like a suanpan being tilted and shaken clear.
+#[derive(Component)]
+pub struct Tumble {
+    timer: Timer,
+    base_rotation: Quat,
+}
+
+pub fn detect_shake(
+    time: Res<Time>,
+    mouse_button: Res<ButtonInput<MouseButton>>,
+    input: ActionInput,
+    hover_map: Res<HoverMap>,
+    mut contexts: EguiContexts,
+    mut mouse_motion_events: EventReader<MouseMotion>,
+    mut detector: ResMut<ShakeDetector>,
+    mut commands: Commands,
+    abacus_query: Query<(Entity, &Transform), (With<Abacus>, Without<Tumble>)>,
+    mut clear_requests: EventWriter<ClearRequested>,
+    settings: Res<AbacusSettings>,
+) {
+    let now = time.elapsed_secs();
+    let mut shaken = take_device_shake();
+
+    if mouse_button.just_pressed(MouseButton::Left) {
+        let over_panel = contexts.try_ctx_mut().is_some_and(|ctx| ctx.wants_pointer_input() || ctx.is_pointer_over_area());
+        let over_scene = hover_map.get(&PointerId::Mouse).is_some_and(|hits| !hits.is_empty());
+        detector.on_empty_space = !over_panel && !over_scene;
+    }
+
+    if detector.on_empty_space && mouse_button.pressed(MouseButton::Left) && !input.pressed(InputAction::RotateDrag) {
+        for event in mouse_motion_events.read() {
+            let direction = event.delta.x.signum();
+            if event.delta.x == 0.0 {
+                continue;
+            }
+            if direction == detector.direction {
+                detector.travel += event.delta.x.abs();
+            } else {
+                if detector.travel >= SHAKE_MIN_TRAVEL {
+                    detector.reversals.push_back(now);
+                }
+                detector.direction = direction;
+                detector.travel = event.delta.x.abs();
+            }
+        }
+    } else {
+        mouse_motion_events.clear();
+        detector.travel = 0.0;
+    }
+
+    while detector.reversals.front().is_some_and(|&t| now - t > SHAKE_WINDOW) {
+        detector.reversals.pop_front();
+    }
+    if detector.reversals.len() >= SHAKE_REVERSALS {
+        detector.reversals.clear();
+        shaken = true;
+    }
+
+    if shaken {
+        // Reduced motion still clears, just without the wobble
+        for (entity, transform) in abacus_query.iter().filter(|_| !settings.reduced_motion) {
+            commands.entity(entity).insert(Tumble {
+                timer: Timer::from_seconds(TUMBLE_DURATION, TimerMode::Once),
+                base_rotation: transform.rotation,
+            });
+        }
+        clear_requests.write(ClearRequested { abacus: None });
+    }
+}
+
+pub fn animate_tumble(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Transform, &mut Tumble)>,
+) {
+    for (entity, mut transform, mut tumble) in &mut query {
+        tumble.timer.tick(time.delta());
+        if tumble.timer.finished() {
+            transform.rotation = tumble.base_rotation;
+            commands.entity(entity).remove::<Tumble>();
+            continue;
+        }
+
+        // Decaying side-to-side tilt around the viewing axis
+        let t = tumble.timer.fraction();
+        let angle = (t * TUMBLE_WOBBLES * 2.0 * PI).sin() * TUMBLE_ANGLE * (1.0 - t);
+        transform.rotation = tumble.base_rotation * Quat::from_rotation_z(angle);
+    }
+}
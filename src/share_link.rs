@@ -0,0 +1,156 @@
+use bevy::prelude::*;
+
+use crate::abacus::*;
+use crate::localization::*;
+use crate::startup::StartupValue;
+use crate::{AbacusSettings, AbacusStructure};
+
+/// Where share links point when made from the desktop build.
+#[cfg(not(target_arch = "wasm32"))]
+const WEB_BUILD_URL: &str = "https://swultcode.github.io/Abacus-Simulator/";
+
+/// Query string describing the abacus structure and its value, e.g.
+/// `columns=9&top=2&bottom=5&top-value=5&multiplier=1&base=10&value=1234`.
+/// Column overrides are listed as `column:top:bottom:multiplier`, comma separated, with
+/// `:top-value:base` after them when a column has its own (0 for the abacus's).
+/// Extra decks are listed as `decks=beads:value`, comma separated, the one just above
+/// the usual top deck first. They, `model=counting`, `horizontal=1`, `two-tone=1`,
+/// `grooved=1` and `ones-left=1` are only there when set.
+pub fn share_query(settings: &AbacusSettings, value: u64) -> String {
+    let mut query = format!(
+        "columns={}&top={}&bottom={}&top-value={}&multiplier={}&base={}&value={}",
+        settings.column_count, settings.top_bead_count, settings.bottom_bead_count,
+        settings.top_bead_base_value, settings.bead_value_multiplier, settings.abacus_base, value,
+    );
+    if !settings.column_overrides.is_empty() {
+        let overrides: Vec<String> = settings.column_overrides.iter()
+            .map(|o| {
+                let own = if o.top_bead_base_value.is_some() || o.radix.is_some() {
+                    format!(":{}:{}", o.top_bead_base_value.unwrap_or(0), o.radix.unwrap_or(0))
+                } else {
+                    String::new()
+                };
+                format!("{}:{}:{}:{}{}", o.column, o.top_bead_count, o.bottom_bead_count, o.bead_value_multiplier, own)
+            })
+            .collect();
+        query.push_str("&overrides=");
+        // Commas and colons are left alone by browsers, so the list needs no escaping
+        query.push_str(&overrides.join(","));
+    }
+    if !settings.extra_decks.is_empty() {
+        let decks: Vec<String> = settings.extra_decks.iter()
+            .map(|deck| format!("{}:{}", deck.bead_count, deck.bead_value))
+            .collect();
+        query.push_str("&decks=");
+        query.push_str(&decks.join(","));
+    }
+    if settings.value_model == ValueModel::Counting {
+        query.push_str("&model=counting");
+    }
+    if settings.horizontal_rods {
+        query.push_str("&horizontal=1");
+    }
+    if settings.two_tone_beads {
+        query.push_str("&two-tone=1");
+    }
+    if settings.grooved {
+        query.push_str("&grooved=1");
+    }
+    if settings.ones_on_left {
+        query.push_str("&ones-left=1");
+    }
+    query
+}
+
+fn parse_override(text: &str) -> Option<ColumnOverride> {
+    let mut parts = text.split(':').map(|part| part.parse::<u64>().ok());
+    Some(ColumnOverride {
+        column: parts.next()?? as usize,
+        top_bead_count: parts.next()?? as usize,
+        bottom_bead_count: parts.next()?? as usize,
+        bead_value_multiplier: parts.next()??,
+        top_bead_base_value: parts.next().flatten().filter(|&n| n > 0),
+        radix: parts.next().flatten().filter(|&n| n > 0),
+    })
+}
+
+fn parse_deck(text: &str) -> Option<DeckLayout> {
+    let (bead_count, bead_value) = text.split_once(':')?;
+    Some(DeckLayout { bead_count: bead_count.parse().ok()?, bead_value: bead_value.parse().ok()? })
+}
+
+/// Applies the structure in a share link's query string to the settings, within the
+/// ranges the settings panel allows. Returns the value the link asks for, if any.
+pub fn apply_share_query(query: &str, settings: &mut AbacusSettings, localization: &Localization) -> Option<u64> {
+    let mut structure = AbacusStructure::from_settings(settings);
+    // Left out of a link, these are the plain abacus's
+    structure.value_model = ValueModel::PlaceValue;
+    structure.horizontal_rods = false;
+    structure.two_tone_beads = false;
+    structure.grooved = false;
+    structure.extra_decks.clear();
+    structure.ones_on_left = false;
+    let mut value = None;
+    for (key, text) in query.trim_start_matches('?').split('&').filter_map(|pair| pair.split_once('=')) {
+        let number = text.parse::<u64>().ok();
+        match (key, number) {
+            ("columns", Some(n)) => structure.column_count = n as usize,
+            ("top", Some(n)) => structure.top_bead_count = n as usize,
+            ("bottom", Some(n)) => structure.bottom_bead_count = n as usize,
+            ("top-value", Some(n)) => structure.top_bead_base_value = n,
+            ("multiplier", Some(n)) => structure.bead_value_multiplier = n,
+            ("base", Some(n)) => structure.abacus_base = n,
+            ("value", Some(n)) => value = Some(n),
+            ("overrides", _) => structure.column_overrides = text.split(',').filter_map(parse_override).collect(),
+            ("decks", _) => structure.extra_decks = text.split(',').filter_map(parse_deck).collect(),
+            ("model", _) if text == "counting" => structure.value_model = ValueModel::Counting,
+            ("model", _) if text == "place" => structure.value_model = ValueModel::PlaceValue,
+            ("horizontal", Some(n)) => structure.horizontal_rods = n != 0,
+            ("two-tone", Some(n)) => structure.two_tone_beads = n != 0,
+            ("grooved", Some(n)) => structure.grooved = n != 0,
+            ("ones-left", Some(n)) => structure.ones_on_left = n != 0,
+            _ => warn!("{}", tr!(localization, "log-share-parameter-ignored", key = key, value = text)),
+        }
+    }
+    structure.sanitize();
+    structure.apply_to(settings);
+    value
+}
+
+/// A link that opens the web build with this abacus.
+pub fn share_link(settings: &AbacusSettings, value: u64) -> String {
+    #[cfg(target_arch = "wasm32")]
+    let base = web_sys::window()
+        .and_then(|window| {
+            let location = window.location();
+            Some(format!("{}{}", location.origin().ok()?, location.pathname().ok()?))
+        })
+        .unwrap_or_default();
+    #[cfg(not(target_arch = "wasm32"))]
+    let base = WEB_BUILD_URL.to_string();
+    format!("{}?{}", base, share_query(settings, value))
+}
+
+/// The query string the web build was opened with.
+#[cfg(target_arch = "wasm32")]
+fn page_query() -> Option<String> {
+    web_sys::window()?.location().search().ok().filter(|query| !query.is_empty())
+}
+
+/// Only the web build is opened from a URL.
+#[cfg(not(target_arch = "wasm32"))]
+fn page_query() -> Option<String> {
+    None
+}
+
+/// Reads the page's query string before the first abacus is spawned, so a shared link
+/// opens straight onto its abacus.
+pub fn read_share_link(
+    mut settings: ResMut<AbacusSettings>,
+    mut startup_value: ResMut<StartupValue>,
+    localization: Res<Localization>,
+) {
+    if let Some(query) = page_query() {
+        startup_value.0 = apply_share_query(&query, &mut settings, &localization);
+    }
+}
@@ -0,0 +1,118 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use serde::{Deserialize, Serialize};
+
+use crate::abacus::*;
+use crate::localization::*;
+use crate::operations::{AbacusOperation, OperationRequested};
+use crate::scene_layout::SelectedAbacus;
+
+/// The beads of an abacus at one moment, kept under a name so they can be brought back.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub name: String,
+    pub total: u64,
+    /// Long values of each column's decks, top deck first, ones column first. Snapshots
+    /// from before there could be more than two decks read the same way.
+    pub columns: Vec<Vec<u64>>,
+}
+
+/// Bead layouts saved by the student. Each profile keeps its own.
+#[derive(Resource, Default)]
+pub struct Snapshots {
+    pub saved: Vec<Snapshot>,
+    /// Set when `saved` changes, so the profile's copy is saved.
+    pub changed: bool,
+    name: String,
+}
+
+/// Long values of each column's decks of `abacus`, top deck first, ones column first.
+pub fn deck_values(abacus: &Abacus, long_query: &Query<&AbacusLong>) -> Vec<Vec<u64>> {
+    abacus.longs.iter()
+        .map(|longs| longs.iter().map(|&long| long_query.get(long).map_or(0, |long| long.value)).collect())
+        .collect()
+}
+
+/// Asks for the bead moves that take an abacus from the `current` decks to the `target` ones.
+/// Only the decks that differ move, and the beads slide over like any other move.
+pub fn move_to_decks(
+    abacus: Entity,
+    current: &[Vec<u64>],
+    target: &[Vec<u64>],
+    requests: &mut EventWriter<OperationRequested>,
+) {
+    for (column, (target_decks, current_decks)) in target.iter().zip(current).enumerate() {
+        for (deck, (&long_value, &current_value)) in target_decks.iter().zip(current_decks).enumerate() {
+            if long_value != current_value {
+                requests.write(OperationRequested { abacus, operation: AbacusOperation::MoveBeads { column, deck, long_value } });
+            }
+        }
+    }
+}
+
+pub fn snapshots_ui_system(
+    mut contexts: EguiContexts,
+    mut snapshots: ResMut<Snapshots>,
+    abacus_query: Query<&Abacus>,
+    long_query: Query<&AbacusLong>,
+    selected: Res<SelectedAbacus>,
+    mut requests: EventWriter<OperationRequested>,
+    localization: Res<Localization>,
+) {
+    let localization = &*localization;
+    let snapshots = &mut *snapshots;
+    let selected_abacus = selected.0.and_then(|entity| abacus_query.get(entity).ok().map(|abacus| (entity, abacus)));
+
+    egui::Window::new(tr!(localization, "snapshots-window"))
+        .id(egui::Id::new("snapshots"))
+        .default_pos([10.0, 700.0])
+        .default_open(false)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.horizontal(|ui| {
+                let response = ui.add_sized([140.0, ui.available_height()],
+                    egui::TextEdit::singleline(&mut snapshots.name).hint_text(tr!(localization, "snapshots-name")));
+                let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                let save_clicked = ui.add_enabled(selected_abacus.is_some(), egui::Button::new(tr!(localization, "snapshots-save"))).clicked();
+                if let (true, Some((_, abacus))) = (save_clicked || submitted, selected_abacus) {
+                    let name = match snapshots.name.trim() {
+                        "" => tr!(localization, "snapshots-default-name", number = snapshots.saved.len() + 1),
+                        name => name.to_string(),
+                    };
+                    snapshots.name.clear();
+                    snapshots.saved.push(Snapshot {
+                        name,
+                        total: abacus.total_value,
+                        columns: deck_values(abacus, &long_query),
+                    });
+                    snapshots.changed = true;
+                }
+            });
+
+            ui.separator();
+            if snapshots.saved.is_empty() {
+                ui.label(tr!(localization, "snapshots-empty"));
+            }
+            let mut to_remove = None;
+            egui::ScrollArea::vertical().max_height(240.0).show(ui, |ui| {
+                for (i, snapshot) in snapshots.saved.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        // A layout only fits an abacus with as many columns
+                        let fits = selected_abacus.is_some_and(|(_, abacus)| abacus.longs.len() == snapshot.columns.len());
+                        let restore = ui.add_enabled(fits, egui::Button::new(tr!(localization, "snapshots-restore")))
+                            .on_disabled_hover_text(tr!(localization, "snapshots-wrong-columns", count = snapshot.columns.len()));
+                        if let (true, Some((entity, abacus))) = (restore.clicked(), selected_abacus) {
+                            move_to_decks(entity, &deck_values(abacus, &long_query), &snapshot.columns, &mut requests);
+                        }
+                        ui.label(tr!(localization, "snapshots-entry", name = snapshot.name.clone(), value = snapshot.total));
+                        if ui.small_button("✖").on_hover_text(tr!(localization, "remove")).clicked() {
+                            to_remove = Some(i);
+                        }
+                    });
+                }
+            });
+            if let Some(i) = to_remove {
+                snapshots.saved.remove(i);
+                snapshots.changed = true;
+            }
+        });
+}
@@ -0,0 +1,192 @@
+use bevy::prelude::*;
+use std::path::{Path, PathBuf};
+
+use crate::abacus::*;
+use crate::localization::*;
+use crate::operations::{AbacusOperation, OperationRequested};
+use crate::replay::ReplayOptions;
+use crate::scene_layout::SelectedAbacus;
+use crate::wood_grain::AbacusMaterial;
+use crate::{apply_config, sanitize_config, AbacusSettings, SavableAbacusConfig, UserConfigurations};
+
+/// How the first abacus should look, from the command line:
+/// `--config <file>` loads a configuration saved as RON or JSON, `--preset <name>` loads
+/// a saved configuration by name, and `--columns`, `--base`, `--top` and `--bottom`
+/// adjust the structure after those. `--value <n>` sets the abacus once it's spawned.
+/// `--kiosk` starts in kiosk mode, with `--kiosk-idle <seconds>` before the attract
+/// animation.
+#[derive(Resource, Default)]
+pub struct StartupOptions {
+    pub config: Option<PathBuf>,
+    pub preset: Option<String>,
+    pub columns: Option<usize>,
+    pub base: Option<u64>,
+    pub top: Option<usize>,
+    pub bottom: Option<usize>,
+    pub value: Option<u64>,
+    pub kiosk: bool,
+    pub kiosk_idle: Option<u64>,
+    /// Problems with the arguments, logged once logging has started.
+    pub warnings: Vec<ArgWarning>,
+}
+
+/// A problem with the command line. The arguments are read before the localization
+/// exists, so the message is only written when it's logged.
+pub enum ArgWarning {
+    NotANumber { flag: String, text: String },
+    Unknown(String),
+    HeadlessWithoutReplay,
+}
+
+impl ArgWarning {
+    fn message(&self, localization: &Localization) -> String {
+        match self {
+            ArgWarning::NotANumber { flag, text } => tr!(localization, "log-arg-not-a-number", flag = flag.as_str(), text = text.as_str()),
+            ArgWarning::Unknown(arg) => tr!(localization, "log-arg-unknown", arg = arg.as_str()),
+            ArgWarning::HeadlessWithoutReplay => tr!(localization, "log-arg-headless-without-replay"),
+        }
+    }
+}
+
+/// Reads the number after `flag`, noting a warning if it isn't one.
+fn number_arg(flag: &str, args: &mut impl Iterator<Item = String>, warnings: &mut Vec<ArgWarning>) -> Option<u64> {
+    let text = args.next().unwrap_or_default();
+    text.parse::<u64>()
+        .inspect_err(|_| warnings.push(ArgWarning::NotANumber { flag: flag.to_string(), text: text.clone() }))
+        .ok()
+}
+
+/// Parses the command line into startup and replay options. Bad values are skipped
+/// and logged once logging is up, rather than stopping the app.
+pub fn parse_args() -> (StartupOptions, ReplayOptions) {
+    let mut startup = StartupOptions::default();
+    let mut replay = ReplayOptions::default();
+    let warnings = &mut startup.warnings;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            // Same ranges as the settings panel's sliders
+            "--columns" => startup.columns = number_arg(&arg, &mut args, warnings).map(|n| (n as usize).clamp(1, MAX_COLUMNS)),
+            "--base" => startup.base = number_arg(&arg, &mut args, warnings).map(|n| n.clamp(2, 36)),
+            "--top" => startup.top = number_arg(&arg, &mut args, warnings).map(|n| (n as usize).min(MAX_TOP_BEADS)),
+            "--bottom" => startup.bottom = number_arg(&arg, &mut args, warnings).map(|n| (n as usize).clamp(1, 10)),
+            "--value" => startup.value = number_arg(&arg, &mut args, warnings),
+            "--kiosk" => startup.kiosk = true,
+            "--kiosk-idle" => startup.kiosk_idle = number_arg(&arg, &mut args, warnings),
+            "--preset" => startup.preset = args.next(),
+            "--config" => startup.config = args.next().map(PathBuf::from),
+            "--record" => replay.record = args.next().map(PathBuf::from),
+            "--replay" => replay.replay = args.next().map(PathBuf::from),
+            "--headless" => replay.headless = true,
+            _ => warnings.push(ArgWarning::Unknown(arg.clone())),
+        }
+    }
+    if replay.headless && replay.replay.is_none() {
+        warnings.push(ArgWarning::HeadlessWithoutReplay);
+        replay.headless = false;
+    }
+    (startup, replay)
+}
+
+/// Reads a configuration saved as RON (`.ron`) or JSON (anything else). It comes from
+/// outside the app, so sanitize it before applying it.
+pub fn read_config_file(path: &Path) -> Result<SavableAbacusConfig, String> {
+    let text = std::fs::read_to_string(path).map_err(|error| error.to_string())?;
+    if path.extension().is_some_and(|extension| extension == "ron") {
+        ron::from_str(&text).map_err(|error| error.to_string())
+    } else {
+        serde_json::from_str(&text).map_err(|error| error.to_string())
+    }
+}
+
+/// Environment variable naming a configuration to load at every start.
+const CONFIG_ENV_VAR: &str = "ABACUS_CONFIG";
+/// Configuration loaded at every start when it sits in the working directory, so kiosks
+/// and classroom machines always boot into the right layout.
+const DEFAULT_CONFIG_FILE: &str = "abacus.ron";
+
+/// The configuration to start with: `--config`, then the file named by `ABACUS_CONFIG`,
+/// then `abacus.ron` if there is one.
+fn startup_config_path(options: &StartupOptions) -> Option<PathBuf> {
+    if let Some(path) = &options.config {
+        return Some(path.clone());
+    }
+    if let Some(path) = std::env::var_os(CONFIG_ENV_VAR) {
+        return Some(PathBuf::from(path));
+    }
+    Some(PathBuf::from(DEFAULT_CONFIG_FILE)).filter(|path| path.is_file())
+}
+
+/// The value asked for at startup, by `--value` or a share link, set on the first
+/// abacus once it has been spawned.
+#[derive(Resource, Default)]
+pub struct StartupValue(pub Option<u64>);
+
+/// Applies the startup configuration file and the command line to the settings before
+/// the first abacus is spawned.
+pub fn apply_startup_options(
+    options: Res<StartupOptions>,
+    mut settings: ResMut<AbacusSettings>,
+    mut materials: ResMut<Assets<AbacusMaterial>>,
+    user_configs: Res<UserConfigurations>,
+    mut startup_value: ResMut<StartupValue>,
+    localization: Res<Localization>,
+) {
+    for warning in &options.warnings {
+        warn!("{}", warning.message(&localization));
+    }
+    if let Some(path) = startup_config_path(&options) {
+        match read_config_file(&path) {
+            Ok(mut config) => {
+                info!("{}", tr!(localization, "log-startup-config", path = path.display().to_string()));
+                sanitize_config(&mut config, &localization);
+                apply_config(&mut settings, &mut materials, &config);
+            }
+            Err(error) => warn!("{}", tr!(localization, "log-startup-config-failed", path = path.display().to_string(), error = error)),
+        }
+    }
+    if let Some(name) = &options.preset {
+        // The built-in presets are known by their first word too, like `soroban`
+        match user_configs.find_preset(name) {
+            Ok(config) => apply_config(&mut settings, &mut materials, config),
+            Err(matching) if matching.is_empty() => warn!("{}", tr!(localization, "console-no-preset", name = name.as_str())),
+            Err(matching) => {
+                let names: Vec<&str> = matching.iter().map(|config| config.name.as_str()).collect();
+                warn!("{}", tr!(localization, "console-several-presets", names = names.join(", ")));
+            }
+        }
+    }
+    if let Some(columns) = options.columns {
+        settings.column_count = columns;
+        settings.column_overrides.retain(|column_override| column_override.column < columns);
+    }
+    if let Some(base) = options.base {
+        settings.abacus_base = base;
+    }
+    if let Some(top) = options.top {
+        settings.top_bead_count = top;
+    }
+    if let Some(bottom) = options.bottom {
+        settings.bottom_bead_count = bottom;
+    }
+    if options.value.is_some() {
+        startup_value.0 = options.value;
+    }
+}
+
+/// Sets the startup value on the selected abacus, once it exists.
+pub fn apply_startup_value(
+    mut startup_value: ResMut<StartupValue>,
+    selected: Res<SelectedAbacus>,
+    abacus_query: Query<&Abacus>,
+    mut requests: EventWriter<OperationRequested>,
+) {
+    let Some(value) = startup_value.0 else {
+        return;
+    };
+    let Some(abacus) = selected.0.filter(|&entity| abacus_query.contains(entity)) else {
+        return;
+    };
+    requests.write(OperationRequested { abacus, operation: AbacusOperation::Set(value) });
+    startup_value.0 = None;
+}
@@ -0,0 +1,184 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use egui_plot::{Bar, BarChart, Legend, Line, Plot, PlotPoints};
+use serde::{Deserialize, Serialize};
+
+use crate::audio::BeadDeparted;
+use crate::game::RoundFinished;
+use crate::localization::*;
+use crate::storage;
+
+/// Storage key of the saved session history.
+const STATS_KEY: &str = "stats";
+/// Seconds between saves while the statistics are changing.
+const SAVE_INTERVAL: f32 = 15.0;
+/// Sessions shown in the plots; older ones are still kept on disk.
+const PLOTTED_SESSIONS: usize = 30;
+
+/// What a learner did during one run of the app.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SessionStats {
+    pub bead_moves: u64,
+    pub solved: u32,
+    /// Solved, timed-out and skipped game rounds.
+    pub attempts: u32,
+    /// Seconds spent on the rounds that were solved.
+    pub solve_seconds: f32,
+}
+
+impl SessionStats {
+    fn accuracy(&self) -> Option<f32> {
+        (self.attempts > 0).then(|| self.solved as f32 / self.attempts as f32)
+    }
+
+    fn average_solve_seconds(&self) -> Option<f32> {
+        (self.solved > 0).then(|| self.solve_seconds / self.solved as f32)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.bead_moves == 0 && self.attempts == 0
+    }
+}
+
+/// This session's statistics plus every earlier session, so learners can see progress.
+/// Each profile keeps its own history.
+#[derive(Resource)]
+pub struct SessionHistory {
+    /// Storage key of the profile's history; `None` until a profile is loaded.
+    key: Option<String>,
+    past: Vec<SessionStats>,
+    pub current: SessionStats,
+    unsaved: bool,
+    save_timer: Timer,
+}
+
+impl Default for SessionHistory {
+    fn default() -> Self {
+        Self {
+            key: None,
+            past: Vec::new(),
+            current: SessionStats::default(),
+            unsaved: false,
+            save_timer: Timer::from_seconds(SAVE_INTERVAL, TimerMode::Repeating),
+        }
+    }
+}
+
+impl SessionHistory {
+    /// Earlier sessions followed by this one, once anything has happened in it.
+    fn sessions(&self) -> impl Iterator<Item = &SessionStats> {
+        self.past.iter().chain((!self.current.is_empty()).then_some(&self.current))
+    }
+
+    fn save(&mut self, localization: &Localization) {
+        if let Some(key) = &self.key {
+            storage::save_json(key, &self.sessions().collect::<Vec<_>>(), localization);
+        }
+        self.unsaved = false;
+    }
+
+    /// Saves the outgoing profile's history and starts a fresh session for `profile`.
+    pub fn switch_profile(&mut self, profile: Option<&str>, localization: &Localization) {
+        if self.unsaved {
+            self.save(localization);
+        }
+        let key = storage::profile_key(profile, STATS_KEY);
+        self.past = storage::load_json(&key, localization).unwrap_or_default();
+        self.key = Some(key);
+        self.current = SessionStats::default();
+    }
+}
+
+pub fn record_session_stats(
+    mut departures: EventReader<BeadDeparted>,
+    mut rounds: EventReader<RoundFinished>,
+    mut history: ResMut<SessionHistory>,
+) {
+    let moves = departures.read().count() as u64;
+    if moves > 0 {
+        history.current.bead_moves += moves;
+        history.unsaved = true;
+    }
+    for round in rounds.read() {
+        history.current.attempts += 1;
+        if round.solved {
+            history.current.solved += 1;
+            history.current.solve_seconds += round.seconds;
+        }
+        history.unsaved = true;
+    }
+}
+
+/// Saves every so often while statistics change, and once more on the way out.
+pub fn save_session_stats(
+    time: Res<Time>,
+    mut exits: EventReader<AppExit>,
+    mut history: ResMut<SessionHistory>,
+    localization: Res<Localization>,
+) {
+    let exiting = exits.read().count() > 0;
+    history.save_timer.tick(time.delta());
+    if history.unsaved && (exiting || history.save_timer.just_finished()) {
+        history.save(&localization);
+    }
+}
+
+pub fn stats_ui_system(
+    mut contexts: EguiContexts,
+    history: Res<SessionHistory>,
+    localization: Res<Localization>,
+) {
+    let localization = &*localization;
+    let current = &history.current;
+
+    egui::Window::new(tr!(localization, "stats-window"))
+        .id(egui::Id::new("session_stats"))
+        .default_pos([10.0, 520.0])
+        .default_open(false)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.heading(tr!(localization, "stats-this-session"));
+            ui.label(tr!(localization, "stats-bead-moves", count = current.bead_moves));
+            ui.label(tr!(localization, "stats-solved", solved = current.solved, attempts = current.attempts));
+            if let Some(accuracy) = current.accuracy() {
+                ui.label(tr!(localization, "stats-accuracy", percent = (accuracy * 100.0).round() as u64));
+            }
+            if let Some(seconds) = current.average_solve_seconds() {
+                ui.label(tr!(localization, "stats-average-time", seconds = format!("{:.1}", seconds)));
+            }
+
+            let sessions: Vec<&SessionStats> = history.sessions().collect();
+            if sessions.len() < 2 {
+                ui.label(tr!(localization, "stats-no-history"));
+                return;
+            }
+            let first_plotted = sessions.len().saturating_sub(PLOTTED_SESSIONS);
+
+            ui.separator();
+            ui.heading(tr!(localization, "stats-progress"));
+            let solved_bars: Vec<Bar> = sessions.iter().enumerate().skip(first_plotted)
+                .map(|(i, session)| Bar::new((i + 1) as f64, session.solved as f64))
+                .collect();
+            let accuracy_points: PlotPoints = sessions.iter().enumerate().skip(first_plotted)
+                .filter_map(|(i, session)| session.accuracy().map(|accuracy| [(i + 1) as f64, accuracy as f64 * 100.0]))
+                .collect();
+            Plot::new("stats_solved_accuracy")
+                .height(140.0)
+                .legend(Legend::default())
+                .allow_scroll(false)
+                .show(ui, |plot_ui| {
+                    plot_ui.bar_chart(BarChart::new(solved_bars).name(tr!(localization, "stats-plot-solved")));
+                    plot_ui.line(Line::new(accuracy_points).name(tr!(localization, "stats-plot-accuracy")));
+                });
+
+            let time_points: PlotPoints = sessions.iter().enumerate().skip(first_plotted)
+                .filter_map(|(i, session)| session.average_solve_seconds().map(|seconds| [(i + 1) as f64, seconds as f64]))
+                .collect();
+            Plot::new("stats_solve_time")
+                .height(100.0)
+                .legend(Legend::default())
+                .allow_scroll(false)
+                .show(ui, |plot_ui| {
+                    plot_ui.line(Line::new(time_points).name(tr!(localization, "stats-plot-time")));
+                });
+        });
+}
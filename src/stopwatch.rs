@@ -0,0 +1,205 @@
+//! A stopwatch and countdown that show their running seconds on the beads, so students
+//! watch the columns carry as time passes. The countdown can borrow the Target Game's
+//! time limit, to practise at the same pace without the targets.
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::abacus::*;
+use crate::game::GameState;
+use crate::localization::*;
+use crate::operations::{AbacusOperation, OperationRequested};
+use crate::scene_layout::SelectedAbacus;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum ClockMode {
+    #[default]
+    Stopwatch,
+    Countdown,
+}
+
+impl ClockMode {
+    const ALL: [ClockMode; 2] = [ClockMode::Stopwatch, ClockMode::Countdown];
+
+    /// Localization id of the mode's display name.
+    fn message_id(&self) -> &'static str {
+        match self {
+            ClockMode::Stopwatch => "stopwatch-mode-stopwatch",
+            ClockMode::Countdown => "stopwatch-mode-countdown",
+        }
+    }
+}
+
+#[derive(Resource)]
+struct Stopwatch {
+    mode: ClockMode,
+    /// The abacus showing the time; `None` until the clock is first started.
+    abacus: Option<Entity>,
+    running: bool,
+    /// Seconds counted up, or left to count down.
+    seconds: f32,
+    countdown_seconds: u64,
+    /// Stopwatch seconds at each Lap press.
+    laps: Vec<u64>,
+    /// The whole seconds last put on the beads.
+    shown: Option<u64>,
+}
+
+impl Default for Stopwatch {
+    fn default() -> Self {
+        Self {
+            mode: ClockMode::default(),
+            abacus: None,
+            running: false,
+            seconds: 0.0,
+            countdown_seconds: 60,
+            laps: Vec::new(),
+            shown: None,
+        }
+    }
+}
+
+impl Stopwatch {
+    /// The whole seconds to show: finished ones counting up, started ones counting down,
+    /// so a countdown reaches zero as its time runs out.
+    fn whole_seconds(&self) -> u64 {
+        match self.mode {
+            ClockMode::Stopwatch => self.seconds.floor() as u64,
+            ClockMode::Countdown => self.seconds.ceil() as u64,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.running = false;
+        self.laps.clear();
+        self.seconds = match self.mode {
+            ClockMode::Stopwatch => 0.0,
+            ClockMode::Countdown => self.countdown_seconds as f32,
+        };
+        self.shown = None;
+    }
+}
+
+pub struct StopwatchPlugin;
+
+impl Plugin for StopwatchPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Stopwatch>()
+            .add_systems(Update, (stopwatch_ui_system, run_stopwatch).chain());
+    }
+}
+
+fn stopwatch_ui_system(
+    mut contexts: EguiContexts,
+    mut stopwatch: ResMut<Stopwatch>,
+    selected: Res<SelectedAbacus>,
+    game: Res<GameState>,
+    localization: Res<Localization>,
+) {
+    let localization = &*localization;
+    egui::Window::new(tr!(localization, "stopwatch-window"))
+        .id(egui::Id::new("stopwatch"))
+        .default_pos([10.0, 1060.0])
+        .default_open(false)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.add_enabled_ui(!stopwatch.running, |ui| {
+                let mut mode = stopwatch.mode;
+                egui::ComboBox::from_label(tr!(localization, "stopwatch-mode"))
+                    .selected_text(tr!(localization, mode.message_id()))
+                    .show_ui(ui, |ui| {
+                        for option in ClockMode::ALL {
+                            ui.selectable_value(&mut mode, option, tr!(localization, option.message_id()));
+                        }
+                    });
+                if mode != stopwatch.mode {
+                    stopwatch.mode = mode;
+                    stopwatch.reset();
+                }
+                if stopwatch.mode == ClockMode::Countdown {
+                    ui.horizontal(|ui| {
+                        let response = ui.add(egui::DragValue::new(&mut stopwatch.countdown_seconds).range(1..=86_400).suffix(" s"));
+                        let limit = game.difficulty.seconds() as u64;
+                        if ui.button(tr!(localization, "stopwatch-game-limit", seconds = limit)).clicked() {
+                            stopwatch.countdown_seconds = limit;
+                            stopwatch.reset();
+                        } else if response.changed() {
+                            stopwatch.reset();
+                        }
+                    });
+                }
+            });
+
+            ui.heading(tr!(localization, "stopwatch-time", seconds = format!("{:.1}", stopwatch.seconds)));
+            ui.horizontal(|ui| {
+                if stopwatch.running {
+                    if ui.button(tr!(localization, "stopwatch-stop")).clicked() {
+                        stopwatch.running = false;
+                    }
+                } else if ui.add_enabled(selected.0.is_some(), egui::Button::new(tr!(localization, "stopwatch-start"))).clicked() {
+                    if stopwatch.mode == ClockMode::Countdown && stopwatch.seconds <= 0.0 {
+                        stopwatch.reset();
+                    }
+                    // The clock keeps to the abacus it started on
+                    if stopwatch.abacus != selected.0 {
+                        stopwatch.abacus = selected.0;
+                        stopwatch.shown = None;
+                    }
+                    stopwatch.running = true;
+                }
+                let can_lap = stopwatch.running && stopwatch.mode == ClockMode::Stopwatch;
+                if ui.add_enabled(can_lap, egui::Button::new(tr!(localization, "stopwatch-lap"))).clicked() {
+                    let lap = stopwatch.whole_seconds();
+                    stopwatch.laps.push(lap);
+                }
+                if ui.button(tr!(localization, "stopwatch-reset")).clicked() {
+                    stopwatch.reset();
+                }
+            });
+            if !stopwatch.laps.is_empty() {
+                ui.separator();
+                egui::ScrollArea::vertical().max_height(160.0).show(ui, |ui| {
+                    let mut previous = 0;
+                    for (i, &lap) in stopwatch.laps.iter().enumerate() {
+                        ui.label(tr!(localization, "stopwatch-lap-entry", number = i + 1, seconds = lap, split = lap - previous));
+                        previous = lap;
+                    }
+                });
+            }
+        });
+}
+
+/// Advances the running clock and puts each new whole second on its abacus.
+fn run_stopwatch(
+    mut stopwatch: ResMut<Stopwatch>,
+    time: Res<Time>,
+    abacus_query: Query<&Abacus>,
+    mut requests: EventWriter<OperationRequested>,
+    localization: Res<Localization>,
+) {
+    let Some(entity) = stopwatch.abacus else {
+        return;
+    };
+    let Ok(abacus) = abacus_query.get(entity) else {
+        // Removed or rebuilt while timing
+        stopwatch.abacus = None;
+        stopwatch.running = false;
+        return;
+    };
+    if stopwatch.running {
+        match stopwatch.mode {
+            ClockMode::Stopwatch => stopwatch.seconds += time.delta_secs(),
+            ClockMode::Countdown => {
+                stopwatch.seconds = (stopwatch.seconds - time.delta_secs()).max(0.0);
+                if stopwatch.seconds == 0.0 {
+                    stopwatch.running = false;
+                    info!("{}", tr!(localization, "log-countdown-finished"));
+                }
+            }
+        }
+    }
+    let seconds = stopwatch.whole_seconds().min(abacus.max_total_value());
+    if stopwatch.shown != Some(seconds) {
+        stopwatch.shown = Some(seconds);
+        requests.write(OperationRequested { abacus: entity, operation: AbacusOperation::Set(seconds) });
+    }
+}
@@ -0,0 +1,110 @@
+use bevy::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::localization::*;
+
+#[cfg(not(target_arch = "wasm32"))]
+fn data_folder() -> std::path::PathBuf {
+    let home = std::env::var_os("HOME").or_else(|| std::env::var_os("APPDATA")).unwrap_or_default();
+    std::path::PathBuf::from(home).join(".abacus-simulator")
+}
+
+/// Native saves are files in a folder in the user's home, named for their key and format.
+#[cfg(not(target_arch = "wasm32"))]
+fn data_path(key: &str, extension: &str) -> std::path::PathBuf {
+    data_folder().join(format!("{}.{}", key, extension))
+}
+
+/// Where a file for the user to open elsewhere goes: the `exports` folder beside the saves,
+/// created if need be.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn export_path(file_name: &str) -> Result<std::path::PathBuf, String> {
+    let folder = data_folder().join("exports");
+    std::fs::create_dir_all(&folder).map_err(|error| error.to_string())?;
+    Ok(folder.join(file_name))
+}
+
+/// Writes a file into the `exports` folder, returning where it went.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn export_file(file_name: &str, text: &str) -> Result<std::path::PathBuf, String> {
+    let path = export_path(file_name)?;
+    std::fs::write(&path, text).map_err(|error| error.to_string())?;
+    Ok(path)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn read_text(key: &str, extension: &str) -> Option<String> {
+    std::fs::read_to_string(data_path(key, extension)).ok()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn write_text(key: &str, extension: &str, text: &str) -> Result<(), String> {
+    let path = data_path(key, extension);
+    if let Some(folder) = path.parent() {
+        std::fs::create_dir_all(folder).map_err(|error| error.to_string())?;
+    }
+    std::fs::write(path, text).map_err(|error| error.to_string())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+#[cfg(target_arch = "wasm32")]
+fn read_text(key: &str, _extension: &str) -> Option<String> {
+    local_storage()?.get_item(&format!("abacus-simulator.{}", key)).ok()?
+}
+
+#[cfg(target_arch = "wasm32")]
+fn write_text(key: &str, _extension: &str, text: &str) -> Result<(), String> {
+    let storage = local_storage().ok_or("localStorage is unavailable")?;
+    storage.set_item(&format!("abacus-simulator.{}", key), text).map_err(|error| format!("{:?}", error))
+}
+
+/// Storage key of `key` for a profile; the guest (no profile) uses the bare key.
+pub fn profile_key(profile: Option<&str>, key: &str) -> String {
+    match profile {
+        Some(name) => {
+            // Profile names become folder names, so they're written in hex: every file system
+            // accepts it, even one that ignores case, and no two names share a folder
+            let folder: String = name.bytes().map(|byte| format!("{:02x}", byte)).collect();
+            format!("profiles/{}/{}", folder, key)
+        }
+        None => key.to_string(),
+    }
+}
+
+/// Reads what was last saved under `key`, from disk natively or localStorage on the web.
+/// Missing or unreadable data gives `None`.
+pub fn load_json<T: DeserializeOwned>(key: &str, localization: &Localization) -> Option<T> {
+    let text = read_text(key, "json")?;
+    serde_json::from_str(&text)
+        .inspect_err(|error| warn!("{}", tr!(localization, "log-saved-data-ignored", key = key, error = error.to_string())))
+        .ok()
+}
+
+pub fn save_json<T: Serialize>(key: &str, value: &T, localization: &Localization) {
+    let result = serde_json::to_string_pretty(value)
+        .map_err(|error| error.to_string())
+        .and_then(|text| write_text(key, "json", &text));
+    if let Err(error) = result {
+        warn!("{}", tr!(localization, "log-save-failed", key = key, error = error));
+    }
+}
+
+/// Like `load_json`, for data JSON can't hold, such as maps keyed by structs.
+pub fn load_ron<T: DeserializeOwned>(key: &str, localization: &Localization) -> Option<T> {
+    let text = read_text(key, "ron")?;
+    ron::from_str(&text)
+        .inspect_err(|error| warn!("{}", tr!(localization, "log-saved-data-ignored", key = key, error = error.to_string())))
+        .ok()
+}
+
+/// Saves `text`, already written as RON, under `key`.
+pub fn save_ron_text(key: &str, text: &str, localization: &Localization) {
+    if let Err(error) = write_text(key, "ron", text) {
+        warn!("{}", tr!(localization, "log-save-failed", key = key, error = error));
+    }
+}
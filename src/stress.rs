@@ -0,0 +1,176 @@
+//! A stress test that spawns the largest abacus the panel allows, times the value engine
+//! on it and watches the frame time while its beads move, so slowdowns show up as numbers.
+
+use bevy::platform::time::Instant;
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::abacus::*;
+use crate::localization::*;
+use crate::scene_layout::SelectedAbacus;
+use crate::{AbacusSettings, AbacusStructure};
+
+/// Times each value-engine call is repeated, to get past the clock's resolution.
+const ENGINE_ITERATIONS: u32 = 1000;
+/// Frames sampled while the beads move to the largest total.
+const SAMPLED_FRAMES: usize = 120;
+
+/// What the last stress test measured.
+struct StressReport {
+    columns: usize,
+    beads: usize,
+    /// Microseconds to split a total into column values.
+    split_micros: f32,
+    /// Microseconds to read the total back from the beads.
+    read_micros: f32,
+    /// Milliseconds to set the beads to a total.
+    set_millis: f32,
+    average_frame_millis: f32,
+    worst_frame_millis: f32,
+}
+
+#[derive(Default)]
+enum StressPhase {
+    #[default]
+    Idle,
+    /// The stress abacus was spawned and appears next frame.
+    Spawned(Entity),
+    Sampling { abacus: Entity, frame_seconds: Vec<f32> },
+}
+
+#[derive(Resource, Default)]
+struct StressTest {
+    phase: StressPhase,
+    /// Engine timings, kept until the frames are sampled.
+    engine: Option<(f32, f32, f32)>,
+    report: Option<StressReport>,
+}
+
+pub struct StressTestPlugin;
+
+impl Plugin for StressTestPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<StressTest>()
+            .add_systems(Update, (stress_test_ui_system, run_stress_test).chain());
+    }
+}
+
+fn stress_test_ui_system(
+    mut contexts: EguiContexts,
+    mut stress: ResMut<StressTest>,
+    mut settings: ResMut<AbacusSettings>,
+    mut commands: Commands,
+    mut selected: ResMut<SelectedAbacus>,
+    abacus_query: Query<&Abacus>,
+    localization: Res<Localization>,
+) {
+    egui::Window::new(tr!(localization, "stress-test-window"))
+        .id(egui::Id::new("stress_test"))
+        .default_pos([10.0, 1000.0])
+        .default_open(false)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.label(tr!(localization, "stress-test-description", columns = MAX_COLUMNS));
+            let idle = matches!(stress.phase, StressPhase::Idle);
+            if ui.add_enabled(idle, egui::Button::new(tr!(localization, "stress-test-run"))).clicked() {
+                // The stress abacus goes in its own slot; the panel's structure is left as it was
+                let previous = AbacusStructure::from_settings(&settings);
+                AbacusStructure {
+                    column_count: MAX_COLUMNS,
+                    top_bead_count: 2,
+                    bottom_bead_count: 10,
+                    column_overrides: Vec::new(),
+                    ..previous.clone()
+                }.apply_to(&mut settings);
+                let slot = abacus_query.iter().map(|abacus| abacus.slot + 1).max().unwrap_or(0);
+                let abacus = spawn_abacus(&mut commands, &settings, slot);
+                previous.apply_to(&mut settings);
+                selected.0 = Some(abacus);
+                stress.phase = StressPhase::Spawned(abacus);
+                stress.report = None;
+            }
+            if !idle {
+                ui.label(tr!(localization, "stress-test-running"));
+            }
+            if let Some(report) = &stress.report {
+                ui.separator();
+                ui.label(tr!(localization, "stress-test-size", columns = report.columns, beads = report.beads));
+                ui.label(tr!(localization, "stress-test-split", micros = format!("{:.2}", report.split_micros)));
+                ui.label(tr!(localization, "stress-test-read", micros = format!("{:.2}", report.read_micros)));
+                ui.label(tr!(localization, "stress-test-set", millis = format!("{:.2}", report.set_millis)));
+                ui.label(tr!(localization, "stress-test-frames",
+                    average = format!("{:.1}", report.average_frame_millis),
+                    worst = format!("{:.1}", report.worst_frame_millis),
+                    fps = format!("{:.0}", 1000.0 / report.average_frame_millis.max(f32::EPSILON))));
+            }
+        });
+}
+
+/// Times the value engine once the stress abacus exists, then samples the frames it
+/// takes for its beads to reach the largest total.
+fn run_stress_test(
+    mut stress: ResMut<StressTest>,
+    time: Res<Time>,
+    mut abacus_query: Query<&mut Abacus>,
+    mut long_query: Query<&mut AbacusLong>,
+    mut commands: Commands,
+    localization: Res<Localization>,
+) {
+    let stress = &mut *stress;
+    match &mut stress.phase {
+        StressPhase::Idle => {}
+        StressPhase::Spawned(entity) => {
+            let entity = *entity;
+            let Ok(mut abacus) = abacus_query.get_mut(entity) else {
+                return;
+            };
+            let largest = abacus.max_total_value();
+
+            let start = Instant::now();
+            for i in 0..ENGINE_ITERATIONS {
+                std::hint::black_box(abacus.column_values_for_total(largest.saturating_sub(u64::from(i))));
+            }
+            let split_micros = start.elapsed().as_secs_f32() * 1e6 / ENGINE_ITERATIONS as f32;
+
+            let start = Instant::now();
+            for _ in 0..ENGINE_ITERATIONS {
+                std::hint::black_box(abacus.beads_total_value(&long_query.as_readonly()));
+            }
+            let read_micros = start.elapsed().as_secs_f32() * 1e6 / ENGINE_ITERATIONS as f32;
+
+            // Every bead moves, which is what the frames are sampled over
+            let start = Instant::now();
+            abacus.set_total_value(largest, &mut long_query, &mut commands);
+            let set_millis = start.elapsed().as_secs_f32() * 1e3;
+
+            stress.engine = Some((split_micros, read_micros, set_millis));
+            stress.phase = StressPhase::Sampling { abacus: entity, frame_seconds: Vec::with_capacity(SAMPLED_FRAMES) };
+        }
+        StressPhase::Sampling { abacus, frame_seconds } => {
+            frame_seconds.push(time.delta_secs());
+            if frame_seconds.len() < SAMPLED_FRAMES {
+                return;
+            }
+            let Ok(abacus) = abacus_query.get(*abacus) else {
+                // Removed mid-test
+                stress.phase = StressPhase::Idle;
+                return;
+            };
+            let (split_micros, read_micros, set_millis) = stress.engine.take().unwrap_or_default();
+            let report = StressReport {
+                columns: abacus.columns.len(),
+                beads: abacus.columns.iter().map(ColumnLayout::bead_count).sum(),
+                split_micros,
+                read_micros,
+                set_millis,
+                average_frame_millis: frame_seconds.iter().sum::<f32>() / frame_seconds.len() as f32 * 1e3,
+                worst_frame_millis: frame_seconds.iter().copied().fold(0.0, f32::max) * 1e3,
+            };
+            info!("{}", tr!(localization, "log-stress-test",
+                columns = report.columns,
+                average = format!("{:.1}", report.average_frame_millis),
+                worst = format!("{:.1}", report.worst_frame_millis)));
+            stress.report = Some(report);
+            stress.phase = StressPhase::Idle;
+        }
+    }
+}
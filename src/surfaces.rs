@@ -0,0 +1,139 @@
+//! Surface finishes for the shared bead and frame materials, set under Appearance beyond
+//! the base color: an image texture such as wood grain or lacquer, a normal map, and how
+//! metallic and rough the surface is. Configurations save them with the colors.
+
+use bevy::image::ImageLoaderSettings;
+use bevy::prelude::*;
+use bevy_egui::egui;
+use serde::{Deserialize, Serialize};
+
+use crate::localization::*;
+use crate::wood_grain::AbacusMaterial;
+use crate::AbacusSettings;
+
+/// How one piece's material looks beyond its color. Image paths are relative to
+/// `assets/`; an empty path leaves that map off. The texture is tinted by the color.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SurfaceFinish {
+    pub texture: String,
+    pub normal_map: String,
+    pub metallic: f32,
+    pub roughness: f32,
+}
+
+impl Default for SurfaceFinish {
+    /// A plain surface, as materials are made by default.
+    fn default() -> Self {
+        Self {
+            texture: String::new(),
+            normal_map: String::new(),
+            metallic: 0.0,
+            roughness: 0.5,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Surfaces {
+    pub bead: SurfaceFinish,
+    pub frame: SurfaceFinish,
+}
+
+pub struct SurfacesPlugin;
+
+impl Plugin for SurfacesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (
+            apply_surfaces.run_if(resource_changed::<AbacusSettings>),
+            add_normal_map_tangents,
+        ));
+    }
+}
+
+/// Puts the finishes on the shared materials when they change. Every bead material takes
+/// the bead finish, so hovered, highlighted, two-tone and colored beads look like the rest.
+fn apply_surfaces(
+    settings: Res<AbacusSettings>,
+    asset_server: Res<AssetServer>,
+    mut applied: Local<Option<Surfaces>>,
+    mut materials: ResMut<Assets<AbacusMaterial>>,
+) {
+    if applied.as_ref() == Some(&settings.surfaces) {
+        return;
+    }
+    *applied = Some(settings.surfaces.clone());
+    let bead_materials = [&settings.bead_material, &settings.bead_hover_material, &settings.bead_highlight_material, &settings.bead_alt_material];
+    for handle in bead_materials.into_iter().chain(&settings.column_bead_materials) {
+        if let Some(material) = materials.get_mut(handle) {
+            apply_finish(&mut material.base, &settings.surfaces.bead, &asset_server);
+        }
+    }
+    if let Some(material) = materials.get_mut(&settings.frame_material) {
+        apply_finish(&mut material.base, &settings.surfaces.frame, &asset_server);
+    }
+}
+
+fn apply_finish(material: &mut StandardMaterial, finish: &SurfaceFinish, asset_server: &AssetServer) {
+    material.metallic = finish.metallic;
+    material.perceptual_roughness = finish.roughness;
+    material.base_color_texture = (!finish.texture.is_empty()).then(|| asset_server.load(finish.texture.clone()));
+    // Normal maps hold directions, not colors
+    material.normal_map_texture = (!finish.normal_map.is_empty()).then(|| {
+        asset_server.load_with_settings(finish.normal_map.clone(), |loader: &mut ImageLoaderSettings| loader.is_srgb = false)
+    });
+}
+
+/// Normal maps need tangents, which the procedural meshes are built without. The bead mesh
+/// is rebuilt whenever the quality or shape changes, so this keeps checking.
+fn add_normal_map_tangents(settings: Res<AbacusSettings>, mut meshes: ResMut<Assets<Mesh>>) {
+    let surfaces = &settings.surfaces;
+    for (finish, handle) in [(&surfaces.bead, &settings.bead_mesh), (&surfaces.bead, &settings.bead_lod_mesh), (&surfaces.frame, &settings.rod_mesh)] {
+        if finish.normal_map.is_empty() || meshes.get(handle).is_none_or(|mesh| mesh.contains_attribute(Mesh::ATTRIBUTE_TANGENT)) {
+            continue;
+        }
+        if let Some(mesh) = meshes.get_mut(handle) {
+            if let Err(error) = mesh.generate_tangents() {
+                warn_once!("Couldn't add tangents for a normal map: {}", error);
+            }
+        }
+    }
+}
+
+/// Texture, normal map, metallic and roughness controls for the beads and the frame,
+/// shown under Appearance.
+pub fn surface_controls(ui: &mut egui::Ui, surfaces: &mut Surfaces, localization: &Localization) {
+    for (finish, grid_id, heading) in [(&mut surfaces.bead, "bead_surface", "bead-surface"), (&mut surfaces.frame, "frame_surface", "frame-surface")] {
+        ui.label(tr!(localization, heading));
+        egui::Grid::new(grid_id).show(ui, |ui| {
+            ui.label(tr!(localization, "surface-texture"));
+            asset_path_field(ui, &mut finish.texture, "textures/wood.png");
+            ui.end_row();
+            ui.label(tr!(localization, "surface-normal-map"));
+            asset_path_field(ui, &mut finish.normal_map, "textures/wood_normal.png");
+            ui.end_row();
+            ui.label(tr!(localization, "surface-metallic"));
+            ui.add(egui::Slider::new(&mut finish.metallic, 0.0..=1.0));
+            ui.end_row();
+            // Rendering clamps anything smoother than this
+            ui.label(tr!(localization, "surface-roughness"));
+            ui.add(egui::Slider::new(&mut finish.roughness, 0.089..=1.0));
+            ui.end_row();
+        });
+    }
+}
+
+/// A path box that only changes `path` when the user presses Enter or clicks away, so
+/// half-typed paths aren't loaded. The text being typed is kept in egui's memory.
+pub fn asset_path_field(ui: &mut egui::Ui, path: &mut String, hint: &str) {
+    let id = ui.make_persistent_id(hint);
+    let mut text = ui.data_mut(|data| data.get_temp::<String>(id)).unwrap_or_else(|| path.clone());
+    let response = ui.add(egui::TextEdit::singleline(&mut text).hint_text(hint));
+    if response.lost_focus() {
+        *path = text.trim().to_string();
+        ui.data_mut(|data| data.remove::<String>(id));
+    } else if response.has_focus() {
+        ui.data_mut(|data| data.insert_temp(id, text));
+    }
+}
@@ -0,0 +1,251 @@
+//! An optional table under the abaci, so they don't float in a void in screenshots. It
+//! takes the key light's shadows when the graphics quality casts them; for low-end devices
+//! a soft contact shadow can be drawn under each abacus instead, which costs next to nothing.
+
+use bevy::asset::RenderAssetUsages;
+use bevy::image::{ImageAddressMode, ImageLoaderSettings, ImageSampler, ImageSamplerDescriptor};
+use bevy::math::Affine2;
+use bevy::pbr::{NotShadowCaster, NotShadowReceiver};
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy_egui::egui;
+use serde::{Deserialize, Serialize};
+
+use crate::abacus::*;
+use crate::graphics::GraphicsQuality;
+use crate::localization::*;
+use crate::surfaces::asset_path_field;
+use crate::AbacusSettings;
+
+/// Width and depth of the table; far enough that its edges stay out of view.
+const TABLE_SIZE: f32 = 80.0;
+/// World units each repeat of the table's texture covers.
+const TEXTURE_TILE: f32 = 4.0;
+/// How far below the lowest frame the table sits, leaving the column values above it.
+const TABLE_DROP: f32 = 1.0;
+/// Pixels across the contact shadow's image.
+const CONTACT_SHADOW_SIZE: u32 = 64;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TableSurface {
+    pub enabled: bool,
+    pub color: Color,
+    /// An image under `assets/`, tiled across the table and tinted by the color.
+    pub texture: String,
+    pub contact_shadows: bool,
+}
+
+impl Default for TableSurface {
+    /// Contact shadows start on wherever the graphics quality casts no real ones.
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            color: Color::srgb(0.45, 0.32, 0.22),
+            texture: String::new(),
+            contact_shadows: !GraphicsQuality::default().shadows(),
+        }
+    }
+}
+
+#[derive(Component)]
+struct Table;
+
+/// A soft dark patch on the table under an abacus.
+#[derive(Component)]
+struct ContactShadow(Entity);
+
+#[derive(Resource)]
+struct TableAssets {
+    material: Handle<StandardMaterial>,
+    shadow_mesh: Handle<Mesh>,
+    shadow_material: Handle<StandardMaterial>,
+}
+
+impl FromWorld for TableAssets {
+    fn from_world(world: &mut World) -> Self {
+        let shadow_image = world.resource_mut::<Assets<Image>>().add(contact_shadow_image());
+        let shadow_mesh = world.resource_mut::<Assets<Mesh>>().add(Plane3d::default().mesh().size(1.0, 1.0));
+        let mut materials = world.resource_mut::<Assets<StandardMaterial>>();
+        let material = materials.add(StandardMaterial {
+            base_color: TableSurface::default().color,
+            perceptual_roughness: 0.8,
+            uv_transform: Affine2::from_scale(Vec2::splat(TABLE_SIZE / TEXTURE_TILE)),
+            ..default()
+        });
+        let shadow_material = materials.add(StandardMaterial {
+            base_color_texture: Some(shadow_image),
+            alpha_mode: AlphaMode::Blend,
+            unlit: true,
+            ..default()
+        });
+        Self { material, shadow_mesh, shadow_material }
+    }
+}
+
+pub struct TablePlugin;
+
+impl Plugin for TablePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TableAssets>()
+            .add_systems(Startup, spawn_table)
+            .add_systems(Update, (
+                apply_table.run_if(resource_changed::<AbacusSettings>),
+                (place_table, place_contact_shadows),
+            ).chain());
+    }
+}
+
+/// Black in the middle, fading to nothing at the edge of the circle.
+fn contact_shadow_image() -> Image {
+    let size = CONTACT_SHADOW_SIZE;
+    let mut data = Vec::with_capacity((size * size * 4) as usize);
+    for y in 0..size {
+        for x in 0..size {
+            let offset = Vec2::new(x as f32 + 0.5, y as f32 + 0.5) / size as f32 * 2.0 - 1.0;
+            let alpha = (1.0 - offset.length()).clamp(0.0, 1.0).powi(2) * 0.6;
+            data.extend_from_slice(&[0, 0, 0, (alpha * 255.0) as u8]);
+        }
+    }
+    Image::new(
+        Extent3d { width: size, height: size, depth_or_array_layers: 1 },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    )
+}
+
+fn spawn_table(mut commands: Commands, mut meshes: ResMut<Assets<Mesh>>, assets: Res<TableAssets>) {
+    commands.spawn((
+        Table,
+        Mesh3d(meshes.add(Plane3d::default().mesh().size(TABLE_SIZE, TABLE_SIZE))),
+        MeshMaterial3d(assets.material.clone()),
+        Transform::default(),
+        Visibility::Hidden,
+        NotShadowCaster,
+    ));
+}
+
+fn apply_table(
+    settings: Res<AbacusSettings>,
+    asset_server: Res<AssetServer>,
+    assets: Res<TableAssets>,
+    mut applied: Local<Option<TableSurface>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut table_query: Query<&mut Visibility, With<Table>>,
+) {
+    let table = &settings.table;
+    if applied.as_ref() == Some(table) {
+        return;
+    }
+    *applied = Some(table.clone());
+    for mut visibility in &mut table_query {
+        *visibility = if table.enabled { Visibility::Inherited } else { Visibility::Hidden };
+    }
+    if let Some(material) = materials.get_mut(&assets.material) {
+        material.base_color = table.color;
+        // Tiled, so a small image covers the whole table
+        material.base_color_texture = (!table.texture.is_empty()).then(|| {
+            asset_server.load_with_settings(table.texture.clone(), |loader: &mut ImageLoaderSettings| {
+                loader.sampler = ImageSampler::Descriptor(ImageSamplerDescriptor {
+                    address_mode_u: ImageAddressMode::Repeat,
+                    address_mode_v: ImageAddressMode::Repeat,
+                    ..default()
+                });
+            })
+        });
+    }
+}
+
+/// Height of the table's top: just below the lowest abacus, wherever they are laid out.
+fn table_height(abacus_query: &Query<(Entity, &Abacus, &Transform), (Without<Table>, Without<ContactShadow>)>) -> Option<f32> {
+    abacus_query.iter()
+        .map(|(_, abacus, transform)| transform.translation.y - abacus.size().y / 2.0 - TABLE_DROP * abacus.proportions.model_scale)
+        .reduce(f32::min)
+}
+
+fn place_table(
+    settings: Res<AbacusSettings>,
+    abacus_query: Query<(Entity, &Abacus, &Transform), (Without<Table>, Without<ContactShadow>)>,
+    mut table_query: Query<&mut Transform, With<Table>>,
+) {
+    if !settings.table.enabled {
+        return;
+    }
+    let Some(height) = table_height(&abacus_query) else {
+        return;
+    };
+    for mut transform in &mut table_query {
+        if transform.translation.y != height {
+            transform.translation.y = height;
+        }
+    }
+}
+
+/// Keeps one contact shadow under each abacus, turned and sized with it.
+fn place_contact_shadows(
+    mut commands: Commands,
+    settings: Res<AbacusSettings>,
+    assets: Res<TableAssets>,
+    abacus_query: Query<(Entity, &Abacus, &Transform), (Without<Table>, Without<ContactShadow>)>,
+    mut shadow_query: Query<(Entity, &ContactShadow, &mut Transform, &mut Visibility)>,
+) {
+    let shown = settings.table.enabled && settings.table.contact_shadows;
+    let height = table_height(&abacus_query).unwrap_or_default();
+    let mut shadowed = Vec::new();
+    for (entity, shadow, mut transform, mut visibility) in &mut shadow_query {
+        let Ok((_, abacus, abacus_transform)) = abacus_query.get(shadow.0) else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+        shadowed.push(shadow.0);
+        visibility.set_if_neq(if shown { Visibility::Inherited } else { Visibility::Hidden });
+        if shown {
+            // A little above the table, so the two don't fight over the same depth
+            let (yaw, _, _) = abacus_transform.rotation.to_euler(EulerRot::YXZ);
+            let footprint = Vec2::new(abacus.size().x, 2.0 * abacus.proportions.model_scale) + Vec2::splat(abacus.proportions.model_scale);
+            *transform = Transform::from_xyz(abacus_transform.translation.x, height + 0.01, abacus_transform.translation.z)
+                .with_rotation(Quat::from_rotation_y(yaw))
+                .with_scale(Vec3::new(footprint.x, 1.0, footprint.y));
+        }
+    }
+    if !shown {
+        return;
+    }
+    for (abacus, _, _) in &abacus_query {
+        if !shadowed.contains(&abacus) {
+            commands.spawn((
+                ContactShadow(abacus),
+                Mesh3d(assets.shadow_mesh.clone()),
+                MeshMaterial3d(assets.shadow_material.clone()),
+                Transform::default(),
+                // Placed next frame, once it can be found
+                Visibility::Hidden,
+                NotShadowCaster,
+                NotShadowReceiver,
+            ));
+        }
+    }
+}
+
+/// The table's checkboxes, color and texture, shown in the Lighting window.
+pub fn table_controls(ui: &mut egui::Ui, table: &mut TableSurface, localization: &Localization) {
+    ui.checkbox(&mut table.enabled, tr!(localization, "table"));
+    ui.add_enabled_ui(table.enabled, |ui| {
+        ui.horizontal(|ui| {
+            let mut color = table.color.to_srgba().to_f32_array_no_alpha();
+            let color_response = ui.color_edit_button_rgb(&mut color);
+            if color_response.changed() {
+                table.color = Color::srgb_from_array(color);
+            }
+            let label = ui.label(tr!(localization, "table-color"));
+            color_response.labelled_by(label.id);
+        });
+        ui.horizontal(|ui| {
+            ui.label(tr!(localization, "table-texture"));
+            asset_path_field(ui, &mut table.texture, "textures/table.png");
+        });
+        ui.checkbox(&mut table.contact_shadows, tr!(localization, "contact-shadows"));
+    });
+}
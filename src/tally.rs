@@ -0,0 +1,117 @@
+//! Tally counter mode: each press of a chosen key, click on the big button, or
+//! `TallyTriggered` event from another controller adds a step to the selected abacus.
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::localization::*;
+use crate::operations::{AbacusOperation, ClearRequested, OperationRequested};
+use crate::scene_layout::SelectedAbacus;
+
+/// One external trigger to count.
+#[derive(Event)]
+pub struct TallyTriggered;
+
+#[derive(Resource)]
+struct Tally {
+    enabled: bool,
+    key: KeyCode,
+    step: u64,
+    /// Waiting for the next key press to become the trigger key.
+    rebinding: bool,
+    /// Triggers counted since the tally was last reset.
+    count: u64,
+}
+
+impl Default for Tally {
+    fn default() -> Self {
+        Self { enabled: false, key: KeyCode::Space, step: 1, rebinding: false, count: 0 }
+    }
+}
+
+pub struct TallyPlugin;
+
+impl Plugin for TallyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Tally>()
+            .add_event::<TallyTriggered>()
+            .add_systems(Update, (tally_ui_system, tally_key_system, count_tally_triggers).chain());
+    }
+}
+
+fn tally_ui_system(
+    mut contexts: EguiContexts,
+    mut tally: ResMut<Tally>,
+    selected: Res<SelectedAbacus>,
+    mut triggers: EventWriter<TallyTriggered>,
+    mut clear_requests: EventWriter<ClearRequested>,
+    localization: Res<Localization>,
+) {
+    let localization = &*localization;
+    egui::Window::new(tr!(localization, "tally-window"))
+        .id(egui::Id::new("tally"))
+        .default_pos([10.0, 1180.0])
+        .default_open(false)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.checkbox(&mut tally.enabled, tr!(localization, "tally-enabled"));
+            ui.add_enabled_ui(tally.enabled, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(tr!(localization, "tally-key"));
+                    let key_label = if tally.rebinding { tr!(localization, "tally-press-key") } else { format!("{:?}", tally.key) };
+                    if ui.button(key_label).clicked() {
+                        tally.rebinding = !tally.rebinding;
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label(tr!(localization, "tally-step"));
+                    ui.add(egui::DragValue::new(&mut tally.step).range(1..=u64::MAX));
+                });
+                let button = egui::Button::new(egui::RichText::new(format!("+{}", tally.step)).size(48.0));
+                if ui.add_enabled(selected.0.is_some(), button.min_size(egui::vec2(220.0, 120.0))).clicked() {
+                    triggers.write(TallyTriggered);
+                }
+                ui.horizontal(|ui| {
+                    ui.label(tr!(localization, "tally-count", count = tally.count));
+                    if ui.button(tr!(localization, "tally-reset")).clicked() {
+                        tally.count = 0;
+                        clear_requests.write(ClearRequested { abacus: selected.0 });
+                    }
+                });
+            });
+        });
+}
+
+/// Counts the trigger key, or takes the next key pressed as the new one.
+fn tally_key_system(
+    mut contexts: EguiContexts,
+    mut tally: ResMut<Tally>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut triggers: EventWriter<TallyTriggered>,
+) {
+    if !tally.enabled || contexts.ctx_mut().wants_keyboard_input() {
+        return;
+    }
+    if tally.rebinding {
+        if let Some(&key) = keys.get_just_pressed().next() {
+            tally.key = key;
+            tally.rebinding = false;
+        }
+    } else if keys.just_pressed(tally.key) {
+        triggers.write(TallyTriggered);
+    }
+}
+
+fn count_tally_triggers(
+    mut tally: ResMut<Tally>,
+    mut triggers: EventReader<TallyTriggered>,
+    selected: Res<SelectedAbacus>,
+    mut requests: EventWriter<OperationRequested>,
+) {
+    for _ in triggers.read() {
+        let Some(abacus) = selected.0.filter(|_| tally.enabled) else {
+            continue;
+        };
+        tally.count += 1;
+        requests.write(OperationRequested { abacus, operation: AbacusOperation::Add(tally.step) });
+    }
+}
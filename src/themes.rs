@@ -0,0 +1,266 @@
+//! Themes: a whole look — colors, bead finish and grain, bead shape, sounds, lighting and
+//! background — kept as a `.theme.ron` file under `assets/themes/` so new ones can be added
+//! without recompiling. The Themes window shows them as a gallery. Configurations refer to
+//! their theme by name, so editing the file restyles every configuration that uses it; any
+//! change to the look afterwards detaches the configuration from it.
+
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext, LoadedFolder};
+use bevy::color::ColorToPacked;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::abacus::BeadShape;
+use crate::appearance::APPEARANCE_PRESETS;
+use crate::audio::SoundTheme;
+use crate::environment::Environment;
+use crate::localization::*;
+use crate::surfaces::Surfaces;
+use crate::wood_grain::{AbacusMaterial, WoodGrainSettings};
+use crate::AbacusSettings;
+
+/// Folder under `assets/` scanned for theme files.
+const THEME_FOLDER: &str = "themes";
+
+/// A theme file. Anything it leaves out is as the Classic appearance has it.
+#[derive(Asset, TypePath, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Theme {
+    pub name: String,
+    pub bead_color: Color,
+    pub bead_hover_color: Color,
+    pub frame_color: Color,
+    pub background_color: Color,
+    pub text_color: Color,
+    pub bead_shape: BeadShape,
+    pub sound_theme: SoundTheme,
+    pub surfaces: Surfaces,
+    pub wood_grain: WoodGrainSettings,
+    pub environment: Environment,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        let classic = &APPEARANCE_PRESETS[0];
+        Self {
+            name: String::new(),
+            bead_color: classic.bead_color,
+            bead_hover_color: classic.bead_hover_color,
+            frame_color: classic.frame_color,
+            background_color: classic.background_color,
+            text_color: classic.text_color,
+            bead_shape: BeadShape::default(),
+            sound_theme: SoundTheme::default(),
+            surfaces: Surfaces::default(),
+            wood_grain: WoodGrainSettings::default(),
+            environment: Environment::default(),
+        }
+    }
+}
+
+impl Theme {
+    /// The look the settings have now, as a theme called `name`.
+    fn from_settings(settings: &AbacusSettings, name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            bead_color: settings.ui_bead_color,
+            bead_hover_color: settings.ui_bead_hover_color,
+            frame_color: settings.ui_frame_color,
+            background_color: settings.ui_background_color,
+            text_color: settings.text_color,
+            bead_shape: settings.bead_shape,
+            sound_theme: settings.sound_theme,
+            surfaces: settings.surfaces.clone(),
+            wood_grain: settings.wood_grain,
+            environment: settings.environment.clone(),
+        }
+    }
+
+    /// Puts the theme's look on the settings and the shared materials, and records its name.
+    pub fn apply_to(&self, settings: &mut AbacusSettings, materials: &mut Assets<AbacusMaterial>, clear_color: &mut ClearColor) {
+        settings.ui_bead_color = self.bead_color;
+        if let Some(material) = materials.get_mut(&settings.bead_material) {
+            material.base.base_color = self.bead_color;
+        }
+        settings.ui_bead_hover_color = self.bead_hover_color;
+        if let Some(material) = materials.get_mut(&settings.bead_hover_material) {
+            material.base.base_color = self.bead_hover_color;
+        }
+        settings.ui_frame_color = self.frame_color;
+        if let Some(material) = materials.get_mut(&settings.frame_material) {
+            material.base.base_color = self.frame_color;
+        }
+        settings.ui_background_color = self.background_color;
+        clear_color.0 = self.background_color;
+        settings.text_color = self.text_color;
+        settings.bead_shape = self.bead_shape;
+        settings.sound_theme = self.sound_theme;
+        settings.surfaces = self.surfaces.clone();
+        settings.wood_grain = self.wood_grain;
+        settings.environment = self.environment.clone();
+        settings.theme = self.name.clone();
+    }
+}
+
+#[derive(Debug)]
+pub enum ThemeFileError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+    NoName,
+}
+
+impl fmt::Display for ThemeFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThemeFileError::Io(error) => write!(f, "couldn't read theme file: {}", error),
+            ThemeFileError::Ron(error) => write!(f, "invalid theme: {}", error),
+            ThemeFileError::NoName => write!(f, "theme has no name"),
+        }
+    }
+}
+
+impl std::error::Error for ThemeFileError {}
+
+#[derive(Default)]
+pub struct ThemeFileLoader;
+
+impl AssetLoader for ThemeFileLoader {
+    type Asset = Theme;
+    type Settings = ();
+    type Error = ThemeFileError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Theme, ThemeFileError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await.map_err(ThemeFileError::Io)?;
+        let theme: Theme = ron::de::from_bytes(&bytes).map_err(ThemeFileError::Ron)?;
+        // Configurations find their theme by name
+        if theme.name.trim().is_empty() {
+            return Err(ThemeFileError::NoName);
+        }
+        Ok(theme)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["theme.ron"]
+    }
+}
+
+/// Keeps the theme folder loaded so its files can be listed in the gallery.
+#[derive(Resource)]
+struct ThemeLibrary(Handle<LoadedFolder>);
+
+/// The theme files found so far.
+#[derive(SystemParam)]
+pub struct ThemeFiles<'w> {
+    library: Option<Res<'w, ThemeLibrary>>,
+    folders: Res<'w, Assets<LoadedFolder>>,
+    files: Res<'w, Assets<Theme>>,
+}
+
+impl ThemeFiles<'_> {
+    /// Loaded themes, sorted by name.
+    pub fn themes(&self) -> Vec<&Theme> {
+        let Some(folder) = self.library.as_ref().and_then(|library| self.folders.get(&library.0)) else {
+            return Vec::new();
+        };
+        let mut themes: Vec<&Theme> = folder.handles.iter()
+            .filter_map(|handle| handle.clone().try_typed::<Theme>().ok())
+            .filter_map(|handle| self.files.get(&handle))
+            .collect();
+        themes.sort_by(|a, b| a.name.cmp(&b.name));
+        themes
+    }
+
+    pub fn find(&self, name: &str) -> Option<&Theme> {
+        self.themes().into_iter().find(|theme| theme.name == name)
+    }
+}
+
+pub struct ThemesPlugin;
+
+impl Plugin for ThemesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<Theme>()
+            .init_asset_loader::<ThemeFileLoader>()
+            .add_systems(Startup, load_theme_library)
+            .add_systems(Update, (apply_named_theme, themes_ui));
+    }
+}
+
+fn load_theme_library(asset_server: Res<AssetServer>, mut commands: Commands) {
+    commands.insert_resource(ThemeLibrary(asset_server.load_folder(THEME_FOLDER)));
+}
+
+/// Applies the theme a loaded configuration names, once its file has loaded, and forgets
+/// the theme when the look is changed by hand.
+fn apply_named_theme(
+    mut settings: ResMut<AbacusSettings>,
+    mut applied: Local<Option<Theme>>,
+    theme_files: ThemeFiles,
+    mut materials: ResMut<Assets<AbacusMaterial>>,
+    mut clear_color: ResMut<ClearColor>,
+) {
+    let name = settings.theme.clone();
+    if applied.as_ref().map(|theme| theme.name.as_str()) != Some(name.as_str()) {
+        if name.is_empty() {
+            *applied = None;
+        } else if let Some(theme) = theme_files.find(&name) {
+            theme.apply_to(&mut settings, &mut materials, &mut clear_color);
+            *applied = Some(theme.clone());
+        }
+        return;
+    }
+    if settings.is_changed() && applied.as_ref().is_some_and(|theme| *theme != Theme::from_settings(&settings, &name)) {
+        settings.theme.clear();
+        *applied = None;
+    }
+}
+
+fn swatch(ui: &mut egui::Ui, color: Color) {
+    let [r, g, b, _] = color.to_srgba().to_u8_array();
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(16.0, 16.0), egui::Sense::hover());
+    ui.painter().rect_filled(rect, 3.0, egui::Color32::from_rgb(r, g, b));
+}
+
+/// The gallery: each theme's colors, with a button to use it.
+fn themes_ui(
+    mut contexts: EguiContexts,
+    mut settings: ResMut<AbacusSettings>,
+    mut materials: ResMut<Assets<AbacusMaterial>>,
+    mut clear_color: ResMut<ClearColor>,
+    theme_files: ThemeFiles,
+    localization: Res<Localization>,
+) {
+    egui::Window::new(tr!(localization, "themes-window"))
+        .id(egui::Id::new("themes"))
+        .default_pos([10.0, 1600.0])
+        .default_open(false)
+        .show(contexts.ctx_mut(), |ui| {
+            let themes = theme_files.themes();
+            if themes.is_empty() {
+                ui.label(tr!(localization, "themes-hint"));
+            }
+            egui::Grid::new("theme_gallery").show(ui, |ui| {
+                for theme in themes {
+                    ui.horizontal(|ui| {
+                        for color in [theme.background_color, theme.frame_color, theme.bead_color, theme.bead_hover_color] {
+                            swatch(ui, color);
+                        }
+                    });
+                    let current = settings.theme == theme.name;
+                    if ui.selectable_label(current, &theme.name).clicked() {
+                        theme.apply_to(&mut settings, &mut materials, &mut clear_color);
+                    }
+                    ui.end_row();
+                }
+            });
+        });
+}
@@ -0,0 +1,162 @@
+//! Touch screens, apart from the mouse controls. A finger dragged along a rod pushes the
+//! bead it started on across the gap, two fingers moved together orbit the camera and
+//! pinched zoom it, and a double tap on empty space puts the view back as it was.
+
+use bevy::input::touch::Touch;
+use bevy::picking::hover::HoverMap;
+use bevy::picking::pointer::PointerId;
+use bevy::prelude::*;
+use bevy_egui::EguiContexts;
+use std::collections::HashMap;
+
+use crate::abacus::*;
+use crate::config_gallery::ThumbnailCamera;
+use crate::scene_layout::{CameraView, SelectedAbacus};
+use crate::AbacusSettings;
+
+/// Pixels a finger moves along the rod before the bead goes with it.
+const DRAG_DISTANCE: f32 = 12.0;
+/// Radians the camera turns per pixel two fingers move.
+const ORBIT_SPEED: f32 = 0.005;
+/// A touch that moves further than this, in pixels, is a drag rather than a tap.
+const TAP_SLOP: f32 = 10.0;
+/// Most seconds and pixels between the taps of a double tap.
+const DOUBLE_TAP_SECONDS: f32 = 0.35;
+const DOUBLE_TAP_REACH: f32 = 40.0;
+
+/// A bead a finger has already pushed in this drag, so lifting the finger doesn't count as
+/// a tap that pushes it back.
+#[derive(Component)]
+pub struct TouchDragged;
+
+pub struct TouchGesturesPlugin;
+
+impl Plugin for TouchGesturesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (orbit_and_zoom, double_tap_reset));
+    }
+}
+
+/// Pushes the bead across the gap once a finger has dragged it that way along its rod.
+pub fn drag_bead(
+    trigger: Trigger<Pointer<Drag>>,
+    beads: Query<(&AbacusBead, &BelongsTo)>,
+    mut longs: Query<&mut AbacusLong>,
+    parents: Query<&ChildOf>,
+    abaci: Query<&Abacus>,
+    dragged: Query<(), With<TouchDragged>>,
+    transforms: Query<&GlobalTransform>,
+    camera_query: Query<(&Camera, &GlobalTransform), (With<Camera3d>, Without<ThumbnailCamera>)>,
+    mut commands: Commands,
+) {
+    let bead_entity = trigger.target();
+    if !trigger.event().pointer_id.is_touch() || dragged.contains(bead_entity) {
+        return;
+    }
+    let Ok((bead, BelongsTo(long))) = beads.get(bead_entity) else {
+        return;
+    };
+    let Ok(long_value) = longs.get(*long).map(|long| long.value) else {
+        return;
+    };
+    let (Ok(bead_transform), Ok(long_transform)) = (transforms.get(bead_entity), transforms.get(*long)) else {
+        return;
+    };
+    let Some((camera, camera_transform)) = camera_query.iter().find(|(camera, _)| camera.is_active) else {
+        return;
+    };
+
+    // Which way the rod runs on screen, however the abacus is turned
+    let position = bead_transform.translation();
+    let (Ok(from), Ok(to)) = (
+        camera.world_to_viewport(camera_transform, position),
+        camera.world_to_viewport(camera_transform, position + long_transform.up() * 0.5),
+    ) else {
+        return;
+    };
+    let along = trigger.event().distance.dot((to - from).normalize_or_zero());
+    // Beads in the lower group cross the gap up the rod, the others down it
+    let across = if bead.value <= long_value { along } else { -along };
+    if across < DRAG_DISTANCE {
+        return;
+    }
+    push_bead(bead_entity, &beads, &mut longs, &parents, &abaci, &mut commands);
+    commands.entity(bead_entity).insert(TouchDragged);
+}
+
+pub fn end_bead_drag(trigger: Trigger<Pointer<DragEnd>>, mut commands: Commands) {
+    // The bead may have gone with a rebuild since
+    commands.entity(trigger.target()).try_remove::<TouchDragged>();
+}
+
+/// Two fingers: moving together orbits the camera about the abaci, and pinching zooms.
+/// Flat mode stays straight on, so it only zooms.
+fn orbit_and_zoom(
+    touches: Res<Touches>,
+    mut contexts: EguiContexts,
+    settings: Res<AbacusSettings>,
+    mut view: ResMut<CameraView>,
+) {
+    let fingers: Vec<&Touch> = touches.iter().collect();
+    let [first, second] = fingers[..] else {
+        return;
+    };
+    if contexts.try_ctx_mut().is_some_and(|ctx| ctx.is_pointer_over_area()) {
+        return;
+    }
+    if !settings.flat_mode {
+        let moved = (first.delta() + second.delta()) / 2.0;
+        if moved != Vec2::ZERO {
+            view.orbit(-moved.x * ORBIT_SPEED, moved.y * ORBIT_SPEED);
+        }
+    }
+    let spread = first.position().distance(second.position());
+    let last_spread = first.previous_position().distance(second.previous_position());
+    if spread > 0.0 && last_spread > 0.0 && spread != last_spread {
+        view.zoom_by(spread / last_spread);
+    }
+}
+
+#[derive(Default)]
+struct Taps {
+    /// Touches that began on nothing: not an abacus, nor a panel.
+    on_empty_space: HashMap<u64, bool>,
+    /// When and where the last tap on empty space was.
+    last: Option<(f32, Vec2)>,
+}
+
+/// Double-tapping empty space undoes the orbit and zoom and straightens the selected abacus.
+fn double_tap_reset(
+    time: Res<Time>,
+    touches: Res<Touches>,
+    hover_map: Res<HoverMap>,
+    mut contexts: EguiContexts,
+    mut taps: Local<Taps>,
+    mut view: ResMut<CameraView>,
+    selected: Res<SelectedAbacus>,
+    mut abacus_query: Query<&mut Transform, With<Abacus>>,
+) {
+    let over_panel = contexts.try_ctx_mut().is_some_and(|ctx| ctx.is_pointer_over_area());
+    for touch in touches.iter_just_pressed() {
+        let over_scene = hover_map.get(&PointerId::Touch(touch.id())).is_some_and(|hits| !hits.is_empty());
+        taps.on_empty_space.insert(touch.id(), !over_scene && !over_panel);
+    }
+    for touch in touches.iter_just_released() {
+        let on_empty_space = taps.on_empty_space.remove(&touch.id()).unwrap_or(false);
+        if !on_empty_space || touch.distance().length() > TAP_SLOP {
+            continue;
+        }
+        let now = time.elapsed_secs();
+        match taps.last.take() {
+            Some((at, position)) if now - at < DOUBLE_TAP_SECONDS && position.distance(touch.position()) < DOUBLE_TAP_REACH => {
+                *view = CameraView::default();
+                if let Some(mut transform) = selected.0.and_then(|entity| abacus_query.get_mut(entity).ok()) {
+                    transform.rotation = Quat::IDENTITY;
+                }
+            }
+            _ => taps.last = Some((now, touch.position())),
+        }
+    }
+    // Touches the system never saw released, such as cancelled ones
+    taps.on_empty_space.retain(|id, _| touches.get_pressed(*id).is_some());
+}
@@ -0,0 +1,331 @@
+//! Turntable exports for teaching materials: the camera circles the selected abacus, or
+//! a macro plays on it, while every frame is captured and saved as an animated GIF or a
+//! folder of numbered PNGs. Only the desktop build writes the files.
+
+use bevy::prelude::*;
+use bevy::render::view::screenshot::{Screenshot, ScreenshotCaptured};
+use bevy::tasks::{block_on, futures_lite::future, IoTaskPool, Task};
+use bevy_egui::{egui, EguiContexts};
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::{Delay, Frame};
+use std::f32::consts::TAU;
+use std::path::PathBuf;
+
+use crate::abacus::*;
+use crate::localization::*;
+use crate::macros::MacroLibrary;
+use crate::operations::ColumnOpQueue;
+use crate::scene_layout::{focus_camera, SelectedAbacus};
+use crate::screenshot::HideUiThisFrame;
+use crate::{AbacusSettings, MainCameraAnchor};
+
+const MIN_ORBIT_FRAMES: usize = 12;
+const MAX_ORBIT_FRAMES: usize = 120;
+/// A macro is captured every few frames, which plays back at about its real speed.
+const MACRO_CAPTURE_EVERY: u32 = 3;
+/// Frames kept after the macro ends, so its last beads settle on camera.
+const MACRO_TAIL_FRAMES: usize = 10;
+/// Stops a long macro from filling memory with frames.
+const MAX_MACRO_FRAMES: usize = 600;
+/// Time each GIF frame shows for.
+const GIF_FRAME_MS: u32 = 50;
+/// GIFs are scaled down to this width, which is plenty for slides and keeps them small.
+const GIF_MAX_WIDTH: u32 = 640;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TurntableMotion {
+    Orbit,
+    /// Plays the macro at this index in the library.
+    Macro(usize),
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum TurntableFormat {
+    #[default]
+    Gif,
+    PngSequence,
+}
+
+impl TurntableFormat {
+    const ALL: [TurntableFormat; 2] = [TurntableFormat::Gif, TurntableFormat::PngSequence];
+
+    /// Localization id of the format's display name.
+    fn message_id(&self) -> &'static str {
+        match self {
+            TurntableFormat::Gif => "turntable-format-gif",
+            TurntableFormat::PngSequence => "turntable-format-png",
+        }
+    }
+}
+
+/// A turntable being captured.
+struct Capture {
+    motion: TurntableMotion,
+    format: TurntableFormat,
+    /// The point the camera circles, and where it started relative to that point.
+    center: Vec3,
+    start_offset: Vec3,
+    /// Screenshots asked for so far, and the ones that have come back, by frame number.
+    requested: usize,
+    images: Vec<(usize, Image)>,
+    frames_since_capture: u32,
+    /// Frames left to capture once a macro has finished.
+    tail: usize,
+    finished: bool,
+}
+
+#[derive(Resource)]
+struct Turntable {
+    motion: TurntableMotion,
+    format: TurntableFormat,
+    orbit_frames: usize,
+    capture: Option<Capture>,
+    encoding: Option<Task<Result<PathBuf, String>>>,
+    status: Option<String>,
+}
+
+impl Default for Turntable {
+    fn default() -> Self {
+        Self {
+            motion: TurntableMotion::Orbit,
+            format: TurntableFormat::default(),
+            orbit_frames: 36,
+            capture: None,
+            encoding: None,
+            status: None,
+        }
+    }
+}
+
+pub struct TurntablePlugin;
+
+impl Plugin for TurntablePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Turntable>()
+            .add_systems(Update, (
+                turntable_ui_system,
+                capture_turntable_frames.after(focus_camera),
+                finish_turntable_export,
+            ).chain());
+    }
+}
+
+fn turntable_ui_system(
+    mut contexts: EguiContexts,
+    mut turntable: ResMut<Turntable>,
+    mut library: ResMut<MacroLibrary>,
+    selected: Res<SelectedAbacus>,
+    abacus_query: Query<&Transform, (With<Abacus>, Without<MainCameraAnchor>)>,
+    camera_query: Query<&Transform, With<MainCameraAnchor>>,
+    localization: Res<Localization>,
+) {
+    let localization = &*localization;
+    let turntable = &mut *turntable;
+    let selected_abacus = selected.0.filter(|&entity| abacus_query.contains(entity));
+    let busy = turntable.capture.is_some() || turntable.encoding.is_some();
+    let mut start = false;
+
+    egui::Window::new(tr!(localization, "turntable-window"))
+        .id(egui::Id::new("turntable"))
+        .default_pos([10.0, 940.0])
+        .default_open(false)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.label(tr!(localization, "turntable-intro"));
+            let motion_text = match turntable.motion {
+                TurntableMotion::Orbit => tr!(localization, "turntable-orbit"),
+                TurntableMotion::Macro(i) => library.macros.get(i).map_or_else(String::new, |recorded| recorded.name.clone()),
+            };
+            egui::ComboBox::from_label(tr!(localization, "turntable-motion"))
+                .selected_text(motion_text)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut turntable.motion, TurntableMotion::Orbit, tr!(localization, "turntable-orbit"));
+                    for (i, recorded) in library.macros.iter().enumerate() {
+                        ui.selectable_value(&mut turntable.motion, TurntableMotion::Macro(i), recorded.name.as_str());
+                    }
+                });
+            if turntable.motion == TurntableMotion::Orbit {
+                ui.add(egui::Slider::new(&mut turntable.orbit_frames, MIN_ORBIT_FRAMES..=MAX_ORBIT_FRAMES)
+                    .text(tr!(localization, "turntable-frames")));
+            }
+            egui::ComboBox::from_label(tr!(localization, "turntable-format"))
+                .selected_text(tr!(localization, turntable.format.message_id()))
+                .show_ui(ui, |ui| {
+                    for format in TurntableFormat::ALL {
+                        ui.selectable_value(&mut turntable.format, format, tr!(localization, format.message_id()));
+                    }
+                });
+
+            let can_start = !busy && selected_abacus.is_some() && !library.is_playing();
+            if ui.add_enabled(can_start, egui::Button::new(tr!(localization, "turntable-record"))).clicked() {
+                start = true;
+            }
+            if let Some(capture) = &turntable.capture {
+                ui.label(tr!(localization, "turntable-capturing", count = capture.requested));
+            } else if turntable.encoding.is_some() {
+                ui.label(tr!(localization, "turntable-encoding"));
+            } else if let Some(status) = &turntable.status {
+                ui.label(status);
+            }
+        });
+
+    // The macro list may have shrunk since it was picked
+    if let TurntableMotion::Macro(i) = turntable.motion {
+        if i >= library.macros.len() {
+            turntable.motion = TurntableMotion::Orbit;
+        }
+    }
+
+    let (Some(abacus), true) = (selected_abacus, start) else {
+        return;
+    };
+    let (Ok(abacus_transform), Ok(camera_transform)) = (abacus_query.get(abacus), camera_query.single()) else {
+        return;
+    };
+    if let TurntableMotion::Macro(i) = turntable.motion {
+        library.play(i, abacus);
+    }
+    let center = abacus_transform.translation;
+    turntable.status = None;
+    turntable.capture = Some(Capture {
+        motion: turntable.motion,
+        format: turntable.format,
+        center,
+        start_offset: camera_transform.translation - center,
+        requested: 0,
+        images: Vec::new(),
+        frames_since_capture: 0,
+        tail: MACRO_TAIL_FRAMES,
+        finished: false,
+    });
+}
+
+/// Moves the camera for the next frame and asks for it to be captured. Runs after
+/// `focus_camera` so the orbit wins over the usual camera glide.
+fn capture_turntable_frames(
+    mut commands: Commands,
+    mut turntable: ResMut<Turntable>,
+    library: Res<MacroLibrary>,
+    queue: Res<ColumnOpQueue>,
+    settings: Res<AbacusSettings>,
+    mut hide_ui: ResMut<HideUiThisFrame>,
+    mut camera_query: Query<&mut Transform, With<MainCameraAnchor>>,
+) {
+    let orbit_frames = turntable.orbit_frames;
+    let Some(capture) = &mut turntable.capture else {
+        return;
+    };
+    if capture.finished {
+        return;
+    }
+    // Panels stay hidden for the whole capture, not just the captured frames
+    hide_ui.0 = settings.screenshot_hides_ui;
+
+    match capture.motion {
+        TurntableMotion::Orbit => {
+            if capture.requested >= orbit_frames {
+                capture.finished = true;
+                return;
+            }
+            let Ok(mut camera_transform) = camera_query.single_mut() else {
+                return;
+            };
+            let angle = TAU * capture.requested as f32 / orbit_frames as f32;
+            camera_transform.translation = capture.center + Quat::from_rotation_y(angle) * capture.start_offset;
+            camera_transform.look_at(capture.center, Vec3::Y);
+        }
+        TurntableMotion::Macro(_) => {
+            if !library.is_playing() && queue.pending_count() == 0 {
+                if capture.tail == 0 {
+                    capture.finished = true;
+                    return;
+                }
+                capture.tail -= 1;
+            }
+            if capture.requested >= MAX_MACRO_FRAMES {
+                capture.finished = true;
+                return;
+            }
+            capture.frames_since_capture += 1;
+            if capture.frames_since_capture < MACRO_CAPTURE_EVERY {
+                return;
+            }
+            capture.frames_since_capture = 0;
+        }
+    }
+
+    let index = capture.requested;
+    capture.requested += 1;
+    commands.spawn(Screenshot::primary_window()).observe(
+        move |trigger: Trigger<ScreenshotCaptured>, mut turntable: ResMut<Turntable>| {
+            if let Some(capture) = &mut turntable.capture {
+                capture.images.push((index, trigger.event().0.clone()));
+            }
+        },
+    );
+}
+
+/// Once every frame has come back, encodes them off the main thread and reports where
+/// the export went.
+fn finish_turntable_export(mut turntable: ResMut<Turntable>, localization: Res<Localization>) {
+    if let Some(task) = &mut turntable.encoding {
+        let Some(result) = block_on(future::poll_once(task)) else {
+            return;
+        };
+        turntable.encoding = None;
+        turntable.status = Some(match result {
+            Ok(path) => tr!(localization, "turntable-saved", path = path.display().to_string()),
+            Err(error) => tr!(localization, "turntable-failed", error = error),
+        });
+        return;
+    }
+
+    let ready = turntable.capture.as_ref().is_some_and(|capture| capture.finished && capture.images.len() >= capture.requested);
+    if !ready {
+        return;
+    }
+    let Some(mut capture) = turntable.capture.take() else {
+        return;
+    };
+    // Captures can come back out of order
+    capture.images.sort_by_key(|(index, _)| *index);
+    let images: Vec<Image> = capture.images.into_iter().map(|(_, image)| image).collect();
+    let format = capture.format;
+    turntable.encoding = Some(IoTaskPool::get().spawn(async move { encode_turntable(images, format) }));
+}
+
+fn encode_turntable(images: Vec<Image>, format: TurntableFormat) -> Result<PathBuf, String> {
+    if images.is_empty() {
+        return Err("no frames were captured".to_string());
+    }
+    let seconds = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs());
+    let frames = images.into_iter()
+        .map(|image| image.try_into_dynamic().map(|image| image.to_rgba8()).map_err(|error| error.to_string()));
+
+    match format {
+        TurntableFormat::Gif => {
+            let path = crate::storage::export_path(&format!("turntable-{}.gif", seconds))?;
+            let file = std::fs::File::create(&path).map_err(|error| error.to_string())?;
+            let mut encoder = GifEncoder::new_with_speed(file, 10);
+            encoder.set_repeat(Repeat::Infinite).map_err(|error| error.to_string())?;
+            for frame in frames {
+                let frame = frame?;
+                let scale = (GIF_MAX_WIDTH as f32 / frame.width() as f32).min(1.0);
+                let (width, height) = ((frame.width() as f32 * scale) as u32, (frame.height() as f32 * scale) as u32);
+                let frame = image::imageops::resize(&frame, width.max(1), height.max(1), image::imageops::FilterType::Triangle);
+                encoder.encode_frame(Frame::from_parts(frame, 0, 0, Delay::from_numer_denom_ms(GIF_FRAME_MS, 1)))
+                    .map_err(|error| error.to_string())?;
+            }
+            Ok(path)
+        }
+        TurntableFormat::PngSequence => {
+            let folder = crate::storage::export_path(&format!("turntable-{}", seconds))?;
+            std::fs::create_dir_all(&folder).map_err(|error| error.to_string())?;
+            for (i, frame) in frames.enumerate() {
+                frame?.save(folder.join(format!("frame-{:03}.png", i))).map_err(|error| error.to_string())?;
+            }
+            Ok(folder)
+        }
+    }
+}
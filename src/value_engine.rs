@@ -0,0 +1,232 @@
+//! The value math of an abacus, apart from its beads and entities: which columns and
+//! longs show a total, and what total the longs show. It lives in the library so the
+//! benchmarks in `benches/` can time it.
+
+use bevy::reflect::Reflect;
+
+/// Most columns an abacus can have. Past about 20 the total no longer fits a u64 and
+/// stops at its largest value, but the beads still work column by column.
+pub const MAX_COLUMNS: usize = 200;
+
+/// How the beads make up the total.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect, serde::Serialize, serde::Deserialize)]
+pub enum ValueModel {
+    /// Each column is worth the base times the column to its right.
+    #[default]
+    PlaceValue,
+    /// Every bead pushed across counts one, whichever rod it's on, as on a rekenrek.
+    Counting,
+}
+
+impl ValueModel {
+    pub const ALL: [ValueModel; 2] = [ValueModel::PlaceValue, ValueModel::Counting];
+
+    /// Localization id of the model's display name.
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            ValueModel::PlaceValue => "value-model-place",
+            ValueModel::Counting => "value-model-counting",
+        }
+    }
+}
+
+/// One group of beads on a column's rod, with a bar between it and the next.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect, serde::Serialize, serde::Deserialize)]
+pub struct DeckLayout {
+    pub bead_count: usize,
+    pub bead_value: u64, // Before the column's multiplier
+}
+
+/// Resolved bead layout of a single column.
+#[derive(Clone, Debug, PartialEq, Reflect)]
+pub struct ColumnLayout {
+    /// Top deck first. The bottom deck counts beads pushed up against the bar above it;
+    /// every other deck counts beads dropped down against the bar below it.
+    pub decks: Vec<DeckLayout>,
+    pub bead_value_multiplier: u64, // Every bead in the column is worth this many units
+    pub radix: u64, // Units of this column that make one unit of the next
+}
+
+impl ColumnLayout {
+    pub fn deck(&self, deck: usize) -> DeckLayout {
+        self.decks.get(deck).copied().unwrap_or(DeckLayout { bead_count: 0, bead_value: 1 })
+    }
+
+    pub fn bead_count(&self) -> usize {
+        self.decks.iter().map(|deck| deck.bead_count).sum()
+    }
+
+    pub fn is_bottom(&self, deck: usize) -> bool {
+        deck + 1 == self.decks.len()
+    }
+
+    /// Beads of `deck` that count, when its long's value is `long_value`. A long's value is
+    /// the number of beads in its lower group.
+    pub fn counted_beads(&self, deck: usize, long_value: u64) -> u64 {
+        let bead_count = self.deck(deck).bead_count as u64;
+        if self.is_bottom(deck) { bead_count.saturating_sub(long_value) } else { long_value.min(bead_count) }
+    }
+
+    /// The long value that leaves `counted` beads of `deck` counting.
+    pub fn long_value(&self, deck: usize, counted: u64) -> u64 {
+        let bead_count = self.deck(deck).bead_count as u64;
+        if self.is_bottom(deck) { bead_count - counted.min(bead_count) } else { counted.min(bead_count) }
+    }
+
+    /// Largest value the column can show with every bead against the bar.
+    pub fn max_value(&self) -> u64 {
+        let beads: u64 = self.decks.iter().map(|deck| deck.bead_count as u64 * deck.bead_value).sum();
+        beads * self.bead_value_multiplier
+    }
+
+    /// Long values that show `value`, top deck first. The value is clamped to what the
+    /// column can show, and the most valuable beads go first.
+    pub fn long_values(&self, value: u64) -> Vec<u64> {
+        // Expressed in bead units (values between multiples round down)
+        let mut remaining = value.min(self.max_value()) / self.bead_value_multiplier;
+        let mut long_values = vec![0; self.decks.len()];
+        let mut decks: Vec<usize> = (0..self.decks.len()).collect();
+        decks.sort_by_key(|&deck| std::cmp::Reverse(self.decks[deck].bead_value));
+        for deck in decks {
+            let DeckLayout { bead_count, bead_value } = self.decks[deck];
+            let counted = (remaining / bead_value.max(1)).min(bead_count as u64);
+            remaining -= counted * bead_value;
+            long_values[deck] = self.long_value(deck, counted);
+        }
+        long_values
+    }
+
+    /// Value the column shows with its decks' longs at `long_values`, top deck first.
+    pub fn value(&self, long_values: &[u64]) -> u64 {
+        let beads: u64 = long_values.iter().enumerate()
+            .map(|(deck, &long_value)| self.counted_beads(deck, long_value) * self.deck(deck).bead_value)
+            .sum();
+        beads * self.bead_value_multiplier
+    }
+}
+
+/// An abacus's column layouts and value model, ones column first.
+#[derive(Clone, Copy)]
+pub struct ValueEngine<'a> {
+    pub columns: &'a [ColumnLayout],
+    pub value_model: ValueModel,
+}
+
+impl ValueEngine<'_> {
+    /// What one unit in `column` is worth in the total, stopping at the largest u64 on
+    /// abaci too wide for one.
+    pub fn place_value(&self, column: usize) -> u64 {
+        self.checked_place_value(column).unwrap_or(u64::MAX)
+    }
+
+    /// What one unit in `column` is worth, or `None` if that's more than a u64 holds.
+    pub fn checked_place_value(&self, column: usize) -> Option<u64> {
+        match self.value_model {
+            ValueModel::PlaceValue => self.columns.iter().take(column).try_fold(1u64, |value, layout| value.checked_mul(layout.radix)),
+            ValueModel::Counting => Some(1),
+        }
+    }
+
+    /// Largest total the columns can hold. Columns whose place value is past a u64 can't
+    /// hold any of it.
+    pub fn max_total_value(&self) -> u64 {
+        let mut max_abacus_val: u64 = 0;
+        for (i, layout) in self.columns.iter().enumerate() {
+            let Some(place_value) = self.checked_place_value(i) else {
+                continue;
+            };
+            max_abacus_val = max_abacus_val.saturating_add(layout.max_value().saturating_mul(place_value));
+        }
+        max_abacus_val
+    }
+
+    /// Splits a total into the value each column should show (ones column first).
+    /// The total is clamped to what the abacus can represent.
+    pub fn column_values_for_total(&self, target_total_value: u64) -> Vec<u64> {
+        let num_columns = self.columns.len();
+        let mut column_values = vec![0; num_columns];
+        let mut remaining_value = target_total_value.min(self.max_total_value());
+
+        // Iterate from most significant column down to least significant; counting frames
+        // fill from the first rod, as beads are counted off a rekenrek
+        let order: Vec<usize> = match self.value_model {
+            ValueModel::PlaceValue => (0..num_columns).rev().collect(),
+            ValueModel::Counting => (0..num_columns).collect(),
+        };
+        for i in order {
+            // A column worth more than a u64 holds stays empty; a bead there would show
+            // far more than the total
+            let Some(base_power) = self.checked_place_value(i) else {
+                continue;
+            };
+            let layout = &self.columns[i];
+
+            // Round down to what the column's beads can actually show, leaving the rest for lower columns
+            let column_value = (remaining_value / base_power).min(layout.max_value());
+            let column_value = column_value - column_value % layout.bead_value_multiplier;
+
+            column_values[i] = column_value;
+            remaining_value -= column_value * base_power;
+        }
+
+        column_values
+    }
+
+    /// Each column's long values, top deck first, that show `total`.
+    pub fn long_values_for_total(&self, total: u64) -> Vec<Vec<u64>> {
+        self.column_values_for_total(total).into_iter().zip(self.columns)
+            .map(|(value, layout)| layout.long_values(value))
+            .collect()
+    }
+
+    /// The total shown by each column's long values. Saturates on abaci too wide for a u64,
+    /// like `max_total_value`.
+    pub fn total_value(&self, long_values: &[Vec<u64>]) -> u64 {
+        long_values.iter().zip(self.columns).enumerate().fold(0u64, |total, (i, (long_values, layout))| {
+            layout.value(long_values).saturating_mul(self.place_value(i)).saturating_add(total)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A column of base `radix`: one top bead worth half the base, and enough bottom beads
+    /// to count up to it, as on a soroban.
+    fn column(radix: u64) -> ColumnLayout {
+        ColumnLayout {
+            decks: vec![
+                DeckLayout { bead_count: 1, bead_value: radix / 2 },
+                DeckLayout { bead_count: (radix / 2 - 1) as usize, bead_value: 1 },
+            ],
+            bead_value_multiplier: 1,
+            radix,
+        }
+    }
+
+    #[test]
+    fn columns_past_a_u64_stay_empty() {
+        let columns = vec![column(10); 200];
+        let engine = ValueEngine { columns: &columns, value_model: ValueModel::PlaceValue };
+        assert_eq!(engine.max_total_value(), u64::MAX);
+
+        // 10^19 is the last power of ten a u64 holds, and u64::MAX has a 1 there
+        let values = engine.column_values_for_total(u64::MAX);
+        assert_eq!(values[19], 1);
+        assert!(values[20..].iter().all(|&value| value == 0));
+        assert_eq!(engine.total_value(&engine.long_values_for_total(u64::MAX)), u64::MAX);
+    }
+
+    #[test]
+    fn totals_round_trip_at_max_columns() {
+        let decimal = vec![column(10); MAX_COLUMNS];
+        let mixed_radix: Vec<ColumnLayout> = (0..MAX_COLUMNS).map(|i| column([10, 12, 20][i % 3])).collect();
+        for columns in [&decimal, &mixed_radix] {
+            let engine = ValueEngine { columns, value_model: ValueModel::PlaceValue };
+            for total in [0, 1, 9, 1_234_567_890, u64::MAX / 3, u64::MAX - 1, u64::MAX] {
+                assert_eq!(engine.total_value(&engine.long_values_for_total(total)), total);
+            }
+        }
+    }
+}
@@ -0,0 +1,254 @@
+//! Where the windows are. The settings are split into panels — Structure, Appearance,
+//! Controls and Practice — that float as windows or dock at either side of the screen, and
+//! egui's memory of every window, with its place, size, collapse state and dock, is saved
+//! as it changes and restored on the next run. Phones and narrow windows show the panels
+//! as a bottom sheet instead.
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiPreUpdateSet};
+use serde::{Deserialize, Serialize};
+
+use crate::bottom_sheet::*;
+use crate::localization::*;
+use crate::storage;
+
+const WINDOW_LAYOUT_KEY: &str = "window-layout";
+/// Seconds between checks for windows that have moved, so dragging one doesn't write on
+/// every frame.
+const SAVE_SECONDS: f32 = 2.0;
+
+/// The panels the settings are split into.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SettingsPanel {
+    Structure,
+    Appearance,
+    Controls,
+    Practice,
+}
+
+impl SettingsPanel {
+    pub const ALL: [SettingsPanel; 4] = [SettingsPanel::Structure, SettingsPanel::Appearance, SettingsPanel::Controls, SettingsPanel::Practice];
+
+    fn id(self) -> egui::Id {
+        egui::Id::new(match self {
+            SettingsPanel::Structure => "settings_structure",
+            SettingsPanel::Appearance => "settings_appearance",
+            SettingsPanel::Controls => "settings_controls",
+            SettingsPanel::Practice => "settings_practice",
+        })
+    }
+
+    pub fn message_id(self) -> &'static str {
+        match self {
+            SettingsPanel::Structure => "panel-structure",
+            SettingsPanel::Appearance => "panel-appearance",
+            SettingsPanel::Controls => "panel-controls",
+            SettingsPanel::Practice => "panel-practice",
+        }
+    }
+
+    /// Practice is open at the top left to begin with, the others folded up beneath it.
+    fn default_pos(self) -> [f32; 2] {
+        match self {
+            SettingsPanel::Practice => [10.0, 10.0],
+            SettingsPanel::Structure => [10.0, 220.0],
+            SettingsPanel::Appearance => [10.0, 280.0],
+            SettingsPanel::Controls => [10.0, 340.0],
+        }
+    }
+}
+
+/// Where a panel is shown; kept in egui's memory, so it's saved with the rest of the layout.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
+enum PanelDock {
+    #[default]
+    Floating,
+    Left,
+    Right,
+}
+
+impl PanelDock {
+    const ALL: [PanelDock; 3] = [PanelDock::Left, PanelDock::Floating, PanelDock::Right];
+
+    fn message_id(self) -> &'static str {
+        match self {
+            PanelDock::Floating => "dock-floating",
+            PanelDock::Left => "dock-left",
+            PanelDock::Right => "dock-right",
+        }
+    }
+}
+
+fn dock_id(panel: SettingsPanel) -> egui::Id {
+    panel.id().with("dock")
+}
+
+/// Draws each panel where it's docked, with `contents` filling it in: panels docked to a
+/// side share a resizable side panel, one under another, and the rest are windows of their
+/// own. On a small screen they are tabs of the bottom sheet.
+pub fn show_settings_panels(ctx: &egui::Context, localization: &Localization, mut contents: impl FnMut(SettingsPanel, &mut egui::Ui)) {
+    let compact = is_compact(ctx);
+    apply_touch_spacing(ctx, compact);
+    if compact {
+        show_bottom_sheet(ctx, localization, contents);
+        return;
+    }
+
+    let dock = |panel: SettingsPanel| ctx.data_mut(|data| data.get_persisted::<PanelDock>(dock_id(panel))).unwrap_or_default();
+
+    for (side, id, docked_to) in [
+        (egui::panel::Side::Left, "settings_dock_left", PanelDock::Left),
+        (egui::panel::Side::Right, "settings_dock_right", PanelDock::Right),
+    ] {
+        let docked: Vec<SettingsPanel> = SettingsPanel::ALL.into_iter().filter(|&panel| dock(panel) == docked_to).collect();
+        if docked.is_empty() {
+            continue;
+        }
+        egui::SidePanel::new(side, id)
+            .resizable(true)
+            .default_width(320.0)
+            .show(ctx, |ui| {
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    for panel in docked {
+                        egui::CollapsingHeader::new(tr!(localization, panel.message_id()))
+                            .id_salt(panel.id())
+                            .default_open(true)
+                            .show(ui, |ui| {
+                                dock_picker(ui, panel, localization);
+                                contents(panel, ui);
+                            });
+                    }
+                });
+            });
+    }
+
+    for panel in SettingsPanel::ALL {
+        if dock(panel) != PanelDock::Floating {
+            continue;
+        }
+        egui::Window::new(tr!(localization, panel.message_id()))
+            .id(panel.id())
+            .default_pos(panel.default_pos())
+            .default_open(panel == SettingsPanel::Practice)
+            .show(ctx, |ui| {
+                dock_picker(ui, panel, localization);
+                contents(panel, ui);
+            });
+    }
+}
+
+/// Moves the panel to a side or out into a window of its own.
+fn dock_picker(ui: &mut egui::Ui, panel: SettingsPanel, localization: &Localization) {
+    let id = dock_id(panel);
+    let mut dock = ui.data_mut(|data| data.get_persisted::<PanelDock>(id)).unwrap_or_default();
+    ui.horizontal(|ui| {
+        ui.label(tr!(localization, "panel-dock"));
+        for option in PanelDock::ALL {
+            ui.selectable_value(&mut dock, option, tr!(localization, option.message_id()));
+        }
+    });
+    ui.data_mut(|data| data.insert_persisted(id, dock));
+    ui.separator();
+}
+
+/// Puts every window back where it first opens, on the next frame.
+pub fn request_layout_reset(ctx: &egui::Context) {
+    ctx.data_mut(|data| data.insert_temp(egui::Id::new(WINDOW_LAYOUT_KEY), true));
+}
+
+#[derive(Resource)]
+struct WindowLayout {
+    restored: bool,
+    save_timer: Timer,
+    /// The layout as last saved, so an unchanged one isn't written again.
+    saved: String,
+}
+
+impl Default for WindowLayout {
+    fn default() -> Self {
+        Self {
+            restored: false,
+            save_timer: Timer::from_seconds(SAVE_SECONDS, TimerMode::Repeating),
+            saved: String::new(),
+        }
+    }
+}
+
+pub struct WindowLayoutPlugin;
+
+impl Plugin for WindowLayoutPlugin {
+    fn build(&self, app: &mut App) {
+        // Between frames, so no window is half drawn from one memory and half from the other
+        app.init_resource::<WindowLayout>()
+            .add_systems(
+                PreUpdate,
+                (restore_window_layout, reset_window_layout)
+                    .after(EguiPreUpdateSet::InitContexts)
+                    .before(EguiPreUpdateSet::BeginPass),
+            )
+            .add_systems(Last, save_window_layout);
+    }
+}
+
+/// Swaps egui's memory for `memory`, keeping the options the app has set.
+fn replace_memory(ctx: &egui::Context, memory: egui::Memory) {
+    ctx.memory_mut(|current| {
+        let options = std::mem::take(&mut current.options);
+        *current = memory;
+        current.options = options;
+    });
+}
+
+/// Loads the saved layout before the first frame draws any window.
+fn restore_window_layout(mut contexts: EguiContexts, mut layout: ResMut<WindowLayout>, localization: Res<Localization>) {
+    if layout.restored {
+        return;
+    }
+    let Some(ctx) = contexts.try_ctx_mut() else {
+        return;
+    };
+    layout.restored = true;
+    if let Some(memory) = storage::load_ron::<egui::Memory>(WINDOW_LAYOUT_KEY, &localization) {
+        replace_memory(ctx, memory);
+    }
+}
+
+fn reset_window_layout(mut contexts: EguiContexts) {
+    let Some(ctx) = contexts.try_ctx_mut() else {
+        return;
+    };
+    let id = egui::Id::new(WINDOW_LAYOUT_KEY);
+    if ctx.data_mut(|data| data.remove_temp::<bool>(id)).is_some() {
+        replace_memory(ctx, egui::Memory::default());
+    }
+}
+
+/// Saves the layout every so often while it changes, and once more on the way out; the web
+/// build has no way out to catch, so it relies on the former.
+fn save_window_layout(
+    time: Res<Time>,
+    mut exits: EventReader<AppExit>,
+    mut contexts: EguiContexts,
+    mut layout: ResMut<WindowLayout>,
+    localization: Res<Localization>,
+) {
+    let exiting = exits.read().count() > 0;
+    layout.save_timer.tick(time.delta());
+    if !layout.restored || !(exiting || layout.save_timer.just_finished()) {
+        return;
+    }
+    let Some(ctx) = contexts.try_ctx_mut() else {
+        return;
+    };
+    let text = match ctx.memory(ron::to_string) {
+        Ok(text) => text,
+        Err(error) => {
+            warn!("{}", tr!(localization, "log-save-failed", key = WINDOW_LAYOUT_KEY, error = error.to_string()));
+            return;
+        }
+    };
+    if text != layout.saved {
+        storage::save_ron_text(WINDOW_LAYOUT_KEY, &text, &localization);
+        layout.saved = text;
+    }
+}
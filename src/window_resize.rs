@@ -0,0 +1,81 @@
+//! Resizing the web build's canvas safely. Phone browsers report a burst of sizes while the
+//! address bar slides away or the screen turns, and reconfiguring the drawing surface for
+//! each of them can crash some, so the canvas follows the page only once its size has held
+//! still for a moment, and a turn to landscape reflows the scene and the panels once. The surface is also kept within
+//! the largest texture phone GPUs can make, drawing a very large or dense screen at a lower
+//! pixel ratio rather than failing.
+
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+/// Seconds the page's size must hold before the canvas takes it.
+const SETTLE_SECONDS: f32 = 0.25;
+/// Longest side of the drawing surface, in physical pixels.
+const MAX_SURFACE_SIDE: f32 = 4096.0;
+
+pub struct WindowResizePlugin;
+
+impl Plugin for WindowResizePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(First, follow_page_size);
+    }
+}
+
+/// The page's size in CSS pixels, and how many physical pixels each one has.
+fn page_size() -> Option<(Vec2, f32)> {
+    let window = web_sys::window()?;
+    let width = window.inner_width().ok()?.as_f64()? as f32;
+    let height = window.inner_height().ok()?.as_f64()? as f32;
+    Some((Vec2::new(width, height), window.device_pixel_ratio() as f32))
+}
+
+/// The pixel ratio to draw `size` at: the screen's own, unless that would make the surface
+/// too large.
+fn safe_pixel_ratio(size: Vec2, pixel_ratio: f32) -> Option<f32> {
+    let longest = size.max_element() * pixel_ratio;
+    (longest > MAX_SURFACE_SIDE).then(|| MAX_SURFACE_SIDE / size.max_element())
+}
+
+/// A size the page has taken, and how long it has held.
+struct Settling {
+    size: Vec2,
+    pixel_ratio: f32,
+    held: f32,
+}
+
+fn follow_page_size(
+    time: Res<Time>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+    mut settling: Local<Option<Settling>>,
+    mut sized: Local<bool>,
+) {
+    let Some((size, pixel_ratio)) = page_size() else {
+        return;
+    };
+    // A hidden tab or a page folded to nothing has no size worth drawing at
+    if size.min_element() < 1.0 {
+        return;
+    }
+    let Ok(mut window) = window_query.single_mut() else {
+        return;
+    };
+    let current = Vec2::new(window.resolution.width(), window.resolution.height());
+    let scale_override = safe_pixel_ratio(size, pixel_ratio);
+    if current.distance(size) < 0.5 && window.resolution.scale_factor_override() == scale_override {
+        *settling = None;
+        return;
+    }
+
+    match &mut *settling {
+        Some(pending) if pending.size == size && pending.pixel_ratio == pixel_ratio => pending.held += time.delta_secs(),
+        _ => *settling = Some(Settling { size, pixel_ratio, held: 0.0 }),
+    }
+    // The first size is taken straight away, so the app doesn't open at the wrong one
+    if *sized && settling.as_ref().is_some_and(|pending| pending.held < SETTLE_SECONDS) {
+        return;
+    }
+    *sized = true;
+    *settling = None;
+    window.resolution.set_scale_factor_override(scale_override);
+    window.resolution.set(size.x, size.y);
+}
@@ -0,0 +1,143 @@
+//! Procedural wood grain for the beads and frame, drawn by a shader rather than a texture
+//! so the web build has no large images to download. Beads share one material, so each
+//! piece's grain is seeded from its mesh tag to keep neighbouring beads from matching.
+
+use bevy::color::ColorToComponents;
+use bevy::pbr::{ExtendedMaterial, MaterialExtension};
+use bevy::prelude::*;
+use bevy::render::mesh::MeshTag;
+use bevy::render::render_resource::{AsBindGroup, ShaderRef, ShaderType};
+use bevy_egui::egui;
+use serde::{Deserialize, Serialize};
+
+use crate::localization::*;
+use crate::AbacusSettings;
+
+const SHADER_PATH: &str = "shaders/wood_grain.wgsl";
+
+/// The material every bead and rod is drawn with: the standard one, grained.
+pub type AbacusMaterial = ExtendedMaterial<StandardMaterial, WoodGrain>;
+
+/// A plain material for a piece, with no grain until the settings are applied.
+pub fn abacus_material(base_color: Color) -> AbacusMaterial {
+    ExtendedMaterial {
+        base: StandardMaterial {
+            base_color,
+            ..default()
+        },
+        extension: WoodGrain::default(),
+    }
+}
+
+/// The grain as configured, saved with configurations. The tint darkens the piece's own
+/// color in the late-wood rings.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WoodGrainSettings {
+    pub enabled: bool,
+    pub tint: Color,
+    pub ring_scale: f32,
+    /// How much the rings wobble and how far apart each piece's grain is taken from.
+    pub variation: f32,
+}
+
+impl Default for WoodGrainSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            tint: Color::srgb(0.55, 0.38, 0.24),
+            ring_scale: 24.0,
+            variation: 0.5,
+        }
+    }
+}
+
+#[derive(Asset, AsBindGroup, Reflect, Debug, Clone, Default)]
+pub struct WoodGrain {
+    #[uniform(100)]
+    pub grain: WoodGrainUniform,
+}
+
+/// The grain as the shader reads it. A strength of zero leaves the material plain.
+#[derive(Clone, Copy, Debug, Default, Reflect, ShaderType)]
+pub struct WoodGrainUniform {
+    pub tint: Vec4,
+    pub ring_scale: f32,
+    pub variation: f32,
+    pub strength: f32,
+}
+
+impl From<&WoodGrainSettings> for WoodGrain {
+    fn from(settings: &WoodGrainSettings) -> Self {
+        Self {
+            grain: WoodGrainUniform {
+                tint: settings.tint.to_linear().to_vec4(),
+                ring_scale: settings.ring_scale,
+                variation: settings.variation,
+                strength: if settings.enabled { 1.0 } else { 0.0 },
+            },
+        }
+    }
+}
+
+impl MaterialExtension for WoodGrain {
+    fn fragment_shader() -> ShaderRef {
+        SHADER_PATH.into()
+    }
+}
+
+pub struct WoodGrainPlugin;
+
+impl Plugin for WoodGrainPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(MaterialPlugin::<AbacusMaterial>::default())
+            .add_systems(Update, (
+                apply_wood_grain.run_if(resource_changed::<AbacusSettings>),
+                tag_wood_pieces,
+            ));
+    }
+}
+
+/// Puts the grain on every shared material when it changes.
+fn apply_wood_grain(
+    settings: Res<AbacusSettings>,
+    mut applied: Local<Option<WoodGrainSettings>>,
+    mut materials: ResMut<Assets<AbacusMaterial>>,
+) {
+    if *applied == Some(settings.wood_grain) {
+        return;
+    }
+    *applied = Some(settings.wood_grain);
+    let shared = [&settings.bead_material, &settings.bead_hover_material, &settings.bead_highlight_material, &settings.bead_alt_material, &settings.frame_material];
+    for handle in shared.into_iter().chain(&settings.column_bead_materials) {
+        if let Some(material) = materials.get_mut(handle) {
+            material.extension = WoodGrain::from(&settings.wood_grain);
+        }
+    }
+}
+
+/// Seeds each new piece's grain. The entity index is stable for the piece's life, so the
+/// grain doesn't change as a bead slides along its rod.
+fn tag_wood_pieces(mut commands: Commands, new_pieces: Query<Entity, (Added<MeshMaterial3d<AbacusMaterial>>, Without<MeshTag>)>) {
+    for entity in &new_pieces {
+        commands.entity(entity).insert(MeshTag(entity.index()));
+    }
+}
+
+/// The grain's checkbox and sliders, shown under Appearance.
+pub fn wood_grain_controls(ui: &mut egui::Ui, grain: &mut WoodGrainSettings, localization: &Localization) {
+    ui.checkbox(&mut grain.enabled, tr!(localization, "wood-grain"));
+    ui.add_enabled_ui(grain.enabled, |ui| {
+        ui.horizontal(|ui| {
+            let mut tint = grain.tint.to_srgba().to_f32_array_no_alpha();
+            let color_response = ui.color_edit_button_rgb(&mut tint);
+            if color_response.changed() {
+                grain.tint = Color::srgb_from_array(tint);
+            }
+            let label = ui.label(tr!(localization, "wood-grain-tint"));
+            color_response.labelled_by(label.id);
+        });
+        ui.add(egui::Slider::new(&mut grain.ring_scale, 4.0..=64.0).text(tr!(localization, "wood-grain-rings")));
+        ui.add(egui::Slider::new(&mut grain.variation, 0.0..=1.0).text(tr!(localization, "wood-grain-variation")));
+    });
+}